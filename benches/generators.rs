@@ -20,6 +20,7 @@ use test::{black_box, Bencher};
 use rand::prelude::*;
 use rand::rngs::adapter::ReseedingRng;
 use rand::rngs::{mock::StepRng, OsRng};
+use rand::TryRngCore;
 use rand_chacha::{ChaCha12Rng, ChaCha20Core, ChaCha20Rng, ChaCha8Rng};
 use rand_pcg::{Pcg32, Pcg64, Pcg64Mcg, Pcg64Dxsm};
 
@@ -51,7 +52,7 @@ gen_bytes!(gen_bytes_chacha20, ChaCha20Rng::from_entropy());
 gen_bytes!(gen_bytes_std, StdRng::from_entropy());
 #[cfg(feature = "small_rng")]
 gen_bytes!(gen_bytes_small, SmallRng::from_entropy());
-gen_bytes!(gen_bytes_os, OsRng);
+gen_bytes!(gen_bytes_os, OsRng.unwrap_err());
 
 macro_rules! gen_uint {
     ($fnn:ident, $ty:ty, $gen:expr) => {
@@ -81,7 +82,7 @@ gen_uint!(gen_u32_chacha20, u32, ChaCha20Rng::from_entropy());
 gen_uint!(gen_u32_std, u32, StdRng::from_entropy());
 #[cfg(feature = "small_rng")]
 gen_uint!(gen_u32_small, u32, SmallRng::from_entropy());
-gen_uint!(gen_u32_os, u32, OsRng);
+gen_uint!(gen_u32_os, u32, OsRng.unwrap_err());
 
 gen_uint!(gen_u64_step, u64, StepRng::new(0, 1));
 gen_uint!(gen_u64_pcg32, u64, Pcg32::from_entropy());
@@ -94,7 +95,7 @@ gen_uint!(gen_u64_chacha20, u64, ChaCha20Rng::from_entropy());
 gen_uint!(gen_u64_std, u64, StdRng::from_entropy());
 #[cfg(feature = "small_rng")]
 gen_uint!(gen_u64_small, u64, SmallRng::from_entropy());
-gen_uint!(gen_u64_os, u64, OsRng);
+gen_uint!(gen_u64_os, u64, OsRng.unwrap_err());
 
 macro_rules! init_gen {
     ($fnn:ident, $gen:ident) => {
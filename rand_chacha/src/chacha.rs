@@ -12,9 +12,10 @@
 #[cfg(feature = "std")] use std as core;
 
 use self::core::fmt;
+use self::core::mem::MaybeUninit;
 use crate::guts::ChaCha;
 use rand_core::block::{BlockRng, BlockRngCore, CryptoBlockRng};
-use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+use rand_core::{impls, CryptoRng, Error, JumpableRng, MixEntropy, RngCore, SeedableRng, StreamRng};
 
 #[cfg(feature = "serde1")] use serde::{Serialize, Deserialize, Serializer, Deserializer};
 
@@ -172,6 +173,10 @@ macro_rules! chacha_impl {
             fn try_fill_bytes(&mut self, bytes: &mut [u8]) -> Result<(), Error> {
                 self.rng.try_fill_bytes(bytes)
             }
+            #[inline]
+            fn fill_bytes_uninit<'a>(&mut self, bytes: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+                self.rng.fill_bytes_uninit(bytes)
+            }
         }
 
         impl $ChaChaXRng {
@@ -260,6 +265,54 @@ macro_rules! chacha_impl {
 
         impl CryptoRng for $ChaChaXRng {}
 
+        impl JumpableRng for $ChaChaXRng {
+            /// Advance the word position by 2<sup>52</sup> (i.e. 2<sup>48</sup> blocks).
+            ///
+            /// Each stream thus holds 2<sup>16</sup> non-overlapping sub-sequences;
+            /// the word position wraps after 2<sup>68</sup> words. Use `set_stream`
+            /// for fully independent sequences.
+            #[inline]
+            fn jump(&mut self) {
+                let wp = self.get_word_pos();
+                self.set_word_pos(wp + (1 << 52));
+            }
+
+            /// Advance the word position by 2<sup>64</sup> (i.e. 2<sup>60</sup> blocks).
+            ///
+            /// Each stream thus holds 16 such starting points, from each of which
+            /// [`jump`](JumpableRng::jump) generates 2<sup>12</sup> non-overlapping
+            /// sub-sequences.
+            #[inline]
+            fn long_jump(&mut self) {
+                let wp = self.get_word_pos();
+                self.set_word_pos(wp + (1 << 64));
+            }
+        }
+
+        impl StreamRng for $ChaChaXRng {
+            /// Set the stream number (nonce), as for the inherent `set_stream`.
+            /// All 2<sup>64</sup> streams are independent.
+            #[inline]
+            fn set_stream(&mut self, stream: u64) {
+                $ChaChaXRng::set_stream(self, stream)
+            }
+
+            /// Get the stream number, as for `get_stream`.
+            #[inline]
+            fn stream(&self) -> u64 {
+                self.get_stream()
+            }
+        }
+
+        impl MixEntropy for $ChaChaXRng {
+            /// Reseed from this generator's output XORed with `entropy`, via
+            /// [`impls::mix_entropy_via_seed`]. The stream and word position
+            /// are reset to zero.
+            fn mix_entropy(&mut self, entropy: &[u8]) {
+                impls::mix_entropy_via_seed(self, entropy)
+            }
+        }
+
         impl From<$ChaChaXCore> for $ChaChaXRng {
             fn from(core: $ChaChaXCore) -> Self {
                 $ChaChaXRng {
@@ -624,6 +677,67 @@ mod test {
         assert_eq!(rng.get_word_pos(), 0);
     }
 
+    #[test]
+    fn test_chacha_jump() {
+        use rand_core::JumpableRng;
+
+        let mut rng = ChaChaRng::from_seed(Default::default());
+        rng.next_u32();
+        rng.jump();
+        assert_eq!(rng.get_word_pos(), (1 << 52) + 1);
+        rng.long_jump();
+        assert_eq!(rng.get_word_pos(), (1 << 64) + (1 << 52) + 1);
+
+        let mut rng2 = ChaChaRng::from_seed(Default::default());
+        rng2.set_word_pos((1 << 64) + (1 << 52) + 1);
+        assert_eq!(rng.next_u64(), rng2.next_u64());
+
+        // The word position wraps after 16 long jumps
+        for _ in 0..15 {
+            rng.long_jump();
+        }
+        rng2.set_word_pos((1 << 52) + 3);
+        assert_eq!(rng.get_word_pos(), (1 << 52) + 3);
+        assert_eq!(rng.next_u64(), rng2.next_u64());
+    }
+
+    #[test]
+    fn test_chacha_stream_trait() {
+        use rand_core::StreamRng;
+
+        let mut rng1 = ChaChaRng::from_seed(Default::default());
+        let mut rng2 = rng1.clone();
+        rng1.next_u32();
+        rng2.next_u32();
+        StreamRng::set_stream(&mut rng1, 3);
+        rng2.set_stream(3);
+        assert_eq!(StreamRng::stream(&rng1), 3);
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
+
+    #[test]
+    fn test_chacha_mix_entropy() {
+        use rand_core::MixEntropy;
+
+        let mut rng = ChaChaRng::from_seed(Default::default());
+        rng.set_stream(5);
+        let mut rng2 = rng.clone();
+        let entropy = [0x55u8; 40];
+        rng.mix_entropy(&entropy);
+
+        // Equivalent to reseeding twice, once per 32-byte chunk
+        for chunk in entropy.chunks(32) {
+            let mut seed = [0u8; 32];
+            rng2.fill_bytes(&mut seed);
+            for (x, y) in seed.iter_mut().zip(chunk) {
+                *x ^= *y;
+            }
+            rng2 = ChaChaRng::from_seed(seed);
+        }
+        assert_eq!(rng.get_stream(), 0);
+        assert_eq!(rng.next_u64(), rng2.next_u64());
+    }
+
     #[test]
     fn test_trait_objects() {
         use rand_core::CryptoRng;
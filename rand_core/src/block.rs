@@ -53,10 +53,13 @@
 //! [`BlockRngCore`]: crate::block::BlockRngCore
 //! [`fill_bytes`]: RngCore::fill_bytes
 
-use crate::impls::{fill_via_u32_chunks, fill_via_u64_chunks};
+use crate::impls::{
+    fill_uninit_via_u32_chunks, fill_uninit_via_u64_chunks, fill_via_u32_chunks, fill_via_u64_chunks,
+};
 use crate::{Error, CryptoRng, RngCore, SeedableRng};
 use core::convert::AsRef;
 use core::fmt;
+use core::mem::MaybeUninit;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -236,10 +239,20 @@ impl<R: BlockRngCore<Item = u32>> RngCore for BlockRng<R> {
         }
     }
 
-    #[inline(always)]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.fill_bytes(dest);
-        Ok(())
+    fn fill_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        let mut read_len = 0;
+        while read_len < dest.len() {
+            if self.index >= self.results.as_ref().len() {
+                self.generate_and_set(0);
+            }
+            let (consumed_u32, filled_u8) =
+                fill_uninit_via_u32_chunks(&self.results.as_ref()[self.index..], &mut dest[read_len..]);
+
+            self.index += consumed_u32;
+            read_len += filled_u8;
+        }
+        // Safety: all bytes were initialized above
+        unsafe { crate::slice_assume_init_mut(dest) }
     }
 }
 
@@ -404,10 +417,25 @@ impl<R: BlockRngCore<Item = u64>> RngCore for BlockRng64<R> {
         }
     }
 
-    #[inline(always)]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.fill_bytes(dest);
-        Ok(())
+    fn fill_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        let mut read_len = 0;
+        self.half_used = false;
+        while read_len < dest.len() {
+            if self.index >= self.results.as_ref().len() {
+                self.core.generate(&mut self.results);
+                self.index = 0;
+            }
+
+            let (consumed_u64, filled_u8) = fill_uninit_via_u64_chunks(
+                &self.results.as_ref()[self.index..],
+                &mut dest[read_len..],
+            );
+
+            self.index += consumed_u64;
+            read_len += filled_u8;
+        }
+        // Safety: all bytes were initialized above
+        unsafe { crate::slice_assume_init_mut(dest) }
     }
 }
 
@@ -538,4 +566,27 @@ mod test {
         (&mut c[12..]).copy_from_slice(&rng3.next_u32().to_le_bytes());
         assert_eq!(b, c);
     }
+
+    #[test]
+    fn blockrng_fill_bytes_uninit() {
+        use core::mem::MaybeUninit;
+
+        let mut rng1 = BlockRng::<DummyRng>::from_seed([1, 2, 3, 4]);
+        rng1.next_u32();
+        let mut rng2 = rng1.clone();
+        let mut a = [0u8; 70];
+        rng1.fill_bytes(&mut a);
+        let mut b = [MaybeUninit::uninit(); 70];
+        assert_eq!(rng2.fill_bytes_uninit(&mut b), &a[..]);
+        assert_eq!(rng1.next_u32(), rng2.next_u32());
+
+        let mut rng1 = BlockRng64::<DummyRng64>::from_seed([1, 2, 3, 4, 5, 6, 7, 8]);
+        rng1.next_u32();
+        let mut rng2 = rng1.clone();
+        let mut a = [0u8; 70];
+        rng1.fill_bytes(&mut a);
+        let mut b = [MaybeUninit::uninit(); 70];
+        assert_eq!(rng2.fill_bytes_uninit(&mut b), &a[..]);
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+    }
 }
@@ -17,8 +17,9 @@
 //! to/from byte sequences, and since its purpose is reproducibility,
 //! non-reproducible sources (e.g. `OsRng`) need not bother with it.
 
-use crate::RngCore;
+use crate::{RngCore, SeedableRng};
 use core::cmp::min;
+use core::mem::MaybeUninit;
 
 /// Implement `next_u64` via `next_u32`, little-endian order.
 pub fn next_u64_via_u32<R: RngCore + ?Sized>(rng: &mut R) -> u64 {
@@ -158,6 +159,27 @@ pub fn fill_via_u64_chunks(src: &mut [u64], dest: &mut [u8]) -> (usize, usize) {
     fill_via_chunks(src, dest)
 }
 
+macro_rules! fill_uninit_via_chunks {
+    ($name:ident, $t:ty) => {
+        /// As `fill_via_chunks`, but for an uninitialized `dest`, which is
+        /// initialized up to the returned `byte_len`.
+        pub(crate) fn $name(src: &[$t], dest: &mut [MaybeUninit<u8>]) -> (usize, usize) {
+            let size = core::mem::size_of::<$t>();
+            let byte_len = min(src.len() * size, dest.len());
+            let num_chunks = (byte_len + size - 1) / size;
+            for (chunk, x) in dest[..byte_len].chunks_mut(size).zip(src) {
+                for (byte, value) in chunk.iter_mut().zip(x.to_le_bytes()) {
+                    *byte = MaybeUninit::new(value);
+                }
+            }
+            (num_chunks, byte_len)
+        }
+    };
+}
+
+fill_uninit_via_chunks!(fill_uninit_via_u32_chunks, u32);
+fill_uninit_via_chunks!(fill_uninit_via_u64_chunks, u64);
+
 /// Implement `next_u32` via `fill_bytes`, little-endian order.
 pub fn next_u32_via_fill<R: RngCore + ?Sized>(rng: &mut R) -> u32 {
     let mut buf = [0; 4];
@@ -172,6 +194,27 @@ pub fn next_u64_via_fill<R: RngCore + ?Sized>(rng: &mut R) -> u64 {
     u64::from_le_bytes(buf)
 }
 
+/// Implement `MixEntropy` by reseeding from the RNG's own output combined
+/// with `entropy`.
+///
+/// `entropy` is split into chunks of the seed length; for each chunk, a seed
+/// is generated by `rng`, the chunk is XORed into it, and `rng` is replaced
+/// via `from_seed`. The new state thus depends on both the previous state
+/// and all of `entropy`. If `entropy` is empty, `rng` is unchanged.
+///
+/// [`MixEntropy`]: crate::MixEntropy
+pub fn mix_entropy_via_seed<R: RngCore + SeedableRng>(rng: &mut R, entropy: &[u8]) {
+    let seed_len = R::Seed::default().as_mut().len();
+    for chunk in entropy.chunks(seed_len.max(1)) {
+        let mut seed = R::Seed::default();
+        rng.fill_bytes(seed.as_mut());
+        for (x, y) in seed.as_mut().iter_mut().zip(chunk.iter()) {
+            *x ^= *y;
+        }
+        *rng = R::from_seed(seed);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -215,4 +258,38 @@ mod test {
         assert_eq!(fill_via_u64_chunks(&mut src, &mut dst), (1, 5));
         assert_eq!(dst, [1, 0, 0, 0, 0]);
     }
+
+    #[test]
+    fn test_mix_entropy_via_seed() {
+        // Yields its state, then increments it
+        #[derive(Debug, PartialEq)]
+        struct CountingRng(u32);
+        impl RngCore for CountingRng {
+            fn next_u32(&mut self) -> u32 {
+                self.0 += 1;
+                self.0 - 1
+            }
+            fn next_u64(&mut self) -> u64 {
+                next_u64_via_u32(self)
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                fill_bytes_via_next(self, dest)
+            }
+        }
+        impl SeedableRng for CountingRng {
+            type Seed = [u8; 4];
+            fn from_seed(seed: Self::Seed) -> Self {
+                CountingRng(u32::from_le_bytes(seed))
+            }
+        }
+
+        let mut rng = CountingRng(7);
+        mix_entropy_via_seed(&mut rng, &[]);
+        assert_eq!(rng, CountingRng(7));
+        mix_entropy_via_seed(&mut rng, &[1, 2]);
+        assert_eq!(rng, CountingRng(0x0201 ^ 7));
+        // One reseed per chunk of 4 bytes
+        mix_entropy_via_seed(&mut rng, &[0, 0, 0, 1, 2]);
+        assert_eq!(rng, CountingRng((0x0206 ^ 0x0100_0000) ^ 2));
+    }
 }
@@ -14,7 +14,9 @@
 //! which re-exports the main traits and error types.
 //!
 //! [`RngCore`] is the core trait implemented by algorithmic pseudo-random number
-//! generators and external random-number sources.
+//! generators and external random-number sources. [`TryRngCore`] is its
+//! fallible counterpart, implemented by sources which may fail and
+//! automatically implemented for every `RngCore`.
 //!
 //! [`SeedableRng`] is an extension trait for construction from fixed seeds and
 //! other random number generators.
@@ -40,6 +42,8 @@
 
 use core::convert::AsMut;
 use core::default::Default;
+use core::fmt;
+use core::mem::MaybeUninit;
 
 #[cfg(feature = "alloc")] extern crate alloc;
 #[cfg(feature = "std")] extern crate std;
@@ -72,9 +76,12 @@ pub mod le;
 /// random bits for efficiency.
 ///
 /// The [`try_fill_bytes`] method is a variant of [`fill_bytes`] allowing error
-/// handling; it is not deemed sufficiently useful to add equivalents for
-/// [`next_u32`] or [`next_u64`] since the latter methods are almost always used
-/// with algorithmic generators (PRNGs), which are normally infallible.
+/// handling. Its default implementation never fails, thus infallible
+/// generators (PRNGs) need not implement it. Sources which can fail, e.g.
+/// hardware or external RNGs, should instead implement [`TryRngCore`],
+/// reporting errors from every method; wrapping such a source in
+/// [`UnwrapErr`] then provides an `RngCore` implementation which panics on
+/// error.
 ///
 /// Implementers should produce bits uniformly. Pathological RNGs (e.g. always
 /// returning the same value, or never setting certain bits) can break rejection
@@ -110,7 +117,7 @@ pub mod le;
 ///
 /// ```
 /// #![allow(dead_code)]
-/// use rand_core::{RngCore, Error, impls};
+/// use rand_core::{RngCore, impls};
 ///
 /// struct CountingRng(u64);
 ///
@@ -127,10 +134,6 @@ pub mod le;
 ///     fn fill_bytes(&mut self, dest: &mut [u8]) {
 ///         impls::fill_bytes_via_next(self, dest)
 ///     }
-///
-///     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-///         Ok(self.fill_bytes(dest))
-///     }
 /// }
 /// ```
 ///
@@ -173,15 +176,55 @@ pub trait RngCore {
     /// Fill `dest` entirely with random data.
     ///
     /// This is the only method which allows an RNG to report errors while
-    /// generating random data thus making this the primary method implemented
-    /// by external (true) RNGs (e.g. `OsRng`) which can fail. It may be used
-    /// directly to generate keys and to seed (infallible) PRNGs.
+    /// generating random data. It may be used directly to generate keys and to
+    /// seed (infallible) PRNGs. Sources which can fail, e.g. `OsRng`, instead
+    /// implement [`TryRngCore`].
     ///
-    /// Other than error handling, this method is identical to [`RngCore::fill_bytes`];
-    /// thus this may be implemented using `Ok(self.fill_bytes(dest))` or
-    /// `fill_bytes` may be implemented with
+    /// Other than error handling, this method is identical to [`RngCore::fill_bytes`].
+    /// The default implementation calls `fill_bytes` and never fails, as is
+    /// appropriate for infallible generators. Fallible RNGs implementing this
+    /// trait should override it, and may implement `fill_bytes` with
     /// `self.try_fill_bytes(dest).unwrap()` or more specific error handling.
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error>;
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+
+    /// Fill the possibly uninitialized buffer `dest` entirely with random
+    /// data, returning it as an initialized slice.
+    ///
+    /// The output is identical to that of [`RngCore::fill_bytes`]. This
+    /// allows generating large random blocks without first zeroing the
+    /// buffer. The default implementation zeroes `dest`, then calls
+    /// `fill_bytes`; RNGs should override it where they can write their
+    /// output directly, as [`block::BlockRng`] does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use rand_core::{RngCore, impls};
+    ///
+    /// struct CountingRng(u64);
+    /// impl RngCore for CountingRng {
+    ///     fn next_u32(&mut self) -> u32 { self.next_u64() as u32 }
+    ///     fn next_u64(&mut self) -> u64 { self.0 += 1; self.0 }
+    ///     fn fill_bytes(&mut self, dest: &mut [u8]) { impls::fill_bytes_via_next(self, dest) }
+    /// }
+    ///
+    /// let mut buf = [MaybeUninit::uninit(); 4];
+    /// let bytes = CountingRng(0).fill_bytes_uninit(&mut buf);
+    /// assert_eq!(bytes, [1, 0, 0, 0]);
+    /// ```
+    fn fill_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        for byte in dest.iter_mut() {
+            *byte = MaybeUninit::new(0);
+        }
+        // Safety: all bytes were initialized above
+        let dest = unsafe { slice_assume_init_mut(dest) };
+        self.fill_bytes(dest);
+        dest
+    }
 
     /// Convert an [`RngCore`] to a [`RngReadAdapter`].
     #[cfg(feature = "std")]
@@ -191,6 +234,17 @@ pub trait RngCore {
     }
 }
 
+/// Convert a fully initialized slice of `MaybeUninit<u8>` to `&mut [u8]`.
+///
+/// # Safety
+///
+/// All elements of `slice` must be initialized.
+#[inline(always)]
+pub(crate) unsafe fn slice_assume_init_mut(slice: &mut [MaybeUninit<u8>]) -> &mut [u8] {
+    // `MaybeUninit<u8>` has the same layout as `u8`
+    &mut *(slice as *mut [MaybeUninit<u8>] as *mut [u8])
+}
+
 /// A marker trait used to indicate that an [`RngCore`] implementation is
 /// supposed to be cryptographically secure.
 ///
@@ -215,6 +269,315 @@ pub trait RngCore {
 /// [`BlockRngCore`]: block::BlockRngCore
 pub trait CryptoRng: RngCore {}
 
+/// An extension trait that is automatically implemented for any type
+/// implementing [`RngCore`] and [`CryptoRng`].
+///
+/// Unlike `CryptoRng + RngCore`, this is a single trait, thus may be used as
+/// a trait object to require a cryptographically secure generator. It
+/// supports upcasting to [`RngCore`] via the [`CryptoRngCore::as_rngcore`]
+/// method.
+///
+/// # Example
+///
+/// ```
+/// use rand_core::CryptoRngCore;
+///
+/// #[allow(unused)]
+/// fn make_token(rng: &mut dyn CryptoRngCore) -> [u8; 32] {
+///     let mut buf = [0u8; 32];
+///     rng.fill_bytes(&mut buf);
+///     buf
+/// }
+/// ```
+pub trait CryptoRngCore: CryptoRng + RngCore {
+    /// Upcast to an [`RngCore`] trait object.
+    fn as_rngcore(&mut self) -> &mut dyn RngCore;
+}
+
+impl<T: CryptoRng + RngCore> CryptoRngCore for T {
+    fn as_rngcore(&mut self) -> &mut dyn RngCore {
+        self
+    }
+}
+
+/// The core of a random number generator which may fail.
+///
+/// This is the counterpart of [`RngCore`] for sources which can fail, e.g.
+/// the operating system's RNG or a hardware RNG: every method reports errors
+/// via the associated [`TryRngCore::Error`] type. It is automatically
+/// implemented for every `RngCore`, forwarding errors from
+/// [`RngCore::try_fill_bytes`], thus functions requiring only a fallible
+/// source can accept `R: TryRngCore` and be used with any RNG.
+///
+/// A fallible source should implement only this trait, not `RngCore`, as
+/// [`OsRng`] does. Users who prefer to panic on error, e.g. to use the
+/// [`rand`] `Rng` methods, can wrap it via [`TryRngCore::unwrap_err`].
+///
+/// Methods of this trait are named differently from those of [`RngCore`],
+/// thus both traits may be in scope without ambiguity.
+///
+/// # Example
+///
+/// ```
+/// use rand_core::{RngCore, TryRngCore};
+///
+/// /// A source failing once its bytes are exhausted
+/// struct Exhaustible(u64);
+///
+/// impl TryRngCore for Exhaustible {
+///     type Error = &'static str;
+///
+///     fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+///         self.try_next_u64().map(|x| x as u32)
+///     }
+///
+///     fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+///         self.0 = self.0.checked_sub(1).ok_or("exhausted")?;
+///         Ok(self.0)
+///     }
+///
+///     fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Self::Error> {
+///         for chunk in dest.chunks_mut(8) {
+///             let x = self.try_next_u64()?.to_le_bytes();
+///             chunk.copy_from_slice(&x[..chunk.len()]);
+///         }
+///         Ok(())
+///     }
+/// }
+///
+/// let mut source = Exhaustible(2);
+/// assert_eq!(source.try_next_u64(), Ok(1));
+/// assert_eq!(source.unwrap_err().next_u64(), 0);
+/// assert_eq!(Exhaustible(0).try_next_u32(), Err("exhausted"));
+/// ```
+///
+/// [`rand`]: https://docs.rs/rand
+/// [`OsRng`]: https://docs.rs/rand_core/latest/rand_core/struct.OsRng.html
+pub trait TryRngCore {
+    /// The type returned in the event of an error.
+    type Error: fmt::Debug + fmt::Display;
+
+    /// Return the next random `u32`.
+    fn try_next_u32(&mut self) -> Result<u32, Self::Error>;
+
+    /// Return the next random `u64`.
+    fn try_next_u64(&mut self) -> Result<u64, Self::Error>;
+
+    /// Fill `dest` entirely with random data.
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Wrap in an [`UnwrapErr`], implementing [`RngCore`] by panicking on
+    /// error.
+    fn unwrap_err(self) -> UnwrapErr<Self>
+    where Self: Sized {
+        UnwrapErr(self)
+    }
+}
+
+impl<R: RngCore + ?Sized> TryRngCore for R {
+    type Error = Error;
+
+    #[inline]
+    fn try_next_u32(&mut self) -> Result<u32, Error> {
+        Ok(self.next_u32())
+    }
+
+    #[inline]
+    fn try_next_u64(&mut self) -> Result<u64, Error> {
+        Ok(self.next_u64())
+    }
+
+    #[inline]
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.try_fill_bytes(dest)
+    }
+}
+
+/// A marker trait indicating that a [`TryRngCore`] implementation is
+/// supposed to be cryptographically secure.
+///
+/// This is the counterpart of [`CryptoRng`] for fallible sources, and is
+/// automatically implemented for every `CryptoRng`.
+pub trait TryCryptoRng: TryRngCore {}
+
+impl<R: CryptoRng + ?Sized> TryCryptoRng for R {}
+
+/// A wrapper around a [`TryRngCore`] implementing [`RngCore`] by panicking
+/// on error.
+///
+/// This is usually constructed via [`TryRngCore::unwrap_err`]. It
+/// implements [`CryptoRng`] if the wrapped source implements
+/// [`TryCryptoRng`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnwrapErr<R: TryRngCore>(pub R);
+
+impl<R: TryRngCore> RngCore for UnwrapErr<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.0.try_next_u32().unwrap()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.0.try_next_u64().unwrap()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.0.try_fill(dest).unwrap()
+    }
+}
+
+impl<R: TryCryptoRng> CryptoRng for UnwrapErr<R> {}
+
+/// A random number generator which can efficiently skip ahead in its output
+/// sequence.
+///
+/// This allows splitting the output of one generator into non-overlapping
+/// sub-sequences, e.g. one per worker thread of a parallel simulation:
+/// starting from the same state, each worker calls [`jump`] a different
+/// number of times. Provided that no worker requests more values than the
+/// jump distance, the sub-sequences are guaranteed not to overlap.
+///
+/// Jump distances are fixed but differ between generators, and are
+/// documented by each implementation. They are measured in steps of the
+/// generator, i.e. `u32` or `u64` outputs, depending on its word size. The
+/// distance of [`long_jump`] is larger than that of [`jump`], allowing a
+/// second level of splitting: e.g. one `long_jump` per machine and one
+/// `jump` per thread.
+///
+/// Calling either method discards any buffered output; the generator then
+/// continues from the new position.
+///
+/// # Example
+///
+/// ```
+/// #![allow(dead_code)]
+/// use rand_core::JumpableRng;
+///
+/// fn worker_rngs<R: JumpableRng + Clone>(mut rng: R, n: usize) -> Vec<R> {
+///     (0..n).map(|_| {
+///         let worker = rng.clone();
+///         rng.jump();
+///         worker
+///     }).collect()
+/// }
+/// ```
+///
+/// [`jump`]: JumpableRng::jump
+/// [`long_jump`]: JumpableRng::long_jump
+pub trait JumpableRng: RngCore {
+    /// Advance the generator by its (short) jump distance.
+    fn jump(&mut self);
+
+    /// Advance the generator by its long jump distance.
+    fn long_jump(&mut self);
+}
+
+/// A random number generator providing multiple output streams per seed.
+///
+/// Generators with the same seed but different streams yield different
+/// output sequences. This allows e.g. seeding all workers of a parallel
+/// computation identically, then assigning each its own stream:
+///
+/// ```
+/// #![allow(dead_code)]
+/// use rand_core::{SeedableRng, StreamRng};
+///
+/// fn worker_rng<R: SeedableRng + StreamRng>(seed: R::Seed, worker: u64) -> R {
+///     let mut rng = R::from_seed(seed);
+///     rng.set_stream(worker);
+///     rng
+/// }
+/// ```
+///
+/// The number of available streams, and how strongly the output of distinct
+/// streams may be correlated, are documented by each implementation.
+pub trait StreamRng: RngCore {
+    /// Select the stream `stream`, keeping the current position otherwise.
+    ///
+    /// Implementations with fewer than 2<sup>64</sup> streams may discard the
+    /// highest bits of `stream`.
+    fn set_stream(&mut self, stream: u64);
+
+    /// Get the current stream, as set via [`set_stream`].
+    ///
+    /// Implementations with more than 2<sup>64</sup> streams return the
+    /// lowest 64 bits of the stream.
+    ///
+    /// [`set_stream`]: StreamRng::set_stream
+    fn stream(&self) -> u64;
+}
+
+/// A random number generator which can be split into independent generators.
+///
+/// [`split`] derives a child generator from the parent's state, advancing
+/// the parent. Both then yield output which is statistically independent
+/// (for cryptographic generators, computationally indistinguishable from
+/// independent). This allows deterministic, tree-structured use of one seed,
+/// e.g. one generator per node of a parse tree or per task of a parallel
+/// computation, where output must not depend on the order in which branches
+/// are evaluated:
+///
+/// ```
+/// #![allow(dead_code)]
+/// use rand_core::SplitRng;
+///
+/// enum Tree { Leaf, Node(Box<Tree>, Box<Tree>) }
+///
+/// fn visit<R: SplitRng>(tree: &Tree, rng: &mut R, out: &mut Vec<u32>) {
+///     match tree {
+///         Tree::Leaf => out.push(rng.next_u32()),
+///         Tree::Node(left, right) => {
+///             // The right subtree's values do not depend on the left's
+///             let mut right_rng = rng.split();
+///             visit(left, rng, out);
+///             visit(right, &mut right_rng, out);
+///         }
+///     }
+/// }
+/// ```
+///
+/// Unlike [`SeedableRng::from_rng`], implementations of this trait take care
+/// to derive the child in a way appropriate for the algorithm, even when
+/// seeding a generator from output of its own type could yield correlated
+/// sequences.
+///
+/// [`split`]: SplitRng::split
+pub trait SplitRng: RngCore + Sized {
+    /// Derive a new generator from this one, advancing `self`.
+    fn split(&mut self) -> Self;
+}
+
+/// A random number generator which can absorb additional entropy.
+///
+/// This allows stirring caller-supplied bytes, e.g. event timings, into the
+/// state of an existing generator without fully reseeding it from an
+/// external source. Mixing in predictable or even attacker-controlled bytes
+/// does not reduce the unpredictability of the output: the new state
+/// depends on both the previous state and the input.
+///
+/// Generators implementing [`SeedableRng`] may implement this via
+/// [`impls::mix_entropy_via_seed`].
+///
+/// # Example
+///
+/// ```
+/// #![allow(dead_code)]
+/// use rand_core::MixEntropy;
+///
+/// fn on_event<R: MixEntropy>(rng: &mut R, timestamp_nanos: u64) {
+///     rng.mix_entropy(&timestamp_nanos.to_le_bytes());
+/// }
+/// ```
+pub trait MixEntropy: RngCore {
+    /// Mix `entropy` into the state of this generator.
+    ///
+    /// The output after mixing is unlike the output without mixing; this
+    /// is deterministic for generators implementing [`SeedableRng`].
+    fn mix_entropy(&mut self, entropy: &[u8]);
+}
+
 /// A random number generator that can be explicitly seeded.
 ///
 /// This trait encapsulates the low-level functionality common to all
@@ -371,6 +734,27 @@ pub trait SeedableRng: Sized {
         Ok(Self::from_seed(seed))
     }
 
+    /// Create a new PRNG seeded from a possibly fallible source.
+    ///
+    /// This is identical to [`SeedableRng::from_rng`], but accepts any
+    /// [`TryRngCore`], e.g. `OsRng`, and returns its error type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #![allow(dead_code)]
+    /// use rand_core::{SeedableRng, TryRngCore};
+    ///
+    /// fn reseed<R: SeedableRng, S: TryRngCore>(source: &mut S) -> Result<R, S::Error> {
+    ///     R::try_from_rng(source)
+    /// }
+    /// ```
+    fn try_from_rng<R: TryRngCore + ?Sized>(rng: &mut R) -> Result<Self, R::Error> {
+        let mut seed = Self::Seed::default();
+        rng.try_fill(seed.as_mut())?;
+        Ok(Self::from_seed(seed))
+    }
+
     /// Creates a new instance of the RNG seeded via [`getrandom`].
     ///
     /// This method is the recommended way to construct non-deterministic PRNGs
@@ -419,6 +803,11 @@ impl<'a, R: RngCore + ?Sized> RngCore for &'a mut R {
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
         (**self).try_fill_bytes(dest)
     }
+
+    #[inline(always)]
+    fn fill_bytes_uninit<'b>(&mut self, dest: &'b mut [MaybeUninit<u8>]) -> &'b mut [u8] {
+        (**self).fill_bytes_uninit(dest)
+    }
 }
 
 // Implement `RngCore` for boxed references to an `RngCore`.
@@ -445,6 +834,11 @@ impl<R: RngCore + ?Sized> RngCore for Box<R> {
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
         (**self).try_fill_bytes(dest)
     }
+
+    #[inline(always)]
+    fn fill_bytes_uninit<'b>(&mut self, dest: &'b mut [MaybeUninit<u8>]) -> &'b mut [u8] {
+        (**self).fill_bytes_uninit(dest)
+    }
 }
 
 /// Adapter that enables reading through a [`io::Read`](std::io::Read) from a [`RngCore`].
@@ -454,9 +848,9 @@ impl<R: RngCore + ?Sized> RngCore for Box<R> {
 /// ```no_run
 /// # use std::{io, io::Read};
 /// # use std::fs::File;
-/// # use rand_core::{OsRng, RngCore};
+/// # use rand_core::{OsRng, RngCore, TryRngCore};
 ///
-/// io::copy(&mut OsRng.read_adapter().take(100), &mut File::create("/tmp/random.bytes").unwrap()).unwrap();
+/// io::copy(&mut OsRng.unwrap_err().read_adapter().take(100), &mut File::create("/tmp/random.bytes").unwrap()).unwrap();
 /// ```
 #[cfg(feature = "std")]
 pub struct RngReadAdapter<'a, R: RngCore + ?Sized> {
@@ -529,4 +923,122 @@ mod test {
         // value-breakage test:
         assert_eq!(results[0], 5029875928683246316);
     }
+
+    #[test]
+    fn test_crypto_rng_core() {
+        struct CountingRng(u64);
+        impl RngCore for CountingRng {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+            fn next_u64(&mut self) -> u64 {
+                self.0 += 1;
+                self.0
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_next(self, dest)
+            }
+        }
+        impl CryptoRng for CountingRng {}
+
+        fn use_dyn(rng: &mut dyn CryptoRngCore) -> u32 {
+            rng.next_u32() + rng.as_rngcore().next_u32()
+        }
+        // Accepts `dyn CryptoRngCore` as `CryptoRng + RngCore`
+        fn use_generic<R: CryptoRng + RngCore + ?Sized>(rng: &mut R) -> u64 {
+            rng.next_u64()
+        }
+
+        let mut rng = CountingRng(0);
+        assert_eq!(use_dyn(&mut rng), 3);
+        let dyn_rng: &mut dyn CryptoRngCore = &mut rng;
+        assert_eq!(use_generic(dyn_rng), 3);
+    }
+
+    #[test]
+    fn test_try_rng_core() {
+        struct CountingRng(u64);
+        impl RngCore for CountingRng {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+            fn next_u64(&mut self) -> u64 {
+                self.0 += 1;
+                self.0
+            }
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                impls::fill_bytes_via_next(self, dest)
+            }
+        }
+
+        // A source yielding decreasing bytes, failing once exhausted
+        struct FailingRng(u8);
+        impl TryRngCore for FailingRng {
+            type Error = &'static str;
+            fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+                let mut buf = [0; 4];
+                self.try_fill(&mut buf)?;
+                Ok(u32::from_le_bytes(buf))
+            }
+            fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+                let mut buf = [0; 8];
+                self.try_fill(&mut buf)?;
+                Ok(u64::from_le_bytes(buf))
+            }
+            fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Self::Error> {
+                for byte in dest {
+                    self.0 = self.0.checked_sub(1).ok_or("exhausted")?;
+                    *byte = self.0;
+                }
+                Ok(())
+            }
+        }
+
+        struct SeedableNum(u32);
+        impl SeedableRng for SeedableNum {
+            type Seed = [u8; 4];
+            fn from_seed(seed: Self::Seed) -> Self {
+                SeedableNum(u32::from_le_bytes(seed))
+            }
+        }
+
+        // Infallible RNGs need not implement `try_fill_bytes`
+        let mut rng = CountingRng(0);
+        let mut buf = [0u8; 4];
+        assert!(rng.try_fill_bytes(&mut buf).is_ok());
+        assert_eq!(buf, [1, 0, 0, 0]);
+        // Every `RngCore` implements `TryRngCore`
+        assert_eq!(rng.try_next_u32().ok(), Some(2));
+        assert_eq!(rng.try_next_u64().ok(), Some(3));
+        assert_eq!(SeedableNum::try_from_rng(&mut rng).ok().map(|x| x.0), Some(4));
+
+        let mut buf = [0u8; 4];
+        assert_eq!(FailingRng(6).try_fill(&mut buf), Ok(()));
+        assert_eq!(buf, [5, 4, 3, 2]);
+        assert_eq!(FailingRng(3).try_fill(&mut buf), Err("exhausted"));
+        assert_eq!(SeedableNum::try_from_rng(&mut FailingRng(5)).map(|x| x.0), Ok(0x0102_0304));
+        assert!(SeedableNum::try_from_rng(&mut FailingRng(3)).is_err());
+        let mut rng = FailingRng(8).unwrap_err();
+        assert_eq!(rng.next_u32(), 0x0405_0607);
+        assert_eq!(rng.next_u32(), 0x0001_0203);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unwrap_err_panic() {
+        struct FailingRng;
+        impl TryRngCore for FailingRng {
+            type Error = &'static str;
+            fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+                Err("failure")
+            }
+            fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+                Err("failure")
+            }
+            fn try_fill(&mut self, _: &mut [u8]) -> Result<(), Self::Error> {
+                Err("failure")
+            }
+        }
+        FailingRng.unwrap_err().next_u64();
+    }
 }
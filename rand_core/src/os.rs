@@ -8,7 +8,7 @@
 
 //! Interface to the random number generator of the operating system.
 
-use crate::{impls, CryptoRng, Error, RngCore};
+use crate::{Error, TryCryptoRng, TryRngCore};
 use getrandom::getrandom;
 
 /// A random number generator that retrieves randomness from the
@@ -34,47 +34,57 @@ use getrandom::getrandom;
 /// significant delays will occur (although performance should be expected to
 /// be much slower than a user-space PRNG).
 ///
+/// `OsRng` therefore implements [`TryRngCore`], reporting errors from every
+/// method, but not [`RngCore`](crate::RngCore). Use
+/// [`TryRngCore::unwrap_err`] where an `RngCore` panicking on error is
+/// preferred, or seed a PRNG via [`SeedableRng::try_from_rng`].
+///
 /// # Usage example
 /// ```
-/// use rand_core::{RngCore, OsRng};
+/// use rand_core::{OsRng, RngCore, TryRngCore};
 ///
 /// let mut key = [0u8; 16];
-/// OsRng.fill_bytes(&mut key);
-/// let random_u64 = OsRng.next_u64();
+/// OsRng.try_fill(&mut key).unwrap();
+/// let random_u64 = OsRng.try_next_u64().unwrap();
+///
+/// // Panic on error instead
+/// let random_u32 = OsRng.unwrap_err().next_u32();
 /// ```
 ///
+/// [`SeedableRng::try_from_rng`]: crate::SeedableRng::try_from_rng
+///
 /// [getrandom]: https://crates.io/crates/getrandom
 #[cfg_attr(doc_cfg, doc(cfg(feature = "getrandom")))]
 #[derive(Clone, Copy, Debug, Default)]
 pub struct OsRng;
 
-impl CryptoRng for OsRng {}
+impl TryRngCore for OsRng {
+    type Error = Error;
 
-impl RngCore for OsRng {
-    fn next_u32(&mut self) -> u32 {
-        impls::next_u32_via_fill(self)
+    fn try_next_u32(&mut self) -> Result<u32, Error> {
+        let mut buf = [0; 4];
+        self.try_fill(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
     }
 
-    fn next_u64(&mut self) -> u64 {
-        impls::next_u64_via_fill(self)
+    fn try_next_u64(&mut self) -> Result<u64, Error> {
+        let mut buf = [0; 8];
+        self.try_fill(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
     }
 
-    fn fill_bytes(&mut self, dest: &mut [u8]) {
-        if let Err(e) = self.try_fill_bytes(dest) {
-            panic!("Error: {}", e);
-        }
-    }
-
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
         getrandom(dest)?;
         Ok(())
     }
 }
 
+impl TryCryptoRng for OsRng {}
+
 #[test]
 fn test_os_rng() {
-    let x = OsRng.next_u64();
-    let y = OsRng.next_u64();
+    let x = OsRng.try_next_u64().unwrap();
+    let y = OsRng.try_next_u64().unwrap();
     assert!(x != 0);
     assert!(x != y);
 }
@@ -82,5 +92,5 @@ fn test_os_rng() {
 #[test]
 fn test_construction() {
     let mut rng = OsRng::default();
-    assert!(rng.next_u64() != 0);
+    assert!(rng.try_next_u64().unwrap() != 0);
 }
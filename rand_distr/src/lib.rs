@@ -72,12 +72,19 @@
 //!   - [`Triangular`] distribution
 //! - Multivariate probability distributions
 //!   - [`Dirichlet`] distribution
+//!   - [`Multinomial`] distribution
 //!   - [`UnitSphere`] distribution
 //!   - [`UnitBall`] distribution
 //!   - [`UnitCircle`] distribution
 //!   - [`UnitDisc`] distribution
-//! - Alternative implementation for weighted index sampling
+//! - Alternative implementations for weighted index sampling
 //!   - [`WeightedAliasIndex`] distribution
+//!   - [`WeightedFenwickIndex`] distribution
+//!   - [`WeightedTreeIndex`] distribution
+//!   - [`LogWeightedIndex`] distribution, using log-weights
+//! - Wrappers restricting distributions to an interval
+//!   - [`Truncated`] distribution
+//!   - [`Clamped`] distribution
 //! - Misc. distributions
 //!   - [`InverseGaussian`] distribution
 //!   - [`NormalInverseGaussian`] distribution
@@ -112,6 +119,12 @@ pub use self::geometric::{Error as GeoError, Geometric, StandardGeometric};
 pub use self::gumbel::{Error as GumbelError, Gumbel};
 pub use self::hypergeometric::{Error as HyperGeoError, Hypergeometric};
 pub use self::inverse_gaussian::{Error as InverseGaussianError, InverseGaussian};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::log_weighted_index::LogWeightedIndex;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::multinomial::{Error as MultinomialError, Multinomial};
 pub use self::normal::{Error as NormalError, LogNormal, Normal, StandardNormal};
 pub use self::normal_inverse_gaussian::{
     Error as NormalInverseGaussianError, NormalInverseGaussian,
@@ -121,6 +134,7 @@ pub use self::pert::{Pert, PertError};
 pub use self::poisson::{Error as PoissonError, Poisson};
 pub use self::skew_normal::{Error as SkewNormalError, SkewNormal};
 pub use self::triangular::{Triangular, TriangularError};
+pub use self::truncated::{Clamped, Error as TruncatedError, Truncated};
 pub use self::unit_ball::UnitBall;
 pub use self::unit_circle::UnitCircle;
 pub use self::unit_disc::UnitDisc;
@@ -133,6 +147,12 @@ pub use rand::distributions::{WeightedError, WeightedIndex};
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub use weighted_alias::WeightedAliasIndex;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use weighted_fenwick::WeightedFenwickIndex;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use weighted_tree::WeightedTreeIndex;
 
 pub use num_traits;
 
@@ -186,6 +206,12 @@ mod test {
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub mod weighted_alias;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod weighted_fenwick;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub mod weighted_tree;
 
 mod binomial;
 mod cauchy;
@@ -197,6 +223,8 @@ mod geometric;
 mod gumbel;
 mod hypergeometric;
 mod inverse_gaussian;
+mod log_weighted_index;
+mod multinomial;
 mod normal;
 mod normal_inverse_gaussian;
 mod pareto;
@@ -204,6 +232,7 @@ mod pert;
 mod poisson;
 mod skew_normal;
 mod triangular;
+mod truncated;
 mod unit_ball;
 mod unit_circle;
 mod unit_disc;
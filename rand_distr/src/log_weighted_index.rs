@@ -0,0 +1,172 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Weighted index sampling from log-weights using the Gumbel-max trick.
+#![cfg(feature = "alloc")]
+
+use crate::{Distribution, Open01, WeightedError};
+use alloc::vec::Vec;
+use num_traits::Float;
+use rand::Rng;
+
+/// A distribution using weighted sampling of discrete items, where weights
+/// are given as (natural) logarithms.
+///
+/// Sampling a [`LogWeightedIndex<F>`] returns the index of a randomly selected
+/// item, where the chance of item `i` being picked is proportional to
+/// `exp(log_weights[i])`. Log-weights need not be normalized, and may span
+/// hundreds of orders of magnitude without overflow or underflow, e.g.
+/// unnormalized log-likelihoods or log-probabilities. Items with log-weight
+/// `-inf` (weight zero) are never picked.
+///
+/// Exponentiating such log-weights for use with [`WeightedIndex`] would
+/// instead overflow or round small weights to zero; even after subtracting
+/// the largest log-weight, weights below `exp(-745)` are lost in `f64`.
+///
+/// # Performance
+///
+/// Sampling uses the Gumbel-max trick: a Gumbel-distributed random value is
+/// added to each log-weight, and the index of the largest sum is returned.
+/// Each sample thus requires `n` random values and logarithms for `n` items,
+/// but no setup beyond copying the log-weights.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, LogWeightedIndex};
+///
+/// // Log-likelihoods of three hypotheses
+/// let log_weights = [-1000.0, -1000.0 - 2f64.ln(), -1500.0];
+/// let dist = LogWeightedIndex::new(log_weights).unwrap();
+/// // About 67% chance of 0, 33% chance of 1 and a negligible chance of 2
+/// println!("{}", dist.sample(&mut rand::thread_rng()));
+/// ```
+///
+/// [`LogWeightedIndex<F>`]: LogWeightedIndex
+/// [`WeightedIndex`]: crate::WeightedIndex
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct LogWeightedIndex<F> {
+    log_weights: Vec<F>,
+}
+
+impl<F: Float> LogWeightedIndex<F> {
+    /// Creates a new [`LogWeightedIndex`] using the values in `log_weights`.
+    ///
+    /// Returns an error if the iterator is empty, if any log-weight is NaN or
+    /// `+inf`, or if all log-weights are `-inf`.
+    pub fn new<I>(log_weights: I) -> Result<Self, WeightedError>
+    where I: IntoIterator<Item = F> {
+        let log_weights: Vec<F> = log_weights.into_iter().collect();
+        if log_weights.is_empty() {
+            return Err(WeightedError::NoItem);
+        }
+        if log_weights.iter().any(|w| w.is_nan() || *w == F::infinity()) {
+            return Err(WeightedError::InvalidWeight);
+        }
+        if log_weights.iter().all(|w| *w == F::neg_infinity()) {
+            return Err(WeightedError::AllWeightsZero);
+        }
+        Ok(LogWeightedIndex { log_weights })
+    }
+
+    /// Returns the log-weights.
+    #[inline]
+    pub fn log_weights(&self) -> &[F] {
+        &self.log_weights
+    }
+}
+
+impl<F> Distribution<usize> for LogWeightedIndex<F>
+where
+    F: Float,
+    Open01: Distribution<F>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let mut best = 0;
+        let mut best_key = F::neg_infinity();
+        for (i, &log_weight) in self.log_weights.iter().enumerate() {
+            if log_weight == F::neg_infinity() {
+                continue;
+            }
+            // Add a standard Gumbel sample `-ln(-ln(u))`
+            let u: F = rng.sample(Open01);
+            let key = log_weight - (-u.ln()).ln();
+            if key > best_key {
+                best = i;
+                best_key = key;
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            LogWeightedIndex::<f64>::new([]).unwrap_err(),
+            WeightedError::NoItem
+        );
+        assert_eq!(
+            LogWeightedIndex::new([0.0, f64::NAN]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            LogWeightedIndex::new([0.0, f64::INFINITY]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            LogWeightedIndex::new([f64::NEG_INFINITY; 2]).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = crate::test::rng(371);
+        // Weights 1, 0, 2, 1 scaled by exp(-5000), and an insignificant weight
+        let offset = -5000.0f64;
+        let log_weights = [
+            offset,
+            f64::NEG_INFINITY,
+            offset + 2f64.ln(),
+            offset,
+            offset - 1000.0,
+        ];
+        let distr = LogWeightedIndex::new(log_weights).unwrap();
+        assert_eq!(distr.log_weights(), log_weights);
+        let mut counts = [0; 5];
+        for _ in 0..10_000 {
+            counts[distr.sample(&mut rng)] += 1;
+        }
+        assert!((2300..2700).contains(&counts[0]));
+        assert_eq!(counts[1], 0);
+        assert!((4700..5300).contains(&counts[2]));
+        assert!((2300..2700).contains(&counts[3]));
+        assert_eq!(counts[4], 0);
+
+        let distr = LogWeightedIndex::new([f64::NEG_INFINITY, f64::MAX]).unwrap();
+        assert_eq!(distr.sample(&mut rng), 1);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(372);
+        let distr = LogWeightedIndex::new([0.0f32, 1.0, 2.0, -1.0]).unwrap();
+        let mut buf = [0; 10];
+        for x in buf.iter_mut() {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [3, 1, 1, 2, 2, 2, 1, 0, 2, 2]);
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The multinomial distribution.
+#![cfg(feature = "alloc")]
+
+use crate::{Binomial, Distribution};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use rand::Rng;
+
+/// The multinomial distribution `Multinomial(n, w)`.
+///
+/// Samples the number of times each category occurs in `n` independent
+/// trials, where category `i` is chosen with probability proportional to the
+/// weight `w[i]`. The result is a `Vec<u64>` with one count per category,
+/// summing to `n`.
+///
+/// This is equivalent to drawing `n` samples from a [`WeightedIndex`] and
+/// counting the occurrences of each index, but no per-trial work is done: the
+/// counts are sampled by conditional binomial splitting, i.e. the count of
+/// category `i` is sampled from `Binomial(m, w[i] / (w[i] + ... + w[k-1]))`,
+/// where `m` is the number of trials not yet assigned to earlier categories.
+/// Sampling is thus `O(k)` in the number of categories `k`, independent of `n`.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Multinomial};
+///
+/// let multinomial = Multinomial::new(100, &[1.0, 2.0, 7.0]).unwrap();
+/// let counts = multinomial.sample(&mut rand::thread_rng());
+/// assert_eq!(counts.len(), 3);
+/// assert_eq!(counts.iter().sum::<u64>(), 100);
+/// ```
+///
+/// [`WeightedIndex`]: crate::WeightedIndex
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Multinomial {
+    /// Number of trials.
+    n: u64,
+    /// Probability of each category, conditional on none of the previous
+    /// categories being chosen.
+    conditional: Vec<f64>,
+}
+
+/// Error type returned from `Multinomial::new`.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The weight slice is empty.
+    NoCategories,
+    /// A weight is negative, infinite or NaN.
+    InvalidWeight,
+    /// All weights are zero.
+    AllWeightsZero,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::NoCategories => "no weights given in multinomial distribution",
+            Error::InvalidWeight => "a weight is negative or not finite in multinomial distribution",
+            Error::AllWeightsZero => "all weights are zero in multinomial distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl Multinomial {
+    /// Construct a new `Multinomial` with `n` trials over categories with the
+    /// given (not necessarily normalized) `weights`.
+    pub fn new(n: u64, weights: &[f64]) -> Result<Multinomial, Error> {
+        if weights.is_empty() {
+            return Err(Error::NoCategories);
+        }
+        if weights.iter().any(|w| !(*w >= 0.0 && w.is_finite())) {
+            return Err(Error::InvalidWeight);
+        }
+
+        // Accumulate the remaining mass from the back, so that the last
+        // category with non-zero weight gets a conditional probability of
+        // exactly 1 and absorbs all remaining trials.
+        let mut conditional = Vec::with_capacity(weights.len());
+        let mut remaining = 0.0;
+        for &w in weights.iter().rev() {
+            remaining += w;
+            let p = if remaining > 0.0 { w / remaining } else { 0.0 };
+            conditional.push(p.min(1.0));
+        }
+        if !(remaining > 0.0) {
+            return Err(Error::AllWeightsZero);
+        }
+        if !remaining.is_finite() {
+            return Err(Error::InvalidWeight);
+        }
+        conditional.reverse();
+
+        Ok(Multinomial { n, conditional })
+    }
+
+    /// Returns the number of trials `n`.
+    #[inline]
+    pub fn num_trials(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns the number of categories.
+    #[inline]
+    pub fn num_categories(&self) -> usize {
+        self.conditional.len()
+    }
+
+    /// Sample the counts of each category into `counts`.
+    ///
+    /// This avoids allocating a new `Vec` for each sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `counts.len()` does not equal the number of categories.
+    pub fn sample_into<R: Rng + ?Sized>(&self, rng: &mut R, counts: &mut [u64]) {
+        assert_eq!(counts.len(), self.conditional.len());
+        let mut remaining = self.n;
+        for (count, &p) in counts.iter_mut().zip(self.conditional.iter()) {
+            *count = if remaining == 0 {
+                0
+            } else {
+                Binomial::new(remaining, p).unwrap().sample(rng)
+            };
+            remaining -= *count;
+        }
+        debug_assert_eq!(remaining, 0);
+    }
+}
+
+impl Distribution<Vec<u64>> for Multinomial {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Vec<u64> {
+        let mut counts = vec![0; self.conditional.len()];
+        self.sample_into(rng, &mut counts);
+        counts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_multinomial_invalid() {
+        assert_eq!(Multinomial::new(10, &[]), Err(Error::NoCategories));
+        assert_eq!(Multinomial::new(10, &[1.0, -1.0]), Err(Error::InvalidWeight));
+        assert_eq!(Multinomial::new(10, &[1.0, f64::NAN]), Err(Error::InvalidWeight));
+        assert_eq!(Multinomial::new(10, &[f64::INFINITY]), Err(Error::InvalidWeight));
+        assert_eq!(Multinomial::new(10, &[f64::MAX, f64::MAX]), Err(Error::InvalidWeight));
+        assert_eq!(Multinomial::new(10, &[0.0, 0.0]), Err(Error::AllWeightsZero));
+    }
+
+    #[test]
+    fn test_multinomial() {
+        let mut rng = crate::test::rng(351);
+        let distr = Multinomial::new(1000, &[0.0, 1.0, 2.0, 0.0, 7.0, 0.0]).unwrap();
+        assert_eq!(distr.num_trials(), 1000);
+        assert_eq!(distr.num_categories(), 6);
+
+        let mut totals = [0u64; 6];
+        for _ in 0..100 {
+            let counts = distr.sample(&mut rng);
+            assert_eq!(counts.iter().sum::<u64>(), 1000);
+            assert_eq!(counts[0], 0);
+            assert_eq!(counts[3], 0);
+            assert_eq!(counts[5], 0);
+            for (t, c) in totals.iter_mut().zip(counts) {
+                *t += c;
+            }
+        }
+        // Expected: 10_000, 20_000, 70_000
+        assert!((9_000..11_000).contains(&totals[1]));
+        assert!((19_000..21_000).contains(&totals[2]));
+        assert!((69_000..71_000).contains(&totals[4]));
+    }
+
+    #[test]
+    fn test_multinomial_edge_cases() {
+        let mut rng = crate::test::rng(352);
+        let distr = Multinomial::new(0, &[1.0, 2.0]).unwrap();
+        assert_eq!(distr.sample(&mut rng), [0, 0]);
+
+        let distr = Multinomial::new(u64::MAX, &[0.0, 3.0]).unwrap();
+        assert_eq!(distr.sample(&mut rng), [0, u64::MAX]);
+
+        let mut counts = [0; 2];
+        Multinomial::new(5, &[1.0, 1.0]).unwrap().sample_into(&mut rng, &mut counts);
+        assert_eq!(counts.iter().sum::<u64>(), 5);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(353);
+        let distr = Multinomial::new(100, &[1.0, 2.0, 3.0, 4.0]).unwrap();
+        let samples: Vec<Vec<u64>> = (0..3).map(|_| distr.sample(&mut rng)).collect();
+        assert_eq!(samples, [[6, 14, 35, 45], [10, 12, 33, 45], [16, 24, 29, 31]]);
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Truncated and clamped distribution wrappers.
+
+use crate::Distribution;
+use core::fmt;
+use rand::Rng;
+
+/// A distribution restricting `D` to the closed interval `[low, high]` by
+/// rejection sampling.
+///
+/// Samples of `D` outside the interval are rejected and `D` is sampled again,
+/// thus the result follows the distribution `D` conditioned on lying within
+/// `[low, high]`. This is the usual meaning of a truncated distribution, e.g.
+/// a truncated normal distribution.
+///
+/// Rejection sampling is only efficient if a large fraction of the samples of
+/// `D` lies within the interval. To avoid looping forever if the interval has
+/// (almost) zero probability, at most `max_iterations` samples are tried; see
+/// [`Truncated::try_sample`]. For a cheaper alternative which does not change
+/// values within the interval but moves all others to the bounds, see
+/// [`Clamped`].
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Distribution, Normal, Truncated};
+///
+/// // Human heights in cm, excluding implausible values
+/// let height = Truncated::new(Normal::new(170.0, 10.0).unwrap(), 50.0, 250.0).unwrap();
+/// let h: f64 = height.sample(&mut rand::thread_rng());
+/// assert!((50.0..=250.0).contains(&h));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Truncated<D, T> {
+    distr: D,
+    low: T,
+    high: T,
+    max_iterations: u32,
+}
+
+/// A distribution restricting `D` to the closed interval `[low, high]` by
+/// clamping.
+///
+/// Samples of `D` below `low` are replaced by `low`, and samples above `high`
+/// by `high`. Unlike [`Truncated`], this requires exactly one sample of `D`,
+/// but the bounds themselves may have a notable probability. Floating-point
+/// NaN values are returned unchanged.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::{Clamped, Distribution, Normal};
+///
+/// // A reading from a sensor which saturates at 0 and 100
+/// let reading = Clamped::new(Normal::new(90.0, 10.0).unwrap(), 0.0, 100.0).unwrap();
+/// let r: f64 = reading.sample(&mut rand::thread_rng());
+/// assert!((0.0..=100.0).contains(&r));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(serde::Serialize, serde::Deserialize))]
+pub struct Clamped<D, T> {
+    distr: D,
+    low: T,
+    high: T,
+}
+
+/// Error type returned from [`Truncated`] and [`Clamped`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// `low > high`, or either bound is NaN.
+    InvalidBounds,
+    /// `max_iterations == 0`.
+    ZeroIterations,
+    /// No sample within the bounds was found in `max_iterations` attempts.
+    TooManyIterations,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Error::InvalidBounds => "low > high or is NaN in truncated distribution",
+            Error::ZeroIterations => "max_iterations is zero in truncated distribution",
+            Error::TooManyIterations => "no sample within bounds in truncated distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl std::error::Error for Error {}
+
+impl<D, T: PartialOrd> Truncated<D, T> {
+    /// The number of attempts made by [`Truncated::try_sample`] when
+    /// constructed with [`Truncated::new`].
+    pub const DEFAULT_MAX_ITERATIONS: u32 = 1000;
+
+    /// Construct a new `Truncated`, restricting `distr` to `[low, high]` and
+    /// trying at most [`Truncated::DEFAULT_MAX_ITERATIONS`] samples.
+    pub fn new(distr: D, low: T, high: T) -> Result<Self, Error> {
+        Self::with_max_iterations(distr, low, high, Self::DEFAULT_MAX_ITERATIONS)
+    }
+
+    /// Construct a new `Truncated`, restricting `distr` to `[low, high]` and
+    /// trying at most `max_iterations` samples.
+    pub fn with_max_iterations(
+        distr: D, low: T, high: T, max_iterations: u32,
+    ) -> Result<Self, Error> {
+        if !(low <= high) {
+            return Err(Error::InvalidBounds);
+        }
+        if max_iterations == 0 {
+            return Err(Error::ZeroIterations);
+        }
+        Ok(Truncated {
+            distr,
+            low,
+            high,
+            max_iterations,
+        })
+    }
+
+    /// Sample from `D` until a value within `[low, high]` is found, or return
+    /// [`Error::TooManyIterations`] after `max_iterations` attempts.
+    pub fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<T, Error>
+    where D: Distribution<T> {
+        for _ in 0..self.max_iterations {
+            let x = self.distr.sample(rng);
+            if self.low <= x && x <= self.high {
+                return Ok(x);
+            }
+        }
+        Err(Error::TooManyIterations)
+    }
+}
+
+impl<D, T> Distribution<T> for Truncated<D, T>
+where
+    D: Distribution<T>,
+    T: PartialOrd,
+{
+    /// Sample a value within `[low, high]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no value within the bounds is found in `max_iterations`
+    /// attempts; see [`Truncated::try_sample`].
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        self.try_sample(rng).unwrap()
+    }
+}
+
+impl<D, T: PartialOrd> Clamped<D, T> {
+    /// Construct a new `Clamped`, restricting `distr` to `[low, high]`.
+    pub fn new(distr: D, low: T, high: T) -> Result<Self, Error> {
+        if !(low <= high) {
+            return Err(Error::InvalidBounds);
+        }
+        Ok(Clamped { distr, low, high })
+    }
+}
+
+impl<D, T> Distribution<T> for Clamped<D, T>
+where
+    D: Distribution<T>,
+    T: PartialOrd + Clone,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        let x = self.distr.sample(rng);
+        if x < self.low {
+            self.low.clone()
+        } else if x > self.high {
+            self.high.clone()
+        } else {
+            x
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Normal, Uniform};
+
+    #[test]
+    fn test_invalid() {
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        assert_eq!(Truncated::new(normal, 1.0, 0.0), Err(Error::InvalidBounds));
+        assert_eq!(Truncated::new(normal, f64::NAN, 0.0), Err(Error::InvalidBounds));
+        assert_eq!(
+            Truncated::with_max_iterations(normal, 0.0, 1.0, 0),
+            Err(Error::ZeroIterations)
+        );
+        assert_eq!(Clamped::new(normal, 0.0, f64::NAN), Err(Error::InvalidBounds));
+        assert!(Truncated::new(normal, 1.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_truncated() {
+        let mut rng = crate::test::rng(361);
+        let distr = Truncated::new(Normal::new(0.0, 1.0).unwrap(), -0.5, 2.0).unwrap();
+        let mut sum = 0.0;
+        for _ in 0..1000 {
+            let x = distr.sample(&mut rng);
+            assert!((-0.5..=2.0).contains(&x));
+            sum += x;
+        }
+        // Mean of the truncated normal is about 0.446
+        assert!((sum / 1000.0 - 0.446f64).abs() < 0.05);
+
+        let distr = Truncated::new(Uniform::new(0u32, 100).unwrap(), 10, 10).unwrap();
+        assert_eq!(distr.sample(&mut rng), 10);
+
+        let distr = Truncated::with_max_iterations(Normal::new(0.0, 1.0).unwrap(), 100.0, 101.0, 10)
+            .unwrap();
+        assert_eq!(distr.try_sample(&mut rng), Err(Error::TooManyIterations));
+    }
+
+    #[test]
+    fn test_clamped() {
+        let mut rng = crate::test::rng(362);
+        let distr = Clamped::new(Uniform::new(0i32, 100).unwrap(), 25, 50).unwrap();
+        let mut counts = [0; 3];
+        for _ in 0..1000 {
+            match distr.sample(&mut rng) {
+                25 => counts[0] += 1,
+                50 => counts[2] += 1,
+                x => {
+                    assert!(25 < x && x < 50);
+                    counts[1] += 1;
+                }
+            }
+        }
+        // Expected: 260, 240, 500
+        assert!((200..320).contains(&counts[0]));
+        assert!((440..560).contains(&counts[2]));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(363);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+        let truncated = Truncated::new(normal, 0.0, 1.0).unwrap();
+        let clamped = Clamped::new(normal, 0.0, 1.0).unwrap();
+        let mut buf = [0.0; 6];
+        for (i, x) in buf.iter_mut().enumerate() {
+            *x = if i < 3 {
+                truncated.sample(&mut rng)
+            } else {
+                clamped.sample(&mut rng)
+            };
+        }
+        assert_eq!(buf, [
+            0.1708279016665473,
+            0.692478786509973,
+            0.16743495816684584,
+            1.0,
+            0.21945595565281797,
+            0.0,
+        ]);
+    }
+}
@@ -38,6 +38,14 @@ use serde::{Serialize, Deserialize};
 /// Sampling is `O(1)`, it makes a call to [`Uniform<u32>::sample`] and a call
 /// to [`Uniform<W>::sample`].
 ///
+/// A copy of the weights is retained to support
+/// [`WeightedAliasIndex::update_weights`]. Updates do not rebuild the alias
+/// table immediately; instead samples are corrected by rejection until enough
+/// weights have changed (roughly `sqrt(n)`, or when more than half of the
+/// table's weight would be rejected), at which point the table is rebuilt in
+/// `O(n)`. While updates are pending, sampling needs a few extra random values
+/// and may take time proportional to the number of pending updates.
+///
 /// # Example
 ///
 /// ```
@@ -69,11 +77,152 @@ use serde::{Serialize, Deserialize};
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "serde1", serde(bound(serialize = "W: Serialize, W::Sampler: Serialize")))]
 #[cfg_attr(feature = "serde1", serde(bound(deserialize = "W: Deserialize<'de>, W::Sampler: Deserialize<'de>")))]
+#[cfg_attr(feature = "serde1", serde(try_from = "WeightedAliasIndexData<W>"))]
 pub struct WeightedAliasIndex<W: AliasableWeight> {
     aliases: Box<[u32]>,
     no_alias_odds: Box<[W]>,
     uniform_index: Uniform<u32>,
     uniform_within_weight_sum: Uniform<W>,
+    // Weights from which the alias table was built, and their sum
+    table_weights: Box<[W]>,
+    weight_sum: W,
+    // Current weights; empty until the first update
+    weights: Box<[W]>,
+    // Indices at which `weights` may differ from `table_weights`
+    dirty: Vec<u32>,
+    // Sum over `dirty` of the amount by which current weights exceed
+    // (`excess_sum`) or fall short of (`deficit_sum`) the table weights
+    excess_sum: W,
+    deficit_sum: W,
+    // Samples from `[0, weight_sum + excess_sum)`
+    uniform_choice: Uniform<W>,
+}
+
+/// The serialized fields of a [`WeightedAliasIndex`]. Data serialized before
+/// weight updates were supported lacks the fields following
+/// `uniform_within_weight_sum`.
+#[cfg(feature = "serde1")]
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "W: Deserialize<'de>, W::Sampler: Deserialize<'de>"))]
+struct WeightedAliasIndexData<W: AliasableWeight> {
+    aliases: Box<[u32]>,
+    no_alias_odds: Box<[W]>,
+    uniform_index: Uniform<u32>,
+    uniform_within_weight_sum: Uniform<W>,
+    #[serde(default)]
+    table_weights: Box<[W]>,
+    #[serde(default = "zero")]
+    weight_sum: W,
+    #[serde(default)]
+    weights: Box<[W]>,
+    #[serde(default)]
+    dirty: Vec<u32>,
+    #[serde(default = "zero")]
+    excess_sum: W,
+    #[serde(default = "zero")]
+    deficit_sum: W,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    uniform_choice: Option<Uniform<W>>,
+}
+
+#[cfg(feature = "serde1")]
+fn zero<W: AliasableWeight>() -> W {
+    W::ZERO
+}
+
+#[cfg(feature = "serde1")]
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Error type indicating that a deserialized [`WeightedAliasIndex`] was
+/// invalid.
+#[cfg(feature = "serde1")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct InvalidWeightedAliasIndex;
+
+#[cfg(feature = "serde1")]
+impl fmt::Display for InvalidWeightedAliasIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid weighted alias index")
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl<W: AliasableWeight> TryFrom<WeightedAliasIndexData<W>> for WeightedAliasIndex<W> {
+    type Error = InvalidWeightedAliasIndex;
+
+    /// Recover the weights of the table from the alias table for data
+    /// serialized without them.
+    fn try_from(data: WeightedAliasIndexData<W>) -> Result<Self, Self::Error> {
+        let WeightedAliasIndexData {
+            aliases,
+            no_alias_odds,
+            uniform_index,
+            uniform_within_weight_sum,
+            table_weights,
+            weight_sum,
+            weights,
+            dirty,
+            excess_sum,
+            deficit_sum,
+            uniform_choice,
+        } = data;
+        if let (false, Some(uniform_choice)) = (table_weights.is_empty(), uniform_choice) {
+            return Ok(Self {
+                aliases,
+                no_alias_odds,
+                uniform_index,
+                uniform_within_weight_sum,
+                table_weights,
+                weight_sum,
+                weights,
+                dirty,
+                excess_sum,
+                deficit_sum,
+                uniform_choice,
+            });
+        }
+
+        // Columns of the table hold `weight_sum`, the greatest odds, and
+        // column `j` gives `weight_sum - no_alias_odds[j]` to its alias. The
+        // weight of each index times `n` is thus the sum of its odds and
+        // those given to it.
+        let n = no_alias_odds.len();
+        if n == 0 || aliases.len() != n {
+            return Err(InvalidWeightedAliasIndex);
+        }
+        let weight_sum = no_alias_odds.iter().fold(W::ZERO, |max, &w| if w > max { w } else { max });
+        let mut table_weights = no_alias_odds.clone();
+        for (&odds, &alias) in no_alias_odds.iter().zip(aliases.iter()) {
+            if odds < weight_sum {
+                let alias = table_weights.get_mut(alias as usize).ok_or(InvalidWeightedAliasIndex)?;
+                *alias += weight_sum - odds;
+            }
+        }
+        let n_converted = W::try_from_u32_lossy(n as u32).ok_or(InvalidWeightedAliasIndex)?;
+        for w in table_weights.iter_mut() {
+            *w /= n_converted;
+        }
+        let uniform_choice = Uniform::new(W::ZERO, weight_sum).map_err(|_| InvalidWeightedAliasIndex)?;
+        Ok(Self {
+            aliases,
+            no_alias_odds,
+            uniform_index,
+            uniform_within_weight_sum,
+            table_weights,
+            weight_sum,
+            weights: Box::new([]),
+            dirty: Vec::new(),
+            excess_sum: W::ZERO,
+            deficit_sum: W::ZERO,
+            uniform_choice,
+        })
+    }
 }
 
 impl<W: AliasableWeight> WeightedAliasIndex<W> {
@@ -119,7 +268,8 @@ impl<W: AliasableWeight> WeightedAliasIndex<W> {
         // `weight_sum` would have been zero if `try_from_lossy` causes an error here.
         let n_converted = W::try_from_u32_lossy(n).unwrap();
 
-        let mut no_alias_odds = weights.into_boxed_slice();
+        let table_weights = weights.into_boxed_slice();
+        let mut no_alias_odds = table_weights.clone();
         for odds in no_alias_odds.iter_mut() {
             *odds *= n_converted;
             // Prevent floating point overflow due to rounding errors.
@@ -229,12 +379,112 @@ impl<W: AliasableWeight> WeightedAliasIndex<W> {
             no_alias_odds,
             uniform_index,
             uniform_within_weight_sum,
+            table_weights,
+            weight_sum,
+            weights: Box::new([]),
+            dirty: Vec::new(),
+            excess_sum: W::ZERO,
+            deficit_sum: W::ZERO,
+            uniform_choice: Uniform::new(W::ZERO, weight_sum).unwrap(),
         })
     }
-}
 
-impl<W: AliasableWeight> Distribution<usize> for WeightedAliasIndex<W> {
-    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+    /// Update a subset of weights, without changing the number of weights.
+    ///
+    /// `new_weights` must be sorted by the index, as for
+    /// [`WeightedIndex::update_weights`], and is subject to the same
+    /// restrictions on weights as [`WeightedAliasIndex::new`]. Returns
+    /// [`WeightedError::InvalidWeight`] if the indices are not strictly
+    /// increasing or a weight is invalid, [`WeightedError::TooMany`] if an
+    /// index is out of bounds and [`WeightedError::AllWeightsZero`] if the
+    /// update would set the total weight to zero.
+    ///
+    /// The alias table is not rebuilt immediately; see the performance notes
+    /// on [`WeightedAliasIndex`]. Updating a small number of weights thus
+    /// typically takes time proportional to the number of pending updates,
+    /// with an amortized `O(sqrt(n))` cost for rebuilding the table.
+    ///
+    /// In case of error, `self` is not modified.
+    ///
+    /// [`WeightedIndex::update_weights`]: crate::WeightedIndex::update_weights
+    pub fn update_weights(&mut self, new_weights: &[(usize, &W)]) -> Result<(), WeightedError> {
+        let n = self.table_weights.len();
+        let max_weight_size = W::try_from_u32_lossy(n as u32)
+            .map(|n| W::MAX / n)
+            .unwrap_or(W::ZERO);
+
+        let mut prev_i = None;
+        for &(i, &w) in new_weights {
+            if let Some(old_i) = prev_i {
+                if old_i >= i {
+                    return Err(WeightedError::InvalidWeight);
+                }
+            }
+            if i >= n {
+                return Err(WeightedError::TooMany);
+            }
+            if !(W::ZERO <= w && w <= max_weight_size) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            prev_i = Some(i);
+        }
+
+        let current = |i: usize| -> W {
+            match new_weights.binary_search_by_key(&i, |&(j, _)| j) {
+                Ok(pos) => *new_weights[pos].1,
+                Err(_) if self.weights.is_empty() => self.table_weights[i],
+                Err(_) => self.weights[i],
+            }
+        };
+
+        // Compute the state after the update, without modifying `self`.
+        let mut dirty = self.dirty.clone();
+        for &(i, &w) in new_weights {
+            if w != self.table_weights[i] && !dirty.contains(&(i as u32)) {
+                dirty.push(i as u32);
+            }
+        }
+        let mut excess_sum = W::ZERO;
+        let mut deficit_sum = W::ZERO;
+        for &i in &dirty {
+            let (b, w) = (self.table_weights[i as usize], current(i as usize));
+            if w > b {
+                excess_sum += w - b;
+            } else {
+                deficit_sum += b - w;
+            }
+        }
+        // The new total weight is `weight_sum + excess_sum - deficit_sum`.
+        if !(self.weight_sum - deficit_sum + excess_sum > W::ZERO) {
+            return Err(WeightedError::AllWeightsZero);
+        }
+
+        let mut limit = 16;
+        while limit * limit < n {
+            limit *= 2;
+        }
+        if dirty.len() > limit || deficit_sum > self.weight_sum - deficit_sum + excess_sum {
+            let weights = (0..n).map(current).collect();
+            *self = Self::new(weights)?;
+            return Ok(());
+        }
+
+        if self.weights.is_empty() {
+            self.weights = self.table_weights.clone();
+        }
+        for &(i, &w) in new_weights {
+            self.weights[i] = w;
+        }
+        self.dirty = dirty;
+        self.excess_sum = excess_sum;
+        self.deficit_sum = deficit_sum;
+        self.uniform_choice = Uniform::new(W::ZERO, self.weight_sum + excess_sum).unwrap();
+        Ok(())
+    }
+
+    /// Sample from the alias table, ignoring any pending updates
+    #[inline]
+    fn sample_table<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
         let candidate = rng.sample(self.uniform_index);
         if rng.sample(&self.uniform_within_weight_sum) < self.no_alias_odds[candidate as usize] {
             candidate as usize
@@ -244,6 +494,43 @@ impl<W: AliasableWeight> Distribution<usize> for WeightedAliasIndex<W> {
     }
 }
 
+impl<W: AliasableWeight> Distribution<usize> for WeightedAliasIndex<W> {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        if self.dirty.is_empty() {
+            return self.sample_table(rng);
+        }
+
+        // Each index is sampled with probability proportional to
+        // `min(w, b) + max(w - b, 0) = w`, where `b` is its table weight and
+        // `w` its current weight: the excess is sampled directly, while
+        // samples from the table are thinned with probability `w / b`.
+        loop {
+            if self.excess_sum > W::ZERO {
+                let mut u = rng.sample(&self.uniform_choice);
+                if u < self.excess_sum {
+                    for &i in &self.dirty {
+                        let (b, w) = (self.table_weights[i as usize], self.weights[i as usize]);
+                        if w > b {
+                            if u < w - b {
+                                return i as usize;
+                            }
+                            u -= w - b;
+                        }
+                    }
+                    // Only reachable due to rounding errors
+                    continue;
+                }
+            }
+
+            let i = self.sample_table(rng);
+            let (b, w) = (self.table_weights[i], self.weights[i]);
+            if w >= b || rng.sample(Uniform::new(W::ZERO, b).unwrap()) < w {
+                return i;
+            }
+        }
+    }
+}
+
 impl<W: AliasableWeight> fmt::Debug for WeightedAliasIndex<W>
 where
     W: fmt::Debug,
@@ -255,6 +542,13 @@ where
             .field("no_alias_odds", &self.no_alias_odds)
             .field("uniform_index", &self.uniform_index)
             .field("uniform_within_weight_sum", &self.uniform_within_weight_sum)
+            .field("table_weights", &self.table_weights)
+            .field("weight_sum", &self.weight_sum)
+            .field("weights", &self.weights)
+            .field("dirty", &self.dirty)
+            .field("excess_sum", &self.excess_sum)
+            .field("deficit_sum", &self.deficit_sum)
+            .field("uniform_choice", &self.uniform_choice)
             .finish()
     }
 }
@@ -268,6 +562,13 @@ where Uniform<W>: Clone
             no_alias_odds: self.no_alias_odds.clone(),
             uniform_index: self.uniform_index,
             uniform_within_weight_sum: self.uniform_within_weight_sum.clone(),
+            table_weights: self.table_weights.clone(),
+            weight_sum: self.weight_sum,
+            weights: self.weights.clone(),
+            dirty: self.dirty.clone(),
+            excess_sum: self.excess_sum,
+            deficit_sum: self.deficit_sum,
+            uniform_choice: self.uniform_choice.clone(),
         }
     }
 }
@@ -374,6 +675,40 @@ impl_weight_for_int!(i8);
 mod test {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "serde1")]
+    fn test_serialization() {
+        let mut distr = WeightedAliasIndex::new(vec![1u32, 5, 0, 2, 4]).unwrap();
+        distr.update_weights(&[(2, &3)]).unwrap();
+        let de_distr: WeightedAliasIndex<u32> =
+            serde_json::from_str(&serde_json::to_string(&distr).unwrap()).unwrap();
+        assert_eq!(alloc::format!("{:?}", de_distr), alloc::format!("{:?}", distr));
+
+        // Data serialized before weight updates were supported
+        let legacy = |distr: &WeightedAliasIndex<u32>| {
+            let mut value = serde_json::to_value(distr).unwrap();
+            let fields = value.as_object_mut().unwrap();
+            for field in &["table_weights", "weight_sum", "weights", "dirty", "excess_sum", "deficit_sum", "uniform_choice"] {
+                fields.remove(*field).unwrap();
+            }
+            value
+        };
+        let weights = vec![1u32, 5, 0, 2, 4, 7, 1];
+        let distr = WeightedAliasIndex::new(weights.clone()).unwrap();
+        let mut de_distr: WeightedAliasIndex<u32> = serde_json::from_value(legacy(&distr)).unwrap();
+        assert_eq!(alloc::format!("{:?}", de_distr), alloc::format!("{:?}", distr));
+        de_distr.update_weights(&[(0, &3)]).unwrap();
+        let mut expected = WeightedAliasIndex::new(weights).unwrap();
+        expected.update_weights(&[(0, &3)]).unwrap();
+        assert_eq!(alloc::format!("{:?}", de_distr), alloc::format!("{:?}", expected));
+
+        // Aliases out of bounds are rejected
+        let mut value = legacy(&WeightedAliasIndex::new(vec![1u32, 3]).unwrap());
+        assert!(serde_json::from_value::<WeightedAliasIndex<u32>>(value.clone()).is_ok());
+        value["aliases"][0] = 7.into();
+        assert!(serde_json::from_value::<WeightedAliasIndex<u32>>(value).is_err());
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Miri is too slow
     fn test_weighted_index_f32() {
@@ -498,6 +833,83 @@ mod test {
         );
     }
 
+    fn assert_frequencies(distr: &WeightedAliasIndex<u32>, weights: &[u32], seed: u64) {
+        const NUM_SAMPLES: u32 = 20000;
+        let mut rng = crate::test::rng(seed);
+        let mut counts = vec![0; weights.len()];
+        for _ in 0..NUM_SAMPLES {
+            counts[rng.sample(distr)] += 1;
+        }
+        let weight_sum = weights.iter().sum::<u32>() as f64;
+        for (&count, &w) in counts.iter().zip(weights) {
+            if w == 0 {
+                assert_eq!(count, 0);
+            }
+            let expected = w as f64 / weight_sum * NUM_SAMPLES as f64;
+            assert!((count as f64 - expected).abs() <= NUM_SAMPLES as f64 * 0.02);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_update_weights() {
+        let mut weights = vec![1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7];
+        let mut distr = WeightedAliasIndex::new(weights.clone()).unwrap();
+
+        // A few updates are applied without rebuilding the table
+        let update = [(0, &10), (2, &1), (3, &4), (13, &0)];
+        distr.update_weights(&update).unwrap();
+        for &(i, &w) in update.iter() {
+            weights[i] = w;
+        }
+        assert!(!distr.dirty.is_empty());
+        assert_frequencies(&distr, &weights, 1);
+
+        // Reverting an update
+        distr.update_weights(&[(0, &1)]).unwrap();
+        weights[0] = 1;
+        assert_frequencies(&distr, &weights, 2);
+
+        // Updating most weights triggers a rebuild
+        for (i, weight) in weights.iter_mut().enumerate() {
+            let w = (i as u32 * 7) % 5;
+            distr.update_weights(&[(i, &w)]).unwrap();
+            *weight = w;
+        }
+        assert!(distr.dirty.len() < weights.len());
+        assert_frequencies(&distr, &weights, 3);
+    }
+
+    #[test]
+    fn test_update_weights_errors() {
+        let mut distr = WeightedAliasIndex::new(vec![0u32, 3, 2]).unwrap();
+        assert_eq!(distr.update_weights(&[(3, &1)]).unwrap_err(), WeightedError::TooMany);
+        assert_eq!(
+            distr.update_weights(&[(1, &1), (0, &1)]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            distr.update_weights(&[(0, &u32::MAX)]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            distr.update_weights(&[(1, &0), (2, &0)]).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        assert!(distr.dirty.is_empty());
+
+        let mut distr = WeightedAliasIndex::new(vec![0.5f64, 0.5]).unwrap();
+        assert_eq!(
+            distr.update_weights(&[(0, &f64::NAN)]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        distr.update_weights(&[(0, &0.0)]).unwrap();
+        let mut rng = crate::test::rng(4);
+        for _ in 0..100 {
+            assert_eq!(distr.sample(&mut rng), 1);
+        }
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<W: AliasableWeight>(weights: Vec<W>, buf: &mut [usize], expected: &[usize]) {
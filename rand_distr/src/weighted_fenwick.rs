@@ -0,0 +1,395 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! This module contains an implementation of weighted index sampling backed by
+//! a Fenwick tree (binary indexed tree), supporting efficient weight updates.
+
+use super::WeightedError;
+use crate::uniform::{SampleBorrow, SampleUniform, UniformSampler};
+use crate::Distribution;
+use core::ops::AddAssign;
+use rand::Rng;
+use alloc::vec::Vec;
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A distribution using weighted sampling of discrete items, supporting
+/// updates of individual weights.
+///
+/// Sampling a [`WeightedFenwickIndex<X>`] distribution returns the index of a
+/// randomly selected element from the iterator used to create it. The chance
+/// of a given element being picked is proportional to the weight of the
+/// element. The weights can use any type `X` for which an implementation of
+/// [`Uniform<X>`] exists. Elements with zero weight are never picked.
+///
+/// # Performance
+///
+/// Given `n` weights, a [`WeightedFenwickIndex<X>`] requires `O(n)` memory
+/// (two `Vec<X>` of length `n`) and takes `O(n)` time to construct.
+///
+/// Sampling is `O(log n)` and updating a single weight via
+/// [`WeightedFenwickIndex::update_weights`] is `O(log² n)`. In comparison,
+/// [`WeightedIndex`] samples in `O(log n)` but must rebuild its cumulative
+/// weights in `O(n)` after an update, and [`WeightedAliasIndex`] samples in
+/// `O(1)` but does not support updates. This type is thus preferable when
+/// weights change frequently, e.g. in simulations where weights change every
+/// step.
+///
+/// Sampling results in a single call to `Uniform<X>::sample`, and for integer
+/// weights the sampled index is identical to that of [`WeightedIndex`] given
+/// the same weights and RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::weighted_fenwick::WeightedFenwickIndex;
+/// use rand::prelude::*;
+///
+/// let choices = ['a', 'b', 'c'];
+/// let mut dist = WeightedFenwickIndex::new(&[2, 1, 1]).unwrap();
+/// let mut rng = thread_rng();
+/// // 50% chance to print 'a', 25% chance to print 'b', 25% chance to print 'c'
+/// println!("{}", choices[dist.sample(&mut rng)]);
+///
+/// dist.update_weights(&[(0, &0)]).unwrap();
+/// // 0% chance to print 'a', 50% chance to print 'b', 50% chance to print 'c'
+/// println!("{}", choices[dist.sample(&mut rng)]);
+/// ```
+///
+/// [`WeightedFenwickIndex<X>`]: WeightedFenwickIndex
+/// [`Uniform<X>`]: crate::Uniform
+/// [`WeightedIndex`]: crate::WeightedIndex
+/// [`WeightedAliasIndex`]: crate::WeightedAliasIndex
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct WeightedFenwickIndex<X: SampleUniform + PartialOrd> {
+    weights: Vec<X>,
+    // Node `i` (1-based) holds the sum of weights in `(i - lsb(i), i]`, stored
+    // at `tree[i - 1]`.
+    tree: Vec<X>,
+    total_weight: X,
+    weight_distribution: X::Sampler,
+}
+
+impl<X> WeightedFenwickIndex<X>
+where X: SampleUniform
+        + PartialOrd
+        + for<'a> AddAssign<&'a X>
+        + Clone
+        + Default
+{
+    /// Creates a new [`WeightedFenwickIndex`] using the values in `weights`.
+    ///
+    /// Returns an error if the iterator is empty, if any weight is `< 0`, or
+    /// if its total value is 0.
+    pub fn new<I>(weights: I) -> Result<Self, WeightedError>
+    where
+        I: IntoIterator,
+        I::Item: SampleBorrow<X>,
+    {
+        let zero = X::default();
+        let mut total_weight = zero.clone();
+        let mut weights_vec = Vec::new();
+        for w in weights {
+            let w = w.borrow();
+            if !(*w >= zero) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            total_weight += w;
+            weights_vec.push(w.clone());
+        }
+        if weights_vec.is_empty() {
+            return Err(WeightedError::NoItem);
+        }
+        if total_weight == zero {
+            return Err(WeightedError::AllWeightsZero);
+        }
+
+        // Build the tree in O(n) by pushing each node's sum to its parent.
+        let n = weights_vec.len();
+        let mut tree = weights_vec.clone();
+        for i in 1..=n {
+            let parent = i + lsb(i);
+            if parent <= n {
+                let node = tree[i - 1].clone();
+                tree[parent - 1] += &node;
+            }
+        }
+
+        let total_weight = tree_total(&tree);
+        let weight_distribution = X::Sampler::new(zero, total_weight.clone()).unwrap();
+        Ok(WeightedFenwickIndex {
+            weights: weights_vec,
+            tree,
+            total_weight,
+            weight_distribution,
+        })
+    }
+
+    /// Update a subset of weights, without changing the number of weights,
+    /// in `O(log² n)` time per updated weight.
+    ///
+    /// `new_weights` must be sorted by the index, as for
+    /// [`WeightedIndex::update_weights`]. Returns
+    /// [`WeightedError::InvalidWeight`] if the indices are not strictly
+    /// increasing or a weight is `< 0`, [`WeightedError::TooMany`] if an index
+    /// is out of bounds and [`WeightedError::AllWeightsZero`] if the update
+    /// would set the total weight to zero. In case of error, the weights are
+    /// not modified.
+    ///
+    /// [`WeightedIndex::update_weights`]: crate::WeightedIndex::update_weights
+    pub fn update_weights(&mut self, new_weights: &[(usize, &X)]) -> Result<(), WeightedError> {
+        if new_weights.is_empty() {
+            return Ok(());
+        }
+
+        // Check for errors first, so we don't modify `self` in case something
+        // goes wrong.
+        let zero = X::default();
+        let mut prev_i = None;
+        for &(i, w) in new_weights {
+            if let Some(old_i) = prev_i {
+                if old_i >= i {
+                    return Err(WeightedError::InvalidWeight);
+                }
+            }
+            if !(*w >= zero) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            if i >= self.weights.len() {
+                return Err(WeightedError::TooMany);
+            }
+            prev_i = Some(i);
+        }
+
+        let old_weights: Vec<X> = new_weights.iter().map(|&(i, _)| self.weights[i].clone()).collect();
+        for &(index, weight) in new_weights {
+            self.weights[index] = weight.clone();
+            self.update_tree(index);
+        }
+        // The tree sums non-negative weights, thus is zero only if all
+        // weights are.
+        let total_weight = tree_total(&self.tree);
+        if !(total_weight > zero) {
+            for (&(index, _), weight) in new_weights.iter().zip(old_weights) {
+                self.weights[index] = weight;
+                self.update_tree(index);
+            }
+            return Err(WeightedError::AllWeightsZero);
+        }
+        self.weight_distribution = X::Sampler::new(zero, total_weight.clone()).unwrap();
+        self.total_weight = total_weight;
+        Ok(())
+    }
+
+    /// Recompute the nodes covering the weight at `index` from the weights.
+    ///
+    /// Applying the change of the weight to each node instead would lose
+    /// precision with floating-point weights, due to cancellation.
+    fn update_tree(&mut self, index: usize) {
+        let n = self.tree.len();
+        let mut i = index + 1;
+        while i <= n {
+            // Node `i` is the sum of weight `i - 1` and of its children,
+            // which are the nodes `i - 1`, `i - 2`, `i - 4`, ... above
+            // `i - lsb(i)`.
+            let mut sum = self.weights[i - 1].clone();
+            let mut step = 1;
+            while step < lsb(i) {
+                sum += &self.tree[i - step - 1];
+                step <<= 1;
+            }
+            self.tree[i - 1] = sum;
+            i += lsb(i);
+        }
+    }
+}
+
+/// The sum of all weights, adding the nodes in the order of
+/// [`WeightedFenwickIndex::sample`]
+fn tree_total<X>(tree: &[X]) -> X
+where X: for<'a> AddAssign<&'a X> + Default
+{
+    let n = tree.len();
+    let mut total = X::default();
+    let mut pos = 0;
+    let mut step = highest_bit(n);
+    while step > 0 {
+        if pos + step <= n {
+            pos += step;
+            total += &tree[pos - 1];
+        }
+        step >>= 1;
+    }
+    total
+}
+
+/// Highest set bit of `n`, or 0 if `n == 0`
+#[inline]
+fn highest_bit(n: usize) -> usize {
+    if n == 0 { 0 } else { 1 << (usize::BITS - 1 - n.leading_zeros()) }
+}
+
+/// Lowest set bit of `i`
+#[inline]
+fn lsb(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+impl<X> Distribution<usize> for WeightedFenwickIndex<X>
+where X: SampleUniform + PartialOrd + for<'a> AddAssign<&'a X> + Clone + Default
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let n = self.tree.len();
+        let chosen_weight = self.weight_distribution.sample(rng);
+
+        // Find the greatest `pos` such that the sum of the first `pos`
+        // weights is at most `chosen_weight`; item `pos` is then the first
+        // item whose cumulative weight is *higher* than the chosen weight.
+        let mut pos = 0;
+        let mut cumulative_weight = X::default();
+        let mut step = highest_bit(n);
+        while step > 0 {
+            let next = pos + step;
+            if next <= n {
+                let mut sum = cumulative_weight.clone();
+                sum += &self.tree[next - 1];
+                if sum <= chosen_weight {
+                    pos = next;
+                    cumulative_weight = sum;
+                }
+            }
+            step >>= 1;
+        }
+
+        if pos < n {
+            return pos;
+        }
+        // With floating-point weights, rounding may cause the chosen weight
+        // to reach the sum of the tree; pick the last possible item then.
+        let zero = X::default();
+        self.weights.iter().rposition(|w| *w > zero).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::WeightedIndex;
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            WeightedFenwickIndex::new(&[10][0..0]).unwrap_err(),
+            WeightedError::NoItem
+        );
+        assert_eq!(
+            WeightedFenwickIndex::new([0, 0]).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        assert_eq!(
+            WeightedFenwickIndex::new([10, 20, -1, 30]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedFenwickIndex::new([0.5, f64::NAN]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+
+        let mut distr = WeightedFenwickIndex::new([0, 3]).unwrap();
+        assert_eq!(distr.update_weights(&[(2, &1)]), Err(WeightedError::TooMany));
+        assert_eq!(distr.update_weights(&[(0, &-1)]), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update_weights(&[(1, &0)]), Err(WeightedError::AllWeightsZero));
+        assert_eq!(distr.update_weights(&[(1, &1), (0, &1)]), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update_weights(&[(0, &1), (0, &1)]), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update_weights(&[(0, &1), (2, &1)]), Err(WeightedError::TooMany));
+        assert_eq!(distr, WeightedFenwickIndex::new([0, 3]).unwrap());
+    }
+
+    #[test]
+    fn test_update_weights() {
+        let weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7];
+        let updates = [(2, 1), (5, 1), (13, 100), (3, 4), (0, 0)];
+        let mut expected = weights;
+        let mut distr = WeightedFenwickIndex::new(weights).unwrap();
+        for &(i, w) in updates.iter() {
+            distr.update_weights(&[(i, &w)]).unwrap();
+            expected[i] = w;
+            assert_eq!(distr, WeightedFenwickIndex::new(expected).unwrap());
+        }
+
+        // Several weights at once
+        distr.update_weights(&[(1, &0), (4, &9), (12, &2)]).unwrap();
+        expected[1] = 0;
+        expected[4] = 9;
+        expected[12] = 2;
+        assert_eq!(distr, WeightedFenwickIndex::new(expected).unwrap());
+        distr.update_weights(&[]).unwrap();
+        assert_eq!(distr, WeightedFenwickIndex::new(expected).unwrap());
+    }
+
+    #[test]
+    fn test_matches_weighted_index() {
+        let mut weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6];
+        let mut distr = WeightedFenwickIndex::new(weights).unwrap();
+        let mut rng1 = crate::test::rng(702);
+        let mut rng2 = crate::test::rng(702);
+        for step in 0..100 {
+            let reference = WeightedIndex::new(weights).unwrap();
+            for _ in 0..10 {
+                let i = distr.sample(&mut rng1);
+                assert_eq!(i, reference.sample(&mut rng2));
+                assert!(weights[i] > 0);
+            }
+            let (i, w) = (step * 7 % weights.len(), step as u32 % 5);
+            distr.update_weights(&[(i, &w)]).unwrap();
+            weights[i] = w;
+        }
+    }
+
+    #[test]
+    fn test_float_weights() {
+        let mut distr = WeightedFenwickIndex::new([0.5f64, 0.0, 0.25, 0.25]).unwrap();
+        distr.update_weights(&[(0, &0.0), (1, &1e-3)]).unwrap();
+        let mut rng = crate::test::rng(703);
+        for _ in 0..1000 {
+            assert_ne!(distr.sample(&mut rng), 0);
+        }
+    }
+
+    #[test]
+    fn test_float_cancellation() {
+        let mut distr = WeightedFenwickIndex::new([1.0f64, 1e20]).unwrap();
+        distr.update_weights(&[(1, &1.0)]).unwrap();
+        assert_eq!(distr, WeightedFenwickIndex::new([1.0f64, 1.0]).unwrap());
+        let mut rng = crate::test::rng(704);
+        let mut counts = [0; 2];
+        for _ in 0..1000 {
+            counts[distr.sample(&mut rng)] += 1;
+        }
+        assert!(counts[0] > 400 && counts[1] > 400);
+
+        // Rejected updates leave the weights unchanged
+        let mut distr = WeightedFenwickIndex::new([0.0f64, 1e20]).unwrap();
+        assert_eq!(distr.update_weights(&[(1, &0.0)]), Err(WeightedError::AllWeightsZero));
+        assert_eq!(distr, WeightedFenwickIndex::new([0.0f64, 1e20]).unwrap());
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut distr = WeightedFenwickIndex::new([1i32, 1, 1, 1, 1, 1, 1, 1, 1]).unwrap();
+        distr.update_weights(&[(3, &5)]).unwrap();
+        let mut rng = crate::test::rng(701);
+        let mut buf = [0; 10];
+        for r in buf.iter_mut() {
+            *r = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [0, 4, 3, 5, 3, 3, 6, 8, 3, 3]);
+    }
+}
@@ -0,0 +1,415 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! This module contains an implementation of weighted index sampling backed by
+//! a balanced binary tree, supporting insertion and removal of items.
+
+use super::WeightedError;
+use crate::uniform::{SampleBorrow, SampleUniform, UniformSampler};
+use crate::Distribution;
+use core::ops::AddAssign;
+use rand::Rng;
+use alloc::vec::Vec;
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A distribution using weighted sampling of discrete items, supporting
+/// insertion and removal of items as well as updates of their weights.
+///
+/// Sampling a [`WeightedTreeIndex<X>`] distribution returns the index of a
+/// randomly selected item. The chance of a given item being picked is
+/// proportional to its weight. The weights can use any type `X` for which an
+/// implementation of [`Uniform<X>`] exists. Items with zero weight are never
+/// picked.
+///
+/// Unlike other weighted index distributions, a [`WeightedTreeIndex<X>`] may be
+/// empty or have only zero weights, since items may be added later. Sampling
+/// via [`WeightedTreeIndex::try_sample`] returns an error in this case, while
+/// [`Distribution::sample`] panics.
+///
+/// # Performance
+///
+/// The weights are stored in an implicit complete binary tree (as in a binary
+/// heap), where each node holds the weight of one item and the total weight of
+/// its subtree. The tree is thus always balanced with depth `O(log n)`.
+///
+/// [`WeightedTreeIndex::push`], [`WeightedTreeIndex::pop`],
+/// [`WeightedTreeIndex::swap_remove`], updating a single weight via
+/// [`WeightedTreeIndex::update_weights`] and sampling are all `O(log n)`. Construction from `n` weights is `O(n)`. This
+/// makes this type suitable for simulations where the set of possible events
+/// changes over time, e.g. the Gillespie algorithm or queueing simulations.
+///
+/// If items are not added or removed, [`WeightedFenwickIndex`] is usually
+/// faster.
+///
+/// # Example
+///
+/// ```
+/// use rand_distr::weighted_tree::WeightedTreeIndex;
+/// use rand::prelude::*;
+///
+/// let mut dist = WeightedTreeIndex::new([2, 1]).unwrap();
+/// dist.push(1).unwrap();
+/// let mut rng = thread_rng();
+/// // 50% chance of 0, 25% chance of 1, 25% chance of 2
+/// println!("{}", dist.sample(&mut rng));
+///
+/// // Remove item 0, moving item 2 in its place
+/// dist.swap_remove(0).unwrap();
+/// dist.update_weights(&[(1, &3)]).unwrap();
+/// // 25% chance of 0 (previously item 2), 75% chance of 1
+/// println!("{}", dist.sample(&mut rng));
+/// ```
+///
+/// [`WeightedTreeIndex<X>`]: WeightedTreeIndex
+/// [`Uniform<X>`]: crate::Uniform
+/// [`WeightedFenwickIndex`]: crate::WeightedFenwickIndex
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct WeightedTreeIndex<X> {
+    weights: Vec<X>,
+    // `subtotals[i]` is the total weight of the subtree rooted at node `i`,
+    // whose children are nodes `2 * i + 1` and `2 * i + 2`.
+    subtotals: Vec<X>,
+}
+
+impl<X> WeightedTreeIndex<X>
+where X: SampleUniform + PartialOrd + for<'a> AddAssign<&'a X> + Clone + Default
+{
+    /// Creates a new [`WeightedTreeIndex`] using the values in `weights`.
+    ///
+    /// Returns [`WeightedError::InvalidWeight`] if any weight is `< 0` or
+    /// otherwise invalid. `weights` may be empty or contain only zero weights.
+    pub fn new<I>(weights: I) -> Result<Self, WeightedError>
+    where
+        I: IntoIterator,
+        I::Item: SampleBorrow<X>,
+    {
+        let zero = X::default();
+        let mut weights_vec = Vec::new();
+        for w in weights {
+            let w = w.borrow();
+            if !(*w >= zero) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            weights_vec.push(w.clone());
+        }
+
+        // Build the subtotals bottom-up; children always follow their parent.
+        let mut subtotals = weights_vec.clone();
+        for i in (1..subtotals.len()).rev() {
+            let child = subtotals[i].clone();
+            subtotals[(i - 1) / 2] += &child;
+        }
+
+        Ok(WeightedTreeIndex {
+            weights: weights_vec,
+            subtotals,
+        })
+    }
+
+    /// Returns the number of items.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns `true` if there are no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns the weight of the item at `index`, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&X> {
+        self.weights.get(index)
+    }
+
+    /// Returns the total weight of all items.
+    pub fn total_weight(&self) -> X {
+        self.subtotals.first().cloned().unwrap_or_default()
+    }
+
+    /// Appends an item with the given `weight`, in `O(log n)` time. The new
+    /// item has index `self.len() - 1`.
+    ///
+    /// Returns [`WeightedError::InvalidWeight`] if `weight < 0`.
+    pub fn push(&mut self, weight: X) -> Result<(), WeightedError> {
+        if !(weight >= X::default()) {
+            return Err(WeightedError::InvalidWeight);
+        }
+        self.weights.push(weight.clone());
+        self.subtotals.push(weight);
+        self.update_ancestors(self.weights.len() - 1);
+        Ok(())
+    }
+
+    /// Removes the last item and returns its weight, or `None` if empty, in
+    /// `O(log n)` time.
+    pub fn pop(&mut self) -> Option<X> {
+        let weight = self.weights.pop()?;
+        self.subtotals.pop();
+        let n = self.weights.len();
+        if n > 0 {
+            self.update_ancestors(n);
+        }
+        Some(weight)
+    }
+
+    /// Removes the item at `index` and returns its weight, in `O(log n)`
+    /// time.
+    ///
+    /// As with [`Vec::swap_remove`], the last item is moved to `index`; the
+    /// indices of other items are unchanged.
+    ///
+    /// Returns [`WeightedError::TooMany`] if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> Result<X, WeightedError> {
+        if index >= self.weights.len() {
+            return Err(WeightedError::TooMany);
+        }
+        let last = self.pop().unwrap();
+        if index == self.weights.len() {
+            return Ok(last);
+        }
+        let weight = core::mem::replace(&mut self.weights[index], last);
+        self.update_subtotal(index);
+        self.update_ancestors(index);
+        Ok(weight)
+    }
+
+    /// Update a subset of weights, without changing the number of items, in
+    /// `O(log n)` time per updated weight.
+    ///
+    /// `new_weights` must be sorted by the index, as for
+    /// [`WeightedIndex::update_weights`]. Returns
+    /// [`WeightedError::InvalidWeight`] if the indices are not strictly
+    /// increasing or a weight is `< 0`, and [`WeightedError::TooMany`] if an
+    /// index is out of bounds. Unlike for other weighted index distributions,
+    /// all weights may become zero. In case of error, `self` is not modified.
+    ///
+    /// [`WeightedIndex::update_weights`]: crate::WeightedIndex::update_weights
+    pub fn update_weights(&mut self, new_weights: &[(usize, &X)]) -> Result<(), WeightedError> {
+        let zero = X::default();
+        let mut prev_i = None;
+        for &(i, w) in new_weights {
+            if let Some(old_i) = prev_i {
+                if old_i >= i {
+                    return Err(WeightedError::InvalidWeight);
+                }
+            }
+            if !(*w >= zero) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            if i >= self.weights.len() {
+                return Err(WeightedError::TooMany);
+            }
+            prev_i = Some(i);
+        }
+
+        for &(index, weight) in new_weights {
+            self.weights[index] = weight.clone();
+            self.update_subtotal(index);
+            self.update_ancestors(index);
+        }
+        Ok(())
+    }
+
+    /// Sample an index, or return an error if there are no items
+    /// ([`WeightedError::NoItem`]), all weights are zero
+    /// ([`WeightedError::AllWeightsZero`]) or the total weight cannot be
+    /// sampled from ([`WeightedError::InvalidWeight`], e.g. when a
+    /// floating-point total is infinite).
+    pub fn try_sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<usize, WeightedError> {
+        let zero = X::default();
+        let total_weight = match self.subtotals.first() {
+            Some(total) => total,
+            None => return Err(WeightedError::NoItem),
+        };
+        if !(*total_weight > zero) {
+            return Err(WeightedError::AllWeightsZero);
+        }
+        let n = self.weights.len();
+        loop {
+            let target = X::Sampler::sample_single(zero.clone(), total_weight, rng)
+                .map_err(|_| WeightedError::InvalidWeight)?;
+
+            // Descend from the root, where the items in the subtree of node
+            // `i` cover the weight range `[cumulative, cumulative + subtotal)`
+            // in the order: node `i`, left subtree, right subtree.
+            let mut i = 0;
+            let mut cumulative = zero.clone();
+            while i < n {
+                let mut upper = cumulative.clone();
+                upper += &self.weights[i];
+                if target < upper {
+                    return Ok(i);
+                }
+                cumulative = upper;
+
+                let left = 2 * i + 1;
+                if left < n {
+                    let mut upper = cumulative.clone();
+                    upper += &self.subtotals[left];
+                    if target < upper {
+                        i = left;
+                        continue;
+                    }
+                    cumulative = upper;
+                }
+                i = left + 1;
+            }
+            // With floating-point weights, rounding may cause the target to
+            // slightly exceed the sum of the weights; retry in this case.
+        }
+    }
+
+    /// Recompute the subtotal of node `i` from its weight and children.
+    fn update_subtotal(&mut self, i: usize) {
+        let n = self.weights.len();
+        let mut subtotal = self.weights[i].clone();
+        for child in [2 * i + 1, 2 * i + 2] {
+            if child < n {
+                subtotal += &self.subtotals[child];
+            }
+        }
+        self.subtotals[i] = subtotal;
+    }
+
+    /// Recompute the subtotals of all ancestors of node `i`.
+    ///
+    /// Subtotals are recomputed rather than adjusted by the change in weight
+    /// so that floating-point errors do not accumulate.
+    fn update_ancestors(&mut self, mut i: usize) {
+        while i > 0 {
+            i = (i - 1) / 2;
+            self.update_subtotal(i);
+        }
+    }
+}
+
+impl<X> Distribution<usize> for WeightedTreeIndex<X>
+where X: SampleUniform + PartialOrd + for<'a> AddAssign<&'a X> + Clone + Default
+{
+    /// Sample an index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`WeightedTreeIndex::try_sample`] returns an error, e.g. if
+    /// there are no items or all weights are zero.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        self.try_sample(rng).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_errors() {
+        let mut rng = crate::test::rng(711);
+        let mut distr = WeightedTreeIndex::new(Vec::<u32>::new()).unwrap();
+        assert!(distr.is_empty());
+        assert_eq!(distr.try_sample(&mut rng), Err(WeightedError::NoItem));
+        distr.push(0).unwrap();
+        assert_eq!(distr.try_sample(&mut rng), Err(WeightedError::AllWeightsZero));
+        assert_eq!(distr.update_weights(&[(1, &1)]), Err(WeightedError::TooMany));
+        assert_eq!(distr.swap_remove(1), Err(WeightedError::TooMany));
+
+        assert_eq!(
+            WeightedTreeIndex::new([10, 20, -1, 30]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        assert_eq!(
+            WeightedTreeIndex::new([0.5, f64::NAN]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+        let mut distr = WeightedTreeIndex::new([1.0, 2.0]).unwrap();
+        assert_eq!(distr.push(-1.0), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update_weights(&[(0, &f64::NAN)]), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update_weights(&[(1, &1.0), (0, &1.0)]), Err(WeightedError::InvalidWeight));
+        assert_eq!(distr.update_weights(&[(0, &3.0), (2, &1.0)]), Err(WeightedError::TooMany));
+        assert_eq!(distr, WeightedTreeIndex::new([1.0, 2.0]).unwrap());
+        distr.push(f64::INFINITY).unwrap();
+        assert_eq!(distr.try_sample(&mut rng), Err(WeightedError::InvalidWeight));
+    }
+
+    #[test]
+    fn test_modifications() {
+        let mut expected: Vec<u32> = vec![1, 2, 3, 0, 5, 6, 7, 1, 2, 3];
+        let mut distr = WeightedTreeIndex::new(&expected).unwrap();
+        assert_eq!(distr.len(), 10);
+        assert_eq!(distr.total_weight(), 30);
+        assert_eq!(distr.get(4), Some(&5));
+        assert_eq!(distr.get(10), None);
+
+        for w in [4, 0, 9] {
+            distr.push(w).unwrap();
+            expected.push(w);
+            assert_eq!(distr, WeightedTreeIndex::new(&expected).unwrap());
+        }
+        for &(i, w) in [(2, 1), (5, 1), (12, 100), (3, 4), (0, 0)].iter() {
+            distr.update_weights(&[(i, &w)]).unwrap();
+            expected[i] = w;
+            assert_eq!(distr, WeightedTreeIndex::new(&expected).unwrap());
+        }
+        distr.update_weights(&[(1, &0), (6, &8), (11, &2)]).unwrap();
+        expected[1] = 0;
+        expected[6] = 8;
+        expected[11] = 2;
+        assert_eq!(distr, WeightedTreeIndex::new(&expected).unwrap());
+        for &i in [1, 11, 0, 4].iter() {
+            assert_eq!(distr.swap_remove(i), Ok(expected.swap_remove(i)));
+            assert_eq!(distr, WeightedTreeIndex::new(&expected).unwrap());
+        }
+        while let Some(w) = distr.pop() {
+            assert_eq!(Some(w), expected.pop());
+            assert_eq!(distr, WeightedTreeIndex::new(&expected).unwrap());
+        }
+        assert_eq!(distr.total_weight(), 0);
+    }
+
+    #[test]
+    fn test_sample() {
+        let mut rng = crate::test::rng(712);
+        let mut distr = WeightedTreeIndex::new([1u32, 0, 2, 0, 3, 4]).unwrap();
+        distr.push(0).unwrap();
+        distr.push(10).unwrap();
+        let weights = [1u32, 0, 2, 0, 3, 4, 0, 10];
+        let mut counts = [0; 8];
+        for _ in 0..20_000 {
+            counts[distr.sample(&mut rng)] += 1;
+        }
+        for (&count, &w) in counts.iter().zip(weights.iter()) {
+            let expected = w * 1000;
+            assert!(count >= expected * 9 / 10 && count <= expected * 11 / 10);
+        }
+
+        let distr = WeightedTreeIndex::new([0.0, 1e-3, 0.0, 0.25, 0.0]).unwrap();
+        for _ in 0..1000 {
+            let i = distr.sample(&mut rng);
+            assert!(i == 1 || i == 3);
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut distr = WeightedTreeIndex::new([1i32, 1, 1, 1, 1, 1, 1, 1]).unwrap();
+        distr.push(1).unwrap();
+        distr.update_weights(&[(3, &5)]).unwrap();
+        let mut rng = crate::test::rng(713);
+        let mut buf = [0; 10];
+        for r in buf.iter_mut() {
+            *r = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [8, 3, 3, 0, 3, 0, 3, 2, 8, 7]);
+    }
+}
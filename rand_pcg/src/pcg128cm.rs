@@ -14,7 +14,7 @@
 const MULTIPLIER: u64 = 15750249268501108917;
 
 use core::fmt;
-use rand_core::{impls, le, Error, RngCore, SeedableRng};
+use rand_core::{impls, le, JumpableRng, RngCore, SeedableRng, StreamRng};
 #[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
 
 /// A PCG random number generator (CM DXSM 128/64 (LCG) variant).
@@ -157,11 +157,41 @@ impl RngCore for Lcg128CmDxsm64 {
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         impls::fill_bytes_via_next(self, dest)
     }
+}
+
+impl JumpableRng for Lcg128CmDxsm64 {
+    /// Advance the generator by 2<sup>64</sup> steps, via [`advance`](Self::advance).
+    ///
+    /// With a period of 2<sup>128</sup>, this generates 2<sup>64</sup>
+    /// non-overlapping sub-sequences.
+    #[inline]
+    fn jump(&mut self) {
+        self.advance(1 << 64);
+    }
+
+    /// Advance the generator by 2<sup>96</sup> steps, via [`advance`](Self::advance).
+    #[inline]
+    fn long_jump(&mut self) {
+        self.advance(1 << 96);
+    }
+}
+
+impl StreamRng for Lcg128CmDxsm64 {
+    /// Select one of the first 2<sup>64</sup> streams, as for the `stream`
+    /// parameter of [`new`](Self::new).
+    ///
+    /// Note that two generators with different streams may be closely
+    /// correlated.
+    #[inline]
+    fn set_stream(&mut self, stream: u64) {
+        // The increment must be odd, hence we discard one bit:
+        self.increment = (u128::from(stream) << 1) | 1;
+    }
 
+    /// Get the lowest 64 bits of the stream.
     #[inline]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.fill_bytes(dest);
-        Ok(())
+    fn stream(&self) -> u64 {
+        (self.increment >> 1) as u64
     }
 }
 
@@ -11,7 +11,7 @@
 //! PCG random number generators
 
 use core::fmt;
-use rand_core::{impls, le, Error, RngCore, SeedableRng};
+use rand_core::{impls, le, JumpableRng, RngCore, SeedableRng, StreamRng};
 #[cfg(feature = "serde1")] use serde::{Deserialize, Serialize};
 
 // This is the default multiplier used by PCG for 64-bit state.
@@ -160,10 +160,40 @@ impl RngCore for Lcg64Xsh32 {
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         impls::fill_bytes_via_next(self, dest)
     }
+}
+
+impl JumpableRng for Lcg64Xsh32 {
+    /// Advance the generator by 2<sup>32</sup> steps, via [`advance`](Self::advance).
+    ///
+    /// With a period of 2<sup>64</sup>, this generates 2<sup>32</sup>
+    /// non-overlapping sub-sequences.
+    #[inline]
+    fn jump(&mut self) {
+        self.advance(1 << 32);
+    }
+
+    /// Advance the generator by 2<sup>48</sup> steps, via [`advance`](Self::advance).
+    #[inline]
+    fn long_jump(&mut self) {
+        self.advance(1 << 48);
+    }
+}
+
+impl StreamRng for Lcg64Xsh32 {
+    /// Select the stream, as for the `stream` parameter of [`new`](Self::new).
+    ///
+    /// There are 2<sup>63</sup> streams, thus the highest bit of `stream` is
+    /// discarded. Note that two generators with different streams may be
+    /// closely correlated.
+    #[inline]
+    fn set_stream(&mut self, stream: u64) {
+        // The increment must be odd, hence we discard one bit:
+        self.increment = (stream << 1) | 1;
+    }
 
+    /// Get the stream.
     #[inline]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.fill_bytes(dest);
-        Ok(())
+    fn stream(&self) -> u64 {
+        self.increment >> 1
     }
 }
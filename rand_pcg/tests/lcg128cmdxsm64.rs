@@ -1,4 +1,4 @@
-use rand_core::{RngCore, SeedableRng};
+use rand_core::{JumpableRng, RngCore, SeedableRng, StreamRng};
 use rand_pcg::{Lcg128CmDxsm64, Pcg64Dxsm};
 
 #[test]
@@ -14,6 +14,38 @@ fn test_lcg128cmdxsm64_advancing() {
     }
 }
 
+#[test]
+fn test_lcg128cmdxsm64_jump() {
+    let mut rng1 = Lcg128CmDxsm64::seed_from_u64(0);
+    let mut rng2 = rng1.clone();
+    rng1.jump();
+    rng1.jump();
+    rng2.advance(1 << 65);
+    assert_eq!(rng1, rng2);
+    rng1.long_jump();
+    rng2.advance(1 << 96);
+    assert_eq!(rng1, rng2);
+}
+
+#[test]
+fn test_lcg128cmdxsm64_stream() {
+    let mut rng1 = Lcg128CmDxsm64::new(0xcafef00dd15ea5e5, 5);
+    let mut rng2 = rng1.clone();
+    assert_eq!(rng1.stream(), 5);
+    rng2.set_stream(7);
+    assert_eq!(rng2.stream(), 7);
+    let (mut a, mut b) = (rng1.clone(), rng2.clone());
+    a.next_u64();
+    b.next_u64();
+    assert_ne!(a.next_u64(), b.next_u64());
+    rng1.set_stream(7);
+    assert_eq!(rng1, rng2);
+
+    // Only the lowest 64 bits of the stream are reported
+    let rng3 = Lcg128CmDxsm64::new(0, (1 << 70) | 3);
+    assert_eq!(rng3.stream(), 3);
+}
+
 #[test]
 fn test_lcg128cmdxsm64_construction() {
     // Test that various construction techniques produce a working RNG.
@@ -1,4 +1,4 @@
-use rand_core::{RngCore, SeedableRng};
+use rand_core::{JumpableRng, RngCore, SeedableRng, StreamRng};
 use rand_pcg::{Lcg64Xsh32, Pcg32};
 
 #[test]
@@ -14,6 +14,38 @@ fn test_lcg64xsh32_advancing() {
     }
 }
 
+#[test]
+fn test_lcg64xsh32_jump() {
+    let mut rng1 = Lcg64Xsh32::seed_from_u64(0);
+    let mut rng2 = rng1.clone();
+    rng1.jump();
+    rng1.jump();
+    rng2.advance(1 << 33);
+    assert_eq!(rng1, rng2);
+    rng1.long_jump();
+    rng2.advance(1 << 48);
+    assert_eq!(rng1, rng2);
+}
+
+#[test]
+fn test_lcg64xsh32_stream() {
+    let mut rng1 = Lcg64Xsh32::new(0xcafef00dd15ea5e5, 5);
+    let mut rng2 = rng1.clone();
+    assert_eq!(rng1.stream(), 5);
+    rng2.set_stream(7);
+    assert_eq!(rng2.stream(), 7);
+    let (mut a, mut b) = (rng1.clone(), rng2.clone());
+    a.next_u64();
+    b.next_u64();
+    assert_ne!(a.next_u64(), b.next_u64());
+    rng1.set_stream(7);
+    assert_eq!(rng1, rng2);
+
+    // The highest bit of the stream is discarded
+    rng2.set_stream(u64::MAX);
+    assert_eq!(rng2.stream(), u64::MAX >> 1);
+}
+
 #[test]
 fn test_lcg64xsh32_construction() {
     // Test that various construction techniques produce a working RNG.
@@ -1,4 +1,4 @@
-use rand_core::{RngCore, SeedableRng};
+use rand_core::{JumpableRng, RngCore, SeedableRng};
 use rand_pcg::{Mcg128Xsl64, Pcg64Mcg};
 
 #[test]
@@ -14,6 +14,19 @@ fn test_mcg128xsl64_advancing() {
     }
 }
 
+#[test]
+fn test_mcg128xsl64_jump() {
+    let mut rng1 = Mcg128Xsl64::seed_from_u64(0);
+    let mut rng2 = rng1.clone();
+    rng1.jump();
+    rng1.jump();
+    rng2.advance(1 << 65);
+    assert_eq!(rng1, rng2);
+    rng1.long_jump();
+    rng2.advance(1 << 96);
+    assert_eq!(rng1, rng2);
+}
+
 #[test]
 fn test_mcg128xsl64_construction() {
     // Test that various construction techniques produce a working RNG.
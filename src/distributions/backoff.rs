@@ -0,0 +1,346 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Jittered exponential backoff delays.
+
+use crate::Rng;
+use core::fmt;
+use core::time::Duration;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// The jitter strategy used by [`Backoff`].
+///
+/// Given the exponentially growing, capped delay `d` of an attempt, each
+/// strategy produces a delay as documented on the variants below, where
+/// `uniform(a, b)` samples uniformly from `[a, b]`.
+///
+/// See Marc Brooker, [*Exponential Backoff And Jitter*](
+/// https://aws.amazon.com/blogs/architecture/exponential-backoff-and-jitter/)
+/// for a comparison.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum Jitter {
+    /// No jitter: `d`.
+    None,
+    /// Full jitter: `uniform(0, d)`.
+    Full,
+    /// Equal jitter: `d / 2 + uniform(0, d / 2)`.
+    Equal,
+    /// Decorrelated jitter: `min(cap, uniform(initial, 3 * previous))`, where
+    /// `previous` is the previous delay (initially `initial`). Unlike the
+    /// other strategies, this does not use `d`, but depends on the previous
+    /// sample.
+    Decorrelated,
+}
+
+/// Exponential backoff with jitter, producing delays for retrying an
+/// operation.
+///
+/// The delay before retry attempt `n` (counting from 0) grows exponentially
+/// as `initial * multiplier^n`, but is capped at `cap`; see
+/// [`Backoff::max_delay`]. Random [`Jitter`] is applied to spread out retries
+/// of many clients, avoiding bursts of simultaneous retries.
+///
+/// Delays are produced by the iterator returned from [`Backoff::delays`],
+/// since the delay depends on the number of attempts made (and for
+/// [`Jitter::Decorrelated`], on the previous delay).
+///
+/// # Example
+///
+/// ```
+/// use core::time::Duration;
+/// use rand::distributions::{Backoff, Jitter};
+///
+/// let backoff = Backoff::new(
+///     Duration::from_millis(100), 2.0, Duration::from_secs(10), Jitter::Full,
+/// ).unwrap();
+///
+/// for (attempt, delay) in backoff.delays(rand::thread_rng()).enumerate().take(5) {
+///     // Retry the operation after sleeping for `delay`
+///     assert!(delay <= backoff.max_delay(attempt as u32));
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Backoff {
+    initial: Duration,
+    multiplier: f64,
+    cap: Duration,
+    jitter: Jitter,
+}
+
+/// Error type returned from `Backoff::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackoffError {
+    /// `multiplier < 1`, infinite or `nan`.
+    InvalidMultiplier,
+    /// `initial > cap`.
+    InitialExceedsCap,
+}
+
+impl fmt::Display for BackoffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BackoffError::InvalidMultiplier => "multiplier < 1 or is not finite in backoff",
+            BackoffError::InitialExceedsCap => "initial delay exceeds cap in backoff",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for BackoffError {}
+
+impl Backoff {
+    /// Construct a new `Backoff`, with delays starting at `initial`, growing
+    /// by a factor `multiplier` per attempt and capped at `cap`.
+    pub fn new(
+        initial: Duration, multiplier: f64, cap: Duration, jitter: Jitter,
+    ) -> Result<Backoff, BackoffError> {
+        if !(multiplier >= 1.0 && multiplier.is_finite()) {
+            return Err(BackoffError::InvalidMultiplier);
+        }
+        if initial > cap {
+            return Err(BackoffError::InitialExceedsCap);
+        }
+        Ok(Backoff {
+            initial,
+            multiplier,
+            cap,
+            jitter,
+        })
+    }
+
+    /// The delay before attempt `attempt` without jitter, i.e.
+    /// `min(cap, initial * multiplier^attempt)`.
+    ///
+    /// With [`Jitter::None`], [`Jitter::Full`] and [`Jitter::Equal`], this is
+    /// the maximum delay of that attempt.
+    pub fn max_delay(&self, attempt: u32) -> Duration {
+        // `initial <= cap` unless deserialized from invalid data
+        let initial = self.initial.min(self.cap);
+        if attempt == 0 || initial == Duration::ZERO {
+            return initial;
+        }
+        let secs = initial.as_secs_f64() * pow(self.multiplier, attempt);
+        if secs >= self.cap.as_secs_f64() {
+            self.cap
+        } else {
+            Duration::from_secs_f64(secs)
+        }
+    }
+
+    /// Create an iterator of the delays of successive attempts, using `rng`
+    /// as the source of randomness.
+    #[inline]
+    pub fn delays<R: Rng>(self, rng: R) -> BackoffDelays<R> {
+        BackoffDelays {
+            backoff: self,
+            rng,
+            attempt: 0,
+            previous: self.initial,
+        }
+    }
+}
+
+/// `base^exp` by repeated squaring, as `f64::powi` is not available without
+/// `std`.
+fn pow(mut base: f64, mut exp: u32) -> f64 {
+    let mut result = 1.0;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result *= base;
+        }
+        base *= base;
+        exp >>= 1;
+    }
+    result
+}
+
+/// An infinite iterator of the delays of successive attempts of a
+/// [`Backoff`].
+///
+/// This `struct` is created by [`Backoff::delays`].
+#[derive(Clone, Debug)]
+pub struct BackoffDelays<R> {
+    backoff: Backoff,
+    rng: R,
+    attempt: u32,
+    previous: Duration,
+}
+
+impl<R: Rng> BackoffDelays<R> {
+    /// The number of delays produced since creation or the last reset.
+    #[inline]
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Restart from the first attempt, e.g. after the operation succeeded.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+        self.previous = self.backoff.initial;
+    }
+
+    /// Sample the delay of the next attempt.
+    pub fn next_delay(&mut self) -> Duration {
+        let backoff = &self.backoff;
+        let delay = match backoff.jitter {
+            Jitter::None => backoff.max_delay(self.attempt),
+            Jitter::Full => {
+                let max = backoff.max_delay(self.attempt);
+                self.rng.gen_range(Duration::ZERO..=max)
+            }
+            Jitter::Equal => {
+                let half = backoff.max_delay(self.attempt) / 2;
+                half + self.rng.gen_range(Duration::ZERO..=half)
+            }
+            Jitter::Decorrelated => {
+                let high = self.previous.checked_mul(3).unwrap_or(Duration::MAX);
+                let delay = self.rng.gen_range(backoff.initial..=high);
+                delay.min(backoff.cap)
+            }
+        };
+        self.attempt = self.attempt.saturating_add(1);
+        self.previous = delay;
+        delay
+    }
+}
+
+impl<R: Rng> Iterator for BackoffDelays<R> {
+    type Item = Duration;
+
+    #[inline]
+    fn next(&mut self) -> Option<Duration> {
+        Some(self.next_delay())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(
+            Backoff::new(ms(10), 0.5, ms(100), Jitter::Full),
+            Err(BackoffError::InvalidMultiplier)
+        );
+        assert_eq!(
+            Backoff::new(ms(10), f64::NAN, ms(100), Jitter::Full),
+            Err(BackoffError::InvalidMultiplier)
+        );
+        assert_eq!(
+            Backoff::new(ms(10), f64::INFINITY, ms(100), Jitter::Full),
+            Err(BackoffError::InvalidMultiplier)
+        );
+        assert_eq!(
+            Backoff::new(ms(101), 2.0, ms(100), Jitter::Full),
+            Err(BackoffError::InitialExceedsCap)
+        );
+    }
+
+    #[test]
+    fn test_max_delay() {
+        let backoff = Backoff::new(ms(100), 2.0, ms(1000), Jitter::None).unwrap();
+        let delays = [100, 200, 400, 800, 1000, 1000];
+        for (attempt, &delay) in delays.iter().enumerate() {
+            assert_eq!(backoff.max_delay(attempt as u32), ms(delay));
+        }
+        assert_eq!(backoff.max_delay(u32::MAX), ms(1000));
+
+        let mut rng = crate::test::rng(271);
+        for (delay, &expected) in backoff.delays(&mut rng).zip(delays.iter()) {
+            assert_eq!(delay, ms(expected));
+        }
+
+        let backoff = Backoff::new(ms(100), 1.0, Duration::MAX, Jitter::None).unwrap();
+        assert_eq!(backoff.max_delay(1000), ms(100));
+        assert_eq!(backoff.max_delay(u32::MAX), ms(100));
+
+        let backoff = Backoff::new(ms(0), 2.0, ms(1000), Jitter::None).unwrap();
+        assert_eq!(backoff.max_delay(u32::MAX), ms(0));
+
+        let backoff = Backoff::new(ms(10), 1.5, Duration::MAX, Jitter::None).unwrap();
+        assert_eq!(backoff.max_delay(2), Duration::from_micros(22_500));
+        assert_eq!(backoff.max_delay(u32::MAX), Duration::MAX);
+    }
+
+    #[test]
+    fn test_max_delay_initial_exceeds_cap() {
+        // Bypassing `Backoff::new`, as deserialization does
+        let backoff = Backoff {
+            initial: ms(200),
+            multiplier: 2.0,
+            cap: ms(100),
+            jitter: Jitter::None,
+        };
+        assert_eq!(backoff.max_delay(0), ms(100));
+        assert_eq!(backoff.max_delay(1), ms(100));
+    }
+
+    fn backoff_with(jitter: Jitter) -> Backoff {
+        Backoff::new(ms(100), 2.0, ms(1000), jitter).unwrap()
+    }
+
+    #[test]
+    fn test_jitter() {
+        let mut rng = crate::test::rng(272);
+        for &jitter in &[Jitter::Full, Jitter::Equal, Jitter::Decorrelated] {
+            let backoff = backoff_with(jitter);
+            let mut delays = backoff.delays(&mut rng);
+            let mut previous = ms(100);
+            for attempt in 0..20 {
+                assert_eq!(delays.attempt(), attempt);
+                let delay = delays.next().unwrap();
+                let max = backoff.max_delay(attempt);
+                match jitter {
+                    Jitter::Full => assert!(delay <= max),
+                    Jitter::Equal => assert!(max / 2 <= delay && delay <= max),
+                    Jitter::Decorrelated => {
+                        assert!(ms(100) <= delay && delay <= ms(1000));
+                        assert!(delay <= previous * 3);
+                    }
+                    Jitter::None => unreachable!(),
+                }
+                previous = delay;
+            }
+            delays.reset();
+            assert_eq!(delays.attempt(), 0);
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(273);
+        let expected = [
+            (Jitter::Full, [97_680_380, 52_420_223, 170_444_730, 520_381_624]),
+            (Jitter::Equal, [84_962_114, 142_923_136, 277_691_022, 402_182_977]),
+            (Jitter::Decorrelated, [216_567_605, 516_989_049, 897_095_938, 172_446_992]),
+        ];
+        for &(jitter, nanos) in expected.iter() {
+            let mut delays = backoff_with(jitter).delays(&mut rng);
+            let mut buf = [0; 4];
+            for x in buf.iter_mut() {
+                *x = delays.next_delay().as_nanos();
+            }
+            assert_eq!(buf, nanos, "{:?}", jitter);
+        }
+    }
+}
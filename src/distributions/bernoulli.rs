@@ -33,7 +33,7 @@ use serde::{Serialize, Deserialize};
 ///
 /// This `Bernoulli` distribution uses 64 bits from the RNG (a `u64`),
 /// so only probabilities that are multiples of 2<sup>-64</sup> can be
-/// represented.
+/// represented. See [`BernoulliRatio`] for exact rational probabilities.
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct Bernoulli {
@@ -127,6 +127,33 @@ impl Bernoulli {
         let p_int = ((f64::from(numerator) / f64::from(denominator)) * SCALE) as u64;
         Ok(Bernoulli { p_int })
     }
+
+    /// Construct a new `Bernoulli` from odds of `successes` to `failures`,
+    /// i.e. with probability of success `successes / (successes + failures)`.
+    /// For example, `from_odds(1, 4)` has a 1-in-5 chance of returning `true`.
+    ///
+    /// The probability is converted to the internal fixed-point representation
+    /// using integer arithmetic, thus it is accurate to within 2<sup>-64</sup>
+    /// (rounding down) without any intermediate floating-point error.
+    ///
+    /// If `failures == 0` (and `successes > 0`), samples are always true; for
+    /// `successes == 0` samples are always false. If both are zero, this returns
+    /// an error.
+    #[inline]
+    pub fn from_odds(successes: u64, failures: u64) -> Result<Bernoulli, BernoulliError> {
+        if successes == 0 && failures == 0 {
+            return Err(BernoulliError::InvalidProbability);
+        }
+        if failures == 0 {
+            return Ok(Bernoulli { p_int: ALWAYS_TRUE });
+        }
+        let total = u128::from(successes) + u128::from(failures);
+        let p_int = (u128::from(successes) << 64) / total;
+        // `p_int < 2^64` since `successes < total`. Only
+        // `from_odds(u64::MAX, 1)` yields `u64::MAX`, which must not be
+        // confused with `ALWAYS_TRUE`.
+        Ok(Bernoulli { p_int: (p_int as u64).min(ALWAYS_TRUE - 1) })
+    }
 }
 
 impl Distribution<bool> for Bernoulli {
@@ -141,9 +168,93 @@ impl Distribution<bool> for Bernoulli {
     }
 }
 
+/// The Bernoulli distribution with an exact rational probability.
+///
+/// Unlike [`Bernoulli`], the probability is not rounded to a multiple of
+/// 2<sup>-64</sup>: sampling compares a random number against the exact
+/// binary expansion of `numerator / denominator`, consuming further `u64`
+/// values from the RNG only in the (2<sup>-64</sup> probable) event of a
+/// tie. Thus samples are identical to those of the fixed-point approximation
+/// except for ties, which are resolved exactly. This is intended for
+/// randomized algorithms whose correctness depends on exact probabilities.
+///
+/// # Example
+///
+/// ```rust
+/// use rand::distributions::{BernoulliRatio, Distribution};
+///
+/// let d = BernoulliRatio::new(1, 3).unwrap();
+/// let v = d.sample(&mut rand::thread_rng());
+/// println!("{} is true with probability exactly 1/3", v);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct BernoulliRatio {
+    /// The first 64 bits of the binary expansion of the probability, or
+    /// `ALWAYS_TRUE`
+    p_int: u64,
+    /// The remainder of the expansion after `p_int`
+    remainder: u64,
+    denominator: u64,
+}
+
+impl BernoulliRatio {
+    /// Construct a new `BernoulliRatio` with the probability of success of
+    /// exactly `numerator`-in-`denominator`.
+    ///
+    /// For `numerator > denominator` and `denominator == 0`, this returns an
+    /// error. Otherwise, for `numerator == denominator`, samples are always
+    /// true; for `numerator == 0` samples are always false.
+    #[inline]
+    pub fn new(numerator: u64, denominator: u64) -> Result<BernoulliRatio, BernoulliError> {
+        if numerator > denominator || denominator == 0 {
+            return Err(BernoulliError::InvalidProbability);
+        }
+        if numerator == denominator {
+            return Ok(BernoulliRatio { p_int: ALWAYS_TRUE, remainder: 0, denominator });
+        }
+        let (p_int, remainder) = next_digit(numerator, denominator);
+        Ok(BernoulliRatio { p_int, remainder, denominator })
+    }
+}
+
+/// The next 64 bits of the binary expansion of `remainder / denominator`,
+/// along with the new remainder. Requires `remainder < denominator`.
+#[inline]
+fn next_digit(remainder: u64, denominator: u64) -> (u64, u64) {
+    let x = u128::from(remainder) << 64;
+    let d = u128::from(denominator);
+    ((x / d) as u64, (x % d) as u64)
+}
+
+impl Distribution<bool> for BernoulliRatio {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+        if self.p_int == ALWAYS_TRUE {
+            return true;
+        }
+        let v: u64 = rng.gen();
+        // If the remainder is zero, the probability is exactly `p_int / 2^64`.
+        if v != self.p_int || self.remainder == 0 {
+            return v < self.p_int;
+        }
+        // Tie: compare with the following digits of the expansion.
+        let mut remainder = self.remainder;
+        loop {
+            let (digit, r) = next_digit(remainder, self.denominator);
+            remainder = r;
+            let v: u64 = rng.gen();
+            if v != digit {
+                return v < digit;
+            }
+            // Each further tie has probability 2^-64.
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Bernoulli;
+    use super::{Bernoulli, BernoulliError, BernoulliRatio};
     use crate::distributions::Distribution;
     use crate::Rng;
 
@@ -200,6 +311,56 @@ mod test {
         assert!((avg2 - (NUM as f64) / (DENOM as f64)).abs() < 5e-3);
     }
 
+    #[test]
+    fn test_from_odds() {
+        assert_eq!(Bernoulli::from_odds(0, 0), Err(BernoulliError::InvalidProbability));
+        assert_eq!(Bernoulli::from_odds(0, 5), Bernoulli::new(0.0));
+        assert_eq!(Bernoulli::from_odds(5, 0), Bernoulli::new(1.0));
+        assert_eq!(Bernoulli::from_odds(1, 1), Bernoulli::new(0.5));
+        assert_eq!(Bernoulli::from_odds(1, 3), Bernoulli::new(0.25));
+        assert_eq!(Bernoulli::from_odds(3, 7).unwrap().p_int, 5534023222112865484);
+        assert_eq!(Bernoulli::from_odds(u64::MAX, 1).unwrap().p_int, u64::MAX - 1);
+        assert_eq!(Bernoulli::from_odds(1, u64::MAX).unwrap().p_int, 1);
+    }
+
+    #[test]
+    fn test_bernoulli_ratio() {
+        use crate::rngs::mock::StepRng;
+
+        assert_eq!(BernoulliRatio::new(1, 0), Err(BernoulliError::InvalidProbability));
+        assert_eq!(BernoulliRatio::new(3, 2), Err(BernoulliError::InvalidProbability));
+        let mut rng = crate::test::rng(4);
+        let never = BernoulliRatio::new(0, 5).unwrap();
+        let always = BernoulliRatio::new(5, 5).unwrap();
+        for _ in 0..5 {
+            assert!(!never.sample(&mut rng));
+            assert!(always.sample(&mut rng));
+        }
+
+        // Dyadic ratios are sampled like the fixed-point value
+        let mut r1 = crate::test::rng(5);
+        let mut r2 = crate::test::rng(5);
+        let d1 = BernoulliRatio::new(3, 8).unwrap();
+        let d2 = Bernoulli::new(0.375).unwrap();
+        for _ in 0..100 {
+            assert_eq!(d1.sample(&mut r1), d2.sample(&mut r2));
+        }
+
+        // 1/3 = 0.010101...b, thus every 64-bit digit is 0x5555...
+        const DIGIT: u64 = 0x5555_5555_5555_5555;
+        let third = BernoulliRatio::new(1, 3).unwrap();
+        assert_eq!(third.p_int, DIGIT);
+        assert!(third.sample(&mut StepRng::new(DIGIT - 1, 0)));
+        assert!(!third.sample(&mut StepRng::new(DIGIT + 1, 0)));
+        // Ties are resolved by the following digits
+        assert!(third.sample(&mut StepRng::new(DIGIT, u64::MAX)));
+        assert!(!third.sample(&mut StepRng::new(DIGIT, 1)));
+
+        let distr = BernoulliRatio::new(2, 7).unwrap();
+        let sum = (0..10_000).filter(|_| distr.sample(&mut rng)).count();
+        assert!((2657..3057).contains(&sum));
+    }
+
     #[test]
     fn value_stability() {
         let mut rng = crate::test::rng(3);
@@ -0,0 +1,196 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A distribution over a user-defined set of characters.
+
+use crate::distributions::uniform::SampleChars;
+use crate::distributions::{DistString, Distribution, Uniform};
+use crate::{Rng, RngCore};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// Sample a `char`, uniformly distributed over a user-defined set of
+/// characters.
+///
+/// This generalises [`Alphanumeric`] to arbitrary alphabets, e.g. for
+/// generating tokens, passwords or test identifiers in hexadecimal, base32 or
+/// any other character set. Unlike [`Slice`], the characters are owned by the
+/// distribution, and may be given as a string.
+///
+/// Each distinct character is sampled with equal probability; duplicate
+/// characters in the input are ignored.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{CharSet, DistString};
+///
+/// let hex = CharSet::try_from("0123456789abcdef").unwrap();
+/// let token = hex.sample_string(&mut rand::thread_rng(), 32);
+/// assert!(token.chars().all(|c| c.is_ascii_hexdigit()));
+/// ```
+///
+/// Characters may also be supplied as an iterator:
+///
+/// ```
+/// use rand::Rng;
+/// use rand::distributions::CharSet;
+///
+/// let dna = CharSet::new(['A', 'C', 'G', 'T']).unwrap();
+/// let base: char = rand::thread_rng().sample(&dna);
+/// println!("{}", base);
+/// ```
+///
+/// See also the notes on [passwords] in the documentation of [`Alphanumeric`].
+///
+/// [`Alphanumeric`]: crate::distributions::Alphanumeric
+/// [`Slice`]: crate::distributions::Slice
+/// [passwords]: crate::distributions::Alphanumeric#passwords
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct CharSet {
+    chars: Vec<char>,
+    // Uniform over indices; `u32` for portability (there are fewer than
+    // 2^21 distinct `char`s).
+    range: Uniform<u32>,
+    max_char_len: usize,
+}
+
+impl CharSet {
+    /// Create a new `CharSet` which samples uniformly from the distinct
+    /// characters yielded by `chars`.
+    ///
+    /// Returns `Err` if `chars` is empty.
+    pub fn new<I: IntoIterator<Item = char>>(chars: I) -> Result<Self, EmptyCharSet> {
+        let mut chars: Vec<char> = chars.into_iter().collect();
+        chars.sort_unstable();
+        chars.dedup();
+        if chars.is_empty() {
+            return Err(EmptyCharSet);
+        }
+        let range = Uniform::new(0, chars.len() as u32).unwrap();
+        let max_char_len = chars.iter().map(|c| c.len_utf8()).max().unwrap();
+        Ok(CharSet {
+            chars,
+            range,
+            max_char_len,
+        })
+    }
+
+    /// The distinct characters of this set, in ascending order.
+    #[inline]
+    pub fn chars(&self) -> &[char] {
+        &self.chars
+    }
+}
+
+impl<'a> TryFrom<&'a str> for CharSet {
+    type Error = EmptyCharSet;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        CharSet::new(s.chars())
+    }
+}
+
+impl<'a> TryFrom<&'a [char]> for CharSet {
+    type Error = EmptyCharSet;
+
+    fn try_from(chars: &'a [char]) -> Result<Self, Self::Error> {
+        CharSet::new(chars.iter().cloned())
+    }
+}
+
+impl Distribution<char> for CharSet {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        self.chars[self.range.sample(rng) as usize]
+    }
+}
+
+impl SampleChars for &CharSet {
+    #[inline]
+    fn sample_char<R: RngCore + ?Sized>(self, rng: &mut R) -> Option<char> {
+        Some(self.sample(rng))
+    }
+}
+
+/// Note: the `String` is potentially left with excess capacity; optionally the
+/// user may call `string.shrink_to_fit()` afterwards.
+impl DistString for CharSet {
+    fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+        string.reserve(self.max_char_len * len);
+        string.extend(self.sample_iter(rng).take(len));
+    }
+}
+
+/// Error type indicating that a [`CharSet`] distribution was improperly
+/// constructed with no characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmptyCharSet;
+
+impl fmt::Display for EmptyCharSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Tried to create a `distributions::CharSet` with no characters"
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EmptyCharSet {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_charset_new() {
+        assert_eq!(CharSet::new(None), Err(EmptyCharSet));
+        assert_eq!(CharSet::try_from(""), Err(EmptyCharSet));
+        assert_eq!(CharSet::try_from(&[][..]), Err(EmptyCharSet));
+
+        let set = CharSet::try_from("banana").unwrap();
+        assert_eq!(set.chars(), ['a', 'b', 'n']);
+        assert_eq!(set, CharSet::try_from(&['n', 'a', 'b'][..]).unwrap());
+    }
+
+    #[test]
+    fn test_charset_sample() {
+        let mut rng = crate::test::rng(221);
+        let set = CharSet::try_from("ACGT").unwrap();
+        let mut counts = [0; 4];
+        for _ in 0..1000 {
+            let c = set.sample(&mut rng);
+            counts[set.chars().iter().position(|&x| x == c).unwrap()] += 1;
+        }
+        assert!(counts.iter().all(|&n| n > 200));
+    }
+
+    #[test]
+    fn test_charset_string() {
+        let mut rng = crate::test::rng(222);
+        let set = CharSet::try_from("αβγ0123").unwrap();
+        let s = set.sample_string(&mut rng, 100);
+        assert_eq!(s.chars().count(), 100);
+        assert!(s.chars().all(|c| set.chars().contains(&c)));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(223);
+        let set = CharSet::try_from("0123456789abcdef").unwrap();
+        assert_eq!(set.sample_string(&mut rng, 16), "0bcd317c5ba7974d");
+    }
+}
@@ -0,0 +1,166 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Fair coin flips with buffered random bits.
+
+use crate::distributions::Distribution;
+use crate::{Rng, RngCore};
+use core::iter::FusedIterator;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A fair coin, sampling `true` and `false` with probability 0.5 each.
+///
+/// Sampling `Coin` via [`Distribution`] is identical to sampling a `bool` from
+/// [`Standard`], using one `u32` from the RNG per sample. Since [`Distribution`]
+/// is stateless, unused bits cannot be kept for later samples.
+///
+/// For many flips, [`Coin::flips`] instead returns an iterator which buffers a
+/// `u64` from the RNG and yields its bits one by one, thus using only a single
+/// bit of randomness per flip.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::Coin;
+/// use rand::Rng;
+///
+/// let mut rng = rand::thread_rng();
+/// let heads: bool = rng.sample(Coin);
+///
+/// // 100 flips using only two `u64` from the RNG
+/// let heads = Coin.flips(&mut rng).take(100).filter(|&b| b).count();
+/// println!("{} heads", heads);
+/// ```
+///
+/// [`Standard`]: crate::distributions::Standard
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Coin;
+
+impl Coin {
+    /// Create an iterator of fair coin flips, using `rng` as the source of
+    /// randomness.
+    ///
+    /// The iterator buffers random bits, using one bit per flip.
+    #[inline]
+    pub fn flips<R: RngCore>(self, rng: R) -> CoinFlips<R> {
+        CoinFlips::new(rng)
+    }
+}
+
+impl Distribution<bool> for Coin {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> bool {
+        // Use the most significant bit, as in `Standard`
+        (rng.next_u32() as i32) < 0
+    }
+}
+
+/// An infinite iterator of fair coin flips, buffering bits from an RNG.
+///
+/// This `struct` is created by [`Coin::flips`] or [`CoinFlips::new`]. Bits are
+/// taken from a `u64`, starting with the most significant bit; once all 64
+/// bits are used, a new `u64` is taken from the RNG.
+#[derive(Clone, Debug)]
+pub struct CoinFlips<R> {
+    rng: R,
+    chunk: u64,
+    chunk_remaining: u32,
+}
+
+impl<R: RngCore> CoinFlips<R> {
+    /// Create a new iterator of coin flips using `rng`.
+    #[inline]
+    pub fn new(rng: R) -> Self {
+        CoinFlips {
+            rng,
+            chunk: 0,
+            chunk_remaining: 0,
+        }
+    }
+
+    /// Flip the coin, returning `true` or `false` with probability 0.5 each.
+    #[inline]
+    pub fn flip(&mut self) -> bool {
+        if self.chunk_remaining == 0 {
+            self.chunk = self.rng.next_u64();
+            self.chunk_remaining = 64;
+        }
+        let bit = (self.chunk as i64) < 0;
+        self.chunk <<= 1;
+        self.chunk_remaining -= 1;
+        bit
+    }
+
+    /// Consume the iterator, returning the RNG. Any buffered bits are lost.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: RngCore> Iterator for CoinFlips<R> {
+    type Item = bool;
+
+    #[inline]
+    fn next(&mut self) -> Option<bool> {
+        Some(self.flip())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<R: RngCore> FusedIterator for CoinFlips<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::Standard;
+
+    #[test]
+    fn test_coin() {
+        let mut rng1 = crate::test::rng(261);
+        let mut rng2 = crate::test::rng(261);
+        for _ in 0..100 {
+            let a: bool = rng1.sample(Coin);
+            let b: bool = rng2.sample(Standard);
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_coin_flips() {
+        let mut rng = crate::test::rng(262);
+        let heads = Coin.flips(&mut rng).take(10_000).filter(|&b| b).count();
+        assert!((4800..5200).contains(&heads));
+
+        // Bits are taken from the most significant bit of each `u64`
+        let mut flips = CoinFlips::new(crate::rngs::mock::StepRng::new(0xC000_0000_0000_0001, 0));
+        let mut bits = [false; 66];
+        for x in bits.iter_mut() {
+            *x = flips.flip();
+        }
+        assert!(bits[0] && bits[1] && !bits[2] && bits[63]);
+        assert!(bits[64] && bits[65]);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut flips = Coin.flips(crate::test::rng(263));
+        let mut buf = [false; 8];
+        for x in buf.iter_mut() {
+            *x = flips.flip();
+        }
+        assert_eq!(buf, [true, false, true, true, false, true, true, true]);
+    }
+}
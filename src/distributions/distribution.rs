@@ -9,10 +9,12 @@
 
 //! Distribution trait and associates
 
-use crate::Rng;
+use crate::{Rng, RngCore};
 use core::iter;
 #[cfg(feature = "alloc")]
 use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
 /// Types (distributions) that can be used to create a random instance of `T`.
 ///
@@ -82,6 +84,95 @@ pub trait Distribution<T> {
         }
     }
 
+    /// Create an iterator that generates random values of `T`, borrowing both
+    /// the distribution and `rng`.
+    ///
+    /// This is equivalent to `(&distr).sample_iter(&mut rng)`, but does not
+    /// require explicit borrowing. The distribution and RNG may be used again
+    /// once the iterator is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let mut rng = thread_rng();
+    /// let die = Uniform::new_inclusive(1, 6).unwrap();
+    ///
+    /// for _ in 0..3 {
+    ///     let rolls: Vec<u8> = die.sample_iter_by_ref(&mut rng).take(5).collect();
+    ///     let bonus = die.sample(&mut rng);
+    ///     println!("{:?} + {}", rolls, bonus);
+    /// }
+    /// ```
+    fn sample_iter_by_ref<'a, R>(&'a self, rng: &'a mut R) -> DistIter<&'a Self, &'a mut R, T>
+    where R: Rng + ?Sized {
+        DistIter {
+            distr: self,
+            rng,
+            phantom: ::core::marker::PhantomData,
+        }
+    }
+
+    /// Create an iterator that generates exactly `n` random values of `T`,
+    /// using `rng` as the source of randomness.
+    ///
+    /// Unlike `sample_iter(rng).take(n)`, the returned iterator implements
+    /// [`ExactSizeIterator`], allowing `collect` to pre-allocate.
+    ///
+    /// As with [`sample_iter`], this function takes `self` by value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let mut rng = thread_rng();
+    ///
+    /// let die = Uniform::new_inclusive(1, 6).unwrap();
+    /// let rolls = die.sample_n(&mut rng, 10);
+    /// assert_eq!(rolls.len(), 10);
+    /// let rolls: Vec<u8> = rolls.collect();
+    /// ```
+    ///
+    /// [`sample_iter`]: Distribution::sample_iter
+    fn sample_n<R>(self, rng: R, n: usize) -> DistIterN<Self, R, T>
+    where
+        R: Rng,
+        Self: Sized,
+    {
+        DistIterN {
+            distr: self,
+            rng,
+            remaining: n,
+            phantom: ::core::marker::PhantomData,
+        }
+    }
+
+    /// Generate a `Vec` of `n` random values of `T`, using `rng` as the
+    /// source of randomness.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Standard};
+    ///
+    /// let v: Vec<f32> = Standard.sample_vec(&mut thread_rng(), 16);
+    /// assert_eq!(v.len(), 16);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn sample_vec<R: Rng + ?Sized>(&self, rng: &mut R, n: usize) -> Vec<T> {
+        let mut v = Vec::with_capacity(n);
+        for _ in 0..n {
+            v.push(self.sample(rng));
+        }
+        v
+    }
+
     /// Create a distribution of values of 'S' by mapping the output of `Self`
     /// through the closure `F`
     ///
@@ -110,6 +201,95 @@ pub trait Distribution<T> {
             phantom: ::core::marker::PhantomData,
         }
     }
+
+    /// Create a distribution of pairs `(T, U)` by sampling from `Self` and
+    /// then from `other`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let mut rng = thread_rng();
+    ///
+    /// let die = Uniform::new_inclusive(1, 6).unwrap();
+    /// let two_dice = die.zip(die);
+    /// let (a, b) = two_dice.sample(&mut rng);
+    /// println!("Rolled {} and {}", a, b);
+    /// ```
+    fn zip<D, U>(self, other: D) -> DistZip<Self, D, T, U>
+    where
+        D: Distribution<U>,
+        Self: Sized,
+    {
+        DistZip {
+            first: self,
+            second: other,
+            phantom: ::core::marker::PhantomData,
+        }
+    }
+
+    /// Create a distribution of values of `S` by sampling from `Self` and
+    /// then from `other`, and combining both samples through the closure `F`
+    ///
+    /// This is equivalent to `self.zip(other).map(|(a, b)| func(a, b))`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let mut rng = thread_rng();
+    ///
+    /// let die = Uniform::new_inclusive(1, 6).unwrap();
+    /// let sum_of_two_dice = die.map_two(die, |a, b| a + b);
+    /// println!("Rolled a total of {}", sum_of_two_dice.sample(&mut rng));
+    /// ```
+    fn map_two<D, U, F, S>(self, other: D, func: F) -> DistMapTwo<Self, D, F, T, U, S>
+    where
+        D: Distribution<U>,
+        F: Fn(T, U) -> S,
+        Self: Sized,
+    {
+        DistMapTwo {
+            first: self,
+            second: other,
+            func,
+            phantom: ::core::marker::PhantomData,
+        }
+    }
+
+    /// Create a distribution of values of `T` which satisfy the predicate `P`
+    ///
+    /// Sampling uses rejection: samples from `Self` are drawn until one
+    /// satisfies the predicate. If the predicate rejects all (or nearly all)
+    /// values of `Self`, sampling will not terminate (in reasonable time).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::thread_rng;
+    /// use rand::distributions::{Distribution, Uniform};
+    ///
+    /// let mut rng = thread_rng();
+    ///
+    /// let die = Uniform::new_inclusive(1, 6).unwrap();
+    /// let not_five = die.filter(|num| *num != 5);
+    /// assert_ne!(not_five.sample(&mut rng), 5);
+    /// ```
+    fn filter<P>(self, predicate: P) -> DistFilter<Self, P, T>
+    where
+        P: Fn(&T) -> bool,
+        Self: Sized,
+    {
+        DistFilter {
+            distr: self,
+            predicate,
+            phantom: ::core::marker::PhantomData,
+        }
+    }
 }
 
 impl<'a, T, D: Distribution<T> + ?Sized> Distribution<T> for &'a D {
@@ -118,6 +298,64 @@ impl<'a, T, D: Distribution<T> + ?Sized> Distribution<T> for &'a D {
     }
 }
 
+/// An object-safe version of [`Distribution`].
+///
+/// [`Distribution::sample`] is generic over the RNG type, thus `Distribution`
+/// cannot be used as a trait object. This trait instead samples using a
+/// `&mut dyn RngCore`, allowing heterogeneous distributions to be stored as
+/// e.g. `Box<dyn DynDistribution<f64>>`. It is implemented for all
+/// [`Distribution`]s.
+///
+/// Trait objects of `DynDistribution` implement [`Distribution`] in turn, so
+/// `&dyn DynDistribution<T>` may be used wherever a [`Distribution`] is
+/// expected. Note that sampling through a trait object uses dynamic dispatch
+/// for each call to the RNG, which may be considerably slower.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{DynDistribution, Open01, Uniform};
+/// use rand::Rng;
+///
+/// let distrs: Vec<Box<dyn DynDistribution<f64>>> = vec![
+///     Box::new(Uniform::new(10.0, 20.0).unwrap()),
+///     Box::new(Open01),
+/// ];
+///
+/// let mut rng = rand::thread_rng();
+/// for distr in &distrs {
+///     let x = distr.sample_dyn(&mut rng);
+///     let y = rng.sample(&**distr);
+///     println!("{} {}", x, y);
+/// }
+/// ```
+pub trait DynDistribution<T> {
+    /// Generate a random value of `T`, using `rng` as the source of randomness.
+    fn sample_dyn(&self, rng: &mut dyn RngCore) -> T;
+}
+
+impl<T, D: Distribution<T>> DynDistribution<T> for D {
+    #[inline]
+    fn sample_dyn(&self, rng: &mut dyn RngCore) -> T {
+        self.sample(rng)
+    }
+}
+
+macro_rules! impl_distribution_for_dyn {
+    ($($bounds:tt)*) => {
+        impl<'a, T> Distribution<T> for dyn DynDistribution<T> $($bounds)* + 'a {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, mut rng: &mut R) -> T {
+                self.sample_dyn(&mut rng)
+            }
+        }
+    };
+}
+
+impl_distribution_for_dyn!();
+impl_distribution_for_dyn!(+ Send);
+impl_distribution_for_dyn!(+ Send + Sync);
+
 /// An iterator that generates random values of `T` with distribution `D`,
 /// using `R` as the source of randomness.
 ///
@@ -159,6 +397,56 @@ where
 {
 }
 
+/// An iterator that generates a fixed number of random values of `T` with
+/// distribution `D`, using `R` as the source of randomness.
+///
+/// This `struct` is created by the [`sample_n`] method on [`Distribution`].
+/// See its documentation for more.
+///
+/// [`sample_n`]: Distribution::sample_n
+#[derive(Debug)]
+pub struct DistIterN<D, R, T> {
+    distr: D,
+    rng: R,
+    remaining: usize,
+    phantom: ::core::marker::PhantomData<T>,
+}
+
+impl<D, R, T> Iterator for DistIterN<D, R, T>
+where
+    D: Distribution<T>,
+    R: Rng,
+{
+    type Item = T;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<T> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.distr.sample(&mut self.rng))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<D, R, T> ExactSizeIterator for DistIterN<D, R, T>
+where
+    D: Distribution<T>,
+    R: Rng,
+{
+}
+
+impl<D, R, T> iter::FusedIterator for DistIterN<D, R, T>
+where
+    D: Distribution<T>,
+    R: Rng,
+{
+}
+
 /// A distribution of values of type `S` derived from the distribution `D`
 /// by mapping its output of type `T` through the closure `F`.
 ///
@@ -181,6 +469,87 @@ where
     }
 }
 
+/// A distribution of pairs `(T, U)` combining the distributions `D1` and
+/// `D2`.
+///
+/// This `struct` is created by the [`Distribution::zip`] method.
+/// See its documentation for more.
+#[derive(Debug, Clone, Copy)]
+pub struct DistZip<D1, D2, T, U> {
+    first: D1,
+    second: D2,
+    phantom: ::core::marker::PhantomData<fn() -> (T, U)>,
+}
+
+impl<D1, D2, T, U> Distribution<(T, U)> for DistZip<D1, D2, T, U>
+where
+    D1: Distribution<T>,
+    D2: Distribution<U>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> (T, U) {
+        let a = self.first.sample(rng);
+        let b = self.second.sample(rng);
+        (a, b)
+    }
+}
+
+/// A distribution of values of type `S` derived from the distributions `D1`
+/// and `D2` by combining their outputs of type `T` and `U` through the
+/// closure `F`.
+///
+/// This `struct` is created by the [`Distribution::map_two`] method.
+/// See its documentation for more.
+#[derive(Debug, Clone, Copy)]
+pub struct DistMapTwo<D1, D2, F, T, U, S> {
+    first: D1,
+    second: D2,
+    func: F,
+    phantom: ::core::marker::PhantomData<fn(T, U) -> S>,
+}
+
+impl<D1, D2, F, T, U, S> Distribution<S> for DistMapTwo<D1, D2, F, T, U, S>
+where
+    D1: Distribution<T>,
+    D2: Distribution<U>,
+    F: Fn(T, U) -> S,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> S {
+        let a = self.first.sample(rng);
+        let b = self.second.sample(rng);
+        (self.func)(a, b)
+    }
+}
+
+/// A distribution of values of type `T` derived from the distribution `D`
+/// by rejecting samples which do not satisfy the predicate `P`.
+///
+/// This `struct` is created by the [`Distribution::filter`] method.
+/// See its documentation for more.
+#[derive(Debug, Clone, Copy)]
+pub struct DistFilter<D, P, T> {
+    distr: D,
+    predicate: P,
+    phantom: ::core::marker::PhantomData<fn() -> T>,
+}
+
+impl<D, P, T> Distribution<T> for DistFilter<D, P, T>
+where
+    D: Distribution<T>,
+    P: Fn(&T) -> bool,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> T {
+        loop {
+            let x = self.distr.sample(rng);
+            if (self.predicate)(&x) {
+                return x;
+            }
+        }
+    }
+}
+
 /// `String` sampler
 ///
 /// Sampling a `String` of random characters is not quite the same as collecting
@@ -217,6 +586,38 @@ mod tests {
         assert!(0. < sum && sum < 100.);
     }
 
+    #[test]
+    fn test_distributions_sample_n() {
+        let distr = Uniform::new(0u32, 100).unwrap();
+        let mut rng1 = crate::test::rng(217);
+        let mut rng2 = crate::test::rng(217);
+
+        let mut iter = distr.sample_n(&mut rng1, 10);
+        assert_eq!(iter.len(), 10);
+        iter.next();
+        assert_eq!(iter.size_hint(), (9, Some(9)));
+        assert_eq!(iter.by_ref().count(), 9);
+        assert_eq!(iter.next(), None);
+
+        let mut rng1 = crate::test::rng(217);
+        let expected = (0..5).map(|_| distr.sample(&mut rng2));
+        assert!(distr.sample_n(&mut rng1, 5).eq(expected));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_distributions_sample_vec() {
+        let distr = Uniform::new(0u32, 100).unwrap();
+        let mut rng1 = crate::test::rng(218);
+        let mut rng2 = crate::test::rng(218);
+
+        let v = distr.sample_vec(&mut rng1, 20);
+        assert_eq!(v.len(), 20);
+        let w: alloc::vec::Vec<u32> = distr.sample_n(&mut rng2, 20).collect();
+        assert_eq!(v, w);
+        assert!(distr.sample_vec(&mut rng1, 0).is_empty());
+    }
+
     #[test]
     fn test_distributions_map() {
         let dist = Uniform::new_inclusive(0, 5).unwrap().map(|val| val + 15);
@@ -226,6 +627,96 @@ mod tests {
         assert!((15..=20).contains(&val));
     }
 
+    #[test]
+    fn test_distributions_zip() {
+        let dist = Uniform::new(0, 10).unwrap().zip(Uniform::new(100, 110).unwrap());
+
+        let mut rng = crate::test::rng(214);
+        for _ in 0..100 {
+            let (a, b) = dist.sample(&mut rng);
+            assert!((0..10).contains(&a));
+            assert!((100..110).contains(&b));
+        }
+    }
+
+    #[test]
+    fn test_distributions_map_two() {
+        let die = Uniform::new_inclusive(1, 6).unwrap();
+        let dist = die.map_two(die, |a, b| a * 10 + b);
+        let zipped = die.zip(die);
+
+        let mut rng1 = crate::test::rng(215);
+        let mut rng2 = crate::test::rng(215);
+        for _ in 0..100 {
+            let val = dist.sample(&mut rng1);
+            let (a, b) = zipped.sample(&mut rng2);
+            assert_eq!(val, a * 10 + b);
+        }
+    }
+
+    #[test]
+    fn test_distributions_filter() {
+        let dist = Uniform::new_inclusive(1, 6).unwrap().filter(|x| x % 2 == 0);
+
+        let mut rng = crate::test::rng(216);
+        for _ in 0..100 {
+            let val = dist.sample(&mut rng);
+            assert!([2, 4, 6].contains(&val));
+        }
+    }
+
+    #[test]
+    fn test_sample_iter_by_ref() {
+        let distr = Uniform::new(0u32, 1000).unwrap();
+        let mut rng = crate::test::rng(218);
+        let mut sum = 0;
+        for _ in 0..3 {
+            for x in distr.sample_iter_by_ref(&mut rng).take(10) {
+                sum += x;
+            }
+            sum += distr.sample(&mut rng);
+        }
+        assert!(sum > 0);
+
+        // Identical to `sample_iter` with explicit borrows
+        let mut rng1 = crate::test::rng(219);
+        let mut rng2 = crate::test::rng(219);
+        let a = distr.sample_iter_by_ref(&mut rng1).take(10);
+        let b = (&distr).sample_iter(&mut rng2).take(10);
+        assert!(a.eq(b));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_dyn_distribution() {
+        use super::DynDistribution;
+        use crate::distributions::Open01;
+        use alloc::boxed::Box;
+        use alloc::vec::Vec;
+        use alloc::vec;
+
+        let distrs: Vec<Box<dyn DynDistribution<f64> + Send + Sync>> = vec![
+            Box::new(Uniform::new(10.0, 20.0).unwrap()),
+            Box::new(Open01),
+            Box::new(Uniform::new(0u32, 10).unwrap().map(f64::from)),
+        ];
+
+        let mut rng = crate::test::rng(216);
+        for _ in 0..100 {
+            assert!((10.0..20.0).contains(&distrs[0].sample_dyn(&mut rng)));
+            assert!((0.0..1.0).contains(&rng.sample(&*distrs[1])));
+            let x = distrs[2].sample(&mut rng);
+            assert!((0.0..10.0).contains(&x) && x.fract() == 0.0);
+        }
+
+        // Sampling through a trait object yields the same values
+        let distr = Uniform::new(0u64, 1000).unwrap();
+        let dyn_distr: &dyn DynDistribution<u64> = &distr;
+        let a: Vec<u64> = (&distr).sample_iter(crate::test::rng(217)).take(10).collect();
+        let b: Vec<u64> = dyn_distr.sample_iter(crate::test::rng(217)).take(10).collect();
+        assert_eq!(a, b);
+    }
+
     #[test]
     fn test_make_an_iter() {
         fn ten_dice_rolls_other_than_five<R: Rng>(
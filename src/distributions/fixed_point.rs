@@ -0,0 +1,192 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The implementations of `Standard` and `Uniform` for the fixed-point
+//! numbers of the `fixed` crate.
+
+use ::fixed::traits::Fixed;
+use ::fixed::types::extra::{LeEqU128, LeEqU16, LeEqU32, LeEqU64, LeEqU8};
+use ::fixed::{
+    FixedI128, FixedI16, FixedI32, FixedI64, FixedI8, FixedU128, FixedU16, FixedU32, FixedU64,
+    FixedU8,
+};
+
+use crate::distributions::uniform::{Error, SampleBorrow, SampleUniform, UniformInt, UniformSampler};
+use crate::distributions::{Distribution, Standard};
+use crate::Rng;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// The back-end implementing [`UniformSampler`] for the fixed-point numbers
+/// of the [`fixed`] crate, e.g. [`I32F32`](::fixed::types::I32F32).
+///
+/// Values are fixed-point numbers `bits * 2^-FRAC`, thus a range of
+/// fixed-point numbers corresponds to a range of their underlying integers
+/// ([`Fixed::to_bits`]), which is sampled with [`UniformInt`]. Results are
+/// therefore like those of the integer type.
+///
+/// Unless you are implementing [`UniformSampler`] for your own type, this type
+/// should not be used directly, use [`Uniform`] instead.
+///
+/// This type requires the crate feature `fixed`.
+///
+/// [`fixed`]: ::fixed
+/// [`Uniform`]: crate::distributions::Uniform
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", serde(bound(serialize = "X::Bits: Serialize")))]
+#[cfg_attr(feature = "serde1", serde(bound(deserialize = "X::Bits: Deserialize<'de>")))]
+pub struct UniformFixed<X: Fixed> {
+    sampler: UniformInt<X::Bits>,
+}
+
+impl<X: Fixed> UniformSampler for UniformFixed<X>
+where
+    X::Bits: SampleUniform,
+    UniformInt<X::Bits>: UniformSampler<X = X::Bits>,
+{
+    type X = X;
+
+    #[inline]
+    fn new<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let sampler = UniformSampler::new(low.borrow().to_bits(), high.borrow().to_bits());
+        sampler.map(|sampler| UniformFixed { sampler })
+    }
+
+    #[inline]
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let sampler =
+            UniformSampler::new_inclusive(low.borrow().to_bits(), high.borrow().to_bits());
+        sampler.map(|sampler| UniformFixed { sampler })
+    }
+
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::X {
+        X::from_bits(self.sampler.sample(rng))
+    }
+
+    #[inline]
+    fn sample_single<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R) -> Result<Self::X, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let bits = UniformInt::<X::Bits>::sample_single(
+            low.borrow().to_bits(),
+            high.borrow().to_bits(),
+            rng,
+        );
+        bits.map(X::from_bits)
+    }
+
+    #[inline]
+    fn sample_single_inclusive<R: Rng + ?Sized, B1, B2>(low: B1, high: B2, rng: &mut R)
+        -> Result<Self::X, Error>
+        where B1: SampleBorrow<Self::X> + Sized,
+              B2: SampleBorrow<Self::X> + Sized
+    {
+        let bits = UniformInt::<X::Bits>::sample_single_inclusive(
+            low.borrow().to_bits(),
+            high.borrow().to_bits(),
+            rng,
+        );
+        bits.map(X::from_bits)
+    }
+}
+
+macro_rules! fixed_impl {
+    ($($ty:ident: $frac:ident),*) => {$(
+        impl<Frac: $frac> Distribution<$ty<Frac>> for Standard {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty<Frac> {
+                $ty::from_bits(rng.gen())
+            }
+        }
+
+        impl<Frac: $frac> SampleUniform for $ty<Frac> {
+            type Sampler = UniformFixed<$ty<Frac>>;
+        }
+    )*}
+}
+
+fixed_impl! {
+    FixedI8: LeEqU8, FixedI16: LeEqU16, FixedI32: LeEqU32, FixedI64: LeEqU64, FixedI128: LeEqU128,
+    FixedU8: LeEqU8, FixedU16: LeEqU16, FixedU32: LeEqU32, FixedU64: LeEqU64, FixedU128: LeEqU128
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::Uniform;
+    use ::fixed::types::{I16F16, I32F32, I8F0, U0F64, U8F8};
+
+    #[test]
+    fn test_standard() {
+        let mut rng = crate::test::rng(560);
+        let x: U0F64 = rng.gen();
+        assert!(x < 1);
+        let (a, b): (I32F32, I32F32) = rng.gen();
+        assert_ne!(a, b);
+
+        // Same bits as the underlying integer
+        let mut r1 = crate::test::rng(561);
+        let mut r2 = crate::test::rng(561);
+        for _ in 0..10 {
+            assert_eq!(r1.gen::<I16F16>().to_bits(), r2.gen::<i32>());
+        }
+    }
+
+    #[test]
+    fn test_uniform() {
+        let mut rng = crate::test::rng(562);
+        let low = I16F16::from_num(-1.5);
+        let high = I16F16::from_num(2.25);
+        let distr = Uniform::new(low, high).unwrap();
+        let distr_inclusive = Uniform::new_inclusive(low, high).unwrap();
+        for _ in 0..100 {
+            let x = rng.sample(distr);
+            assert!(low <= x && x < high);
+            let x = rng.sample(distr_inclusive);
+            assert!(low <= x && x <= high);
+            let x = rng.gen_range(low..high);
+            assert!(low <= x && x < high);
+            let x = rng.gen_range(low..=high);
+            assert!(low <= x && x <= high);
+        }
+
+        // Sampling a single value
+        let x = U8F8::from_bits(0x1234);
+        assert_eq!(rng.gen_range(x..=x), x);
+        assert!(Uniform::new(x, x).is_err());
+
+        // The full range
+        let distr = Uniform::new_inclusive(I8F0::MIN, I8F0::MAX).unwrap();
+        let mut seen = [false; 256];
+        for _ in 0..10_000 {
+            seen[(rng.sample(distr).to_bits() as u8) as usize] = true;
+        }
+        assert!(seen.iter().all(|&b| b));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(563);
+        let x: I32F32 = rng.gen();
+        let y = rng.gen_range(U8F8::from_num(1)..U8F8::from_num(2));
+        assert_eq!((x.to_bits(), y.to_bits()), (-7445009323648967386, 304));
+    }
+}
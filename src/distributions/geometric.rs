@@ -0,0 +1,154 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The geometric distribution.
+
+use crate::distributions::{Distribution, OpenClosed01};
+use crate::Rng;
+use core::fmt;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// The geometric distribution `Geometric(p)` bounded to `[0, u64::MAX]`.
+///
+/// This is the probability distribution of the number of failures before the
+/// first success in a series of [`Bernoulli`] trials, each with probability of
+/// success `p`. It has the density function `f(k) = (1 - p)^k p` for `k >= 0`.
+///
+/// Typical uses are skip-sampling (how many items to skip before the next
+/// selected one) and simulating the number of retries of an operation.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Geometric};
+///
+/// let geo = Geometric::new(0.25).unwrap();
+/// let v = geo.sample(&mut rand::thread_rng());
+/// println!("{} failures before the first success", v);
+/// ```
+///
+/// # Implementation details
+///
+/// Samples are generated using the inverse-CDF method,
+/// `floor(ln(u) / ln(1 - p))` where `u` is uniform on `(0, 1]`, thus each
+/// sample consumes a single `f64` from the RNG. The [`rand_distr`] crate
+/// provides an alternative implementation with different performance
+/// characteristics.
+///
+/// [`Bernoulli`]: crate::distributions::Bernoulli
+/// [`rand_distr`]: https://crates.io/crates/rand_distr
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Geometric {
+    /// `ln(1 - p)`
+    ln_q: f64,
+}
+
+/// Error type returned from `Geometric::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GeometricError {
+    /// `p < 0 || p > 1` or `nan`
+    InvalidProbability,
+}
+
+impl fmt::Display for GeometricError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GeometricError::InvalidProbability => {
+                "p is NaN or outside the interval [0, 1] in geometric distribution"
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for GeometricError {}
+
+impl Geometric {
+    /// Construct a new `Geometric` with the given probability of success `p`.
+    ///
+    /// For `p = 1`, samples are always `0`; for `p = 0`, samples are always
+    /// `u64::MAX`.
+    #[inline]
+    pub fn new(p: f64) -> Result<Geometric, GeometricError> {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(GeometricError::InvalidProbability);
+        }
+        Ok(Geometric { ln_q: (-p).ln_1p() })
+    }
+}
+
+impl Distribution<u64> for Geometric {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        if self.ln_q == f64::NEG_INFINITY {
+            return 0;
+        } else if self.ln_q == 0.0 {
+            return u64::MAX;
+        }
+        let u: f64 = rng.sample(OpenClosed01);
+        // The cast saturates for results beyond `u64::MAX`.
+        (u.ln() / self.ln_q).floor() as u64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_geometric_invalid() {
+        for &p in &[-0.1, 1.1, f64::NAN, f64::INFINITY] {
+            assert_eq!(Geometric::new(p), Err(GeometricError::InvalidProbability));
+        }
+    }
+
+    #[test]
+    fn test_geometric_edge_cases() {
+        let mut rng = crate::test::rng(1);
+        let always_zero = Geometric::new(1.0).unwrap();
+        let always_max = Geometric::new(0.0).unwrap();
+        for _ in 0..10 {
+            assert_eq!(always_zero.sample(&mut rng), 0);
+            assert_eq!(always_max.sample(&mut rng), u64::MAX);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_geometric_mean() {
+        const N: u64 = 100_000;
+        let mut rng = crate::test::rng(2);
+        for &p in &[0.9, 0.5, 0.1, 0.01] {
+            let distr = Geometric::new(p).unwrap();
+            let sum: u64 = (0..N).map(|_| distr.sample(&mut rng)).sum();
+            let mean = sum as f64 / N as f64;
+            let expected = (1.0 - p) / p;
+            let std_dev = (1.0 - p).sqrt() / p;
+            assert!((mean - expected).abs() < 5.0 * std_dev / (N as f64).sqrt());
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(3);
+        let distr = Geometric::new(0.25).unwrap();
+        let mut buf = [0u64; 10];
+        for x in &mut buf {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [4, 0, 2, 5, 1, 2, 3, 3, 3, 3]);
+    }
+
+    #[test]
+    fn geometric_distributions_can_be_compared() {
+        assert_eq!(Geometric::new(0.5), Geometric::new(0.5));
+    }
+}
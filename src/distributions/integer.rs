@@ -20,6 +20,8 @@ use core::arch::x86_64::__m512i;
 use core::arch::x86_64::{__m128i, __m256i};
 use core::num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
     NonZeroU128};
+use core::num::{NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+    NonZeroI128};
 #[cfg(feature = "simd_support")] use core::simd::*;
 use core::mem;
 
@@ -114,6 +116,13 @@ impl_nzint!(NonZeroU64, NonZeroU64::new);
 impl_nzint!(NonZeroU128, NonZeroU128::new);
 impl_nzint!(NonZeroUsize, NonZeroUsize::new);
 
+impl_nzint!(NonZeroI8, NonZeroI8::new);
+impl_nzint!(NonZeroI16, NonZeroI16::new);
+impl_nzint!(NonZeroI32, NonZeroI32::new);
+impl_nzint!(NonZeroI64, NonZeroI64::new);
+impl_nzint!(NonZeroI128, NonZeroI128::new);
+impl_nzint!(NonZeroIsize, NonZeroIsize::new);
+
 macro_rules! x86_intrinsic_impl {
     ($($intrinsic:ident),+) => {$(
         /// Available only on x86/64 platforms
@@ -186,6 +195,29 @@ mod tests {
         rng.sample::<u128, _>(Standard);
     }
 
+    #[test]
+    fn test_nonzero_integers() {
+        let mut rng = crate::test::rng(808);
+
+        // Zero has probability 1/256 per sample for 8-bit types
+        for _ in 0..1000 {
+            rng.sample::<NonZeroU8, _>(Standard);
+            rng.sample::<NonZeroI8, _>(Standard);
+        }
+
+        rng.sample::<NonZeroUsize, _>(Standard);
+        rng.sample::<NonZeroU16, _>(Standard);
+        rng.sample::<NonZeroU32, _>(Standard);
+        rng.sample::<NonZeroU64, _>(Standard);
+        rng.sample::<NonZeroU128, _>(Standard);
+
+        rng.sample::<NonZeroIsize, _>(Standard);
+        rng.sample::<NonZeroI16, _>(Standard);
+        rng.sample::<NonZeroI32, _>(Standard);
+        rng.sample::<NonZeroI64, _>(Standard);
+        rng.sample::<NonZeroI128, _>(Standard);
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<T: Copy + core::fmt::Debug + PartialEq>(zero: T, expected: &[T])
@@ -223,6 +255,19 @@ mod tests {
         test_samples(0i8, &[9, -9, 111]);
         // Skip further i* types: they are simple reinterpretation of u* samples
 
+        // Non-zero types are identical to their integer variants, except that
+        // zero is rejected
+        test_samples(NonZeroU32::new(1).unwrap(), &[
+            NonZeroU32::new(2220326409).unwrap(),
+            NonZeroU32::new(2575017975).unwrap(),
+            NonZeroU32::new(2018088303).unwrap(),
+        ]);
+        test_samples(NonZeroI8::new(1).unwrap(), &[
+            NonZeroI8::new(9).unwrap(),
+            NonZeroI8::new(-9).unwrap(),
+            NonZeroI8::new(111).unwrap(),
+        ]);
+
         #[cfg(feature = "simd_support")]
         {
             // We only test a sub-set of types here and make assumptions about the rest.
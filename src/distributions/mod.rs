@@ -44,7 +44,14 @@
 //!
 //! [`Alphanumeric`] is a simple distribution to sample random letters and
 //! numbers of the `char` type; in contrast [`Standard`] may sample any valid
-//! `char`.
+//! `char`. [`Unicode`] samples only assigned Unicode scalar values, and
+//! [`CharSet`] samples from a user-defined set of characters. With the
+//! `unicode` feature, [`Alphabetic`], [`Numeric`], [`Whitespace`] and
+//! [`Emoji`] sample from the respective Unicode categories.
+//!
+//! For test data, [`LowerHex`], [`Base64`] and [`Base64Url`] generate tokens,
+//! [`Identifier`] generates valid Rust and C identifiers and [`Slug`]
+//! generates URL slugs, all via [`DistString`].
 //!
 //!
 //! # Uniform numeric ranges
@@ -76,11 +83,23 @@
 //! # Non-uniform sampling
 //!
 //! Sampling a simple true/false outcome with a given probability has a name:
-//! the [`Bernoulli`] distribution (this is used by [`Rng::gen_bool`]).
+//! the [`Bernoulli`] distribution (this is used by [`Rng::gen_bool`]). The
+//! number of failed trials before the first success follows the [`Geometric`]
+//! distribution. For many fair coin flips, [`Coin::flips`] uses a single
+//! random bit per flip.
 //!
 //! For weighted sampling from a sequence of discrete values, use the
 //! [`WeightedIndex`] distribution.
 //!
+//! The [`Zipf`] distribution samples integer ranks following Zipf's law,
+//! which is useful for generating skewed access patterns.
+//!
+//! [`Backoff`] produces jittered, exponentially growing delays for retrying
+//! operations.
+//!
+//! With the `normal` feature, the [`StandardNormal`] distribution samples from
+//! the normal (Gaussian) distribution `N(0, 1)`.
+//!
 //! This crate no longer includes other non-uniform distributions; instead
 //! it is recommended that you use either [`rand_distr`] or [`statrs`].
 //!
@@ -93,15 +112,37 @@
 //! [`rand_distr`]: https://crates.io/crates/rand_distr
 //! [`statrs`]: https://crates.io/crates/statrs
 
+mod backoff;
 mod bernoulli;
+#[cfg(feature = "alloc")]
+mod charset;
+mod coin;
 mod distribution;
+#[cfg(feature = "fixed")]
+mod fixed_point;
 mod float;
+#[cfg(feature = "std")]
+mod geometric;
 mod integer;
+#[cfg(feature = "normal")]
+mod normal;
+mod option;
 mod other;
+mod sign;
 mod slice;
+mod text;
+mod unicode;
+#[cfg(feature = "unicode")]
+mod unicode_categories;
+mod unicode_table;
 mod utils;
+mod weighted_error;
 #[cfg(feature = "alloc")]
 mod weighted_index;
+#[cfg(feature = "normal")]
+mod ziggurat_tables;
+#[cfg(feature = "std")]
+mod zipf;
 
 #[doc(hidden)]
 pub mod hidden_export {
@@ -116,17 +157,41 @@ pub mod uniform;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub mod weighted;
 
-pub use self::bernoulli::{Bernoulli, BernoulliError};
-pub use self::distribution::{Distribution, DistIter, DistMap};
+pub use self::backoff::{Backoff, BackoffDelays, BackoffError, Jitter};
+pub use self::bernoulli::{Bernoulli, BernoulliError, BernoulliRatio};
+#[cfg(feature = "alloc")]
+pub use self::charset::{CharSet, EmptyCharSet};
+pub use self::coin::{Coin, CoinFlips};
+pub use self::distribution::{
+    Distribution, DistIter, DistIterN, DistMap, DistZip, DistMapTwo, DistFilter,
+    DynDistribution,
+};
 #[cfg(feature = "alloc")]
 pub use self::distribution::DistString;
 pub use self::float::{Open01, OpenClosed01};
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use self::geometric::{Geometric, GeometricError};
+#[cfg(feature = "normal")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "normal")))]
+pub use self::normal::StandardNormal;
+pub use self::option::{OptionOf, ResultOf};
 pub use self::other::Alphanumeric;
+pub use self::sign::{Sign, SignedUnit};
 pub use self::slice::Slice;
+pub use self::text::{Base64, Base64Url, Identifier, LowerHex, Slug};
+pub use self::unicode::Unicode;
+#[cfg(feature = "unicode")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "unicode")))]
+pub use self::unicode::{Alphabetic, Emoji, Numeric, Whitespace};
 #[doc(inline)]
 pub use self::uniform::Uniform;
+pub use self::weighted_error::WeightedError;
 #[cfg(feature = "alloc")]
-pub use self::weighted_index::{WeightedError, WeightedIndex};
+pub use self::weighted_index::{WeightedIndex, WeightedIndexIter};
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use self::zipf::{Zipf, ZipfError};
 
 #[allow(unused)]
 use crate::Rng;
@@ -151,8 +216,13 @@ use crate::Rng;
 ///   half-open range `[0, 1)`. See notes below.
 /// * Wrapping integers ([`Wrapping<T>`]), besides the type identical to their
 ///   normal integer variants.
-/// * Non-zero integers ([`NonZeroU8`]), which are like their normal integer
-///   variants but cannot produce zero.
+/// * Fixed-point numbers of the `fixed` crate (e.g. `I32F32`, `U0F64`;
+///   requires the `fixed` feature): uniformly distributed over all values of
+///   the type, like their underlying integers. Thus `U0F64` is uniformly
+///   distributed in `[0, 1)`.
+/// * Non-zero integers ([`NonZeroU8`], [`NonZeroI8`] etc.), which are like
+///   their normal integer variants but cannot produce zero. Samples equal to
+///   zero are rejected, thus the result is uniform over all non-zero values.
 /// * SIMD types like x86's [`__m128i`], `std::simd`'s [`u32x4`]/[`f32x4`]/
 ///   [`mask32x4`] (requires [`simd_support`]), where each lane is distributed
 ///   like their scalar `Standard` variants. See the list of `Standard`
@@ -161,7 +231,8 @@ use crate::Rng;
 /// The `Standard` distribution also supports generation of the following
 /// compound types where all component types are supported:
 ///
-/// *   Tuples (up to 12 elements): each element is generated sequentially.
+/// *   Tuples (up to 16 elements): each element is generated sequentially.
+///     Tuples and arrays may be nested, e.g. `([f32; 3], u8, [u16; 4])`.
 /// *   Arrays: each element is generated sequentially;
 ///     see also [`Rng::fill`] which supports arbitrary array length for integer
 ///     and float types and tends to be faster for `u32` and smaller types.
@@ -170,7 +241,10 @@ use crate::Rng;
 ///     element types smaller than the RNG word size), while the latter supports
 ///     any element type supported by `Standard`.
 /// *   `Option<T>` first generates a `bool`, and if true generates and returns
-///     `Some(value)` where `value: T`, otherwise returning `None`.
+///     `Some(value)` where `value: T`, otherwise returning `None`. See also
+///     [`OptionOf`] for a configurable probability of `Some`.
+/// *   `Result<T, E>` similarly generates a `bool`, then either `Ok(value)`
+///     or `Err(error)`. See also [`ResultOf`].
 ///
 /// ## Custom implementations
 ///
@@ -219,6 +293,7 @@ use crate::Rng;
 /// [`Uniform`]: uniform::Uniform
 /// [`Wrapping<T>`]: std::num::Wrapping
 /// [`NonZeroU8`]: std::num::NonZeroU8
+/// [`NonZeroI8`]: std::num::NonZeroI8
 /// [`__m128i`]: https://doc.rust-lang.org/core/arch/x86/struct.__m128i.html
 /// [`u32x4`]: std::simd::u32x4
 /// [`f32x4`]: std::simd::f32x4
@@ -0,0 +1,153 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The standard normal distribution.
+
+use crate::distributions::float::IntoFloat;
+use crate::distributions::ziggurat_tables::{ZIG_NORM_F, ZIG_NORM_R, ZIG_NORM_X};
+use crate::distributions::{Distribution, Open01};
+use crate::Rng;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// Samples floating-point numbers according to the normal distribution
+/// `N(0, 1)` (a.k.a. a standard normal, or Gaussian).
+///
+/// This is a lightweight alternative to the `StandardNormal` distribution of
+/// [`rand_distr`] for users who only require normal samples; it produces
+/// identical values. For normal distributions with other parameters and many
+/// related distributions, see [`rand_distr`].
+///
+/// Implemented via the ZIGNOR variant[^1] of the Ziggurat method.
+///
+/// [^1]: Jurgen A. Doornik (2005). [*An Improved Ziggurat Method to
+///       Generate Normal Random Samples*](
+///       https://www.doornik.com/research/ziggurat.pdf).
+///       Nuffield College, Oxford
+///
+/// # Example
+/// ```
+/// use rand::prelude::*;
+/// use rand::distributions::StandardNormal;
+///
+/// let val: f64 = thread_rng().sample(StandardNormal);
+/// println!("{}", val);
+/// ```
+///
+/// [`rand_distr`]: https://crates.io/crates/rand_distr
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct StandardNormal;
+
+impl Distribution<f32> for StandardNormal {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let x: f64 = self.sample(rng);
+        x as f32
+    }
+}
+
+impl Distribution<f64> for StandardNormal {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        loop {
+            // As an optimisation we re-implement the conversion to a f64.
+            // From the remaining 12 most significant bits we use 8 to
+            // construct `i`.
+            let bits = rng.next_u64();
+            let i = bits as usize & 0xff;
+
+            // Convert to a value in the range [2,4) and subtract to get [-1,1)
+            let u = (bits >> 12).into_float_with_exponent(1) - 3.0;
+            let x = u * ZIG_NORM_X[i];
+
+            // algebraically equivalent to |u| < x_tab[i+1]/x_tab[i]
+            if x.abs() < ZIG_NORM_X[i + 1] {
+                return x;
+            }
+            if i == 0 {
+                return zero_case(rng, u);
+            }
+            // algebraically equivalent to f1 + DRanU()*(f0 - f1) < 1
+            let f = ZIG_NORM_F[i + 1] + (ZIG_NORM_F[i] - ZIG_NORM_F[i + 1]) * rng.gen::<f64>();
+            if f < (-x * x / 2.0).exp() {
+                return x;
+            }
+        }
+    }
+}
+
+/// Sample from the tail of the distribution
+#[inline]
+fn zero_case<R: Rng + ?Sized>(rng: &mut R, u: f64) -> f64 {
+    // The loop condition is true on the first iteration and the initial
+    // values are overwritten.
+    let mut x = 1.0f64;
+    let mut y = 0.0f64;
+
+    while -2.0 * y < x * x {
+        let x_: f64 = rng.sample(Open01);
+        let y_: f64 = rng.sample(Open01);
+
+        x = x_.ln() / ZIG_NORM_R;
+        y = y_.ln();
+    }
+
+    if u < 0.0 {
+        x - ZIG_NORM_R
+    } else {
+        ZIG_NORM_R - x
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_standard_normal() {
+        const N: usize = 10_000;
+        let mut rng = crate::test::rng(251);
+        let samples: [f64; N] = {
+            let mut buf = [0.0; N];
+            for x in buf.iter_mut() {
+                *x = rng.sample(StandardNormal);
+            }
+            buf
+        };
+        let mean = samples.iter().sum::<f64>() / N as f64;
+        let var = samples.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / N as f64;
+        assert!(mean.abs() < 0.05);
+        assert!((var - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn value_stability() {
+        fn test_samples<T: Copy + core::fmt::Debug + PartialEq, D: Distribution<T>>(
+            distr: &D, zero: T, expected: &[T],
+        ) {
+            let mut rng = crate::test::rng(213);
+            let mut buf = [zero; 4];
+            for x in &mut buf {
+                *x = rng.sample(distr);
+            }
+            assert_eq!(&buf, expected);
+        }
+
+        // Identical to `rand_distr::StandardNormal`
+        test_samples(&StandardNormal, 0f32, &[
+            -0.11844189f32, 0.781378, 0.06563994, -1.1932899,
+        ]);
+        test_samples(&StandardNormal, 0f64, &[
+            -0.11844188827977231f64,
+            0.7813779637772346,
+            0.06563993969580051,
+            -1.1932899004186373,
+        ]);
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Distributions over `Option<T>` and `Result<T, E>`.
+
+use crate::distributions::{Bernoulli, BernoulliError, Distribution};
+use crate::Rng;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A distribution of `Option<T>`, yielding `Some` with probability `p` and
+/// `None` otherwise, where `Some` values are sampled from `D`.
+///
+/// The [`Standard`] distribution also supports `Option<T>`, but always with a
+/// 50% rate of `Some`, and only for types supported by [`Standard`].
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, OptionOf, Uniform};
+///
+/// // A field which is set 90% of the time
+/// let age = OptionOf::with_probability(Uniform::new(0u8, 120).unwrap(), 0.9).unwrap();
+/// let v: Option<u8> = age.sample(&mut rand::thread_rng());
+/// println!("age: {:?}", v);
+/// ```
+///
+/// [`Standard`]: crate::distributions::Standard
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct OptionOf<D> {
+    distr: D,
+    some: Bernoulli,
+}
+
+impl<D> OptionOf<D> {
+    /// Construct a new `OptionOf`, yielding `Some` with probability 0.5.
+    #[inline]
+    pub fn new(distr: D) -> Self {
+        OptionOf {
+            distr,
+            some: Bernoulli::new(0.5).unwrap(),
+        }
+    }
+
+    /// Construct a new `OptionOf`, yielding `Some` with probability `p`.
+    ///
+    /// Returns an error if `p` is outside `[0, 1]`.
+    #[inline]
+    pub fn with_probability(distr: D, p: f64) -> Result<Self, BernoulliError> {
+        Ok(OptionOf {
+            distr,
+            some: Bernoulli::new(p)?,
+        })
+    }
+}
+
+impl<T, D: Distribution<T>> Distribution<Option<T>> for OptionOf<D> {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Option<T> {
+        if self.some.sample(rng) {
+            Some(self.distr.sample(rng))
+        } else {
+            None
+        }
+    }
+}
+
+/// A distribution of `Result<T, E>`, yielding `Ok` with probability `p` and
+/// `Err` otherwise, where `Ok` values are sampled from `D` and `Err` values
+/// from `DE`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, ResultOf, Standard};
+///
+/// // An operation which fails 1% of the time
+/// let status = ResultOf::with_probability(Standard, Standard, 0.99).unwrap();
+/// let v: Result<u32, u8> = status.sample(&mut rand::thread_rng());
+/// println!("status: {:?}", v);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ResultOf<D, DE> {
+    ok_distr: D,
+    err_distr: DE,
+    ok: Bernoulli,
+}
+
+impl<D, DE> ResultOf<D, DE> {
+    /// Construct a new `ResultOf`, yielding `Ok` with probability 0.5.
+    #[inline]
+    pub fn new(ok_distr: D, err_distr: DE) -> Self {
+        ResultOf {
+            ok_distr,
+            err_distr,
+            ok: Bernoulli::new(0.5).unwrap(),
+        }
+    }
+
+    /// Construct a new `ResultOf`, yielding `Ok` with probability `p`.
+    ///
+    /// Returns an error if `p` is outside `[0, 1]`.
+    #[inline]
+    pub fn with_probability(ok_distr: D, err_distr: DE, p: f64) -> Result<Self, BernoulliError> {
+        Ok(ResultOf {
+            ok_distr,
+            err_distr,
+            ok: Bernoulli::new(p)?,
+        })
+    }
+}
+
+impl<T, E, D, DE> Distribution<Result<T, E>> for ResultOf<D, DE>
+where
+    D: Distribution<T>,
+    DE: Distribution<E>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<T, E> {
+        if self.ok.sample(rng) {
+            Ok(self.ok_distr.sample(rng))
+        } else {
+            Err(self.err_distr.sample(rng))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::distributions::{Standard, Uniform};
+
+    #[test]
+    fn test_option_of() {
+        let mut rng = crate::test::rng(241);
+        assert!(OptionOf::with_probability(Standard, 1.5).is_err());
+
+        let never = OptionOf::with_probability(Standard, 0.0).unwrap();
+        let always = OptionOf::with_probability(Uniform::new(10, 20).unwrap(), 1.0).unwrap();
+        for _ in 0..100 {
+            assert_eq!(Distribution::<Option<u8>>::sample(&never, &mut rng), None);
+            let x = always.sample(&mut rng).unwrap();
+            assert!((10..20).contains(&x));
+        }
+
+        let half = OptionOf::new(Standard);
+        let count = (0..1000)
+            .filter(|_| Distribution::<Option<u32>>::sample(&half, &mut rng).is_some())
+            .count();
+        assert!((400..600).contains(&count));
+    }
+
+    #[test]
+    fn test_result_of() {
+        let mut rng = crate::test::rng(242);
+        assert!(ResultOf::with_probability(Standard, Standard, -0.5).is_err());
+
+        let ok = Uniform::new(0, 10).unwrap();
+        let err = Uniform::new(100, 110).unwrap();
+        let distr = ResultOf::with_probability(ok, err, 0.25).unwrap();
+        let mut count = 0;
+        for _ in 0..1000 {
+            match distr.sample(&mut rng) {
+                Ok(x) => {
+                    assert!((0..10).contains(&x));
+                    count += 1;
+                }
+                Err(x) => assert!((100..110).contains(&x)),
+            }
+        }
+        assert!((150..350).contains(&count));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(243);
+        let distr = OptionOf::new(Uniform::new(0u32, 100).unwrap());
+        let mut buf = [None; 6];
+        for x in buf.iter_mut() {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [Some(13), Some(20), Some(22), Some(68), None, Some(10)]);
+
+        let distr = ResultOf::new(Standard, Standard);
+        let mut buf: [Result<bool, u8>; 6] = [Ok(false); 6];
+        for x in buf.iter_mut() {
+            *x = distr.sample(&mut rng);
+        }
+        assert_eq!(buf, [Err(194), Err(94), Ok(true), Ok(false), Ok(false), Ok(false)]);
+    }
+}
@@ -234,6 +234,10 @@ tuple_impl! {A, B, C, D, E, F, G, H, I}
 tuple_impl! {A, B, C, D, E, F, G, H, I, J}
 tuple_impl! {A, B, C, D, E, F, G, H, I, J, K}
 tuple_impl! {A, B, C, D, E, F, G, H, I, J, K, L}
+tuple_impl! {A, B, C, D, E, F, G, H, I, J, K, L, M}
+tuple_impl! {A, B, C, D, E, F, G, H, I, J, K, L, M, N}
+tuple_impl! {A, B, C, D, E, F, G, H, I, J, K, L, M, N, O}
+tuple_impl! {A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P}
 
 impl<T, const N: usize> Distribution<[T; N]> for Standard
 where Standard: Distribution<T>
@@ -264,6 +268,20 @@ where Standard: Distribution<T>
     }
 }
 
+impl<T, E> Distribution<Result<T, E>> for Standard
+where
+    Standard: Distribution<T> + Distribution<E>,
+{
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<T, E> {
+        if rng.gen::<bool>() {
+            Ok(rng.gen())
+        } else {
+            Err(rng.gen())
+        }
+    }
+}
+
 impl<T> Distribution<Wrapping<T>> for Standard
 where Standard: Distribution<T>
 {
@@ -280,6 +298,18 @@ mod tests {
     use crate::RngCore;
     #[cfg(feature = "alloc")] use alloc::string::String;
 
+    #[test]
+    fn test_large_tuples() {
+        type Tuple16 = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, [u16; 2]);
+        let mut rng = crate::test::rng(821);
+        let _: Tuple16 = rng.gen();
+
+        let mut rng1 = crate::test::rng(822);
+        let mut rng2 = crate::test::rng(822);
+        let x: ([f32; 3], u8, [u16; 4]) = rng1.gen();
+        assert_eq!(x, (rng2.gen(), rng2.gen(), rng2.gen()));
+    }
+
     #[test]
     fn test_misc() {
         let rng: &mut dyn RngCore = &mut crate::test::rng(820);
@@ -348,6 +378,13 @@ mod tests {
             None,
             Some(false),
         ]);
+        test_samples(&Standard, Ok(false) as Result<bool, u8>, &[
+            Ok(true),
+            Err(68),
+            Err(13),
+            Ok(false),
+            Err(172),
+        ]);
         test_samples(&Standard, Wrapping(0i32), &[
             Wrapping(-2074640887),
             Wrapping(-1719949321),
@@ -373,6 +410,14 @@ mod tests {
             (false, false),
         ]);
 
+        test_samples(&Standard, ([0u8; 2], false, [(0u8, false); 1]), &[
+            ([9, 247], false, [(68, false)]),
+            ([13, 174], false, [(194, false)]),
+            ([69, 213], true, [(207, true)]),
+            ([252, 4], false, [(145, false)]),
+            ([246, 149], false, [(108, true)]),
+        ]);
+
         test_samples(&Standard, [0u8; 0], &[[], [], [], [], []]);
         test_samples(&Standard, [0u8; 3], &[
             [9, 247, 111],
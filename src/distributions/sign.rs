@@ -0,0 +1,178 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random signs and signed unit values.
+
+use crate::distributions::Distribution;
+use crate::Rng;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A random sign: `1` or `-1` with probability 0.5 each.
+///
+/// This is implemented for all signed integer types and for `f32` and
+/// `f64`. A single random bit, the most significant bit of a `u32`, is used
+/// without branching: for integers it is extended to `-1` or kept as `1`,
+/// while for floats it is used as the sign bit of `1.0`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::Sign;
+/// use rand::Rng;
+///
+/// let mut rng = rand::thread_rng();
+/// let step: i32 = rng.sample(Sign);
+/// let scale: f64 = rng.sample(Sign);
+/// assert!(step == 1 || step == -1);
+/// assert!(scale.abs() == 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Sign;
+
+macro_rules! sign_int_impl {
+    ($($ty:ty),*) => {$(
+        impl Distribution<$ty> for Sign {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> $ty {
+                // Either all bits set (-1) or only the lowest bit (1)
+                (((rng.next_u32() as i32) >> 31) | 1) as $ty
+            }
+        }
+    )*}
+}
+
+sign_int_impl! { i8, i16, i32, i64, i128, isize }
+
+impl Distribution<f32> for Sign {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        f32::from_bits(1.0f32.to_bits() | (rng.next_u32() & (1 << 31)))
+    }
+}
+
+impl Distribution<f64> for Sign {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let sign = u64::from(rng.next_u32() & (1 << 31)) << 32;
+        f64::from_bits(1.0f64.to_bits() | sign)
+    }
+}
+
+/// A floating point value in the closed interval `[-1, 1]`, symmetric about
+/// zero.
+///
+/// Both the magnitude and the sign are taken from one random word: for `f64`
+/// the 53 most significant bits of a `u64` give a magnitude in `(0, 1]`, in
+/// steps of `2^-53`, and the next bit gives the sign; `f32` likewise uses 24
+/// bits of a `u32` for the magnitude. Thus `-1` and `1` are both possible
+/// while zero is not, and the distribution is exactly symmetric.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::SignedUnit;
+/// use rand::Rng;
+///
+/// let x: f64 = rand::thread_rng().sample(SignedUnit);
+/// assert!((-1.0..=1.0).contains(&x));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SignedUnit;
+
+impl Distribution<f32> for SignedUnit {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f32 {
+        let x = rng.next_u32();
+        // 1 - k * 2^-24 is exact for k < 2^24
+        let magnitude = 1.0 - (x >> 8) as f32 * (1.0 / (1u32 << 24) as f32);
+        f32::from_bits(magnitude.to_bits() | (x << 24 & (1 << 31)))
+    }
+}
+
+impl Distribution<f64> for SignedUnit {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        let x = rng.next_u64();
+        // 1 - k * 2^-53 is exact for k < 2^53
+        let magnitude = 1.0 - (x >> 11) as f64 * (1.0 / (1u64 << 53) as f64);
+        f64::from_bits(magnitude.to_bits() | (x << 53 & (1 << 63)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+
+    #[test]
+    fn test_sign() {
+        let mut rng = StepRng::new(0, 1 << 31);
+        let v: [i8; 4] = [rng.sample(Sign), rng.sample(Sign), rng.sample(Sign), rng.sample(Sign)];
+        assert_eq!(v, [1, -1, 1, -1]);
+        let x: f32 = rng.sample(Sign);
+        assert_eq!(x, 1.0);
+        let x: f64 = rng.sample(Sign);
+        assert_eq!(x, -1.0);
+        let x: i128 = rng.sample(Sign);
+        assert_eq!(x, 1);
+        let x: isize = rng.sample(Sign);
+        assert_eq!(x, -1);
+
+        let mut rng = crate::test::rng(155);
+        let sum: i32 = (0..10_000).map(|_| rng.sample::<i32, _>(Sign)).sum();
+        assert!(sum.abs() < 400);
+    }
+
+    #[test]
+    fn test_signed_unit() {
+        // The extreme values of the magnitude, with each sign
+        let mut rng = StepRng::new(0, 1 << 10);
+        let x: f64 = rng.sample(SignedUnit);
+        assert_eq!(x, 1.0);
+        let x: f64 = rng.sample(SignedUnit);
+        assert_eq!(x, -1.0);
+        let mut rng = StepRng::new(!0, 0);
+        let x: f64 = rng.sample(SignedUnit);
+        assert_eq!(x, -1.0 / (1u64 << 53) as f64);
+        let x: f32 = rng.sample(SignedUnit);
+        assert_eq!(x, -1.0 / (1u32 << 24) as f32);
+        let mut rng = StepRng::new(1 << 7, 0);
+        let x: f32 = rng.sample(SignedUnit);
+        assert_eq!(x, -1.0);
+
+        let mut rng = crate::test::rng(156);
+        let mut sum = 0.0;
+        for _ in 0..10_000 {
+            let x: f64 = rng.sample(SignedUnit);
+            let y: f32 = rng.sample(SignedUnit);
+            assert!((-1.0..=1.0).contains(&x) && x != 0.0);
+            assert!((-1.0..=1.0).contains(&y) && y != 0.0);
+            sum += x;
+        }
+        assert!(sum.abs() < 250.0);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(157);
+        let mut signs = [0i32; 8];
+        for x in signs.iter_mut() {
+            *x = rng.sample(Sign);
+        }
+        assert_eq!(signs, [-1, 1, 1, 1, -1, -1, -1, -1]);
+        let mut v = [0f64; 3];
+        for x in v.iter_mut() {
+            *x = rng.sample(SignedUnit);
+        }
+        assert_eq!(v, [0.6613740940379459, -0.5880639182354515, -0.40517667490792386]);
+    }
+}
@@ -0,0 +1,303 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Distributions of ASCII text for common test-data shapes.
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::distributions::Distribution;
+#[cfg(feature = "alloc")]
+use crate::distributions::DistString;
+use crate::Rng;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+const LOWER_HEX_CHARSET: &[u8] = b"0123456789abcdef";
+const BASE64_CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_CHARSET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+#[cfg(feature = "alloc")]
+const LETTERS_CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+#[cfg(feature = "alloc")]
+const IDENTIFIER_CHARSET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789_";
+#[cfg(feature = "alloc")]
+const SLUG_CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789-";
+
+/// Sample a byte of `charset`, which has at most 64 elements, using the
+/// 6 most significant bits of a `u32` with rejection.
+#[cfg(feature = "alloc")]
+#[inline]
+fn sample_charset<R: Rng + ?Sized>(rng: &mut R, charset: &[u8]) -> u8 {
+    debug_assert!(charset.len() <= 64);
+    loop {
+        let var = (rng.next_u32() >> (32 - 6)) as usize;
+        if var < charset.len() {
+            return charset[var];
+        }
+    }
+}
+
+/// Sample a `u8`, uniformly distributed over lowercase hexadecimal digits:
+/// 0-9 and a-f.
+///
+/// Each digit uses 4 random bits. With [`DistString`], this generates tokens
+/// such as `"3f9a0c17"`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{DistString, LowerHex};
+///
+/// let token = LowerHex.sample_string(&mut rand::thread_rng(), 32);
+/// assert!(token.bytes().all(|b| b.is_ascii_hexdigit()));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LowerHex;
+
+/// Sample a `u8`, uniformly distributed over the 64 characters of the
+/// standard base64 alphabet: A-Z, a-z, 0-9, `+` and `/`.
+///
+/// Each character uses 6 random bits. No padding is generated. See also
+/// [`Base64Url`] for the URL and filename safe alphabet.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Base64, DistString};
+///
+/// let token = Base64.sample_string(&mut rand::thread_rng(), 22);
+/// println!("{}", token);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Base64;
+
+/// Sample a `u8`, uniformly distributed over the 64 characters of the URL and
+/// filename safe base64 alphabet: A-Z, a-z, 0-9, `-` and `_`.
+///
+/// Each character uses 6 random bits. No padding is generated.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Base64Url, DistString};
+///
+/// let token = Base64Url.sample_string(&mut rand::thread_rng(), 22);
+/// println!("https://example.com/invite/{}", token);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Base64Url;
+
+/// Generate valid Rust and C identifiers via [`DistString`].
+///
+/// The first character is an ASCII letter (A-Z, a-z), and the remaining
+/// characters are ASCII letters, digits (0-9) or `_`, all uniformly
+/// distributed. Rust and C keywords (e.g. `fn` or `int`) are never generated;
+/// if a keyword is sampled, the identifier is sampled again.
+///
+/// For `len == 0`, nothing is appended; note that the empty string is not a
+/// valid identifier.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{DistString, Identifier};
+///
+/// let name = Identifier.sample_string(&mut rand::thread_rng(), 8);
+/// println!("fn {}() {{}}", name);
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Identifier;
+
+/// Generate simple URL slugs via [`DistString`].
+///
+/// Characters are lowercase ASCII letters (a-z), digits (0-9) or `-`, except
+/// that the first and last characters are never `-`.
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{DistString, Slug};
+///
+/// let slug = Slug.sample_string(&mut rand::thread_rng(), 12);
+/// assert!(!slug.starts_with('-') && !slug.ends_with('-'));
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Slug;
+
+impl Distribution<u8> for LowerHex {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        LOWER_HEX_CHARSET[(rng.next_u32() >> (32 - 4)) as usize]
+    }
+}
+
+impl Distribution<u8> for Base64 {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        BASE64_CHARSET[(rng.next_u32() >> (32 - 6)) as usize]
+    }
+}
+
+impl Distribution<u8> for Base64Url {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u8 {
+        BASE64_URL_CHARSET[(rng.next_u32() >> (32 - 6)) as usize]
+    }
+}
+
+macro_rules! impl_dist_string_ascii {
+    ($($ty:ty),*) => {$(
+        #[cfg(feature = "alloc")]
+        impl DistString for $ty {
+            fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+                // Safety: all sampled bytes are ASCII
+                unsafe {
+                    let v = string.as_mut_vec();
+                    v.extend(self.sample_iter(rng).take(len));
+                }
+            }
+        }
+    )*};
+}
+
+impl_dist_string_ascii!(LowerHex, Base64, Base64Url);
+
+/// Strict and reserved keywords of Rust (2021 edition) and keywords of C
+/// (C99), excluding those starting with `_`.
+#[cfg(feature = "alloc")]
+const KEYWORDS: &[&str] = &[
+    // Rust
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if",
+    "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override",
+    "priv", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait",
+    "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where",
+    "while", "yield",
+    // C, not already listed
+    "auto", "case", "char", "default", "double", "float", "goto", "inline", "int", "long",
+    "register", "restrict", "short", "signed", "sizeof", "switch", "typedef", "union",
+    "unsigned", "void", "volatile",
+];
+
+#[cfg(feature = "alloc")]
+impl DistString for Identifier {
+    fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+        if len == 0 {
+            return;
+        }
+        let start = string.len();
+        string.reserve(len);
+        loop {
+            // Safety: all sampled bytes are ASCII
+            unsafe {
+                let v = string.as_mut_vec();
+                v.push(sample_charset(rng, LETTERS_CHARSET));
+                v.extend((1..len).map(|_| sample_charset(rng, IDENTIFIER_CHARSET)));
+            }
+            if !KEYWORDS.contains(&&string[start..]) {
+                return;
+            }
+            string.truncate(start);
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DistString for Slug {
+    fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+        // Exclude the trailing `-` for the first and last characters
+        let alphanumeric = &SLUG_CHARSET[..SLUG_CHARSET.len() - 1];
+        string.reserve(len);
+        // Safety: all sampled bytes are ASCII
+        unsafe {
+            let v = string.as_mut_vec();
+            v.extend((0..len).map(|i| {
+                if i == 0 || i + 1 == len {
+                    sample_charset(rng, alphanumeric)
+                } else {
+                    sample_charset(rng, SLUG_CHARSET)
+                }
+            }));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let mut rng = crate::test::rng(281);
+        let hex = LowerHex.sample_string(&mut rng, 1000);
+        assert!(hex.bytes().all(|b| b"0123456789abcdef".contains(&b)));
+        for b in b"0123456789abcdef" {
+            assert!(hex.as_bytes().contains(b));
+        }
+
+        let b64 = Base64.sample_string(&mut rng, 1000);
+        assert_eq!(b64.len(), 1000);
+        assert!(b64.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/'));
+        assert!(b64.contains('+') && b64.contains('/'));
+
+        let b64 = Base64Url.sample_string(&mut rng, 1000);
+        assert!(b64.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_'));
+        assert!(b64.contains('-') && b64.contains('_'));
+    }
+
+    #[test]
+    fn test_identifier() {
+        let mut rng = crate::test::rng(282);
+        assert_eq!(Identifier.sample_string(&mut rng, 0), "");
+        for len in 1..10 {
+            for _ in 0..100 {
+                let id = Identifier.sample_string(&mut rng, len);
+                assert_eq!(id.len(), len);
+                assert!(id.as_bytes()[0].is_ascii_alphabetic());
+                assert!(id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'_'));
+                assert!(!KEYWORDS.contains(&id.as_str()));
+            }
+        }
+
+        // Appending does not check previous contents for keywords
+        let mut s = String::from("f");
+        Identifier.append_string(&mut rng, &mut s, 1);
+        assert_eq!(s.len(), 2);
+    }
+
+    #[test]
+    fn test_slug() {
+        let mut rng = crate::test::rng(283);
+        assert_eq!(Slug.sample_string(&mut rng, 0), "");
+        for len in 1..20 {
+            let slug = Slug.sample_string(&mut rng, len);
+            assert_eq!(slug.len(), len);
+            assert!(!slug.starts_with('-') && !slug.ends_with('-'));
+            assert!(slug.bytes().all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'));
+        }
+        assert!(Slug.sample_string(&mut rng, 1000).contains('-'));
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(284);
+        assert_eq!(LowerHex.sample_string(&mut rng, 12), "66029b99f10d");
+        assert_eq!(Base64.sample_string(&mut rng, 12), "grrR1Et8fMa5");
+        assert_eq!(Base64Url.sample_string(&mut rng, 12), "IVLKf4ab2QOj");
+        assert_eq!(Identifier.sample_string(&mut rng, 12), "dMdGzLvsM34R");
+        assert_eq!(Slug.sample_string(&mut rng, 12), "4tctgyyszy-2");
+    }
+}
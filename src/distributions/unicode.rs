@@ -0,0 +1,344 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Distributions over Unicode scalar values.
+
+#[cfg(feature = "unicode")]
+use crate::distributions::unicode_categories;
+use crate::distributions::unicode_table::{ASSIGNED, TOTAL, UNICODE_VERSION};
+use crate::distributions::Distribution;
+#[cfg(feature = "alloc")]
+use crate::distributions::DistString;
+use crate::Rng;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// Sample a `char`, uniformly distributed over all assigned Unicode scalar
+/// values.
+///
+/// In contrast to the [`Standard`] distribution for `char`, which samples
+/// from all scalar values (including unassigned code points), this
+/// distribution only yields scalar values which are assigned in Unicode
+/// version [`Unicode::UNICODE_VERSION`]: all code points with a General
+/// Category other than `Cn`. This includes control, format and private-use
+/// characters, but excludes surrogates, noncharacters and reserved code points.
+///
+/// Since every `char` is a valid scalar value, strings generated by this
+/// distribution are always well-formed UTF-8. This is typically used for
+/// fuzzing and property testing of text processing.
+///
+/// Strings of a given number of chars may be generated via [`DistString`];
+/// strings of an exact length in bytes via [`Unicode::sample_string_bytes`].
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{DistString, Unicode};
+///
+/// let mut rng = rand::thread_rng();
+/// let s = Unicode.sample_string(&mut rng, 10);
+/// assert_eq!(s.chars().count(), 10);
+///
+/// let s = Unicode.sample_string_bytes(&mut rng, 10);
+/// assert_eq!(s.len(), 10);
+/// ```
+///
+/// # Implementation details
+///
+/// Samples are generated by choosing an index uniformly among the assigned
+/// scalar values and locating it via binary search in a table of about 700
+/// ranges.
+///
+/// Note that the set of assigned scalar values grows with new Unicode
+/// versions; updating the table is a value-breaking change.
+///
+/// [`Standard`]: crate::distributions::Standard
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Unicode;
+
+impl Unicode {
+    /// The `(major, minor, update)` version of Unicode from which the set of
+    /// assigned scalar values, as well as the category distributions such as
+    /// `Alphabetic`, are taken.
+    pub const UNICODE_VERSION: (u8, u8, u8) = UNICODE_VERSION;
+
+    /// Append random chars to `string`, such that exactly `byte_len` bytes
+    /// are appended.
+    ///
+    /// Chars are sampled uniformly from all assigned scalar values, except
+    /// that up to the last three chars are restricted to those short enough
+    /// to fit within the remaining bytes.
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn append_string_bytes<R: Rng + ?Sized>(
+        &self, rng: &mut R, string: &mut String, byte_len: usize,
+    ) {
+        string.reserve(byte_len);
+        let mut remaining = byte_len;
+        while remaining > 0 {
+            let limit = match remaining {
+                1 => count_below(0x80),
+                2 => count_below(0x800),
+                3 => count_below(0x1_0000),
+                _ => TOTAL,
+            };
+            let c = nth_assigned(rng.gen_range(0..limit));
+            remaining -= c.len_utf8();
+            string.push(c);
+        }
+    }
+
+    /// Generate a `String` of random chars, exactly `byte_len` bytes long.
+    ///
+    /// See [`Unicode::append_string_bytes`].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn sample_string_bytes<R: Rng + ?Sized>(&self, rng: &mut R, byte_len: usize) -> String {
+        let mut s = String::new();
+        self.append_string_bytes(rng, &mut s, byte_len);
+        s
+    }
+}
+
+/// The `n`-th scalar value of `table`, for `n` less than its size
+#[inline]
+fn nth_in(table: &[(u32, u32)], n: u32) -> char {
+    debug_assert!(n < table[table.len() - 1].1);
+    let i = table.partition_point(|&(_, count)| count <= n) - 1;
+    let (first, count) = table[i];
+    // Safety: the tables only contain ranges of valid scalar values.
+    unsafe { char::from_u32_unchecked(first + (n - count)) }
+}
+
+/// The `n`-th assigned scalar value, for `n < TOTAL`
+#[inline]
+fn nth_assigned(n: u32) -> char {
+    nth_in(ASSIGNED, n)
+}
+
+/// The number of assigned scalar values below `code_point`
+#[cfg(feature = "alloc")]
+fn count_below(code_point: u32) -> u32 {
+    let i = ASSIGNED.partition_point(|&(first, _)| first < code_point);
+    if i == 0 {
+        return 0;
+    }
+    let (first, count) = ASSIGNED[i - 1];
+    let len = ASSIGNED[i].1 - count;
+    count + (code_point - first).min(len)
+}
+
+impl Distribution<char> for Unicode {
+    #[inline]
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+        nth_assigned(rng.gen_range(0..TOTAL))
+    }
+}
+
+/// Note: the `String` is potentially left with excess capacity; optionally the
+/// user may call `string.shrink_to_fit()` afterwards.
+#[cfg(feature = "alloc")]
+impl DistString for Unicode {
+    fn append_string<R: Rng + ?Sized>(&self, rng: &mut R, string: &mut String, len: usize) {
+        string.reserve(4 * len);
+        string.extend(Distribution::<char>::sample_iter(self, rng).take(len));
+    }
+}
+
+#[cfg(feature = "unicode")]
+macro_rules! category_distr {
+    ($(#[$meta:meta])* $name:ident, $table:ident) => {
+        $(#[$meta])*
+        ///
+        /// Samples are uniformly distributed over the matching scalar values
+        /// of Unicode version [`Unicode::UNICODE_VERSION`]. Strings
+        /// may be generated via [`DistString`].
+        #[derive(Debug, Clone, Copy)]
+        #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "unicode")))]
+        pub struct $name;
+
+        impl Distribution<char> for $name {
+            #[inline]
+            fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> char {
+                let table = unicode_categories::$table;
+                nth_in(table, rng.gen_range(0..table[table.len() - 1].1))
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl DistString for $name {
+            fn append_string<R: Rng + ?Sized>(
+                &self, rng: &mut R, string: &mut String, len: usize,
+            ) {
+                string.reserve(4 * len);
+                string.extend(Distribution::<char>::sample_iter(self, rng).take(len));
+            }
+        }
+    };
+}
+
+#[cfg(feature = "unicode")]
+category_distr!(
+    /// Sample a `char` with the `Alphabetic` property per Unicode 16.0.0.
+    ///
+    /// This matches [`char::is_alphabetic`] only if Rust uses the same
+    /// Unicode version.
+    Alphabetic, ALPHABETIC
+);
+
+#[cfg(feature = "unicode")]
+category_distr!(
+    /// Sample a numeric `char` (General Category `Nd`, `Nl` or `No`) per
+    /// Unicode 16.0.0.
+    ///
+    /// This matches [`char::is_numeric`] only if Rust uses the same Unicode
+    /// version.
+    ///
+    /// Note that this includes many digits other than `0`-`9`, as well as
+    /// other numeric characters such as fractions and Roman numerals.
+    Numeric, NUMERIC
+);
+
+#[cfg(feature = "unicode")]
+category_distr!(
+    /// Sample a `char` with the `White_Space` property per Unicode 16.0.0.
+    ///
+    /// This matches [`char::is_whitespace`] only if Rust uses the same
+    /// Unicode version.
+    Whitespace, WHITESPACE
+);
+
+#[cfg(feature = "unicode")]
+category_distr!(
+    /// Sample a `char` with the `Emoji` property per Unicode 16.0.0.
+    ///
+    /// Note that this property includes some characters which are not
+    /// usually displayed as emoji without a variation selector, such as the
+    /// digits `0`-`9`, `#` and `*`. Emoji sequences (e.g. flags or skin tone
+    /// modifiers) are not generated.
+    Emoji, EMOJI
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        assert_eq!(ASSIGNED[0], (0, 0));
+        assert_eq!(*ASSIGNED.last().unwrap(), (0x11_0000, TOTAL));
+        for w in ASSIGNED.windows(2) {
+            let ((first, count), (next, next_count)) = (w[0], w[1]);
+            // Ranges are non-empty, non-adjacent and do not contain surrogates.
+            assert!(next_count > count);
+            assert!(first + (next_count - count) < next);
+            assert!(first > 0xDFFF || first + (next_count - count) <= 0xD800);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_count_below() {
+        assert_eq!(count_below(0), 0);
+        assert_eq!(count_below(0x80), 0x80);
+        assert_eq!(count_below(0x378), 0x378);
+        assert_eq!(count_below(0x37A), 0x378);
+        assert_eq!(count_below(0x11_0000), TOTAL);
+        assert_eq!(nth_assigned(0), '\0');
+        assert_eq!(nth_assigned(0x378), '\u{37A}');
+        assert_eq!(nth_assigned(TOTAL - 1), '\u{10FFFD}');
+    }
+
+    #[test]
+    fn test_unicode() {
+        let mut rng = crate::test::rng(231);
+        for _ in 0..1000 {
+            let c: char = rng.sample(Unicode);
+            assert!(!('\u{378}'..='\u{379}').contains(&c));
+            assert!(!('\u{FDD0}'..='\u{FDEF}').contains(&c));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_unicode_string() {
+        let mut rng = crate::test::rng(232);
+        let s = Unicode.sample_string(&mut rng, 20);
+        assert_eq!(s.chars().count(), 20);
+
+        for len in 0..20 {
+            let s = Unicode.sample_string_bytes(&mut rng, len);
+            assert_eq!(s.len(), len);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn test_categories() {
+        use super::unicode_categories::*;
+
+        for table in [ALPHABETIC, NUMERIC, WHITESPACE, EMOJI] {
+            assert_eq!(table[0].1, 0);
+            assert_eq!(table[table.len() - 1].0, 0x11_0000);
+            for w in table.windows(2) {
+                assert!(w[1].1 > w[0].1);
+                assert!(w[0].0 + (w[1].1 - w[0].1) < w[1].0);
+            }
+        }
+
+        fn contains(table: &[(u32, u32)], c: char) -> bool {
+            let i = table.partition_point(|&(first, _)| first <= c as u32);
+            i > 0 && c as u32 - table[i - 1].0 < table[i].1 - table[i - 1].1
+        }
+
+        // The predicates of `char` follow the Unicode version of Rust
+        let check_std = core::char::UNICODE_VERSION >= UNICODE_VERSION;
+        let mut rng = crate::test::rng(234);
+        for _ in 0..1000 {
+            let c = rng.sample(Alphabetic);
+            assert!(contains(ALPHABETIC, c) && (!check_std || c.is_alphabetic()));
+            let c = rng.sample(Numeric);
+            assert!(contains(NUMERIC, c) && (!check_std || c.is_numeric()));
+            let c = rng.sample(Whitespace);
+            assert!(contains(WHITESPACE, c) && (!check_std || c.is_whitespace()));
+            let c = rng.sample(Emoji);
+            assert!(contains(EMOJI, c));
+            assert!(c == '#' || c == '*' || c.is_ascii_digit() || !c.is_ascii());
+        }
+        assert!(contains(ALPHABETIC, 'a') && !contains(ALPHABETIC, '1'));
+        assert!(!contains(NUMERIC, '\0') && !contains(NUMERIC, '\u{10FFFF}'));
+        assert_eq!(WHITESPACE[WHITESPACE.len() - 1].1, 25);
+    }
+
+    #[test]
+    #[cfg(feature = "unicode")]
+    fn category_value_stability() {
+        let mut rng = crate::test::rng(235);
+        let mut buf = ['\0'; 4];
+        buf[0] = rng.sample(Alphabetic);
+        buf[1] = rng.sample(Numeric);
+        buf[2] = rng.sample(Whitespace);
+        buf[3] = rng.sample(Emoji);
+        assert_eq!(buf, ['\u{8BC5}', '\u{110F8}', '\u{2008}', '\u{1F95D}']);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(233);
+        let mut buf = ['\0'; 5];
+        for c in buf.iter_mut() {
+            *c = rng.sample(Unicode);
+        }
+        assert_eq!(buf, ['\u{53BE}', '\u{107F24}', '\u{109C6F}', '\u{2E0DC}', '\u{2DB0A}']);
+    }
+}
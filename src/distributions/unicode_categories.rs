@@ -0,0 +1,230 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Tables of Unicode scalar values by category.
+//!
+//! Generated from the Unicode Character Database, of the version given by
+//! [`UNICODE_VERSION`]. The layout of each table is as described for
+//! [`ASSIGNED`].
+//!
+//! [`ASSIGNED`]: super::unicode_table::ASSIGNED
+//! [`UNICODE_VERSION`]: super::unicode_table::UNICODE_VERSION
+
+/// Scalar values with the `Alphabetic` property
+#[rustfmt::skip]
+pub(crate) const ALPHABETIC: &[(u32, u32)] = &[
+    (0x41, 0), (0x61, 26), (0xAA, 52), (0xB5, 53), (0xBA, 54), (0xC0, 55), (0xD8, 78), (0xF8, 109),
+    (0x2C6, 567), (0x2E0, 579), (0x2EC, 584), (0x2EE, 585), (0x345, 586), (0x363, 587),
+    (0x376, 605), (0x37A, 607), (0x37F, 611), (0x386, 612), (0x388, 613), (0x38C, 616),
+    (0x38E, 617), (0x3A3, 637), (0x3F7, 720), (0x48A, 859), (0x531, 1025), (0x559, 1063),
+    (0x560, 1064), (0x5B0, 1105), (0x5BF, 1119), (0x5C1, 1120), (0x5C4, 1122), (0x5C7, 1124),
+    (0x5D0, 1125), (0x5EF, 1152), (0x610, 1156), (0x620, 1167), (0x659, 1223), (0x66E, 1230),
+    (0x6D5, 1332), (0x6E1, 1340), (0x6ED, 1348), (0x6FA, 1351), (0x6FF, 1354), (0x710, 1355),
+    (0x74D, 1403), (0x7CA, 1504), (0x7F4, 1537), (0x7FA, 1539), (0x800, 1540), (0x81A, 1564),
+    (0x840, 1583), (0x860, 1608), (0x870, 1619), (0x889, 1643), (0x897, 1649), (0x8A0, 1650),
+    (0x8D4, 1692), (0x8E3, 1704), (0x8F0, 1711), (0x93D, 1787), (0x94E, 1803), (0x955, 1806),
+    (0x971, 1821), (0x985, 1840), (0x98F, 1848), (0x993, 1850), (0x9AA, 1872), (0x9B2, 1879),
+    (0x9B6, 1880), (0x9BD, 1884), (0x9C7, 1892), (0x9CB, 1894), (0x9CE, 1896), (0x9D7, 1897),
+    (0x9DC, 1898), (0x9DF, 1900), (0x9F0, 1905), (0x9FC, 1907), (0xA01, 1908), (0xA05, 1911),
+    (0xA0F, 1917), (0xA13, 1919), (0xA2A, 1941), (0xA32, 1948), (0xA35, 1950), (0xA38, 1952),
+    (0xA3E, 1954), (0xA47, 1959), (0xA4B, 1961), (0xA51, 1963), (0xA59, 1964), (0xA5E, 1968),
+    (0xA70, 1969), (0xA81, 1975), (0xA85, 1978), (0xA8F, 1987), (0xA93, 1990), (0xAAA, 2012),
+    (0xAB2, 2019), (0xAB5, 2021), (0xABD, 2026), (0xAC7, 2035), (0xACB, 2038), (0xAD0, 2040),
+    (0xAE0, 2041), (0xAF9, 2045), (0xB01, 2049), (0xB05, 2052), (0xB0F, 2060), (0xB13, 2062),
+    (0xB2A, 2084), (0xB32, 2091), (0xB35, 2093), (0xB3D, 2098), (0xB47, 2106), (0xB4B, 2108),
+    (0xB56, 2110), (0xB5C, 2112), (0xB5F, 2114), (0xB71, 2119), (0xB82, 2120), (0xB85, 2122),
+    (0xB8E, 2128), (0xB92, 2131), (0xB99, 2135), (0xB9C, 2137), (0xB9E, 2138), (0xBA3, 2140),
+    (0xBA8, 2142), (0xBAE, 2145), (0xBBE, 2157), (0xBC6, 2162), (0xBCA, 2165), (0xBD0, 2168),
+    (0xBD7, 2169), (0xC00, 2170), (0xC0E, 2183), (0xC12, 2186), (0xC2A, 2209), (0xC3D, 2225),
+    (0xC46, 2233), (0xC4A, 2236), (0xC55, 2239), (0xC58, 2241), (0xC5D, 2244), (0xC60, 2245),
+    (0xC80, 2249), (0xC85, 2253), (0xC8E, 2261), (0xC92, 2264), (0xCAA, 2287), (0xCB5, 2297),
+    (0xCBD, 2302), (0xCC6, 2310), (0xCCA, 2313), (0xCD5, 2316), (0xCDD, 2318), (0xCE0, 2320),
+    (0xCF1, 2324), (0xD00, 2327), (0xD0E, 2340), (0xD12, 2343), (0xD3D, 2384), (0xD46, 2392),
+    (0xD4A, 2395), (0xD4E, 2398), (0xD54, 2399), (0xD5F, 2403), (0xD7A, 2408), (0xD81, 2414),
+    (0xD85, 2417), (0xD9A, 2435), (0xDB3, 2459), (0xDBD, 2468), (0xDC0, 2469), (0xDCF, 2476),
+    (0xDD6, 2482), (0xDD8, 2483), (0xDF2, 2491), (0xE01, 2493), (0xE40, 2551), (0xE4D, 2558),
+    (0xE81, 2559), (0xE84, 2561), (0xE86, 2562), (0xE8C, 2567), (0xEA5, 2591), (0xEA7, 2592),
+    (0xEBB, 2611), (0xEC0, 2614), (0xEC6, 2619), (0xECD, 2620), (0xEDC, 2621), (0xF00, 2625),
+    (0xF40, 2626), (0xF49, 2634), (0xF71, 2670), (0xF88, 2689), (0xF99, 2705), (0x1000, 2741),
+    (0x1038, 2796), (0x103B, 2797), (0x1050, 2802), (0x109A, 2866), (0x10A0, 2870), (0x10C7, 2908),
+    (0x10CD, 2909), (0x10D0, 2910), (0x10FC, 2953), (0x124A, 3286), (0x1250, 3290), (0x1258, 3297),
+    (0x125A, 3298), (0x1260, 3302), (0x128A, 3343), (0x1290, 3347), (0x12B2, 3380), (0x12B8, 3384),
+    (0x12C0, 3391), (0x12C2, 3392), (0x12C8, 3396), (0x12D8, 3411), (0x1312, 3468), (0x1318, 3472),
+    (0x1380, 3539), (0x13A0, 3555), (0x13F8, 3641), (0x1401, 3647), (0x166F, 4267), (0x1681, 4284),
+    (0x16A0, 4310), (0x16EE, 4385), (0x1700, 4396), (0x171F, 4416), (0x1740, 4437), (0x1760, 4457),
+    (0x176E, 4470), (0x1772, 4473), (0x1780, 4475), (0x17B6, 4527), (0x17D7, 4546), (0x17DC, 4547),
+    (0x1820, 4548), (0x1880, 4637), (0x18B0, 4680), (0x1900, 4750), (0x1920, 4781), (0x1930, 4793),
+    (0x1950, 4802), (0x1970, 4832), (0x1980, 4837), (0x19B0, 4881), (0x1A00, 4907), (0x1A20, 4935),
+    (0x1A61, 4998), (0x1AA7, 5018), (0x1ABF, 5019), (0x1ACC, 5021), (0x1B00, 5024), (0x1B35, 5076),
+    (0x1B45, 5091), (0x1B80, 5099), (0x1BAC, 5141), (0x1BBA, 5145), (0x1BE7, 5189), (0x1C00, 5200),
+    (0x1C4D, 5255), (0x1C5A, 5258), (0x1C80, 5294), (0x1C90, 5305), (0x1CBD, 5348), (0x1CE9, 5351),
+    (0x1CEE, 5355), (0x1CF5, 5361), (0x1CFA, 5363), (0x1D00, 5364), (0x1DD3, 5556), (0x1E00, 5590),
+    (0x1F18, 5868), (0x1F20, 5874), (0x1F48, 5912), (0x1F50, 5918), (0x1F59, 5926), (0x1F5B, 5927),
+    (0x1F5D, 5928), (0x1F5F, 5929), (0x1F80, 5960), (0x1FB6, 6013), (0x1FBE, 6020), (0x1FC2, 6021),
+    (0x1FC6, 6024), (0x1FD0, 6031), (0x1FD6, 6035), (0x1FE0, 6041), (0x1FF2, 6054), (0x1FF6, 6057),
+    (0x2071, 6064), (0x207F, 6065), (0x2090, 6066), (0x2102, 6079), (0x2107, 6080), (0x210A, 6081),
+    (0x2115, 6091), (0x2119, 6092), (0x2124, 6097), (0x2126, 6098), (0x2128, 6099), (0x212A, 6100),
+    (0x212F, 6104), (0x213C, 6115), (0x2145, 6119), (0x214E, 6124), (0x2160, 6125), (0x24B6, 6166),
+    (0x2C00, 6218), (0x2CEB, 6447), (0x2CF2, 6451), (0x2D00, 6453), (0x2D27, 6491), (0x2D2D, 6492),
+    (0x2D30, 6493), (0x2D6F, 6549), (0x2D80, 6550), (0x2DA0, 6573), (0x2DA8, 6580), (0x2DB0, 6587),
+    (0x2DB8, 6594), (0x2DC0, 6601), (0x2DC8, 6608), (0x2DD0, 6615), (0x2DD8, 6622), (0x2DE0, 6629),
+    (0x2E2F, 6661), (0x3005, 6662), (0x3021, 6665), (0x3031, 6674), (0x3038, 6679), (0x3041, 6684),
+    (0x309D, 6770), (0x30A1, 6773), (0x30FC, 6863), (0x3105, 6867), (0x3131, 6910), (0x31A0, 7004),
+    (0x31F0, 7036), (0x3400, 7052), (0x4E00, 13644), (0xA4D0, 35801), (0xA500, 35847),
+    (0xA610, 36116), (0xA62A, 36132), (0xA640, 36134), (0xA674, 36181), (0xA67F, 36189),
+    (0xA717, 36302), (0xA722, 36311), (0xA78B, 36414), (0xA7D0, 36481), (0xA7D3, 36483),
+    (0xA7D5, 36484), (0xA7F2, 36492), (0xA807, 36512), (0xA840, 36545), (0xA880, 36597),
+    (0xA8C5, 36665), (0xA8F2, 36666), (0xA8FB, 36672), (0xA8FD, 36673), (0xA90A, 36676),
+    (0xA930, 36709), (0xA960, 36744), (0xA980, 36773), (0xA9B4, 36824), (0xA9CF, 36836),
+    (0xA9E0, 36837), (0xA9FA, 36853), (0xAA00, 36858), (0xAA40, 36913), (0xAA60, 36927),
+    (0xAA7A, 36950), (0xAAC0, 37019), (0xAAC2, 37020), (0xAADB, 37021), (0xAAE0, 37024),
+    (0xAAF2, 37040), (0xAB01, 37044), (0xAB09, 37050), (0xAB11, 37056), (0xAB20, 37062),
+    (0xAB28, 37069), (0xAB30, 37076), (0xAB5C, 37119), (0xAB70, 37133), (0xAC00, 37256),
+    (0xD7B0, 48428), (0xD7CB, 48451), (0xF900, 48500), (0xFA70, 48866), (0xFB00, 48972),
+    (0xFB13, 48979), (0xFB1D, 48984), (0xFB2A, 48996), (0xFB38, 49009), (0xFB3E, 49014),
+    (0xFB40, 49015), (0xFB43, 49017), (0xFB46, 49019), (0xFBD3, 49127), (0xFD50, 49490),
+    (0xFD92, 49554), (0xFDF0, 49608), (0xFE70, 49620), (0xFE76, 49625), (0xFF21, 49760),
+    (0xFF41, 49786), (0xFF66, 49812), (0xFFC2, 49901), (0xFFCA, 49907), (0xFFD2, 49913),
+    (0xFFDA, 49919), (0x10000, 49922), (0x1000D, 49934), (0x10028, 49960), (0x1003C, 49979),
+    (0x1003F, 49981), (0x10050, 49996), (0x10080, 50010), (0x10140, 50133), (0x10280, 50186),
+    (0x102A0, 50215), (0x10300, 50264), (0x1032D, 50296), (0x10350, 50326), (0x10380, 50369),
+    (0x103A0, 50399), (0x103C8, 50435), (0x103D1, 50443), (0x10400, 50448), (0x104B0, 50606),
+    (0x104D8, 50642), (0x10500, 50678), (0x10530, 50718), (0x10570, 50770), (0x1057C, 50781),
+    (0x1058C, 50796), (0x10594, 50803), (0x10597, 50805), (0x105A3, 50816), (0x105B3, 50831),
+    (0x105BB, 50838), (0x105C0, 50840), (0x10600, 50892), (0x10740, 51203), (0x10760, 51225),
+    (0x10780, 51233), (0x10787, 51239), (0x107B2, 51281), (0x10800, 51290), (0x10808, 51296),
+    (0x1080A, 51297), (0x10837, 51341), (0x1083C, 51343), (0x1083F, 51344), (0x10860, 51367),
+    (0x10880, 51390), (0x108E0, 51421), (0x108F4, 51440), (0x10900, 51442), (0x10920, 51464),
+    (0x10980, 51490), (0x109BE, 51546), (0x10A00, 51548), (0x10A05, 51552), (0x10A0C, 51554),
+    (0x10A15, 51562), (0x10A19, 51565), (0x10A60, 51594), (0x10A80, 51623), (0x10AC0, 51652),
+    (0x10AC9, 51660), (0x10B00, 51688), (0x10B40, 51742), (0x10B60, 51764), (0x10B80, 51783),
+    (0x10C00, 51801), (0x10C80, 51874), (0x10CC0, 51925), (0x10D00, 51976), (0x10D4A, 52016),
+    (0x10D69, 52044), (0x10D6F, 52045), (0x10E80, 52068), (0x10EAB, 52110), (0x10EB0, 52112),
+    (0x10EC2, 52114), (0x10EFC, 52117), (0x10F00, 52118), (0x10F27, 52147), (0x10F30, 52148),
+    (0x10F70, 52170), (0x10FB0, 52188), (0x10FE0, 52209), (0x11000, 52232), (0x11071, 52302),
+    (0x11080, 52307), (0x110C2, 52364), (0x110D0, 52365), (0x11100, 52390), (0x11144, 52441),
+    (0x11150, 52445), (0x11176, 52480), (0x11180, 52481), (0x111C1, 52545), (0x111CE, 52549),
+    (0x111DA, 52551), (0x111DC, 52552), (0x11200, 52553), (0x11213, 52571), (0x11237, 52605),
+    (0x1123E, 52606), (0x11280, 52610), (0x11288, 52617), (0x1128A, 52618), (0x1128F, 52622),
+    (0x1129F, 52637), (0x112B0, 52647), (0x11300, 52704), (0x11305, 52708), (0x1130F, 52716),
+    (0x11313, 52718), (0x1132A, 52740), (0x11332, 52747), (0x11335, 52749), (0x1133D, 52754),
+    (0x11347, 52762), (0x1134B, 52764), (0x11350, 52766), (0x11357, 52767), (0x1135D, 52768),
+    (0x11380, 52775), (0x1138B, 52785), (0x1138E, 52786), (0x11390, 52787), (0x113B7, 52825),
+    (0x113C2, 52835), (0x113C5, 52836), (0x113C7, 52837), (0x113CC, 52841), (0x113D1, 52843),
+    (0x113D3, 52844), (0x11400, 52845), (0x11443, 52911), (0x11447, 52914), (0x1145F, 52918),
+    (0x11480, 52921), (0x114C4, 52987), (0x114C7, 52989), (0x11580, 52990), (0x115B8, 53044),
+    (0x115D8, 53051), (0x11600, 53057), (0x11640, 53120), (0x11644, 53121), (0x11680, 53122),
+    (0x116B8, 53176), (0x11700, 53177), (0x1171D, 53204), (0x11740, 53218), (0x11800, 53225),
+    (0x118A0, 53282), (0x118FF, 53346), (0x11909, 53354), (0x1190C, 53355), (0x11915, 53363),
+    (0x11918, 53365), (0x11937, 53395), (0x1193B, 53397), (0x1193F, 53399), (0x119A0, 53403),
+    (0x119AA, 53411), (0x119DA, 53457), (0x119E1, 53463), (0x119E3, 53464), (0x11A00, 53466),
+    (0x11A35, 53517), (0x11A50, 53527), (0x11A9D, 53599), (0x11AB0, 53600), (0x11BC0, 53673),
+    (0x11C00, 53706), (0x11C0A, 53715), (0x11C38, 53760), (0x11C40, 53767), (0x11C72, 53768),
+    (0x11C92, 53798), (0x11CA9, 53820), (0x11D00, 53834), (0x11D08, 53841), (0x11D0B, 53843),
+    (0x11D3A, 53887), (0x11D3C, 53888), (0x11D3F, 53890), (0x11D43, 53893), (0x11D46, 53894),
+    (0x11D60, 53896), (0x11D67, 53902), (0x11D6A, 53904), (0x11D90, 53941), (0x11D93, 53943),
+    (0x11D98, 53947), (0x11EE0, 53948), (0x11F00, 53971), (0x11F12, 53988), (0x11F3E, 54029),
+    (0x11FB0, 54032), (0x12000, 54033), (0x12400, 54955), (0x12480, 55066), (0x12F90, 55262),
+    (0x13000, 55359), (0x13441, 56431), (0x13460, 56437), (0x14400, 60432), (0x16100, 61015),
+    (0x16800, 61062), (0x16A40, 61631), (0x16A70, 61662), (0x16AD0, 61741), (0x16B00, 61771),
+    (0x16B40, 61819), (0x16B63, 61823), (0x16B7D, 61844), (0x16D40, 61863), (0x16E40, 61908),
+    (0x16F00, 61972), (0x16F4F, 62047), (0x16F8F, 62104), (0x16FE0, 62121), (0x16FE3, 62123),
+    (0x16FF0, 62124), (0x17000, 62126), (0x18800, 68262), (0x18CFF, 69500), (0x1AFF0, 69510),
+    (0x1AFF5, 69514), (0x1AFFD, 69521), (0x1B000, 69523), (0x1B132, 69814), (0x1B150, 69815),
+    (0x1B155, 69818), (0x1B164, 69819), (0x1B170, 69823), (0x1BC00, 70219), (0x1BC70, 70326),
+    (0x1BC80, 70339), (0x1BC90, 70348), (0x1BC9E, 70358), (0x1D400, 70359), (0x1D456, 70444),
+    (0x1D49E, 70515), (0x1D4A2, 70517), (0x1D4A5, 70518), (0x1D4A9, 70520), (0x1D4AE, 70524),
+    (0x1D4BB, 70536), (0x1D4BD, 70537), (0x1D4C5, 70544), (0x1D507, 70609), (0x1D50D, 70613),
+    (0x1D516, 70621), (0x1D51E, 70628), (0x1D53B, 70656), (0x1D540, 70660), (0x1D546, 70665),
+    (0x1D54A, 70666), (0x1D552, 70673), (0x1D6A8, 71013), (0x1D6C2, 71038), (0x1D6DC, 71063),
+    (0x1D6FC, 71094), (0x1D716, 71119), (0x1D736, 71150), (0x1D750, 71175), (0x1D770, 71206),
+    (0x1D78A, 71231), (0x1D7AA, 71262), (0x1D7C4, 71287), (0x1DF00, 71295), (0x1DF25, 71326),
+    (0x1E000, 71332), (0x1E008, 71339), (0x1E01B, 71356), (0x1E023, 71363), (0x1E026, 71365),
+    (0x1E030, 71370), (0x1E08F, 71432), (0x1E100, 71433), (0x1E137, 71478), (0x1E14E, 71485),
+    (0x1E290, 71486), (0x1E2C0, 71516), (0x1E4D0, 71560), (0x1E5D0, 71588), (0x1E5F0, 71618),
+    (0x1E7E0, 71619), (0x1E7E8, 71626), (0x1E7ED, 71630), (0x1E7F0, 71632), (0x1E800, 71647),
+    (0x1E900, 71844), (0x1E947, 71912), (0x1E94B, 71913), (0x1EE00, 71914), (0x1EE05, 71918),
+    (0x1EE21, 71945), (0x1EE24, 71947), (0x1EE27, 71948), (0x1EE29, 71949), (0x1EE34, 71959),
+    (0x1EE39, 71963), (0x1EE3B, 71964), (0x1EE42, 71965), (0x1EE47, 71966), (0x1EE49, 71967),
+    (0x1EE4B, 71968), (0x1EE4D, 71969), (0x1EE51, 71972), (0x1EE54, 71974), (0x1EE57, 71975),
+    (0x1EE59, 71976), (0x1EE5B, 71977), (0x1EE5D, 71978), (0x1EE5F, 71979), (0x1EE61, 71980),
+    (0x1EE64, 71982), (0x1EE67, 71983), (0x1EE6C, 71987), (0x1EE74, 71994), (0x1EE79, 71998),
+    (0x1EE7E, 72002), (0x1EE80, 72003), (0x1EE8B, 72013), (0x1EEA1, 72030), (0x1EEA5, 72033),
+    (0x1EEAB, 72038), (0x1F130, 72055), (0x1F150, 72081), (0x1F170, 72107), (0x20000, 72133),
+    (0x2A700, 114853), (0x2B740, 119007), (0x2B820, 119229), (0x2CEB0, 124991), (0x2EBF0, 132464),
+    (0x2F800, 133086), (0x30000, 133628), (0x31350, 138567), (0x110000, 142759),
+];
+
+/// Scalar values with a General Category of `Nd`, `Nl` or `No`
+#[rustfmt::skip]
+pub(crate) const NUMERIC: &[(u32, u32)] = &[
+    (0x30, 0), (0xB2, 10), (0xB9, 12), (0xBC, 13), (0x660, 16), (0x6F0, 26), (0x7C0, 36),
+    (0x966, 46), (0x9E6, 56), (0x9F4, 66), (0xA66, 72), (0xAE6, 82), (0xB66, 92), (0xB72, 102),
+    (0xBE6, 108), (0xC66, 121), (0xC78, 131), (0xCE6, 138), (0xD58, 148), (0xD66, 155),
+    (0xDE6, 174), (0xE50, 184), (0xED0, 194), (0xF20, 204), (0x1040, 224), (0x1090, 234),
+    (0x1369, 244), (0x16EE, 264), (0x17E0, 267), (0x17F0, 277), (0x1810, 287), (0x1946, 297),
+    (0x19D0, 307), (0x1A80, 318), (0x1A90, 328), (0x1B50, 338), (0x1BB0, 348), (0x1C40, 358),
+    (0x1C50, 368), (0x2070, 378), (0x2074, 379), (0x2080, 385), (0x2150, 395), (0x2185, 446),
+    (0x2460, 451), (0x24EA, 511), (0x2776, 533), (0x2CFD, 563), (0x3007, 564), (0x3021, 565),
+    (0x3038, 574), (0x3192, 577), (0x3220, 581), (0x3248, 591), (0x3251, 599), (0x3280, 614),
+    (0x32B1, 624), (0xA620, 639), (0xA6E6, 649), (0xA830, 659), (0xA8D0, 665), (0xA900, 675),
+    (0xA9D0, 685), (0xA9F0, 695), (0xAA50, 705), (0xABF0, 715), (0xFF10, 725), (0x10107, 735),
+    (0x10140, 780), (0x1018A, 837), (0x102E1, 839), (0x10320, 866), (0x10341, 870), (0x1034A, 871),
+    (0x103D1, 872), (0x104A0, 877), (0x10858, 887), (0x10879, 895), (0x108A7, 902), (0x108FB, 911),
+    (0x10916, 916), (0x109BC, 922), (0x109C0, 924), (0x109D2, 940), (0x10A40, 986), (0x10A7D, 995),
+    (0x10A9D, 997), (0x10AEB, 1000), (0x10B58, 1005), (0x10B78, 1013), (0x10BA9, 1021),
+    (0x10CFA, 1028), (0x10D30, 1034), (0x10D40, 1044), (0x10E60, 1054), (0x10F1D, 1085),
+    (0x10F51, 1095), (0x10FC5, 1099), (0x11052, 1106), (0x110F0, 1136), (0x11136, 1146),
+    (0x111D0, 1156), (0x111E1, 1166), (0x112F0, 1186), (0x11450, 1196), (0x114D0, 1206),
+    (0x11650, 1216), (0x116C0, 1226), (0x116D0, 1236), (0x11730, 1256), (0x118E0, 1268),
+    (0x11950, 1287), (0x11BF0, 1297), (0x11C50, 1307), (0x11D50, 1336), (0x11DA0, 1346),
+    (0x11F50, 1356), (0x11FC0, 1366), (0x12400, 1387), (0x16130, 1498), (0x16A60, 1508),
+    (0x16AC0, 1518), (0x16B50, 1528), (0x16B5B, 1538), (0x16D70, 1545), (0x16E80, 1555),
+    (0x1CCF0, 1578), (0x1D2C0, 1588), (0x1D2E0, 1608), (0x1D360, 1628), (0x1D7CE, 1653),
+    (0x1E140, 1703), (0x1E2F0, 1713), (0x1E4F0, 1723), (0x1E5F1, 1733), (0x1E8C7, 1743),
+    (0x1E950, 1752), (0x1EC71, 1762), (0x1ECAD, 1821), (0x1ECB1, 1824), (0x1ED01, 1828),
+    (0x1ED2F, 1873), (0x1F100, 1888), (0x1FBF0, 1901), (0x110000, 1911),
+];
+
+/// Scalar values with the `White_Space` property
+#[rustfmt::skip]
+pub(crate) const WHITESPACE: &[(u32, u32)] = &[
+    (0x9, 0), (0x20, 5), (0x85, 6), (0xA0, 7), (0x1680, 8), (0x2000, 9), (0x2028, 20), (0x202F, 22),
+    (0x205F, 23), (0x3000, 24), (0x110000, 25),
+];
+
+/// Scalar values with the `Emoji` property
+#[rustfmt::skip]
+pub(crate) const EMOJI: &[(u32, u32)] = &[
+    (0x23, 0), (0x2A, 1), (0x30, 2), (0xA9, 12), (0xAE, 13), (0x203C, 14), (0x2049, 15),
+    (0x2122, 16), (0x2139, 17), (0x2194, 18), (0x21A9, 24), (0x231A, 26), (0x2328, 28),
+    (0x23CF, 29), (0x23E9, 30), (0x23F8, 41), (0x24C2, 44), (0x25AA, 45), (0x25B6, 47),
+    (0x25C0, 48), (0x25FB, 49), (0x2600, 53), (0x260E, 58), (0x2611, 59), (0x2614, 60),
+    (0x2618, 62), (0x261D, 63), (0x2620, 64), (0x2622, 65), (0x2626, 67), (0x262A, 68),
+    (0x262E, 69), (0x2638, 71), (0x2640, 74), (0x2642, 75), (0x2648, 76), (0x265F, 88),
+    (0x2663, 90), (0x2665, 91), (0x2668, 93), (0x267B, 94), (0x267E, 95), (0x2692, 97),
+    (0x2699, 103), (0x269B, 104), (0x26A0, 106), (0x26A7, 108), (0x26AA, 109), (0x26B0, 111),
+    (0x26BD, 113), (0x26C4, 115), (0x26C8, 117), (0x26CE, 118), (0x26D1, 120), (0x26D3, 121),
+    (0x26E9, 123), (0x26F0, 125), (0x26F7, 131), (0x26FD, 135), (0x2702, 136), (0x2705, 137),
+    (0x2708, 138), (0x270F, 144), (0x2712, 145), (0x2714, 146), (0x2716, 147), (0x271D, 148),
+    (0x2721, 149), (0x2728, 150), (0x2733, 151), (0x2744, 153), (0x2747, 154), (0x274C, 155),
+    (0x274E, 156), (0x2753, 157), (0x2757, 160), (0x2763, 161), (0x2795, 163), (0x27A1, 166),
+    (0x27B0, 167), (0x27BF, 168), (0x2934, 169), (0x2B05, 171), (0x2B1B, 174), (0x2B50, 176),
+    (0x2B55, 177), (0x3030, 178), (0x303D, 179), (0x3297, 180), (0x3299, 181), (0x1F004, 182),
+    (0x1F0CF, 183), (0x1F170, 184), (0x1F17E, 186), (0x1F18E, 188), (0x1F191, 189), (0x1F1E6, 199),
+    (0x1F201, 225), (0x1F21A, 227), (0x1F22F, 228), (0x1F232, 229), (0x1F250, 238), (0x1F300, 240),
+    (0x1F324, 274), (0x1F396, 386), (0x1F399, 388), (0x1F39E, 391), (0x1F3F3, 474), (0x1F3F7, 477),
+    (0x1F4FF, 740), (0x1F549, 803), (0x1F550, 809), (0x1F56F, 833), (0x1F573, 835), (0x1F587, 843),
+    (0x1F58A, 844), (0x1F590, 848), (0x1F595, 849), (0x1F5A4, 851), (0x1F5A8, 853), (0x1F5B1, 854),
+    (0x1F5BC, 856), (0x1F5C2, 857), (0x1F5D1, 860), (0x1F5DC, 863), (0x1F5E1, 866), (0x1F5E3, 867),
+    (0x1F5E8, 868), (0x1F5EF, 869), (0x1F5F3, 870), (0x1F5FA, 871), (0x1F680, 957), (0x1F6CB, 1027),
+    (0x1F6D5, 1035), (0x1F6DC, 1038), (0x1F6E9, 1048), (0x1F6EB, 1049), (0x1F6F0, 1051),
+    (0x1F6F3, 1052), (0x1F7E0, 1062), (0x1F7F0, 1074), (0x1F90C, 1075), (0x1F93C, 1122),
+    (0x1F947, 1132), (0x1FA70, 1317), (0x1FA80, 1330), (0x1FA8F, 1340), (0x1FACE, 1396),
+    (0x1FADF, 1411), (0x1FAF0, 1422), (0x110000, 1431),
+];
@@ -0,0 +1,168 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Table of assigned Unicode scalar values.
+//!
+//! Generated from the Unicode Character Database, version 16.0.0. A scalar
+//! value is assigned unless its General Category is `Cn` (unassigned,
+//! including noncharacters); surrogates are not scalar values and are
+//! excluded.
+
+/// The Unicode version of [`ASSIGNED`] and of the tables in
+/// `unicode_categories`, which must be generated from the same version.
+pub(crate) const UNICODE_VERSION: (u8, u8, u8) = (16, 0, 0);
+
+/// The number of assigned scalar values.
+pub(crate) const TOTAL: u32 = 292531;
+
+/// Ranges of assigned scalar values as `(first, count)`, where `first` is the
+/// first scalar value of the range and `count` is the number of assigned
+/// scalar values below `first`. Range `i` thus holds the
+/// `ASSIGNED[i + 1].1 - ASSIGNED[i].1` scalar values starting at
+/// `ASSIGNED[i].0`. The last entry is a sentinel.
+#[rustfmt::skip]
+pub(crate) const ASSIGNED: &[(u32, u32)] = &[
+    (0x0, 0), (0x37A, 888), (0x384, 894), (0x38C, 901), (0x38E, 902), (0x3A3, 922), (0x531, 1319),
+    (0x559, 1357), (0x58D, 1407), (0x591, 1410), (0x5D0, 1465), (0x5EF, 1492), (0x600, 1498),
+    (0x70F, 1768), (0x74D, 1828), (0x7C0, 1929), (0x7FD, 1988), (0x830, 2037), (0x840, 2052),
+    (0x85E, 2080), (0x860, 2081), (0x870, 2092), (0x890, 2123), (0x897, 2125), (0x985, 2362),
+    (0x98F, 2370), (0x993, 2372), (0x9AA, 2394), (0x9B2, 2401), (0x9B6, 2402), (0x9BC, 2406),
+    (0x9C7, 2415), (0x9CB, 2417), (0x9D7, 2421), (0x9DC, 2422), (0x9DF, 2424), (0x9E6, 2429),
+    (0xA01, 2454), (0xA05, 2457), (0xA0F, 2463), (0xA13, 2465), (0xA2A, 2487), (0xA32, 2494),
+    (0xA35, 2496), (0xA38, 2498), (0xA3C, 2500), (0xA3E, 2501), (0xA47, 2506), (0xA4B, 2508),
+    (0xA51, 2511), (0xA59, 2512), (0xA5E, 2516), (0xA66, 2517), (0xA81, 2534), (0xA85, 2537),
+    (0xA8F, 2546), (0xA93, 2549), (0xAAA, 2571), (0xAB2, 2578), (0xAB5, 2580), (0xABC, 2585),
+    (0xAC7, 2595), (0xACB, 2598), (0xAD0, 2601), (0xAE0, 2602), (0xAE6, 2606), (0xAF9, 2618),
+    (0xB01, 2625), (0xB05, 2628), (0xB0F, 2636), (0xB13, 2638), (0xB2A, 2660), (0xB32, 2667),
+    (0xB35, 2669), (0xB3C, 2674), (0xB47, 2683), (0xB4B, 2685), (0xB55, 2688), (0xB5C, 2691),
+    (0xB5F, 2693), (0xB66, 2698), (0xB82, 2716), (0xB85, 2718), (0xB8E, 2724), (0xB92, 2727),
+    (0xB99, 2731), (0xB9C, 2733), (0xB9E, 2734), (0xBA3, 2736), (0xBA8, 2738), (0xBAE, 2741),
+    (0xBBE, 2753), (0xBC6, 2758), (0xBCA, 2761), (0xBD0, 2765), (0xBD7, 2766), (0xBE6, 2767),
+    (0xC00, 2788), (0xC0E, 2801), (0xC12, 2804), (0xC2A, 2827), (0xC3C, 2843), (0xC46, 2852),
+    (0xC4A, 2855), (0xC55, 2859), (0xC58, 2861), (0xC5D, 2864), (0xC60, 2865), (0xC66, 2869),
+    (0xC77, 2879), (0xC8E, 2901), (0xC92, 2904), (0xCAA, 2927), (0xCB5, 2937), (0xCBC, 2942),
+    (0xCC6, 2951), (0xCCA, 2954), (0xCD5, 2958), (0xCDD, 2960), (0xCE0, 2962), (0xCE6, 2966),
+    (0xCF1, 2976), (0xD00, 2979), (0xD0E, 2992), (0xD12, 2995), (0xD46, 3046), (0xD4A, 3049),
+    (0xD54, 3055), (0xD66, 3071), (0xD81, 3097), (0xD85, 3100), (0xD9A, 3118), (0xDB3, 3142),
+    (0xDBD, 3151), (0xDC0, 3152), (0xDCA, 3159), (0xDCF, 3160), (0xDD6, 3166), (0xDD8, 3167),
+    (0xDE6, 3175), (0xDF2, 3185), (0xE01, 3188), (0xE3F, 3246), (0xE81, 3275), (0xE84, 3277),
+    (0xE86, 3278), (0xE8C, 3283), (0xEA5, 3307), (0xEA7, 3308), (0xEC0, 3331), (0xEC6, 3336),
+    (0xEC8, 3337), (0xED0, 3344), (0xEDC, 3354), (0xF00, 3358), (0xF49, 3430), (0xF71, 3466),
+    (0xF99, 3505), (0xFBE, 3541), (0xFCE, 3556), (0x1000, 3569), (0x10C7, 3767), (0x10CD, 3768),
+    (0x10D0, 3769), (0x124A, 4146), (0x1250, 4150), (0x1258, 4157), (0x125A, 4158), (0x1260, 4162),
+    (0x128A, 4203), (0x1290, 4207), (0x12B2, 4240), (0x12B8, 4244), (0x12C0, 4251), (0x12C2, 4252),
+    (0x12C8, 4256), (0x12D8, 4271), (0x1312, 4328), (0x1318, 4332), (0x135D, 4399), (0x1380, 4431),
+    (0x13A0, 4457), (0x13F8, 4543), (0x1400, 4549), (0x16A0, 5218), (0x1700, 5307), (0x171F, 5329),
+    (0x1740, 5353), (0x1760, 5373), (0x176E, 5386), (0x1772, 5389), (0x1780, 5391), (0x17E0, 5485),
+    (0x17F0, 5495), (0x1800, 5505), (0x1820, 5531), (0x1880, 5620), (0x18B0, 5663), (0x1900, 5733),
+    (0x1920, 5764), (0x1930, 5776), (0x1940, 5788), (0x1944, 5789), (0x1970, 5831), (0x1980, 5836),
+    (0x19B0, 5880), (0x19D0, 5906), (0x19DE, 5917), (0x1A1E, 5979), (0x1A60, 6044), (0x1A7F, 6073),
+    (0x1A90, 6084), (0x1AA0, 6094), (0x1AB0, 6108), (0x1B00, 6139), (0x1B4E, 6216), (0x1BFC, 6382),
+    (0x1C3B, 6442), (0x1C4D, 6457), (0x1C90, 6519), (0x1CBD, 6562), (0x1CD0, 6573), (0x1D00, 6616),
+    (0x1F18, 7150), (0x1F20, 7156), (0x1F48, 7194), (0x1F50, 7200), (0x1F59, 7208), (0x1F5B, 7209),
+    (0x1F5D, 7210), (0x1F5F, 7211), (0x1F80, 7242), (0x1FB6, 7295), (0x1FC6, 7310), (0x1FD6, 7324),
+    (0x1FDD, 7330), (0x1FF2, 7349), (0x1FF6, 7352), (0x2000, 7361), (0x2066, 7462), (0x2074, 7474),
+    (0x2090, 7501), (0x20A0, 7514), (0x20D0, 7547), (0x2100, 7580), (0x2190, 7720), (0x2440, 8386),
+    (0x2460, 8397), (0x2B76, 10209), (0x2B97, 10241), (0x2CF9, 10590), (0x2D27, 10635),
+    (0x2D2D, 10636), (0x2D30, 10637), (0x2D6F, 10693), (0x2D7F, 10695), (0x2DA0, 10719),
+    (0x2DA8, 10726), (0x2DB0, 10733), (0x2DB8, 10740), (0x2DC0, 10747), (0x2DC8, 10754),
+    (0x2DD0, 10761), (0x2DD8, 10768), (0x2DE0, 10775), (0x2E80, 10901), (0x2E9B, 10927),
+    (0x2F00, 11016), (0x2FF0, 11230), (0x3041, 11310), (0x3099, 11396), (0x3105, 11499),
+    (0x3131, 11542), (0x3190, 11636), (0x31EF, 11722), (0x3220, 11770), (0xA490, 41063),
+    (0xA4D0, 41118), (0xA640, 41466), (0xA700, 41650), (0xA7D0, 41856), (0xA7D3, 41858),
+    (0xA7D5, 41859), (0xA7F2, 41867), (0xA830, 41926), (0xA840, 41936), (0xA880, 41992),
+    (0xA8CE, 42062), (0xA8E0, 42074), (0xA95F, 42190), (0xA980, 42220), (0xA9CF, 42298),
+    (0xA9DE, 42309), (0xAA00, 42342), (0xAA40, 42397), (0xAA50, 42411), (0xAA5C, 42421),
+    (0xAADB, 42524), (0xAB01, 42552), (0xAB09, 42558), (0xAB11, 42564), (0xAB20, 42570),
+    (0xAB28, 42577), (0xAB30, 42584), (0xAB70, 42644), (0xABF0, 42770), (0xAC00, 42780),
+    (0xD7B0, 53952), (0xD7CB, 53975), (0xE000, 54024), (0xFA70, 60790), (0xFB00, 60896),
+    (0xFB13, 60903), (0xFB1D, 60908), (0xFB38, 60934), (0xFB3E, 60939), (0xFB40, 60940),
+    (0xFB43, 60942), (0xFB46, 60944), (0xFBD3, 61069), (0xFD92, 61514), (0xFDCF, 61568),
+    (0xFDF0, 61569), (0xFE20, 61611), (0xFE54, 61662), (0xFE68, 61681), (0xFE70, 61685),
+    (0xFE76, 61690), (0xFEFF, 61825), (0xFF01, 61826), (0xFFC2, 62016), (0xFFCA, 62022),
+    (0xFFD2, 62028), (0xFFDA, 62034), (0xFFE0, 62037), (0xFFE8, 62044), (0xFFF9, 62051),
+    (0x10000, 62056), (0x1000D, 62068), (0x10028, 62094), (0x1003C, 62113), (0x1003F, 62115),
+    (0x10050, 62130), (0x10080, 62144), (0x10100, 62267), (0x10107, 62270), (0x10137, 62315),
+    (0x10190, 62403), (0x101A0, 62416), (0x101D0, 62417), (0x10280, 62463), (0x102A0, 62492),
+    (0x102E0, 62541), (0x10300, 62569), (0x1032D, 62605), (0x10350, 62635), (0x10380, 62678),
+    (0x1039F, 62708), (0x103C8, 62745), (0x10400, 62759), (0x104A0, 62917), (0x104B0, 62927),
+    (0x104D8, 62963), (0x10500, 62999), (0x10530, 63039), (0x1056F, 63091), (0x1057C, 63103),
+    (0x1058C, 63118), (0x10594, 63125), (0x10597, 63127), (0x105A3, 63138), (0x105B3, 63153),
+    (0x105BB, 63160), (0x105C0, 63162), (0x10600, 63214), (0x10740, 63525), (0x10760, 63547),
+    (0x10780, 63555), (0x10787, 63561), (0x107B2, 63603), (0x10800, 63612), (0x10808, 63618),
+    (0x1080A, 63619), (0x10837, 63663), (0x1083C, 63665), (0x1083F, 63666), (0x10857, 63689),
+    (0x108A7, 63761), (0x108E0, 63770), (0x108F4, 63789), (0x108FB, 63791), (0x1091F, 63824),
+    (0x1093F, 63851), (0x10980, 63852), (0x109BC, 63908), (0x109D2, 63928), (0x10A05, 63978),
+    (0x10A0C, 63980), (0x10A15, 63988), (0x10A19, 63991), (0x10A38, 64020), (0x10A3F, 64023),
+    (0x10A50, 64033), (0x10A60, 64042), (0x10AC0, 64106), (0x10AEB, 64145), (0x10B00, 64157),
+    (0x10B39, 64211), (0x10B58, 64240), (0x10B78, 64267), (0x10B99, 64293), (0x10BA9, 64297),
+    (0x10C00, 64304), (0x10C80, 64377), (0x10CC0, 64428), (0x10CFA, 64479), (0x10D30, 64525),
+    (0x10D40, 64535), (0x10D69, 64573), (0x10D8E, 64602), (0x10E60, 64604), (0x10E80, 64635),
+    (0x10EAB, 64677), (0x10EB0, 64680), (0x10EC2, 64682), (0x10EFC, 64685), (0x10F30, 64729),
+    (0x10F70, 64771), (0x10FB0, 64797), (0x10FE0, 64825), (0x11000, 64848), (0x11052, 64926),
+    (0x1107F, 64962), (0x110CD, 65030), (0x110D0, 65031), (0x110F0, 65056), (0x11100, 65066),
+    (0x11136, 65119), (0x11150, 65137), (0x11180, 65176), (0x111E1, 65272), (0x11200, 65292),
+    (0x11213, 65310), (0x11280, 65357), (0x11288, 65364), (0x1128A, 65365), (0x1128F, 65369),
+    (0x1129F, 65384), (0x112B0, 65395), (0x112F0, 65454), (0x11300, 65464), (0x11305, 65468),
+    (0x1130F, 65476), (0x11313, 65478), (0x1132A, 65500), (0x11332, 65507), (0x11335, 65509),
+    (0x1133B, 65514), (0x11347, 65524), (0x1134B, 65526), (0x11350, 65529), (0x11357, 65530),
+    (0x1135D, 65531), (0x11366, 65538), (0x11370, 65545), (0x11380, 65550), (0x1138B, 65560),
+    (0x1138E, 65561), (0x11390, 65562), (0x113B7, 65600), (0x113C2, 65610), (0x113C5, 65611),
+    (0x113C7, 65612), (0x113CC, 65616), (0x113D7, 65626), (0x113E1, 65628), (0x11400, 65630),
+    (0x1145D, 65722), (0x11480, 65727), (0x114D0, 65799), (0x11580, 65809), (0x115B8, 65863),
+    (0x11600, 65901), (0x11650, 65970), (0x11660, 65980), (0x11680, 65993), (0x116C0, 66051),
+    (0x116D0, 66061), (0x11700, 66081), (0x1171D, 66108), (0x11730, 66123), (0x11800, 66146),
+    (0x118A0, 66206), (0x118FF, 66289), (0x11909, 66297), (0x1190C, 66298), (0x11915, 66306),
+    (0x11918, 66308), (0x11937, 66338), (0x1193B, 66340), (0x11950, 66352), (0x119A0, 66362),
+    (0x119AA, 66370), (0x119DA, 66416), (0x11A00, 66427), (0x11A50, 66499), (0x11AB0, 66582),
+    (0x11B00, 66655), (0x11BC0, 66665), (0x11BF0, 66699), (0x11C00, 66709), (0x11C0A, 66718),
+    (0x11C38, 66763), (0x11C50, 66777), (0x11C70, 66806), (0x11C92, 66838), (0x11CA9, 66860),
+    (0x11D00, 66874), (0x11D08, 66881), (0x11D0B, 66883), (0x11D3A, 66927), (0x11D3C, 66928),
+    (0x11D3F, 66930), (0x11D50, 66939), (0x11D60, 66949), (0x11D67, 66955), (0x11D6A, 66957),
+    (0x11D90, 66994), (0x11D93, 66996), (0x11DA0, 67002), (0x11EE0, 67012), (0x11F00, 67037),
+    (0x11F12, 67054), (0x11F3E, 67095), (0x11FB0, 67124), (0x11FC0, 67125), (0x11FFF, 67175),
+    (0x12400, 68098), (0x12470, 68209), (0x12480, 68214), (0x12F90, 68410), (0x13000, 68509),
+    (0x13460, 69619), (0x14400, 73614), (0x16100, 74197), (0x16800, 74255), (0x16A40, 74824),
+    (0x16A60, 74855), (0x16A6E, 74865), (0x16AC0, 74946), (0x16AD0, 74956), (0x16AF0, 74986),
+    (0x16B00, 74992), (0x16B50, 75062), (0x16B5B, 75072), (0x16B63, 75079), (0x16B7D, 75100),
+    (0x16D40, 75119), (0x16E40, 75177), (0x16F00, 75268), (0x16F4F, 75343), (0x16F8F, 75400),
+    (0x16FE0, 75417), (0x16FF0, 75422), (0x17000, 75424), (0x18800, 81560), (0x18CFF, 82798),
+    (0x1AFF0, 82808), (0x1AFF5, 82812), (0x1AFFD, 82819), (0x1B000, 82821), (0x1B132, 83112),
+    (0x1B150, 83113), (0x1B155, 83116), (0x1B164, 83117), (0x1B170, 83121), (0x1BC00, 83517),
+    (0x1BC70, 83624), (0x1BC80, 83637), (0x1BC90, 83646), (0x1BC9C, 83656), (0x1CC00, 83664),
+    (0x1CD00, 83914), (0x1CF00, 84350), (0x1CF30, 84396), (0x1CF50, 84419), (0x1D000, 84535),
+    (0x1D100, 84781), (0x1D129, 84820), (0x1D200, 85014), (0x1D2C0, 85084), (0x1D2E0, 85104),
+    (0x1D300, 85124), (0x1D360, 85211), (0x1D400, 85236), (0x1D456, 85321), (0x1D49E, 85392),
+    (0x1D4A2, 85394), (0x1D4A5, 85395), (0x1D4A9, 85397), (0x1D4AE, 85401), (0x1D4BB, 85413),
+    (0x1D4BD, 85414), (0x1D4C5, 85421), (0x1D507, 85486), (0x1D50D, 85490), (0x1D516, 85498),
+    (0x1D51E, 85505), (0x1D53B, 85533), (0x1D540, 85537), (0x1D546, 85542), (0x1D54A, 85543),
+    (0x1D552, 85550), (0x1D6A8, 85890), (0x1D7CE, 86182), (0x1DA9B, 86884), (0x1DAA1, 86889),
+    (0x1DF00, 86904), (0x1DF25, 86935), (0x1E000, 86941), (0x1E008, 86948), (0x1E01B, 86965),
+    (0x1E023, 86972), (0x1E026, 86974), (0x1E030, 86979), (0x1E08F, 87041), (0x1E100, 87042),
+    (0x1E130, 87087), (0x1E140, 87101), (0x1E14E, 87111), (0x1E290, 87113), (0x1E2C0, 87144),
+    (0x1E2FF, 87202), (0x1E4D0, 87203), (0x1E5D0, 87245), (0x1E5FF, 87288), (0x1E7E0, 87289),
+    (0x1E7E8, 87296), (0x1E7ED, 87300), (0x1E7F0, 87302), (0x1E800, 87317), (0x1E8C7, 87514),
+    (0x1E900, 87530), (0x1E950, 87606), (0x1E95E, 87616), (0x1EC71, 87618), (0x1ED01, 87686),
+    (0x1EE00, 87747), (0x1EE05, 87751), (0x1EE21, 87778), (0x1EE24, 87780), (0x1EE27, 87781),
+    (0x1EE29, 87782), (0x1EE34, 87792), (0x1EE39, 87796), (0x1EE3B, 87797), (0x1EE42, 87798),
+    (0x1EE47, 87799), (0x1EE49, 87800), (0x1EE4B, 87801), (0x1EE4D, 87802), (0x1EE51, 87805),
+    (0x1EE54, 87807), (0x1EE57, 87808), (0x1EE59, 87809), (0x1EE5B, 87810), (0x1EE5D, 87811),
+    (0x1EE5F, 87812), (0x1EE61, 87813), (0x1EE64, 87815), (0x1EE67, 87816), (0x1EE6C, 87820),
+    (0x1EE74, 87827), (0x1EE79, 87831), (0x1EE7E, 87835), (0x1EE80, 87836), (0x1EE8B, 87846),
+    (0x1EEA1, 87863), (0x1EEA5, 87866), (0x1EEAB, 87871), (0x1EEF0, 87888), (0x1F000, 87890),
+    (0x1F030, 87934), (0x1F0A0, 88034), (0x1F0B1, 88049), (0x1F0C1, 88064), (0x1F0D1, 88079),
+    (0x1F100, 88116), (0x1F1E6, 88290), (0x1F210, 88319), (0x1F240, 88363), (0x1F250, 88372),
+    (0x1F260, 88374), (0x1F300, 88380), (0x1F6DC, 89364), (0x1F6F0, 89381), (0x1F700, 89394),
+    (0x1F77B, 89513), (0x1F7E0, 89608), (0x1F7F0, 89620), (0x1F800, 89621), (0x1F810, 89633),
+    (0x1F850, 89689), (0x1F860, 89699), (0x1F890, 89739), (0x1F8B0, 89769), (0x1F8C0, 89781),
+    (0x1F900, 89783), (0x1FA60, 90123), (0x1FA70, 90137), (0x1FA80, 90150), (0x1FA8F, 90160),
+    (0x1FACE, 90216), (0x1FADF, 90231), (0x1FAF0, 90242), (0x1FB00, 90251), (0x1FB94, 90398),
+    (0x20000, 90500), (0x2A700, 133220), (0x2B740, 137374), (0x2B820, 137596), (0x2CEB0, 143358),
+    (0x2EBF0, 150831), (0x2F800, 151453), (0x30000, 151995), (0x31350, 156934), (0xE0001, 161126),
+    (0xE0020, 161127), (0xE0100, 161223), (0xF0000, 161463), (0x100000, 226997), (0x110000, 292531),
+];
@@ -21,7 +21,9 @@
 //! The types [`UniformInt`], [`UniformFloat`] and [`UniformDuration`] are the
 //! back-ends supporting sampling from primitive integer and floating-point
 //! ranges as well as from [`std::time::Duration`]; these types do not normally
-//! need to be used directly (unless implementing a derived back-end).
+//! need to be used directly (unless implementing a derived back-end). With the
+//! `fixed` feature, `UniformFixed` supports the fixed-point numbers of the
+//! `fixed` crate.
 //!
 //! # Example usage
 //!
@@ -105,7 +107,7 @@
 
 use core::fmt;
 use core::time::Duration;
-use core::ops::{Range, RangeInclusive};
+use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 use core::convert::TryFrom;
 
 use crate::distributions::float::IntoFloat;
@@ -121,6 +123,10 @@ use crate::distributions::utils::Float;
 
 #[cfg(feature = "simd_support")] use core::simd::*;
 
+#[cfg(feature = "fixed")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "fixed")))]
+pub use super::fixed_point::UniformFixed;
+
 /// Error type returned from [`Uniform::new`] and `new_inclusive`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Error {
@@ -409,6 +415,366 @@ impl<T: SampleUniform + PartialOrd> SampleRange<T> for RangeInclusive<T> {
     }
 }
 
+// Borrowed ranges, avoiding a clone of non-`Copy` bounds
+impl<T: SampleUniform + PartialOrd> SampleRange<T> for &Range<T> {
+    #[inline]
+    fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<T, Error> {
+        T::Sampler::sample_single(&self.start, &self.end, rng)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        !(self.start < self.end)
+    }
+}
+
+impl<T: SampleUniform + PartialOrd> SampleRange<T> for &RangeInclusive<T> {
+    #[inline]
+    fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<T, Error> {
+        T::Sampler::sample_single_inclusive(self.start(), self.end(), rng)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        !(self.start() <= self.end())
+    }
+}
+
+/// A `(low, high)` pair is the half-open range `low..high`, as accepted by
+/// `gen_range` prior to `rand` 0.8.
+impl<T: SampleUniform + PartialOrd> SampleRange<T> for (T, T) {
+    #[inline]
+    fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<T, Error> {
+        T::Sampler::sample_single(self.0, self.1, rng)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        !(self.0 < self.1)
+    }
+}
+
+// Open-ended ranges, where a missing bound is the type's minimum or maximum
+macro_rules! impl_sample_range_open {
+    ($($ty:ty: $min:expr, $max:expr;)*) => {$(
+        impl SampleRange<$ty> for RangeFrom<$ty> {
+            #[inline]
+            fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<$ty, Error> {
+                (self.start..=$max).sample_single(rng)
+            }
+
+            #[inline]
+            fn is_empty(&self) -> bool {
+                false
+            }
+        }
+
+        impl SampleRange<$ty> for RangeTo<$ty> {
+            #[inline]
+            fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<$ty, Error> {
+                ($min..self.end).sample_single(rng)
+            }
+
+            #[inline]
+            fn is_empty(&self) -> bool {
+                self.end == $min
+            }
+        }
+
+        impl SampleRange<$ty> for RangeToInclusive<$ty> {
+            #[inline]
+            fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<$ty, Error> {
+                ($min..=self.end).sample_single(rng)
+            }
+
+            #[inline]
+            fn is_empty(&self) -> bool {
+                false
+            }
+        }
+
+        impl SampleRange<$ty> for RangeFull {
+            #[inline]
+            fn sample_single<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<$ty, Error> {
+                ($min..=$max).sample_single(rng)
+            }
+
+            #[inline]
+            fn is_empty(&self) -> bool {
+                false
+            }
+        }
+    )*};
+}
+
+impl_sample_range_open! {
+    i8: i8::MIN, i8::MAX;
+    i16: i16::MIN, i16::MAX;
+    i32: i32::MIN, i32::MAX;
+    i64: i64::MIN, i64::MAX;
+    i128: i128::MIN, i128::MAX;
+    isize: isize::MIN, isize::MAX;
+    u8: u8::MIN, u8::MAX;
+    u16: u16::MIN, u16::MAX;
+    u32: u32::MIN, u32::MAX;
+    u64: u64::MIN, u64::MAX;
+    u128: u128::MIN, u128::MAX;
+    usize: usize::MIN, usize::MAX;
+    char: '\0', char::MAX;
+}
+
+/// Integer type supporting uniform sampling of a single value below a bound.
+///
+/// This is implemented for unsigned integer types, and used by
+/// `Rng::gen_below`. Sampling uses Lemire's nearly divisionless method, which
+/// is unbiased and only computes a modulus when a sample may need to be
+/// rejected. As for [`Uniform`], `u8` and `u16` are sampled via `u32`; `usize`
+/// is sampled via `u32` if the bound fits, making results portable between
+/// 32-bit and 64-bit platforms.
+pub trait SampleBelow: Sized {
+    /// Sample a value uniformly from `0..n`, or return `None` if `n == 0`.
+    fn sample_below<R: RngCore + ?Sized>(n: Self, rng: &mut R) -> Option<Self>;
+}
+
+macro_rules! impl_sample_below {
+    ($($ty:ty: $sample_ty:ty),*) => {$(
+        impl SampleBelow for $ty {
+            #[inline]
+            fn sample_below<R: RngCore + ?Sized>(n: $ty, rng: &mut R) -> Option<$ty> {
+                if n == 0 {
+                    return None;
+                }
+                let n = n as $sample_ty;
+                let (mut hi, mut lo) = rng.gen::<$sample_ty>().wmul(n);
+                // The threshold is less than `n`, hence only needed if `lo < n`
+                if lo < n {
+                    let thresh = n.wrapping_neg() % n;
+                    while lo < thresh {
+                        let (new_hi, new_lo) = rng.gen::<$sample_ty>().wmul(n);
+                        hi = new_hi;
+                        lo = new_lo;
+                    }
+                }
+                Some(hi as $ty)
+            }
+        }
+    )*};
+}
+
+impl_sample_below! { u8: u32, u16: u32, u32: u32, u64: u64, u128: u128 }
+
+impl SampleBelow for usize {
+    #[inline]
+    fn sample_below<R: RngCore + ?Sized>(n: usize, rng: &mut R) -> Option<usize> {
+        if n <= u32::MAX as usize {
+            u32::sample_below(n as u32, rng).map(|x| x as usize)
+        } else {
+            u64::sample_below(n as u64, rng).map(|x| x as usize)
+        }
+    }
+}
+
+/// Integer range supporting sampling as part of a batch.
+///
+/// This is implemented for `Range` and `RangeInclusive` of integer types of
+/// at most 64 bits. Tuples of such ranges implement [`SampleRanges`].
+pub trait BatchRange {
+    /// The type of values in the range.
+    type Output;
+
+    /// Returns the number of values in the range, which is 0 if empty.
+    fn size(&self) -> u128;
+
+    /// Returns the value of the range at `offset`, where `offset < size`.
+    fn value_at(&self, offset: u64) -> Self::Output;
+}
+
+macro_rules! impl_batch_range {
+    ($($ty:ty: $uty:ty),*) => {$(
+        impl BatchRange for Range<$ty> {
+            type Output = $ty;
+
+            #[inline]
+            fn size(&self) -> u128 {
+                if self.start < self.end {
+                    self.end.wrapping_sub(self.start) as $uty as u128
+                } else {
+                    0
+                }
+            }
+
+            #[inline]
+            fn value_at(&self, offset: u64) -> $ty {
+                self.start.wrapping_add(offset as $ty)
+            }
+        }
+
+        impl BatchRange for RangeInclusive<$ty> {
+            type Output = $ty;
+
+            #[inline]
+            fn size(&self) -> u128 {
+                if self.start() <= self.end() {
+                    self.end().wrapping_sub(*self.start()) as $uty as u128 + 1
+                } else {
+                    0
+                }
+            }
+
+            #[inline]
+            fn value_at(&self, offset: u64) -> $ty {
+                self.start().wrapping_add(offset as $ty)
+            }
+        }
+    )*};
+}
+
+impl_batch_range! {
+    i8: u8, i16: u16, i32: u32, i64: u64, isize: usize,
+    u8: u8, u16: u16, u32: u32, u64: u64, usize: usize
+}
+
+/// Tuple of ranges supporting sampling a value from each range at once.
+///
+/// This is implemented for tuples of up to six [`BatchRange`]s, possibly of
+/// different types, and used by `Rng::gen_ranges`.
+///
+/// If the product of the range sizes is less than 2<sup>64</sup>, all
+/// values are derived from a single `u64` (rejecting it with probability
+/// less than `product / 2^64`), via successive widening multiplications as
+/// in Lemire's method. Otherwise each value is sampled separately. Sampling
+/// is unbiased in either case.
+pub trait SampleRanges {
+    /// The tuple of sampled values.
+    type Output;
+
+    /// Sample a value from each range.
+    fn sample_ranges<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<Self::Output, Error>;
+
+    /// Check whether any of the ranges is empty.
+    fn is_empty(&self) -> bool;
+}
+
+/// Sample an offset below each (non-zero) size.
+fn sample_offsets<R: RngCore + ?Sized, const K: usize>(rng: &mut R, sizes: &[u128; K]) -> [u64; K] {
+    let mut offsets = [0u64; K];
+    let product = sizes
+        .iter()
+        .try_fold(1u128, |p, &n| p.checked_mul(n))
+        .filter(|&p| p <= u128::from(u64::MAX));
+    if let Some(product) = product {
+        let product = product as u64;
+        loop {
+            let mut lo = rng.next_u64();
+            for (offset, &n) in offsets.iter_mut().zip(sizes.iter()) {
+                let (hi, new_lo) = lo.wmul(n as u64);
+                *offset = hi;
+                lo = new_lo;
+            }
+            // `lo` is now the low word of the first `u64` times `product`
+            if lo >= product || lo >= product.wrapping_neg() % product {
+                break;
+            }
+        }
+    } else {
+        for (offset, &n) in offsets.iter_mut().zip(sizes.iter()) {
+            *offset = match u64::try_from(n) {
+                Ok(n) => u64::sample_below(n, rng).unwrap(),
+                Err(_) => rng.next_u64(),
+            };
+        }
+    }
+    offsets
+}
+
+macro_rules! impl_sample_ranges {
+    ($($B:ident $i:tt),+) => {
+        impl<$($B: BatchRange),+> SampleRanges for ($($B,)+) {
+            type Output = ($($B::Output,)+);
+
+            #[inline]
+            fn sample_ranges<R: RngCore + ?Sized>(self, rng: &mut R) -> Result<Self::Output, Error> {
+                let sizes = [$(self.$i.size()),+];
+                if sizes.contains(&0) {
+                    return Err(Error::EmptyRange);
+                }
+                let offsets = sample_offsets(rng, &sizes);
+                Ok(($(self.$i.value_at(offsets[$i]),)+))
+            }
+
+            #[inline]
+            fn is_empty(&self) -> bool {
+                $(self.$i.size() == 0)||+
+            }
+        }
+    };
+}
+
+impl_sample_ranges! { B0 0 }
+impl_sample_ranges! { B0 0, B1 1 }
+impl_sample_ranges! { B0 0, B1 1, B2 2 }
+impl_sample_ranges! { B0 0, B1 1, B2 2, B3 3 }
+impl_sample_ranges! { B0 0, B1 1, B2 2, B3 3, B4 4 }
+impl_sample_ranges! { B0 0, B1 1, B2 2, B3 3, B4 4, B5 5 }
+
+/// Set of characters supporting sampling of a single character.
+///
+/// Any type implementing this trait can be used to specify the characters
+/// sampled by `Rng::gen_char`. Each character is sampled with equal
+/// probability; for a string, each character occurrence is equally likely,
+/// thus duplicate characters are more likely.
+pub trait SampleChars {
+    /// Sample a character, or return `None` if the set is empty.
+    fn sample_char<R: RngCore + ?Sized>(self, rng: &mut R) -> Option<char>;
+}
+
+/// Sample an index of `0..len`, as a `u32` where possible for portability.
+#[inline]
+fn sample_char_index<R: RngCore + ?Sized>(rng: &mut R, len: usize) -> usize {
+    if len <= u32::MAX as usize {
+        rng.gen_range(0..len as u32) as usize
+    } else {
+        rng.gen_range(0..len)
+    }
+}
+
+/// Takes `O(n)` time for a string of `n` characters, without allocation.
+impl SampleChars for &str {
+    fn sample_char<R: RngCore + ?Sized>(self, rng: &mut R) -> Option<char> {
+        match self.chars().count() {
+            0 => None,
+            len => self.chars().nth(sample_char_index(rng, len)),
+        }
+    }
+}
+
+impl SampleChars for &[char] {
+    #[inline]
+    fn sample_char<R: RngCore + ?Sized>(self, rng: &mut R) -> Option<char> {
+        match self.len() {
+            0 => None,
+            len => Some(self[sample_char_index(rng, len)]),
+        }
+    }
+}
+
+impl<const N: usize> SampleChars for &[char; N] {
+    #[inline]
+    fn sample_char<R: RngCore + ?Sized>(self, rng: &mut R) -> Option<char> {
+        self[..].sample_char(rng)
+    }
+}
+
+impl SampleChars for RangeInclusive<char> {
+    #[inline]
+    fn sample_char<R: RngCore + ?Sized>(self, rng: &mut R) -> Option<char> {
+        if self.is_empty() {
+            return None;
+        }
+        self.sample_single(rng).ok()
+    }
+}
+
 
 ////////////////////////////////////////////////////////////////////////////////
 
@@ -1116,11 +1482,57 @@ uniform_float_impl! { f64x8, u64x8, f64, u64, 64 - 52 }
 ///
 /// Unless you are implementing [`UniformSampler`] for your own types, this type
 /// should not be used directly, use [`Uniform`] instead.
+///
+/// By default samples have nanosecond resolution. A coarser resolution may be
+/// selected with [`UniformDuration::new_with_resolution`] or
+/// [`UniformDuration::new_inclusive_with_resolution`], in which case all
+/// samples are whole multiples of the chosen unit.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct UniformDuration {
     mode: UniformDurationMode,
     offset: u32,
+    #[cfg_attr(feature = "serde1", serde(default))]
+    resolution: DurationResolution,
+}
+
+/// The resolution of samples produced by [`UniformDuration`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum DurationResolution {
+    /// Whole seconds
+    Secs,
+    /// Whole milliseconds
+    Millis,
+    /// Whole microseconds
+    Micros,
+    /// Whole nanoseconds (the default)
+    Nanos,
+}
+
+impl Default for DurationResolution {
+    fn default() -> Self {
+        DurationResolution::Nanos
+    }
+}
+
+impl DurationResolution {
+    /// Length of one unit in nanoseconds
+    #[inline]
+    fn unit_nanos(self) -> u32 {
+        match self {
+            DurationResolution::Secs => 1_000_000_000,
+            DurationResolution::Millis => 1_000_000,
+            DurationResolution::Micros => 1_000,
+            DurationResolution::Nanos => 1,
+        }
+    }
+
+    /// Number of units in one second
+    #[inline]
+    fn units_per_sec(self) -> u32 {
+        1_000_000_000 / self.unit_nanos()
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -1128,14 +1540,17 @@ pub struct UniformDuration {
 enum UniformDurationMode {
     Small {
         secs: u64,
-        nanos: Uniform<u32>,
+        #[cfg_attr(feature = "serde1", serde(alias = "nanos"))]
+        units: Uniform<u32>,
     },
     Medium {
-        nanos: Uniform<u64>,
+        #[cfg_attr(feature = "serde1", serde(alias = "nanos"))]
+        units: Uniform<u64>,
     },
     Large {
         max_secs: u64,
-        max_nanos: u32,
+        #[cfg_attr(feature = "serde1", serde(alias = "max_nanos"))]
+        max_units: u32,
         secs: Uniform<u64>,
     },
 }
@@ -1144,28 +1559,36 @@ impl SampleUniform for Duration {
     type Sampler = UniformDuration;
 }
 
-impl UniformSampler for UniformDuration {
-    type X = Duration;
-
-    #[inline]
-    fn new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, Error>
+impl UniformDuration {
+    /// Construct a sampler over `[low, high)` producing only whole multiples
+    /// of `resolution`.
+    ///
+    /// Fails with [`Error::EmptyRange`] if no such multiple lies in the range.
+    pub fn new_with_resolution<B1, B2>(
+        low_b: B1, high_b: B2, resolution: DurationResolution,
+    ) -> Result<Self, Error>
     where
-        B1: SampleBorrow<Self::X> + Sized,
-        B2: SampleBorrow<Self::X> + Sized,
+        B1: SampleBorrow<Duration> + Sized,
+        B2: SampleBorrow<Duration> + Sized,
     {
         let low = *low_b.borrow();
         let high = *high_b.borrow();
         if !(low < high) {
             return Err(Error::EmptyRange);
         }
-        UniformDuration::new_inclusive(low, high - Duration::new(0, 1))
+        UniformDuration::new_inclusive_with_resolution(low, high - Duration::new(0, 1), resolution)
     }
 
-    #[inline]
-    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, Error>
+    /// Construct a sampler over `[low, high]` producing only whole multiples
+    /// of `resolution`.
+    ///
+    /// Fails with [`Error::EmptyRange`] if no such multiple lies in the range.
+    pub fn new_inclusive_with_resolution<B1, B2>(
+        low_b: B1, high_b: B2, resolution: DurationResolution,
+    ) -> Result<Self, Error>
     where
-        B1: SampleBorrow<Self::X> + Sized,
-        B2: SampleBorrow<Self::X> + Sized,
+        B1: SampleBorrow<Duration> + Sized,
+        B2: SampleBorrow<Duration> + Sized,
     {
         let low = *low_b.borrow();
         let high = *high_b.borrow();
@@ -1173,71 +1596,113 @@ impl UniformSampler for UniformDuration {
             return Err(Error::EmptyRange);
         }
 
-        let low_s = low.as_secs();
-        let low_n = low.subsec_nanos();
+        let unit = resolution.unit_nanos();
+        let units_per_sec = resolution.units_per_sec();
+
+        // Round low up and high down to whole units
+        let mut low_s = low.as_secs();
+        let mut low_u = (low.subsec_nanos() + unit - 1) / unit;
+        if low_u == units_per_sec {
+            low_s = low_s.checked_add(1).ok_or(Error::EmptyRange)?;
+            low_u = 0;
+        }
         let mut high_s = high.as_secs();
-        let mut high_n = high.subsec_nanos();
+        let mut high_u = high.subsec_nanos() / unit;
+        if (low_s, low_u) > (high_s, high_u) {
+            return Err(Error::EmptyRange);
+        }
 
-        if high_n < low_n {
+        if high_u < low_u {
             high_s -= 1;
-            high_n += 1_000_000_000;
+            high_u += units_per_sec;
         }
 
         let mode = if low_s == high_s {
             UniformDurationMode::Small {
                 secs: low_s,
-                nanos: Uniform::new_inclusive(low_n, high_n)?,
+                units: Uniform::new_inclusive(low_u, high_u)?,
             }
         } else {
             let max = high_s
-                .checked_mul(1_000_000_000)
-                .and_then(|n| n.checked_add(u64::from(high_n)));
+                .checked_mul(u64::from(units_per_sec))
+                .and_then(|n| n.checked_add(u64::from(high_u)));
 
             if let Some(higher_bound) = max {
-                let lower_bound = low_s * 1_000_000_000 + u64::from(low_n);
+                let lower_bound = low_s * u64::from(units_per_sec) + u64::from(low_u);
                 UniformDurationMode::Medium {
-                    nanos: Uniform::new_inclusive(lower_bound, higher_bound)?,
+                    units: Uniform::new_inclusive(lower_bound, higher_bound)?,
                 }
             } else {
-                // An offset is applied to simplify generation of nanoseconds
-                let max_nanos = high_n - low_n;
+                // An offset is applied to simplify generation of sub-second units
+                let max_units = high_u - low_u;
                 UniformDurationMode::Large {
                     max_secs: high_s,
-                    max_nanos,
+                    max_units,
                     secs: Uniform::new_inclusive(low_s, high_s)?,
                 }
             }
         };
         Ok(UniformDuration {
             mode,
-            offset: low_n,
+            offset: low_u,
+            resolution,
         })
     }
 
+    /// The resolution of samples
+    pub fn resolution(&self) -> DurationResolution {
+        self.resolution
+    }
+}
+
+impl UniformSampler for UniformDuration {
+    type X = Duration;
+
+    #[inline]
+    fn new<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        UniformDuration::new_with_resolution(low_b, high_b, DurationResolution::Nanos)
+    }
+
+    #[inline]
+    fn new_inclusive<B1, B2>(low_b: B1, high_b: B2) -> Result<Self, Error>
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        UniformDuration::new_inclusive_with_resolution(low_b, high_b, DurationResolution::Nanos)
+    }
+
     #[inline]
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Duration {
+        let unit = self.resolution.unit_nanos();
+        let units_per_sec = self.resolution.units_per_sec();
         match self.mode {
-            UniformDurationMode::Small { secs, nanos } => {
-                let n = nanos.sample(rng);
-                Duration::new(secs, n)
+            UniformDurationMode::Small { secs, units } => {
+                let n = units.sample(rng);
+                Duration::new(secs, n * unit)
             }
-            UniformDurationMode::Medium { nanos } => {
-                let nanos = nanos.sample(rng);
-                Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32)
+            UniformDurationMode::Medium { units } => {
+                let units = units.sample(rng);
+                let secs = units / u64::from(units_per_sec);
+                let n = (units % u64::from(units_per_sec)) as u32;
+                Duration::new(secs, n * unit)
             }
             UniformDurationMode::Large {
                 max_secs,
-                max_nanos,
+                max_units,
                 secs,
             } => {
-                // constant folding means this is at least as fast as `Rng::sample(Range)`
-                let nano_range = Uniform::new(0, 1_000_000_000).unwrap();
+                let unit_range = Uniform::new(0, units_per_sec).unwrap();
                 loop {
                     let s = secs.sample(rng);
-                    let n = nano_range.sample(rng);
-                    if !(s == max_secs && n > max_nanos) {
+                    let n = unit_range.sample(rng);
+                    if !(s == max_secs && n > max_units) {
                         let sum = n + self.offset;
-                        break Duration::new(s, sum);
+                        break Duration::new(s, sum * unit);
                     }
                 }
             }
@@ -1256,6 +1721,28 @@ mod tests {
         let distr = UniformDuration::new(Duration::from_secs(10), Duration::from_secs(60)).unwrap();
         let de_distr: UniformDuration = bincode::deserialize(&bincode::serialize(&distr).unwrap()).unwrap();
         assert_eq!(distr, de_distr);
+
+        // Data serialized before `resolution` was added, with nanosecond units
+        let ranges = [
+            (Duration::new(10, 0), Duration::new(10, 500)),
+            (Duration::from_secs(10), Duration::from_secs(60)),
+            (Duration::from_secs(1), Duration::new(u64::MAX, 999_999_999)),
+        ];
+        for &(low, high) in &ranges {
+            let distr = UniformDuration::new(low, high).unwrap();
+            let mut value = serde_json::to_value(distr).unwrap();
+            value.as_object_mut().unwrap().remove("resolution").unwrap();
+            for mode in value["mode"].as_object_mut().unwrap().values_mut() {
+                let fields = mode.as_object_mut().unwrap();
+                for &(new, old) in &[("units", "nanos"), ("max_units", "max_nanos")] {
+                    if let Some(field) = fields.remove(new) {
+                        fields.insert(old.into(), field);
+                    }
+                }
+            }
+            let de_distr: UniformDuration = serde_json::from_value(value).unwrap();
+            assert_eq!(distr, de_distr);
+        }
     }
 
     #[test]
@@ -1619,6 +2106,59 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_duration_resolution() {
+        let mut rng = crate::test::rng(254);
+
+        let v = &[
+            (Duration::new(10, 50000), Duration::new(100, 1234)),
+            (Duration::new(0, 100), Duration::new(1, 50)),
+            (Duration::new(3, 999_999_999), Duration::new(5, 1)),
+            (
+                Duration::new(0, 0),
+                Duration::new(u64::MAX, 999_999_999),
+            ),
+        ];
+        let resolutions = [
+            DurationResolution::Secs,
+            DurationResolution::Millis,
+            DurationResolution::Micros,
+            DurationResolution::Nanos,
+        ];
+        for &(low, high) in v.iter() {
+            for &res in resolutions.iter() {
+                let distr = UniformDuration::new_with_resolution(low, high, res).unwrap();
+                assert_eq!(distr.resolution(), res);
+                for _ in 0..1000 {
+                    let v = distr.sample(&mut rng);
+                    assert!(low <= v && v < high);
+                    assert_eq!(v.subsec_nanos() % res.unit_nanos(), 0);
+                }
+            }
+        }
+
+        let distr = UniformDuration::new_inclusive_with_resolution(
+            Duration::new(2, 0), Duration::new(2, 0), DurationResolution::Secs,
+        ).unwrap();
+        assert_eq!(distr.sample(&mut rng), Duration::new(2, 0));
+
+        // No whole second lies in these ranges
+        assert_eq!(
+            UniformDuration::new_with_resolution(
+                Duration::new(1, 1), Duration::new(2, 0), DurationResolution::Secs,
+            ),
+            Err(Error::EmptyRange)
+        );
+        assert_eq!(
+            UniformDuration::new_inclusive_with_resolution(
+                Duration::new(u64::MAX, 1), Duration::new(u64::MAX, 999_999_999),
+                DurationResolution::Secs,
+            ),
+            Err(Error::EmptyRange)
+        );
+    }
+
     #[test]
     fn test_custom_uniform() {
         use crate::distributions::uniform::{
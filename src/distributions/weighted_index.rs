@@ -9,12 +9,13 @@
 //! Weighted index sampling
 
 use crate::distributions::uniform::{SampleBorrow, SampleUniform, UniformSampler};
-use crate::distributions::Distribution;
+use crate::distributions::{Distribution, WeightedError};
 use crate::Rng;
-use core::cmp::PartialOrd;
+use core::cmp::{Ordering, PartialOrd};
 use core::fmt;
 
 // Note that this whole module is only imported if feature="alloc" is enabled.
+use alloc::vec;
 use alloc::vec::Vec;
 
 #[cfg(feature = "serde1")]
@@ -132,6 +133,88 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
         })
     }
 
+    /// Creates a new a `WeightedIndex` [`Distribution`] using the values
+    /// in `weights`, using compensated summation to compute the cumulative
+    /// weights.
+    ///
+    /// With floating-point weights, [`WeightedIndex::new`] accumulates
+    /// rounding errors in the cumulative weights, which may significantly skew
+    /// the distribution of the last items when constructing from many small
+    /// weights (especially with `f32`). This constructor uses Kahan
+    /// summation to keep the cumulative weights and the total weight
+    /// accurate, at the cost of slower construction. Sampling performance is
+    /// unaffected.
+    ///
+    /// For integer weights the result is identical to [`WeightedIndex::new`].
+    ///
+    /// Returns an error if the iterator is empty, if any weight is `< 0`, or
+    /// if its total value is 0.
+    pub fn new_compensated<I>(weights: I) -> Result<WeightedIndex<X>, WeightedError>
+    where
+        I: IntoIterator,
+        I::Item: SampleBorrow<X>,
+        X: for<'a> ::core::ops::AddAssign<&'a X>
+            + for<'a> ::core::ops::SubAssign<&'a X>
+            + Clone
+            + Default,
+    {
+        let mut iter = weights.into_iter();
+        let first: X = iter.next().ok_or(WeightedError::NoItem)?.borrow().clone();
+
+        let zero = <X as Default>::default();
+        if !(first >= zero) {
+            return Err(WeightedError::InvalidWeight);
+        }
+
+        // The running sum, its accumulated rounding error (which is fed back
+        // into the next addition), and the maximal sum so far
+        let mut sum = first;
+        let mut compensation = zero.clone();
+        let mut total_weight = sum.clone();
+
+        let mut weights = Vec::<X>::with_capacity(iter.size_hint().0);
+        for w in iter {
+            let w = w.borrow();
+            if !(*w >= zero) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            weights.push(total_weight.clone());
+            if *w == zero {
+                // Applying the compensation here could make a zero weight
+                // selectable.
+                continue;
+            }
+
+            // y = w - compensation; t = sum + y; compensation = (t - sum) - y
+            // For integer types, `compensation` is always zero, thus no
+            // intermediate result is negative.
+            let mut y = w.clone();
+            y -= &compensation;
+            let mut t = sum.clone();
+            t += &y;
+            compensation = t.clone();
+            compensation -= &sum;
+            compensation -= &y;
+            sum = t;
+
+            // Guard against the sum decreasing due to the compensation.
+            if sum > total_weight {
+                total_weight = sum.clone();
+            }
+        }
+
+        if total_weight == zero {
+            return Err(WeightedError::AllWeightsZero);
+        }
+        let distr = X::Sampler::new(zero, total_weight.clone()).unwrap();
+
+        Ok(WeightedIndex {
+            cumulative_weights: weights,
+            total_weight,
+            weight_distribution: distr,
+        })
+    }
+
     /// Update a subset of weights, without changing the number of weights.
     ///
     /// `new_weights` must be sorted by the index.
@@ -224,6 +307,164 @@ impl<X: SampleUniform + PartialOrd> WeightedIndex<X> {
 
         Ok(())
     }
+
+    /// Draw `amount` independent samples at once.
+    ///
+    /// The result is identical to calling [`Distribution::sample`] `amount`
+    /// times with the same RNG. However, the random keys are sorted so that
+    /// all lookups happen in a single forward pass over the cumulative
+    /// weights: each lookup is an exponential search starting from the
+    /// previous result. Given `n` weights, lookups thus take
+    /// `O(amount log(n / amount))` time in total (plus `O(amount log amount)`
+    /// for sorting), which is significantly faster than repeated binary
+    /// searches when drawing many samples from the same distribution.
+    ///
+    /// This allocates two vectors of length `amount`.
+    pub fn sample_multiple<R: Rng + ?Sized>(&self, rng: &mut R, amount: usize) -> Vec<usize> {
+        let mut keys: Vec<(X, usize)> = (0..amount)
+            .map(|i| (self.weight_distribution.sample(rng), i))
+            .collect();
+        keys.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+        let mut result = vec![0; amount];
+        let mut index = 0;
+        for (chosen_weight, i) in keys {
+            // Find the first item which has a weight *higher* than the chosen
+            // weight, starting from the previous result: double the step
+            // while the cumulative weight is not higher, then search within
+            // the last step.
+            let rest = &self.cumulative_weights[index..];
+            let mut step = 1;
+            while step <= rest.len() && rest[step - 1] <= chosen_weight {
+                step *= 2;
+            }
+            let (low, high) = (step / 2, step.min(rest.len()));
+            index += low + rest[low..high].partition_point(|w| w <= &chosen_weight);
+            result[i] = index;
+        }
+        result
+    }
+}
+
+impl<X> WeightedIndex<X>
+where X: SampleUniform
+        + PartialOrd
+        + for<'a> ::core::ops::SubAssign<&'a X>
+        + Clone
+        + Default
+{
+    /// Returns the number of weights (i.e. the number of items).
+    ///
+    /// This is never zero, since a `WeightedIndex` cannot be constructed
+    /// without items.
+    #[allow(clippy::len_without_is_empty)]
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cumulative_weights.len() + 1
+    }
+
+    /// Returns the weight at `index`, or `None` if `index` is out of bounds.
+    ///
+    /// The weight is computed from the cumulative weights; with
+    /// floating-point weights it may not be exactly equal to the original
+    /// weight due to rounding.
+    pub fn weight(&self, index: usize) -> Option<X> {
+        let mut weight = match index.cmp(&self.cumulative_weights.len()) {
+            Ordering::Less => self.cumulative_weights[index].clone(),
+            Ordering::Equal => self.total_weight.clone(),
+            Ordering::Greater => return None,
+        };
+        if index > 0 {
+            weight -= &self.cumulative_weights[index - 1];
+        }
+        Some(weight)
+    }
+
+    /// Returns the sum of all weights.
+    #[inline]
+    pub fn total_weight(&self) -> X {
+        self.total_weight.clone()
+    }
+
+    /// Returns an iterator over `(index, weight)` pairs of all items.
+    ///
+    /// See [`WeightedIndex::weight`] regarding the accuracy of weights.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::distributions::WeightedIndex;
+    ///
+    /// let weights = [1, 2, 3];
+    /// let dist = WeightedIndex::new(&weights).unwrap();
+    /// assert!(dist.iter().eq(weights.iter().cloned().enumerate()));
+    /// ```
+    pub fn iter(&self) -> WeightedIndexIter<'_, X> {
+        WeightedIndexIter {
+            weighted_index: self,
+            index: 0,
+        }
+    }
+}
+
+/// An iterator over the `(index, weight)` pairs of a [`WeightedIndex`].
+///
+/// This `struct` is created by the [`WeightedIndex::iter`] method.
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct WeightedIndexIter<'a, X: SampleUniform + PartialOrd> {
+    weighted_index: &'a WeightedIndex<X>,
+    index: usize,
+}
+
+impl<'a, X: SampleUniform + PartialOrd> fmt::Debug for WeightedIndexIter<'a, X>
+where WeightedIndex<X>: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WeightedIndexIter")
+            .field("weighted_index", self.weighted_index)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+impl<'a, X: SampleUniform + PartialOrd> Clone for WeightedIndexIter<'a, X> {
+    fn clone(&self) -> Self {
+        WeightedIndexIter {
+            weighted_index: self.weighted_index,
+            index: self.index,
+        }
+    }
+}
+
+impl<'a, X> Iterator for WeightedIndexIter<'a, X>
+where X: SampleUniform
+        + PartialOrd
+        + for<'b> ::core::ops::SubAssign<&'b X>
+        + Clone
+        + Default
+{
+    type Item = (usize, X);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let weight = self.weighted_index.weight(self.index)?;
+        let index = self.index;
+        self.index += 1;
+        Some((index, weight))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.weighted_index.len().saturating_sub(self.index);
+        (len, Some(len))
+    }
+}
+
+impl<'a, X> ExactSizeIterator for WeightedIndexIter<'a, X>
+where X: SampleUniform
+        + PartialOrd
+        + for<'b> ::core::ops::SubAssign<&'b X>
+        + Clone
+        + Default
+{
 }
 
 impl<X> Distribution<usize> for WeightedIndex<X>
@@ -386,6 +627,102 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_new_compensated() {
+        let weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 0];
+        assert_eq!(
+            WeightedIndex::new_compensated(weights).unwrap(),
+            WeightedIndex::new(weights).unwrap()
+        );
+        assert_eq!(
+            WeightedIndex::new_compensated(&[10][0..0]).unwrap_err(),
+            WeightedError::NoItem
+        );
+        assert_eq!(
+            WeightedIndex::new_compensated([0.0, 0.0]).unwrap_err(),
+            WeightedError::AllWeightsZero
+        );
+        assert_eq!(
+            WeightedIndex::new_compensated([1.0, f64::NAN]).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+
+        // Zero weights are never picked
+        let distr = WeightedIndex::new_compensated([1e-8f32, 1e8, 0.0, 1e-8, 0.0]).unwrap();
+        let mut rng = crate::test::rng(703);
+        for _ in 0..1000 {
+            let i = distr.sample(&mut rng);
+            assert!(i != 2 && i != 4);
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_new_compensated_many_weights() {
+        // Many small weights: the naive sum drifts significantly
+        const N: usize = 1_000_000;
+        let weights = vec![0.1f32; N];
+        let exact = 0.1f32 as f64 * N as f64;
+        let naive = WeightedIndex::new(&weights).unwrap();
+        let compensated = WeightedIndex::new_compensated(&weights).unwrap();
+        assert!((naive.total_weight as f64 - exact).abs() > 100.0);
+        assert!((compensated.total_weight as f64 - exact).abs() < 0.1);
+        let mid = compensated.cumulative_weights[N / 2 - 1] as f64;
+        assert!((mid - exact / 2.0).abs() < 0.1);
+        assert!(compensated.cumulative_weights.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 0];
+        let distr = WeightedIndex::new(weights).unwrap();
+        assert_eq!(distr.len(), weights.len());
+        assert_eq!(distr.total_weight(), weights.iter().sum::<u32>());
+        for (i, &w) in weights.iter().enumerate() {
+            assert_eq!(distr.weight(i), Some(w));
+        }
+        assert_eq!(distr.weight(weights.len()), None);
+
+        let mut iter = distr.iter();
+        assert_eq!(iter.len(), weights.len());
+        iter.next();
+        assert_eq!(iter.len(), weights.len() - 1);
+        assert!(distr.iter().eq(weights.iter().cloned().enumerate()));
+
+        let distr = WeightedIndex::new([0.5f64]).unwrap();
+        assert_eq!(distr.len(), 1);
+        assert_eq!(distr.iter().collect::<Vec<_>>(), [(0, 0.5)]);
+    }
+
+    #[test]
+    fn test_sample_multiple() {
+        let weights = [1u32, 2, 3, 0, 5, 6, 7, 1, 2, 3, 4, 5, 6, 7];
+        let distr = WeightedIndex::new(weights).unwrap();
+        let mut rng1 = crate::test::rng(702);
+        let mut rng2 = crate::test::rng(702);
+        for &amount in &[0, 1, 10, 1000] {
+            let result = distr.sample_multiple(&mut rng1, amount);
+            assert_eq!(result.len(), amount);
+            for &i in &result {
+                assert_eq!(i, distr.sample(&mut rng2));
+                assert_ne!(weights[i], 0);
+            }
+        }
+
+        // Few and many samples of many weights
+        let weights: Vec<u32> = (0..1000).map(|i| i % 7).collect();
+        let distr = WeightedIndex::new(&weights).unwrap();
+        for &amount in &[3, 5000] {
+            for i in distr.sample_multiple(&mut rng1, amount) {
+                assert_eq!(i, distr.sample(&mut rng2));
+            }
+        }
+
+        let distr = WeightedIndex::new([0.5f64, 0.0, 0.25, 0.25]).unwrap();
+        let result = distr.sample_multiple(&mut rng1, 1000);
+        assert!(result.iter().all(|&i| i != 1 && i < 4));
+    }
+
     #[test]
     fn value_stability() {
         fn test_samples<X: SampleUniform + PartialOrd, I>(
@@ -421,35 +758,3 @@ mod test {
         assert_eq!(WeightedIndex::new(&[1, 2]), WeightedIndex::new(&[1, 2]));
     }
 }
-
-/// Error type returned from `WeightedIndex::new`.
-#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum WeightedError {
-    /// The provided weight collection contains no items.
-    NoItem,
-
-    /// A weight is either less than zero, greater than the supported maximum,
-    /// NaN, or otherwise invalid.
-    InvalidWeight,
-
-    /// All items in the provided weight collection are zero.
-    AllWeightsZero,
-
-    /// Too many weights are provided (length greater than `u32::MAX`)
-    TooMany,
-}
-
-#[cfg(feature = "std")]
-impl std::error::Error for WeightedError {}
-
-impl fmt::Display for WeightedError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.write_str(match *self {
-            WeightedError::NoItem => "No weights provided in distribution",
-            WeightedError::InvalidWeight => "A weight is invalid in distribution",
-            WeightedError::AllWeightsZero => "All weights are zero in distribution",
-            WeightedError::TooMany => "Too many weights (hit u32::MAX) in distribution",
-        })
-    }
-}
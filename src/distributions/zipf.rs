@@ -0,0 +1,226 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Zipf distribution.
+
+use crate::distributions::Distribution;
+use crate::Rng;
+use core::fmt;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// Samples integers in `1..=n` according to the Zipf distribution.
+///
+/// The samples follow Zipf's law: the probability of sampling `k` is
+/// proportional to `k^(-s)`, i.e. inversely proportional to a power of its
+/// rank (with exponent `s`). For `s = 0`, this is a uniform distribution.
+///
+/// This is typically used to generate skewed access patterns, e.g. for cache
+/// or database benchmarks. For a float-valued Zipf distribution and the
+/// related Zeta distribution, see [`rand_distr`].
+///
+/// # Example
+///
+/// ```
+/// use rand::distributions::{Distribution, Zipf};
+///
+/// let zipf = Zipf::new(1000, 1.1).unwrap();
+/// let key = zipf.sample(&mut rand::thread_rng());
+/// println!("accessing key {}", key);
+/// ```
+///
+/// # Implementation details
+///
+/// Sampling uses the rejection-inversion method of W. Hörmann and
+/// G. Derflinger, *Rejection-inversion to generate variates from monotone
+/// discrete distributions* (1996). Construction is `O(1)`, and the expected
+/// number of iterations per sample is bounded by a small constant for all
+/// parameters.
+///
+/// [`rand_distr`]: https://crates.io/crates/rand_distr
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Zipf {
+    n: u64,
+    s: f64,
+    h_integral_x1: f64,
+    h_integral_n: f64,
+    threshold: f64,
+}
+
+/// Error type returned from `Zipf::new`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ZipfError {
+    /// `s < 0`, infinite or `nan`.
+    STooSmall,
+    /// `n < 1`.
+    NTooSmall,
+}
+
+impl fmt::Display for ZipfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ZipfError::STooSmall => "s < 0 or is not finite in Zipf distribution",
+            ZipfError::NTooSmall => "n < 1 in Zipf distribution",
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ZipfError {}
+
+impl Zipf {
+    /// Construct a new `Zipf` distribution over `1..=n` with exponent `s`.
+    #[inline]
+    pub fn new(n: u64, s: f64) -> Result<Zipf, ZipfError> {
+        if !(s >= 0.0 && s.is_finite()) {
+            return Err(ZipfError::STooSmall);
+        }
+        if n < 1 {
+            return Err(ZipfError::NTooSmall);
+        }
+        let mut zipf = Zipf {
+            n,
+            s,
+            h_integral_x1: 0.0,
+            h_integral_n: 0.0,
+            threshold: 0.0,
+        };
+        zipf.h_integral_x1 = zipf.h_integral(1.5) - 1.0;
+        zipf.h_integral_n = zipf.h_integral(n as f64 + 0.5);
+        zipf.threshold = 2.0 - zipf.h_integral_inv(zipf.h_integral(2.5) - zipf.h(2.0));
+        Ok(zipf)
+    }
+
+    /// `h(x) = x^(-s)`, the (unnormalised) probability of `x`
+    #[inline]
+    fn h(&self, x: f64) -> f64 {
+        (-self.s * x.ln()).exp()
+    }
+
+    /// `H(x)`, an integral of `h` such that `H(1) = 0`
+    #[inline]
+    fn h_integral(&self, x: f64) -> f64 {
+        let log_x = x.ln();
+        helper2((1.0 - self.s) * log_x) * log_x
+    }
+
+    /// The inverse of `H`
+    #[inline]
+    fn h_integral_inv(&self, x: f64) -> f64 {
+        let mut t = x * (1.0 - self.s);
+        if t < -1.0 {
+            // Limit value to the range [-1, +inf); t may be slightly smaller
+            // than -1 due to rounding
+            t = -1.0;
+        }
+        (helper1(t) * x).exp()
+    }
+}
+
+/// `ln(1 + x) / x`, accurate near `x = 0`
+#[inline]
+fn helper1(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.ln_1p() / x
+    } else {
+        1.0 - x * (0.5 - x * (1.0 / 3.0 - 0.25 * x))
+    }
+}
+
+/// `(exp(x) - 1) / x`, accurate near `x = 0`
+#[inline]
+fn helper2(x: f64) -> f64 {
+    if x.abs() > 1e-8 {
+        x.exp_m1() / x
+    } else {
+        1.0 + x * 0.5 * (1.0 + x * (1.0 / 3.0) * (1.0 + 0.25 * x))
+    }
+}
+
+impl Distribution<u64> for Zipf {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        loop {
+            let p: f64 = rng.gen();
+            let u = self.h_integral_n + p * (self.h_integral_x1 - self.h_integral_n);
+            let x = self.h_integral_inv(u);
+            // Round to the nearest integer, clamping to the valid range to
+            // guard against rounding errors
+            let k = (x + 0.5) as u64;
+            let k = k.max(1).min(self.n);
+            let kf = k as f64;
+
+            if kf - x <= self.threshold || u >= self.h_integral(kf + 0.5) - self.h(kf) {
+                return k;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_zipf_invalid() {
+        assert_eq!(Zipf::new(10, -1.0), Err(ZipfError::STooSmall));
+        assert_eq!(Zipf::new(10, f64::NAN), Err(ZipfError::STooSmall));
+        assert_eq!(Zipf::new(10, f64::INFINITY), Err(ZipfError::STooSmall));
+        assert_eq!(Zipf::new(0, 1.0), Err(ZipfError::NTooSmall));
+    }
+
+    #[test]
+    fn test_zipf_range() {
+        let mut rng = crate::test::rng(1);
+        for &(n, s) in &[(1, 1.0), (10, 0.0), (10, 0.5), (10, 1.0), (100, 2.0), (u64::MAX, 1.5)] {
+            let d = Zipf::new(n, s).unwrap();
+            for _ in 0..1000 {
+                let k = d.sample(&mut rng);
+                assert!((1..=n).contains(&k));
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Miri is too slow
+    fn test_zipf_frequencies() {
+        const N: usize = 10;
+        const SAMPLES: usize = 100_000;
+        let s = 1.2;
+        let d = Zipf::new(N as u64, s).unwrap();
+        let mut rng = crate::test::rng(2);
+        let mut counts = [0usize; N];
+        for _ in 0..SAMPLES {
+            counts[d.sample(&mut rng) as usize - 1] += 1;
+        }
+
+        let norm: f64 = (1..=N).map(|k| (k as f64).powf(-s)).sum();
+        for (i, &count) in counts.iter().enumerate() {
+            let expected = ((i + 1) as f64).powf(-s) / norm;
+            let observed = count as f64 / SAMPLES as f64;
+            assert!((observed - expected).abs() < 0.01, "{} vs {}", observed, expected);
+        }
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut rng = crate::test::rng(3);
+        let d = Zipf::new(1000, 1.1).unwrap();
+        let mut buf = [0u64; 8];
+        for x in &mut buf {
+            *x = d.sample(&mut rng);
+        }
+        assert_eq!(buf, [63, 1, 13, 138, 7, 14, 29, 28]);
+    }
+
+    #[test]
+    fn zipf_distributions_can_be_compared() {
+        assert_eq!(Zipf::new(1, 2.0), Zipf::new(1, 2.0));
+    }
+}
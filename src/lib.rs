@@ -91,7 +91,10 @@ macro_rules! error { ($($x:tt)*) => (
 ) }
 
 // Re-exports from rand_core
-pub use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+pub use rand_core::{
+    CryptoRng, CryptoRngCore, Error, JumpableRng, MixEntropy, RngCore, SeedableRng, SplitRng,
+    StreamRng, TryCryptoRng, TryRngCore, UnwrapErr,
+};
 
 // Public modules
 pub mod distributions;
@@ -172,6 +175,36 @@ mod test {
         rand_pcg::Pcg32::new(seed, INC)
     }
 
+    /// Assert that `counts` of outcomes are plausible for a uniform
+    /// distribution, i.e. that each count is within 10% of their mean
+    ///
+    /// For this to hold with high probability, the mean should be at least
+    /// about 1000.
+    #[track_caller]
+    pub fn assert_uniform<I>(counts: I)
+    where
+        I: IntoIterator + Clone,
+        I::Item: core::borrow::Borrow<i32>,
+    {
+        use core::borrow::Borrow;
+
+        let (mut sum, mut n) = (0i64, 0i64);
+        for c in counts.clone() {
+            sum += i64::from(*c.borrow());
+            n += 1;
+        }
+        assert!(n > 0, "no counts");
+        for c in counts {
+            let c = i64::from(*c.borrow());
+            assert!(
+                (c * n - sum).abs() * 10 <= sum,
+                "count {} too far from the mean {}",
+                c,
+                sum as f64 / n as f64
+            );
+        }
+    }
+
     #[test]
     #[cfg(all(feature = "std", feature = "std_rng"))]
     fn test_random() {
@@ -9,8 +9,10 @@
 
 //! [`Rng`] trait
 
-use rand_core::{Error, RngCore};
-use crate::distributions::uniform::{SampleRange, SampleUniform};
+use rand_core::{CryptoRng, Error, RngCore};
+use crate::distributions::uniform::{
+    SampleBelow, SampleChars, SampleRange, SampleRanges, SampleUniform,
+};
 use crate::distributions::{self, Distribution, Standard};
 use core::num::Wrapping;
 use core::{mem, slice};
@@ -69,7 +71,7 @@ pub trait Rng: RngCore {
     /// # Arrays and tuples
     ///
     /// The `rng.gen()` method is able to generate arrays
-    /// and tuples (up to 12 elements), so long as all element types can be
+    /// and tuples (up to 16 elements), so long as all element types can be
     /// generated.
     ///
     /// For arrays of integers, especially for those with small element types
@@ -99,7 +101,13 @@ pub trait Rng: RngCore {
     /// made from the given range. See also the [`Uniform`] distribution
     /// type which may be faster if sampling from the same range repeatedly.
     ///
-    /// Only `gen_range(low..high)` and `gen_range(low..=high)` are supported.
+    /// All range types are supported: `gen_range(low..high)` and
+    /// `gen_range(low..=high)` for any [`SampleUniform`] type, and, for
+    /// integers and `char`, open-ended ranges such as `gen_range(low..)` or
+    /// `gen_range(..=high)`, where a missing bound is the type's minimum or
+    /// maximum value. Borrowed ranges `&(low..high)` and `&(low..=high)`
+    /// avoid cloning the bounds, e.g. of a range stored in a struct, and, as
+    /// a migration aid, a tuple `(low, high)` is equivalent to `low..high`.
     ///
     /// # Panics
     ///
@@ -121,9 +129,19 @@ pub trait Rng: RngCore {
     /// // Inclusive range
     /// let n: u32 = rng.gen_range(0..=10);
     /// println!("{}", n);
+    ///
+    /// // Open-ended range, equivalent to 1..=u64::MAX
+    /// let n: u64 = rng.gen_range(1..);
+    /// println!("{}", n);
+    ///
+    /// // Borrowed range
+    /// let range = 0.5..1.5;
+    /// let x = rng.gen_range(&range);
+    /// println!("{}", x);
     /// ```
     ///
     /// [`Uniform`]: distributions::uniform::Uniform
+    /// [`SampleUniform`]: distributions::uniform::SampleUniform
     fn gen_range<T, R>(&mut self, range: R) -> T
     where
         T: SampleUniform,
@@ -133,29 +151,117 @@ pub trait Rng: RngCore {
         range.sample_single(self).unwrap()
     }
 
-    /// Generate values via an iterator
+    /// Generate one random integer from each of several ranges at once.
+    ///
+    /// `ranges` is a tuple of up to six integer ranges, which may be of
+    /// different types, e.g. `(0..6, 1..=12u64, -5i8..5)`; see
+    /// [`SampleRanges`]. When the ranges are small, all values are derived
+    /// from a single random `u64`, which is faster than separate calls to
+    /// [`Rng::gen_range`]. Note that results differ from those of
+    /// `gen_range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any range is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let (x, y, z) = rng.gen_ranges((0..640u32, 0..480usize, -10..=10i16));
+    /// assert!(x < 640 && y < 480 && (-10..=10).contains(&z));
+    /// ```
+    ///
+    /// [`SampleRanges`]: distributions::uniform::SampleRanges
+    fn gen_ranges<B: SampleRanges>(&mut self, ranges: B) -> B::Output {
+        assert!(!ranges.is_empty(), "cannot sample empty range");
+        ranges.sample_ranges(self).unwrap()
+    }
+
+    /// Generate a random integer uniformly from `0..n`.
+    ///
+    /// This is equivalent to `gen_range(0..n)` for unsigned integer types,
+    /// but is always unbiased and usually faster, using Lemire's method; see
+    /// [`SampleBelow`]. Note that results differ from those of `gen_range`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let die = 1 + rng.gen_below(6u32);
+    /// let items = ["a", "b", "c"];
+    /// println!("{} {}", die, items[rng.gen_below(items.len())]);
+    /// ```
+    ///
+    /// [`SampleBelow`]: distributions::uniform::SampleBelow
+    fn gen_below<T: SampleBelow>(&mut self, n: T) -> T {
+        T::sample_below(n, self).expect("cannot sample empty range")
+    }
+
+    /// Generate a random character from the given set of characters.
+    ///
+    /// The set may be given as a `&str`, a slice or array of `char`, a
+    /// `RangeInclusive<char>` or a [`CharSet`]; see [`SampleChars`]. Each
+    /// character is equally likely. Sampling from a string takes time linear
+    /// in its length; use [`CharSet`] to sample repeatedly, e.g. for
+    /// strings.
     ///
-    /// This is a just a wrapper over [`Rng::sample_iter`] using
-    /// [`distributions::Standard`].
+    /// # Panics
+    ///
+    /// Panics if the set is empty.
     ///
-    /// Note: this method consumes its argument. Use
-    /// `(&mut rng).gen_iter()` to avoid consuming the RNG.
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let vowel = rng.gen_char("aeiou");
+    /// let digit = rng.gen_char('0'..='9');
+    /// let suit = rng.gen_char(&['♠', '♥', '♦', '♣']);
+    /// println!("{} {} {}", vowel, digit, suit);
+    /// ```
+    ///
+    /// [`CharSet`]: distributions::CharSet
+    /// [`SampleChars`]: distributions::uniform::SampleChars
+    fn gen_char<C: SampleChars>(&mut self, charset: C) -> char {
+        charset
+            .sample_char(self)
+            .expect("cannot sample from an empty set of characters")
+    }
+
+    /// Generate an infinite iterator of values via the [`Standard`]
+    /// distribution
+    ///
+    /// The iterator borrows the RNG, which may be used again once the
+    /// iterator is dropped. This is equivalent to
+    /// `(&mut rng).sample_iter(Standard)`; use [`Rng::sample_iter`] for an
+    /// iterator owning the RNG.
+    ///
+    /// [`Standard`]: distributions::Standard
     ///
     /// # Example
     ///
     /// ```
     /// use rand::{rngs::mock::StepRng, Rng};
     ///
-    /// let rng = StepRng::new(1, 1);
+    /// let mut rng = StepRng::new(1, 1);
     /// let v: Vec<i32> = rng.gen_iter().take(5).collect();
     /// assert_eq!(&v, &[1, 2, 3, 4, 5]);
+    /// let bytes: Vec<u8> = rng.gen_iter::<u8>().take(2).collect();
+    /// assert_eq!(&bytes, &[6, 7]);
     /// ```
     #[inline]
-    fn gen_iter<T>(self) -> distributions::DistIter<Standard, Self, T>
-    where
-        Self: Sized,
-        Standard: Distribution<T>,
-    {
+    fn gen_iter<T>(&mut self) -> distributions::DistIter<Standard, &mut Self, T>
+    where Standard: Distribution<T> {
         Standard.sample_iter(self)
     }
 
@@ -177,6 +283,71 @@ pub trait Rng: RngCore {
         distr.sample(self)
     }
 
+    /// Sample a new value, using the given distribution, forwarding errors
+    /// of the RNG.
+    ///
+    /// Random data is generated via [`RngCore::try_fill_bytes`] only, thus
+    /// this is suitable for RNGs reporting errors that way. (Fallible sources
+    /// such as [`OsRng`] implement only [`TryRngCore`], thus are not an
+    /// `Rng`.) If an error occurs, the first error is returned; the
+    /// distribution may still draw further values, but is then given
+    /// non-random data and its result is discarded.
+    ///
+    /// The generated value may differ from that of [`Rng::sample`] with the
+    /// same RNG state, since `next_u32` and `next_u64` are not used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    /// use rand::distributions::Uniform;
+    ///
+    /// # fn try_inner() -> Result<(), rand::Error> {
+    /// let die = Uniform::new_inclusive(1, 6).unwrap();
+    /// let roll: u8 = thread_rng().try_sample(die)?;
+    /// println!("{}", roll);
+    /// # Ok(())
+    /// # }
+    /// # try_inner().unwrap()
+    /// ```
+    ///
+    /// [`TryRngCore`]: rand_core::TryRngCore
+    /// [`OsRng`]: crate::rngs::OsRng
+    fn try_sample<T, D: Distribution<T>>(&mut self, distr: D) -> Result<T, Error> {
+        let mut rng = TryRng { rng: self, error: None, fallback: 0 };
+        let value = distr.sample(&mut rng);
+        match rng.error {
+            Some(error) => Err(error),
+            None => Ok(value),
+        }
+    }
+
+    /// Return a random value supporting the [`Standard`] distribution,
+    /// forwarding errors of the RNG.
+    ///
+    /// This is the fallible equivalent of [`Rng::gen`]; see
+    /// [`Rng::try_sample`] for details.
+    ///
+    /// [`Standard`]: distributions::Standard
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// # fn try_inner() -> Result<(), rand::Error> {
+    /// let key: [u8; 16] = thread_rng().try_gen()?;
+    /// let x: f64 = thread_rng().try_gen()?;
+    /// # Ok(())
+    /// # }
+    /// # try_inner().unwrap()
+    /// ```
+    #[inline]
+    fn try_gen<T>(&mut self) -> Result<T, Error>
+    where Standard: Distribution<T> {
+        self.try_sample(Standard)
+    }
+
     /// Create an iterator that generates values using the given distribution.
     ///
     /// Note: this method consumes its arguments. Use
@@ -268,6 +439,87 @@ pub trait Rng: RngCore {
         dest.try_fill(self)
     }
 
+    /// Fill the first `n_bits` bits of `dest` with random bits, packed into
+    /// bytes with the least significant bit first.
+    ///
+    /// The first `(n_bits + 7) / 8` bytes are written, using one random byte
+    /// per 8 bits; unused high bits of the last written byte are set to zero,
+    /// while any further bytes are left unchanged. This is useful for
+    /// initializing bitmaps and masks.
+    ///
+    /// # Panics
+    ///
+    /// If `n_bits > 8 * dest.len()`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut mask = [0u8; 2];
+    /// thread_rng().fill_bits(&mut mask, 12);
+    /// assert_eq!(mask[1] >> 4, 0);
+    /// ```
+    fn fill_bits(&mut self, dest: &mut [u8], n_bits: usize) {
+        let n_bytes = n_bits / 8 + (n_bits % 8 != 0) as usize;
+        assert!(n_bytes <= dest.len(), "`n_bits` exceeds the length of `dest`");
+        self.fill_bytes(&mut dest[..n_bytes]);
+        if n_bits % 8 != 0 {
+            dest[n_bytes - 1] &= (1 << (n_bits % 8)) - 1;
+        }
+    }
+
+    /// Return `n` random bits as the low bits of a `u64`; the high bits are
+    /// zero.
+    ///
+    /// This uses the most significant bits of one `u32` if `n <= 32` or one
+    /// `u64` otherwise, thus each call consumes a full word. Wrap the RNG in
+    /// a [`BitRng`] to buffer unused bits across calls.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > 64`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let nibble = thread_rng().gen_bits(4);
+    /// assert!(nibble < 16);
+    /// ```
+    ///
+    /// [`BitRng`]: crate::rngs::BitRng
+    fn gen_bits(&mut self, n: u32) -> u64 {
+        assert!(n <= 64, "cannot generate more than 64 bits");
+        match n {
+            0 => 0,
+            1..=32 => u64::from(self.next_u32() >> (32 - n)),
+            _ => self.next_u64() >> (64 - n),
+        }
+    }
+
+    /// Generate a random byte array, e.g. a cryptographic key or nonce.
+    ///
+    /// This is only available for RNGs implementing [`CryptoRng`], and fills
+    /// the array via [`RngCore::fill_bytes`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{rngs::OsRng, thread_rng, Rng, TryRngCore};
+    ///
+    /// let key: [u8; 32] = OsRng.unwrap_err().gen_key();
+    /// let nonce = thread_rng().gen_key::<12>();
+    /// ```
+    #[inline]
+    fn gen_key<const N: usize>(&mut self) -> [u8; N]
+    where Self: CryptoRng {
+        let mut key = [0; N];
+        self.fill_bytes(&mut key);
+        key
+    }
+
     /// Return a bool with a probability `p` of being true.
     ///
     /// See also the [`Bernoulli`] distribution, which may be faster if
@@ -321,13 +573,217 @@ pub trait Rng: RngCore {
         let d = distributions::Bernoulli::from_ratio(numerator, denominator).unwrap();
         self.sample(d)
     }
+
+    /// Return a bool with a probability of exactly `numerator/denominator`
+    /// of being true.
+    ///
+    /// This is like [`gen_ratio`] but accepts 64-bit arguments, e.g.
+    /// `gen_ratio_u64(1, 10_000_000_000)` for an event with a chance of 1 in
+    /// 10 billion. The result is exact, without bias: it is `true` if a value
+    /// sampled via [`gen_below(denominator)`] is less than `numerator`. If
+    /// `numerator == denominator`, the result is `true` without using the
+    /// RNG.
+    ///
+    /// # Panics
+    ///
+    /// If `denominator == 0` or `numerator > denominator`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let events_per_day = 86_400 * 1_000_000u64;
+    /// if rng.gen_ratio_u64(1, events_per_day) {
+    ///     println!("log this event");
+    /// }
+    /// ```
+    ///
+    /// [`gen_ratio`]: Rng::gen_ratio
+    /// [`gen_below(denominator)`]: Rng::gen_below
+    #[inline]
+    fn gen_ratio_u64(&mut self, numerator: u64, denominator: u64) -> bool {
+        assert!(denominator != 0, "denominator must not be zero");
+        assert!(numerator <= denominator, "numerator must not exceed denominator");
+        numerator == denominator || self.gen_below(denominator) < numerator
+    }
+
+    /// Return `1` or `-1` with probability 0.5 each, as any signed integer
+    /// or floating point type.
+    ///
+    /// This is a shorthand for [`distributions::Sign`], which uses a single
+    /// random bit without branching.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// let dx: i32 = rng.gen_sign();
+    /// let x = 0.5 * rng.gen_sign::<f64>();
+    /// ```
+    #[inline]
+    fn gen_sign<T>(&mut self) -> T
+    where distributions::Sign: Distribution<T> {
+        distributions::Sign.sample(self)
+    }
+
+    /// Return a random `f64` in the closed interval `[-1, 1]`.
+    ///
+    /// This is a shorthand for [`distributions::SignedUnit`], which takes
+    /// the magnitude and the sign from a single `u64`. The distribution is
+    /// exactly symmetric about zero; zero itself is never returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let x = thread_rng().gen_signed_unit();
+    /// assert!((-1.0..=1.0).contains(&x));
+    /// ```
+    #[inline]
+    fn gen_signed_unit(&mut self) -> f64 {
+        distributions::SignedUnit.sample(self)
+    }
+
+    /// Return a bool with a probability of exactly `2^-k` of being true.
+    ///
+    /// Unlike [`gen_bool`], whose resolution is limited to about `2^-64`,
+    /// this is exact for any `k`: the result is `true` if `k` random bits
+    /// are all zero. Bits are generated 64 at a time, stopping as soon as a
+    /// set bit is found, so on average little more than one `u64` is used.
+    /// If `k == 0`, the result is always `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// if rng.gen_bool_2pow(100) {
+    ///     println!("A one in 2^100 event!");
+    /// }
+    /// ```
+    ///
+    /// [`gen_bool`]: Rng::gen_bool
+    fn gen_bool_2pow(&mut self, k: u32) -> bool {
+        let mut k = k;
+        while k >= 64 {
+            if self.next_u64() != 0 {
+                return false;
+            }
+            k -= 64;
+        }
+        k == 0 || self.next_u64() >> (64 - k) == 0
+    }
+
+    /// Return a bool with a probability of `exp(ln_p)` of being true.
+    ///
+    /// This allows sampling events whose probability is too small to be
+    /// represented by an `f64`, or to be sampled accurately by [`gen_bool`].
+    /// The probability is written as `2^-k * q` for an integer `k` and
+    /// `1/2 < q <= 1`; the result is the conjunction of
+    /// [`gen_bool_2pow(k)`](Rng::gen_bool_2pow) and `gen_bool(q)`, thus is
+    /// accurate relative to the probability (rather than absolutely) and
+    /// uses little more than two `u64` on average.
+    ///
+    /// If `ln_p == 0`, the result is always `true`; if `ln_p` is negative
+    /// infinity, the result is always `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::{thread_rng, Rng};
+    ///
+    /// let mut rng = thread_rng();
+    /// // The chance of 1000 fair coin flips all landing heads
+    /// let ln_p = 1000.0 * 0.5f64.ln();
+    /// println!("{}", rng.gen_bool_log(ln_p));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `ln_p > 0` or `ln_p` is NaN.
+    ///
+    /// [`gen_bool`]: Rng::gen_bool
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn gen_bool_log(&mut self, ln_p: f64) -> bool {
+        assert!(ln_p <= 0.0, "`ln_p` must be at most 0");
+        if ln_p == f64::NEG_INFINITY {
+            return false;
+        }
+        let mut x = -ln_p * core::f64::consts::LOG2_E;
+        // Handle exponents beyond the range of `u32` in steps
+        const STEP: f64 = (1u64 << 31) as f64;
+        while x >= STEP {
+            if !self.gen_bool_2pow(1 << 31) {
+                return false;
+            }
+            x -= STEP;
+        }
+        let k = x.floor();
+        self.gen_bool_2pow(k as u32) && self.gen_bool((k - x).exp2())
+    }
 }
 
 impl<R: RngCore + ?Sized> Rng for R {}
 
+/// Adapter generating data via `try_fill_bytes` only, which records the first
+/// error and then yields non-random data, for [`Rng::try_sample`].
+struct TryRng<'a, R: ?Sized> {
+    rng: &'a mut R,
+    error: Option<Error>,
+    // Varying data after an error, such that rejection sampling terminates
+    fallback: u64,
+}
+
+impl<'a, R: RngCore + ?Sized> RngCore for TryRng<'a, R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        let mut buf = [0; 4];
+        self.fill_bytes(&mut buf);
+        u32::from_le_bytes(buf)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        let mut buf = [0; 8];
+        self.fill_bytes(&mut buf);
+        u64::from_le_bytes(buf)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if self.error.is_none() {
+            match self.rng.try_fill_bytes(dest) {
+                Ok(()) => return,
+                Err(error) => self.error = Some(error),
+            }
+        }
+        for byte in dest {
+            self.fallback = self.fallback.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            *byte = (self.fallback >> 56) as u8;
+        }
+    }
+}
+
 /// Types which may be filled with random data
 ///
-/// This trait allows arrays to be efficiently filled with random data.
+/// This trait allows arrays to be efficiently filled with random data. It is
+/// implemented for slices and arrays of primitive types, nested arrays such
+/// as `[[u32; 4]; N]`, mutable references and tuples of fillable types:
+///
+/// ```
+/// use rand::{thread_rng, Rng};
+///
+/// let mut matrix = [[0f32; 4]; 4];
+/// let mut header = [0u8; 16];
+/// let mut body = vec![0u32; 100];
+/// thread_rng().fill(&mut (&mut matrix, &mut header, &mut body[..]));
+/// ```
 ///
 /// Implementations are expected to be portable across machines unless
 /// clearly documented otherwise (see the
@@ -355,7 +811,23 @@ macro_rules! impl_fill_each {
     };
 }
 
-impl_fill_each!(bool, char, f32, f64,);
+impl_fill_each!(char,);
+
+/// Fills with packed random bits, generating one random byte per 8 values,
+/// thus differs from [`Rng::gen`] for each element.
+impl Fill for [bool] {
+    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+        let mut buf = [0u8; 32];
+        for chunk in self.chunks_mut(buf.len() * 8) {
+            let bytes = &mut buf[..(chunk.len() + 7) / 8];
+            rng.try_fill_bytes(bytes)?;
+            for (i, x) in chunk.iter_mut().enumerate() {
+                *x = (bytes[i / 8] >> (i % 8)) & 1 == 1;
+            }
+        }
+        Ok(())
+    }
+}
 
 impl Fill for [u8] {
     fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
@@ -413,6 +885,34 @@ macro_rules! impl_fill {
 impl_fill!(u16, u32, u64, usize, u128,);
 impl_fill!(i8, i16, i32, i64, isize, i128,);
 
+macro_rules! impl_fill_float {
+    ($t:ty, $u:ty, $fraction_bits:expr) => {
+        /// Fills with the same [`Standard`] mapping as [`Rng::gen`], but using
+        /// [`RngCore::fill_bytes`] for all values at once.
+        impl Fill for [$t] {
+            #[inline(never)]
+            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+                // Safety: the integer type has the same size and alignment,
+                // and its values are converted to valid float bits below
+                let ints = unsafe {
+                    slice::from_raw_parts_mut(self.as_mut_ptr() as *mut $u, self.len())
+                };
+                ints.try_fill(rng)?;
+                let precision = $fraction_bits + 1;
+                let scale = 1.0 / ((1 as $u << precision) as $t);
+                for x in ints {
+                    let value = *x >> (mem::size_of::<$u>() as $u * 8 - precision);
+                    *x = (scale * value as $t).to_bits();
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_fill_float!(f32, u32, 23);
+impl_fill_float!(f64, u64, 52);
+
 impl<T, const N: usize> Fill for [T; N]
 where [T]: Fill
 {
@@ -421,6 +921,45 @@ where [T]: Fill
     }
 }
 
+/// Fills nested arrays, e.g. `[[u32; 4]]` or `[[[u8; 3]; 4]; 5]`, as if
+/// flattened into a single slice.
+impl<T, const N: usize> Fill for [[T; N]]
+where [T]: Fill
+{
+    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+        let len = self.len() * N;
+        // Safety: `[[T; N]]` has the same layout as `[T]` of `N` times the length
+        let flat = unsafe { core::slice::from_raw_parts_mut(self.as_mut_ptr() as *mut T, len) };
+        flat.try_fill(rng)
+    }
+}
+
+impl<T: Fill + ?Sized> Fill for &mut T {
+    #[inline]
+    fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+        (**self).try_fill(rng)
+    }
+}
+
+macro_rules! impl_fill_tuple {
+    ($($T:ident $i:tt),+) => {
+        /// Fills each element in order.
+        impl<$($T: Fill),+> Fill for ($($T,)+) {
+            fn try_fill<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Result<(), Error> {
+                $(self.$i.try_fill(rng)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_fill_tuple! { A 0 }
+impl_fill_tuple! { A 0, B 1 }
+impl_fill_tuple! { A 0, B 1, C 2 }
+impl_fill_tuple! { A 0, B 1, C 2, D 3 }
+impl_fill_tuple! { A 0, B 1, C 2, D 3, E 4 }
+impl_fill_tuple! { A 0, B 1, C 2, D 3, E 4, F 5 }
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -471,13 +1010,124 @@ mod test {
         assert_eq!(array[0], warray[0].0);
         assert_eq!(array[1], warray[1].0);
 
-        // Check equivalence for generated floats
+        // Check floats use the `Standard` mapping of filled integers
         let mut array = [0f32; 2];
         rng.fill(&mut array);
-        let gen: [f32; 2] = rng.gen();
+        let lo = (x as u32 >> 8) as f32 / (1 << 24) as f32;
+        let hi = ((x >> 40) as u32) as f32 / (1 << 24) as f32;
+        assert_eq!(array, [lo, hi]);
+        let mut array = [0f64; 2];
+        rng.fill(&mut array);
+        let gen: [f64; 2] = rng.gen();
         assert_eq!(array, gen);
     }
 
+    #[test]
+    fn test_fill_nested() {
+        let mut r1 = rng(151);
+        let mut r2 = rng(151);
+        let mut nested = [[0u32; 4]; 3];
+        let mut flat = [0u32; 12];
+        r1.fill(&mut nested);
+        r2.fill(&mut flat);
+        assert!(nested.iter().flatten().eq(flat.iter()));
+
+        let mut nested = [[[0i8; 2]; 3]; 2];
+        let mut flat = [0i8; 12];
+        r1.fill(&mut nested);
+        r2.fill(&mut flat);
+        assert!(nested.iter().flatten().flatten().eq(flat.iter()));
+        let mut empty: [[u64; 0]; 5] = [[]; 5];
+        r1.fill(&mut empty[..]);
+    }
+
+    #[test]
+    fn test_fill_tuple() {
+        let mut r1 = rng(152);
+        let mut r2 = rng(152);
+        let mut header = [0u8; 4];
+        let mut body = [0u32; 6];
+        let mut buf: ([[u16; 2]; 3], [bool; 5]) = Default::default();
+        r1.fill(&mut (&mut header[..], &mut body, &mut buf));
+
+        let mut expected = ([0u8; 4], [0u32; 6], [[0u16; 2]; 3], [false; 5]);
+        r2.fill(&mut expected.0);
+        r2.fill(&mut expected.1);
+        r2.fill(&mut expected.2);
+        r2.fill(&mut expected.3);
+        assert_eq!((header, body, buf), (expected.0, expected.1, (expected.2, expected.3)));
+    }
+
+    #[test]
+    fn test_fill_float() {
+        let mut r = rng(115);
+        let mut a = [0f32; 100];
+        r.fill(&mut a[..]);
+        assert!(a.iter().all(|x| (0.0..1.0).contains(x)));
+        assert!(a.iter().any(|&x| x != a[0]));
+
+        // As for Pcg32, bytes are usually consumed as by `next_u64`
+        let mut r2 = rng(115);
+        r2.fill(&mut [0f32; 100][..]);
+        let mut b = [0f64; 20];
+        r.fill(&mut b[..]);
+        for &x in b.iter() {
+            assert_eq!(x, r2.gen::<f64>());
+        }
+    }
+
+    #[test]
+    fn test_fill_bits() {
+        let mut step = StepRng::new(0x0123_4567_89ab_cdef, 0);
+        let mut bools = [false; 12];
+        step.fill(&mut bools[..]);
+        // The low byte 0xef, then the low 4 bits of 0xcd
+        assert_eq!(bools, [
+            true, true, true, true, false, true, true, true, true, false, true, true
+        ]);
+
+        let mut bytes = [0xffu8; 4];
+        step.fill_bits(&mut bytes, 12);
+        assert_eq!(bytes, [0xef, 0x0d, 0xff, 0xff]);
+        step.fill_bits(&mut bytes, 32);
+        assert_eq!(bytes, [0xef, 0xcd, 0xab, 0x89]);
+        step.fill_bits(&mut bytes[..0], 0);
+
+        let mut r = rng(116);
+        let mut bools = [false; 1000];
+        r.fill(&mut bools[..]);
+        let count = bools.iter().filter(|&&b| b).count();
+        assert!((450..550).contains(&count));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_fill_bits_panic() {
+        StepRng::new(0, 0).fill_bits(&mut [0u8; 2], 17);
+    }
+
+    #[test]
+    fn test_gen_bits() {
+        let mut step = StepRng::new(0x0123_4567_89ab_cdef, 0);
+        assert_eq!(step.gen_bits(0), 0);
+        assert_eq!(step.gen_bits(4), 0x8);
+        assert_eq!(step.gen_bits(32), 0x89ab_cdef);
+        assert_eq!(step.gen_bits(40), 0x01_2345_6789);
+        assert_eq!(step.gen_bits(64), 0x0123_4567_89ab_cdef);
+
+        let mut r = rng(154);
+        for n in 0..=64 {
+            let x = r.gen_bits(n);
+            assert!(n == 64 || x >> n == 0);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_bits_panic() {
+        StepRng::new(0, 0).gen_bits(65);
+    }
+
     #[test]
     fn test_fill_empty() {
         let mut array = [0u32; 0];
@@ -522,6 +1172,150 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_gen_range_open() {
+        let mut r = rng(104);
+        for _ in 0..1000 {
+            let a: u8 = r.gen_range(250..);
+            assert!(a >= 250);
+            let a: i16 = r.gen_range(..-32760);
+            assert!(a < -32760);
+            let a: i8 = r.gen_range(..=-127);
+            assert!(a <= -127);
+            let a: char = r.gen_range(..='\u{1}');
+            assert!(a <= '\u{1}');
+        }
+        assert_eq!(r.gen_range(u64::MAX..), u64::MAX);
+        assert_eq!(r.gen_range(..=i32::MIN), i32::MIN);
+        assert_eq!(r.gen_range(..1u16), 0);
+
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let a: u8 = r.gen_range(..);
+            counts[(a >> 6) as usize] += 1;
+        }
+        crate::test::assert_uniform(&counts);
+        let _: u128 = r.gen_range(..);
+        let _: char = r.gen_range(..);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_panic_open() {
+        let mut r = rng(106);
+        r.gen_range(..0u32);
+    }
+
+    #[test]
+    fn test_gen_range_borrowed() {
+        struct Config {
+            range: core::ops::Range<f64>,
+            counts: core::ops::RangeInclusive<u32>,
+        }
+        let config = Config { range: 1.5..2.5, counts: 3..=7 };
+
+        let mut r = rng(159);
+        let mut r2 = rng(159);
+        for _ in 0..100 {
+            let x = r.gen_range(&config.range);
+            assert_eq!(x, r2.gen_range(1.5..2.5));
+            let n = r.gen_range(&config.counts);
+            assert_eq!(n, r2.gen_range(3..=7));
+            let n = r.gen_range((10i64, 20));
+            assert_eq!(n, r2.gen_range(10..20));
+        }
+        let single = 5..=5;
+        assert_eq!(r.gen_range(&single), 5);
+        assert_eq!(r.gen_range((-3, -2)), -3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_panic_borrowed() {
+        let mut r = rng(160);
+        let empty = 1..1;
+        r.gen_range(&empty);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_range_panic_tuple() {
+        let mut r = rng(161);
+        r.gen_range((2.0, 1.0));
+    }
+
+    #[test]
+    fn test_gen_ranges() {
+        let mut r = rng(146);
+        for _ in 0..1000 {
+            let (a, b, c, d) = r.gen_ranges((0..6u8, 10..=12usize, -5i64..5, i16::MIN..=i16::MAX));
+            assert!(a < 6);
+            assert!((10..=12).contains(&b));
+            assert!((-5..5).contains(&c));
+            let _: i16 = d;
+
+            // Too large to derive from a single `u64`
+            let (a, b) = r.gen_ranges((0..=u64::MAX, 1..3u32));
+            let _: u64 = a;
+            assert!((1..3).contains(&b));
+            let (a,) = r.gen_ranges((u32::MAX - 1..u32::MAX,));
+            assert_eq!(a, u32::MAX - 1);
+        }
+
+        let mut counts = [[0; 3]; 2];
+        for _ in 0..6000 {
+            let (a, b) = r.gen_ranges((0..2usize, 0..3usize));
+            counts[a][b] += 1;
+        }
+        crate::test::assert_uniform(counts.iter().flatten());
+
+        // Value stability
+        let mut r = rng(147);
+        assert_eq!(r.gen_ranges((0..6u32, 0..6u32, 0..6u32)), (4, 5, 1));
+        assert_eq!(r.gen_ranges((0..=u64::MAX, -3i8..=3)), (17323159137883641629, -2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_ranges_panic() {
+        #![allow(clippy::reversed_empty_ranges)]
+        rng(148).gen_ranges((0..2u32, 3..3u8));
+    }
+
+    #[test]
+    fn test_gen_below() {
+        let mut r = rng(143);
+        for _ in 0..1000 {
+            assert!(r.gen_below(3u8) < 3);
+            assert!(r.gen_below(1000u16) < 1000);
+            assert!(r.gen_below(u32::MAX) < u32::MAX);
+            assert!(r.gen_below(1u64 << 40) < 1 << 40);
+            assert!(r.gen_below(u128::MAX - 1) < u128::MAX - 1);
+            assert!(r.gen_below(7usize) < 7);
+        }
+        assert_eq!(r.gen_below(1u32), 0);
+
+        // 3 does not divide 2^32, thus some samples are rejected
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            counts[r.gen_below(3usize)] += 1;
+        }
+        crate::test::assert_uniform(&counts);
+
+        // Value stability
+        let mut r = rng(144);
+        let v = [r.gen_below(10u32), r.gen_below(10u32), r.gen_below(10u32)];
+        assert_eq!(v, [1, 4, 4]);
+        assert_eq!(r.gen_below(1u64 << 60), 812564060474942214);
+        assert_eq!(r.gen_below(100usize), 88);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_below_panic() {
+        rng(145).gen_below(0u8);
+    }
+
     #[test]
     #[should_panic]
     fn test_gen_range_panic_int() {
@@ -549,6 +1343,154 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_gen_bool_2pow() {
+        let mut r = rng(112);
+        assert!((0..100).all(|_| r.gen_bool_2pow(0)));
+        for &k in &[1, 2, 5] {
+            let count = (0..32_000).filter(|_| r.gen_bool_2pow(k)).count();
+            let expected = 32_000 >> k;
+            assert!(count > expected * 9 / 10 && count < expected * 11 / 10);
+        }
+        assert!(!(0..100).any(|_| r.gen_bool_2pow(200)));
+
+        // All zero bits, with the mock RNG
+        let mut zero = crate::rngs::mock::StepRng::new(0, 0);
+        assert!(zero.gen_bool_2pow(64) && zero.gen_bool_2pow(65) && zero.gen_bool_2pow(1000));
+        let mut one = crate::rngs::mock::StepRng::new(1, 0);
+        assert!(one.gen_bool_2pow(63) && !one.gen_bool_2pow(64));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_gen_bool_log() {
+        let mut r = rng(113);
+        assert!((0..100).all(|_| r.gen_bool_log(0.0)));
+        assert!(!(0..100).any(|_| r.gen_bool_log(f64::NEG_INFINITY)));
+        assert!(!(0..100).any(|_| r.gen_bool_log(-1e12)));
+        for &p in &[0.75f64, 0.3, 0.01] {
+            let count = (0..20_000).filter(|_| r.gen_bool_log(p.ln())).count();
+            let expected = 20_000.0 * p;
+            assert!((count as f64 - expected).abs() < 5.0 * expected.sqrt());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "std")]
+    fn test_gen_bool_log_panic() {
+        rng(114).gen_bool_log(0.5);
+    }
+
+    #[test]
+    fn test_gen_iter() {
+        let mut r = rng(117);
+        let mut r2 = rng(117);
+        for x in r.gen_iter::<u8>().take(10) {
+            assert_eq!(x, r2.gen::<u8>());
+        }
+        // The RNG is only borrowed
+        assert_eq!(r.gen::<u64>(), r2.gen::<u64>());
+
+        let r3 = &mut r as &mut dyn RngCore;
+        assert_eq!(r3.gen_iter::<bool>().take(3).count(), 3);
+    }
+
+    #[test]
+    fn test_try_gen() {
+        use core::num::NonZeroU32;
+
+        // Fails after `n` calls
+        struct FailingRng(u32);
+        impl RngCore for FailingRng {
+            fn next_u32(&mut self) -> u32 {
+                unreachable!("FailingRng is only used via try_* methods")
+            }
+            fn next_u64(&mut self) -> u64 {
+                unreachable!("FailingRng is only used via try_* methods")
+            }
+            fn fill_bytes(&mut self, _: &mut [u8]) {
+                unreachable!("FailingRng is only used via try_* methods")
+            }
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+                if self.0 == 0 {
+                    return Err(NonZeroU32::new(Error::CUSTOM_START + 1).unwrap().into());
+                }
+                self.0 -= 1;
+                for byte in dest {
+                    *byte = 1;
+                }
+                Ok(())
+            }
+        }
+
+        let mut ok = FailingRng(3);
+        assert_eq!(ok.try_gen::<u32>().unwrap(), 0x0101_0101);
+        assert_eq!(ok.try_gen::<[u8; 2]>().unwrap(), [1, 1]);
+        let err = ok.try_gen::<u64>().unwrap_err();
+        assert_eq!(err.code().unwrap().get(), Error::CUSTOM_START + 1);
+
+        // Rejection sampling terminates after an error
+        let die = crate::distributions::Uniform::new(0u32, 3).unwrap();
+        assert!(FailingRng(0).try_sample(die).is_err());
+        assert!(FailingRng(0).try_gen::<[f64; 8]>().is_err());
+
+        let mut r = rng(140);
+        let x: u64 = r.try_gen().unwrap();
+        let y: u64 = r.try_gen().unwrap();
+        assert_ne!(x, y);
+        assert!(r.try_sample(die).unwrap() < 3);
+    }
+
+    #[test]
+    fn test_gen_char() {
+        let mut r = rng(141);
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let c = r.gen_char("abcd");
+            counts[(c as u8 - b'a') as usize] += 1;
+        }
+        crate::test::assert_uniform(&counts);
+        for _ in 0..100 {
+            assert!("😀é✓".contains(r.gen_char("😀é✓")));
+            assert!(['x', 'y'].contains(&r.gen_char(&['x', 'y'])));
+            assert!(['x', 'y'].contains(&r.gen_char(&['x', 'y'][..])));
+            assert!(('a'..='f').contains(&r.gen_char('a'..='f')));
+        }
+        assert_eq!(r.gen_char("z"), 'z');
+        assert_eq!(r.gen_char('q'..='q'), 'q');
+        #[cfg(feature = "alloc")]
+        {
+            let set = distributions::CharSet::try_from("01").unwrap();
+            assert!(r.gen_char(&set) <= '1');
+        }
+        assert_eq!("".sample_char(&mut r), None);
+        assert_eq!((&[][..]).sample_char(&mut r), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let empty = 'b'..='a';
+        assert_eq!(empty.sample_char(&mut r), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_char_panic() {
+        rng(142).gen_char("");
+    }
+
+    #[test]
+    #[cfg(feature = "std_rng")]
+    fn test_gen_key() {
+        use crate::{rngs::StdRng, SeedableRng};
+        let mut r1 = StdRng::seed_from_u64(143);
+        let mut r2 = StdRng::seed_from_u64(143);
+        let key: [u8; 32] = r1.gen_key();
+        let mut expected = [0u8; 32];
+        r2.fill_bytes(&mut expected);
+        assert_eq!(key, expected);
+        assert_eq!(r1.gen_key::<0>(), [0u8; 0]);
+        assert_ne!(r1.gen_key::<16>(), [0; 16]);
+    }
+
     #[test]
     fn test_rng_trait_object() {
         use crate::distributions::{Distribution, Standard};
@@ -590,4 +1532,41 @@ mod test {
         let expected = (NUM * N) / DENOM; // exact integer
         assert!(((sum - expected) as i32).abs() < 500);
     }
+
+    #[test]
+    fn test_gen_sign() {
+        let mut r = rng(158);
+        let mut r2 = rng(158);
+        for _ in 0..100 {
+            let a: i16 = r.gen_sign();
+            assert_eq!(a, r2.sample::<i16, _>(distributions::Sign));
+            let x: f32 = r.gen_sign();
+            assert_eq!(x, r2.sample::<f32, _>(distributions::Sign));
+            let x: f64 = r.gen_signed_unit();
+            assert_eq!(x, r2.sample::<f64, _>(distributions::SignedUnit));
+        }
+    }
+
+    #[test]
+    fn test_gen_ratio_u64() {
+        let mut r = rng(149);
+        assert!(r.gen_ratio_u64(u64::MAX, u64::MAX));
+        for _ in 0..100 {
+            assert!(!r.gen_ratio_u64(0, 1 << 40));
+            assert!(!r.gen_ratio_u64(1, u64::MAX));
+            assert!(r.gen_ratio_u64(u64::MAX - 1, u64::MAX));
+        }
+
+        let mut sum = 0;
+        for _ in 0..10_000 {
+            sum += r.gen_ratio_u64(3_000_000_000, 10_000_000_000) as i32;
+        }
+        assert!((sum - 3000).abs() < 150);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gen_ratio_u64_panic() {
+        rng(150).gen_ratio_u64(2, 1);
+    }
 }
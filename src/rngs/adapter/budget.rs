@@ -0,0 +1,181 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper limiting the number of bytes drawn from an RNG.
+
+use std::fmt;
+
+use rand_core::{CryptoRng, RngCore, TryCryptoRng, TryRngCore};
+
+/// An RNG limiting the number of bytes drawn from the wrapped RNG.
+///
+/// Each call is charged the number of bytes it returns: 4 for `next_u32`,
+/// 8 for `next_u64` and the length of the buffer for `fill_bytes`. A call
+/// which would exceed the remaining budget draws nothing from the wrapped
+/// RNG and fails with [`BudgetExceeded`]. Thus `BudgetRng` only implements
+/// [`TryRngCore`]; use [`TryRngCore::unwrap_err`] to get an [`RngCore`]
+/// panicking when the budget is exceeded.
+///
+/// This helps keeping code under test deterministic, e.g. a fuzzing harness
+/// providing a fixed amount of random input, and catches code drawing far
+/// more randomness than expected (such as a rejection-sampling loop which
+/// never terminates).
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::adapter::BudgetRng;
+/// use rand::rngs::mock::StepRng;
+/// use rand::{Rng, TryRngCore};
+///
+/// let mut rng = BudgetRng::new(StepRng::new(0, 1), 12);
+/// rng.try_next_u64().unwrap();
+/// assert!(rng.try_fill(&mut [0u8; 5]).is_err());
+///
+/// let mut rng = rng.unwrap_err();
+/// let _: u32 = rng.gen();
+/// assert_eq!(rng.0.remaining(), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BudgetRng<R> {
+    rng: R,
+    remaining: u64,
+}
+
+/// Error returned by [`BudgetRng`] when a call exceeds the budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetExceeded {
+    /// Number of bytes requested by the call.
+    pub requested: u64,
+    /// Number of bytes remaining in the budget.
+    pub remaining: u64,
+}
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RNG budget exceeded: {} bytes requested, {} remaining",
+            self.requested, self.remaining
+        )
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+impl<R: RngCore> BudgetRng<R> {
+    /// Create a new `BudgetRng` allowing `budget` bytes to be drawn from
+    /// `rng`.
+    pub fn new(rng: R, budget: u64) -> Self {
+        BudgetRng { rng, remaining: budget }
+    }
+
+    /// Returns the number of bytes which may still be drawn.
+    #[inline]
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Set the number of bytes which may still be drawn.
+    #[inline]
+    pub fn set_remaining(&mut self, budget: u64) {
+        self.remaining = budget;
+    }
+
+    /// Returns the wrapped RNG.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+
+    /// Charge `n` bytes to the budget, failing without charging if they
+    /// exceed it.
+    #[inline]
+    fn charge(&mut self, n: usize) -> Result<(), BudgetExceeded> {
+        let requested = n as u64;
+        if requested > self.remaining {
+            return Err(BudgetExceeded { requested, remaining: self.remaining });
+        }
+        self.remaining -= requested;
+        Ok(())
+    }
+}
+
+impl<R: RngCore> TryRngCore for BudgetRng<R> {
+    type Error = BudgetExceeded;
+
+    #[inline]
+    fn try_next_u32(&mut self) -> Result<u32, BudgetExceeded> {
+        self.charge(4)?;
+        Ok(self.rng.next_u32())
+    }
+
+    #[inline]
+    fn try_next_u64(&mut self) -> Result<u64, BudgetExceeded> {
+        self.charge(8)?;
+        Ok(self.rng.next_u64())
+    }
+
+    #[inline]
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), BudgetExceeded> {
+        self.charge(dest.len())?;
+        self.rng.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl<R: RngCore + CryptoRng> TryCryptoRng for BudgetRng<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+    use crate::Rng;
+
+    #[test]
+    fn test_budget() {
+        let mut rng = BudgetRng::new(StepRng::new(0, 1), 20);
+        assert_eq!(rng.try_next_u32(), Ok(0));
+        assert_eq!(rng.try_next_u64(), Ok(1));
+        let mut buf = [0u8; 9];
+        let err = rng.try_fill(&mut buf).unwrap_err();
+        assert_eq!(err, BudgetExceeded { requested: 9, remaining: 8 });
+
+        // Failed calls neither charge the budget nor draw from the RNG
+        assert_eq!(rng.remaining(), 8);
+        rng.try_fill(&mut buf[..8]).unwrap();
+        assert_eq!(rng.remaining(), 0);
+        assert_eq!(rng.into_inner().next_u64(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "BudgetExceeded { requested: 8, remaining: 4 }")]
+    fn test_budget_panic() {
+        let mut rng = BudgetRng::new(StepRng::new(0, 1), 12).unwrap_err();
+        let _: u64 = rng.gen();
+        let _: u64 = rng.gen();
+    }
+
+    #[test]
+    fn test_budget_set_remaining() {
+        let mut rng = BudgetRng::new(StepRng::new(0, 1), 0);
+        assert!(rng.try_fill(&mut [0u8; 1]).is_err());
+        rng.set_remaining(4);
+        let mut rng = rng.unwrap_err();
+        assert_eq!(rng.gen_range(0..10u32), 0);
+        assert_eq!(rng.0.remaining(), 0);
+    }
+
+    #[test]
+    fn test_budget_exceeded_display() {
+        let err = BudgetExceeded { requested: 8, remaining: 4 };
+        assert_eq!(
+            std::format!("{}", err),
+            "RNG budget exceeded: 8 bytes requested, 4 remaining"
+        );
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper counting the use of an RNG.
+
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// Counters of the use of an RNG, as reported by [`CountingRng::counts`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RngCounts {
+    /// Number of calls to `next_u32`.
+    pub next_u32: u64,
+    /// Number of calls to `next_u64`.
+    pub next_u64: u64,
+    /// Number of calls to `fill_bytes` and successful calls to
+    /// `try_fill_bytes`.
+    pub fill_bytes: u64,
+    /// Number of bytes drawn by all calls, i.e. 4 per `next_u32`, 8 per
+    /// `next_u64` and the buffer length per `fill_bytes`.
+    pub bytes: u64,
+}
+
+impl RngCounts {
+    /// Returns the total number of calls.
+    #[inline]
+    pub fn calls(&self) -> u64 {
+        self.next_u32 + self.next_u64 + self.fill_bytes
+    }
+}
+
+/// An RNG counting the calls made to the wrapped RNG and the bytes drawn.
+///
+/// This is a tool for performance work on sampling algorithms, answering
+/// questions such as how many random words a shuffle consumes, or how often
+/// a rejection sampler rejects. Calls are forwarded unchanged, thus the
+/// output is that of the wrapped RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::rngs::adapter::CountingRng;
+///
+/// let mut rng = CountingRng::new(StdRng::seed_from_u64(1));
+/// let mut v: Vec<u32> = (0..100).collect();
+/// v.shuffle(&mut rng);
+/// let counts = rng.counts();
+/// println!("shuffle: {} calls, {} bytes", counts.calls(), counts.bytes);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CountingRng<R> {
+    rng: R,
+    counts: RngCounts,
+}
+
+impl<R: RngCore> CountingRng<R> {
+    /// Create a new `CountingRng` wrapping `rng`, with all counters zero.
+    pub fn new(rng: R) -> Self {
+        CountingRng { rng, counts: RngCounts::default() }
+    }
+
+    /// Returns the counters.
+    #[inline]
+    pub fn counts(&self) -> RngCounts {
+        self.counts
+    }
+
+    /// Reset all counters to zero, returning their previous values.
+    #[inline]
+    pub fn reset(&mut self) -> RngCounts {
+        core::mem::take(&mut self.counts)
+    }
+
+    /// Returns the wrapped RNG.
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+impl<R: RngCore> RngCore for CountingRng<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.counts.next_u32 += 1;
+        self.counts.bytes += 4;
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.counts.next_u64 += 1;
+        self.counts.bytes += 8;
+        self.rng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.counts.fill_bytes += 1;
+        self.counts.bytes += dest.len() as u64;
+        self.rng.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)?;
+        self.counts.fill_bytes += 1;
+        self.counts.bytes += dest.len() as u64;
+        Ok(())
+    }
+}
+
+impl<R: RngCore + CryptoRng> CryptoRng for CountingRng<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+
+    #[test]
+    fn test_counting() {
+        let mut rng = CountingRng::new(StepRng::new(0, 1));
+        assert_eq!(rng.next_u32(), 0);
+        assert_eq!(rng.next_u64(), 1);
+        assert_eq!(rng.next_u64(), 2);
+        let mut buf = [0u8; 11];
+        rng.fill_bytes(&mut buf);
+        rng.try_fill_bytes(&mut buf[..3]).unwrap();
+
+        let expected = RngCounts { next_u32: 1, next_u64: 2, fill_bytes: 2, bytes: 4 + 16 + 14 };
+        assert_eq!(rng.counts(), expected);
+        assert_eq!(rng.counts().calls(), 5);
+        assert_eq!(rng.reset(), expected);
+        assert_eq!(rng.counts(), RngCounts::default());
+    }
+
+    #[test]
+    fn test_counting_try_fill_error() {
+        use crate::rngs::mock::SequenceRng;
+
+        let mut rng = CountingRng::new(SequenceRng::from_bytes(&[1, 2][..]));
+        assert!(rng.try_fill_bytes(&mut [0u8; 4]).is_err());
+        assert_eq!(rng.counts(), RngCounts::default());
+    }
+}
@@ -8,9 +8,17 @@
 
 //! Wrappers / adapters forming RNGs
 
+mod budget;
+mod counting;
+mod policy;
 mod read;
 mod reseeding;
+mod xor;
 
+pub use self::budget::{BudgetExceeded, BudgetRng};
+pub use self::counting::{CountingRng, RngCounts};
 #[allow(deprecated)]
 pub use self::read::{ReadError, ReadRng};
+pub use self::policy::{ReseedFlag, ReseedInterval, ReseedPolicy};
 pub use self::reseeding::ReseedingRng;
+pub use self::xor::XorRng;
@@ -0,0 +1,201 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Policies deciding when a `ReseedingRng` reseeds.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::time::Duration;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A policy deciding when a [`ReseedingRng`] reseeds its PRNG, in addition to
+/// the byte threshold and fork detection built into [`ReseedingRng`].
+///
+/// Policies compose: a pair `(A, B)` of policies reseeds whenever either of
+/// them asks for it, while `()` never asks for a reseed.
+///
+/// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
+pub trait ReseedPolicy {
+    /// Returns `true` if the PRNG should be reseeded before generating its
+    /// next block of results, which is `num_bytes` long.
+    fn should_reseed(&mut self, num_bytes: usize) -> bool;
+
+    /// Returns `true` if buffered results should be discarded and the PRNG
+    /// reseeded now. This is checked on every use of the RNG, thus must be
+    /// cheap; by default it returns `false`.
+    #[inline(always)]
+    fn is_urgent(&self) -> bool {
+        false
+    }
+
+    /// Called after each successful reseed of the PRNG.
+    #[inline(always)]
+    fn on_reseed(&mut self) {}
+
+    /// Called after a failed attempt to reseed the PRNG, which then keeps its
+    /// old state. A policy which consumed its request in `should_reseed`
+    /// should restore it here, such that the reseed is retried.
+    #[inline(always)]
+    fn on_reseed_failed(&mut self) {}
+}
+
+impl ReseedPolicy for () {
+    #[inline(always)]
+    fn should_reseed(&mut self, _num_bytes: usize) -> bool {
+        false
+    }
+}
+
+impl<A: ReseedPolicy, B: ReseedPolicy> ReseedPolicy for (A, B) {
+    #[inline]
+    fn should_reseed(&mut self, num_bytes: usize) -> bool {
+        // Both policies are asked, since either may track state
+        self.0.should_reseed(num_bytes) | self.1.should_reseed(num_bytes)
+    }
+
+    #[inline]
+    fn is_urgent(&self) -> bool {
+        self.0.is_urgent() || self.1.is_urgent()
+    }
+
+    #[inline]
+    fn on_reseed(&mut self) {
+        self.0.on_reseed();
+        self.1.on_reseed();
+    }
+
+    #[inline]
+    fn on_reseed_failed(&mut self) {
+        self.0.on_reseed_failed();
+        self.1.on_reseed_failed();
+    }
+}
+
+/// Reseed once a given time has passed since the last reseed.
+///
+/// The clock is read before each block of results is generated. The time
+/// until the first reseed starts with the construction of the policy.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use rand::rngs::adapter::{ReseedInterval, ReseedingRng};
+/// use rand::rngs::OsRng;
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha20Core;
+///
+/// let policy = ReseedInterval::new(Duration::from_secs(60));
+/// let rng = ReseedingRng::with_policy(ChaCha20Core::from_entropy(), 0, OsRng, policy);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ReseedInterval {
+    interval: Duration,
+    last: Instant,
+}
+
+impl ReseedInterval {
+    /// Create a policy reseeding every `interval`.
+    pub fn new(interval: Duration) -> Self {
+        ReseedInterval { interval, last: Instant::now() }
+    }
+}
+
+impl ReseedPolicy for ReseedInterval {
+    #[inline]
+    fn should_reseed(&mut self, _num_bytes: usize) -> bool {
+        self.last.elapsed() >= self.interval
+    }
+
+    #[inline]
+    fn on_reseed(&mut self) {
+        self.last = Instant::now();
+    }
+}
+
+/// Reseed on the next use after a flag is raised, possibly from another
+/// thread.
+///
+/// Clones of a `ReseedFlag` share their flag, thus a clone kept e.g. by a
+/// VM-resume or fork handler (see [`ReseedFlag::raise`]) can request a reseed
+/// of the RNG owning the policy. When raised, any buffered results are
+/// discarded: the next value is generated by the reseeded PRNG. The flag is
+/// cleared before reseeding, thus a request raised during a reseed causes
+/// another; if reseeding fails, the flag is raised again.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::adapter::{ReseedFlag, ReseedingRng};
+/// use rand::rngs::OsRng;
+/// use rand::{RngCore, SeedableRng};
+/// use rand_chacha::ChaCha20Core;
+///
+/// let flag = ReseedFlag::new();
+/// let mut rng = ReseedingRng::with_policy(ChaCha20Core::from_entropy(), 0, OsRng, flag.clone());
+/// let handle = std::thread::spawn(move || flag.raise());
+/// handle.join().unwrap();
+/// let _ = rng.next_u32(); // generated after a reseed
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReseedFlag {
+    flag: Arc<AtomicBool>,
+    // Whether the flag was cleared by `should_reseed` for a pending reseed
+    taken: bool,
+}
+
+impl ReseedFlag {
+    /// Create a new flag, initially not raised.
+    pub fn new() -> Self {
+        ReseedFlag::default()
+    }
+
+    /// Request a reseed on the next use of the RNG.
+    ///
+    /// This only stores to an atomic, thus may also be called from a signal
+    /// or fork handler.
+    #[inline]
+    pub fn raise(&self) {
+        self.flag.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` if a reseed has been requested but not yet performed.
+    #[inline]
+    pub fn is_raised(&self) -> bool {
+        self.taken || self.flag.load(Ordering::Relaxed)
+    }
+}
+
+impl ReseedPolicy for ReseedFlag {
+    #[inline]
+    fn should_reseed(&mut self, _num_bytes: usize) -> bool {
+        // Clear the flag before reseeding, so no request is lost
+        if self.flag.swap(false, Ordering::Relaxed) {
+            self.taken = true;
+        }
+        self.taken
+    }
+
+    #[inline(always)]
+    fn is_urgent(&self) -> bool {
+        self.is_raised()
+    }
+
+    #[inline]
+    fn on_reseed(&mut self) {
+        self.taken = false;
+    }
+
+    #[inline]
+    fn on_reseed_failed(&mut self) {
+        if self.taken {
+            self.taken = false;
+            self.flag.store(true, Ordering::Relaxed);
+        }
+    }
+}
@@ -10,10 +10,12 @@
 //! A wrapper around another PRNG that reseeds it after it
 //! generates a certain number of random bytes.
 
-use core::mem::size_of_val;
+use core::mem::{size_of_val, MaybeUninit};
 
 use rand_core::block::{BlockRng, BlockRngCore, CryptoBlockRng};
-use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
+use rand_core::{CryptoRng, Error, MixEntropy, RngCore, SeedableRng, TryCryptoRng, TryRngCore};
+
+use super::ReseedPolicy;
 
 /// A wrapper around any PRNG that implements [`BlockRngCore`], that adds the
 /// ability to reseed it.
@@ -23,10 +25,13 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 /// - On a manual call to [`reseed()`].
 /// - After `clone()`, the clone will be reseeded on first use.
 /// - When a process is forked on UNIX, the RNGs in both the parent and child
-///   processes will be reseeded just before the next call to
-///   [`BlockRngCore::generate`], i.e. "soon". For ChaCha and Hc128, this is a
-///   maximum of 63 and 15, respectively, `u32` values before reseeding.
+///   processes will be reseeded on their next use, discarding any buffered
+///   values; thus parent and child never produce the same output.
 /// - After the PRNG has generated a configurable number of random bytes.
+/// - When asked to by its [`ReseedPolicy`], e.g. after some time has passed
+///   ([`ReseedInterval`]) or on request from another thread ([`ReseedFlag`]).
+///   The policy is given with [`ReseedingRng::with_policy`]; by default, `()`
+///   adds no further reseeds.
 ///
 /// # When should reseeding after a fixed number of generated bytes be used?
 ///
@@ -55,7 +60,8 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 /// never panic but try to handle the error intelligently through some
 /// combination of retrying and delaying reseeding until later.
 /// If handling the source error fails `ReseedingRng` will continue generating
-/// data from the wrapped PRNG without reseeding.
+/// data from the wrapped PRNG without reseeding. A [`ReseedPolicy`] keeps
+/// requesting a reseed after a failure, e.g. a [`ReseedFlag`] stays raised.
 ///
 /// Manually calling [`reseed()`] will not have this retry or delay logic, but
 /// reports the error.
@@ -81,16 +87,20 @@ use rand_core::{CryptoRng, Error, RngCore, SeedableRng};
 /// [`BlockRngCore`]: rand_core::block::BlockRngCore
 /// [`ReseedingRng::new`]: ReseedingRng::new
 /// [`reseed()`]: ReseedingRng::reseed
+/// [`ReseedInterval`]: super::ReseedInterval
+/// [`ReseedFlag`]: super::ReseedFlag
+/// [`ReseedPolicy`]: super::ReseedPolicy
 #[derive(Debug)]
-pub struct ReseedingRng<R, Rsdr>(BlockRng<ReseedingCore<R, Rsdr>>)
+pub struct ReseedingRng<R, Rsdr, P = ()>(BlockRng<ReseedingCore<R, Rsdr, P>>)
 where
     R: BlockRngCore + SeedableRng,
-    Rsdr: RngCore;
+    Rsdr: TryRngCore,
+    P: ReseedPolicy;
 
 impl<R, Rsdr> ReseedingRng<R, Rsdr>
 where
     R: BlockRngCore + SeedableRng,
-    Rsdr: RngCore,
+    Rsdr: TryRngCore,
 {
     /// Create a new `ReseedingRng` from an existing PRNG, combined with a RNG
     /// to use as reseeder.
@@ -98,126 +108,234 @@ where
     /// `threshold` sets the number of generated bytes after which to reseed the
     /// PRNG. Set it to zero to never reseed based on the number of generated
     /// values.
+    ///
+    /// The reseeder may be any [`TryRngCore`], e.g. [`OsRng`]; [`reseed()`]
+    /// returns its error type.
+    ///
+    /// [`OsRng`]: crate::rngs::OsRng
+    /// [`reseed()`]: ReseedingRng::reseed
     pub fn new(rng: R, threshold: u64, reseeder: Rsdr) -> Self {
-        ReseedingRng(BlockRng::new(ReseedingCore::new(rng, threshold, reseeder)))
+        ReseedingRng::with_policy(rng, threshold, reseeder, ())
+    }
+}
+
+impl<R, Rsdr, P> ReseedingRng<R, Rsdr, P>
+where
+    R: BlockRngCore + SeedableRng,
+    Rsdr: TryRngCore,
+    P: ReseedPolicy,
+{
+    /// Create a new `ReseedingRng` as [`ReseedingRng::new`] does, which
+    /// additionally reseeds when asked to by `policy`.
+    pub fn with_policy(rng: R, threshold: u64, reseeder: Rsdr, policy: P) -> Self {
+        ReseedingRng(BlockRng::new(ReseedingCore::new(rng, threshold, reseeder, policy)))
     }
 
     /// Reseed the internal PRNG.
-    pub fn reseed(&mut self) -> Result<(), Error> {
+    pub fn reseed(&mut self) -> Result<(), Rsdr::Error> {
         self.0.core.reseed()
     }
+
+    /// Returns a reference to the reseeding policy.
+    pub fn policy(&self) -> &P {
+        &self.0.core.policy
+    }
+
+    /// Returns a mutable reference to the reseeding policy.
+    pub fn policy_mut(&mut self) -> &mut P {
+        &mut self.0.core.policy
+    }
+
+    /// Set the number of generated bytes after which to reseed the PRNG.
+    ///
+    /// As for [`ReseedingRng::new`], a `threshold` of zero disables
+    /// reseeding based on the number of generated values. If fewer than
+    /// `threshold` bytes remain until the next scheduled reseed, the
+    /// schedule is unchanged; otherwise the next reseed is brought forward.
+    /// Subsequent reseeds occur every `threshold` bytes.
+    pub fn set_threshold(&mut self, threshold: u64) {
+        self.0.core.set_threshold(threshold)
+    }
+
+    /// Returns the number of generated bytes after which the PRNG is
+    /// reseeded, or zero if reseeding based on the number of generated
+    /// values is disabled.
+    pub fn threshold(&self) -> u64 {
+        match self.0.core.threshold {
+            i64::MAX => 0,
+            threshold => threshold as u64,
+        }
+    }
+
+    /// Discard buffered values if the process was forked or the policy asks
+    /// for an immediate reseed, so that the next use reseeds the PRNG.
+    #[inline(always)]
+    fn check_fork(&mut self) {
+        if self.0.core.is_forked(fork::get_fork_counter()) || self.0.core.policy.is_urgent() {
+            self.0.reset();
+        }
+    }
 }
 
 // TODO: this should be implemented for any type where the inner type
 // implements RngCore, but we can't specify that because ReseedingCore is private
-impl<R, Rsdr: RngCore> RngCore for ReseedingRng<R, Rsdr>
+impl<R, Rsdr: TryRngCore, P: ReseedPolicy> RngCore for ReseedingRng<R, Rsdr, P>
 where
     R: BlockRngCore<Item = u32> + SeedableRng,
 {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
+        self.check_fork();
         self.0.next_u32()
     }
 
     #[inline(always)]
     fn next_u64(&mut self) -> u64 {
+        self.check_fork();
         self.0.next_u64()
     }
 
     fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.check_fork();
         self.0.fill_bytes(dest)
     }
 
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.check_fork();
         self.0.try_fill_bytes(dest)
     }
+
+    fn fill_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        self.check_fork();
+        self.0.fill_bytes_uninit(dest)
+    }
 }
 
-impl<R, Rsdr> Clone for ReseedingRng<R, Rsdr>
+impl<R, Rsdr: TryRngCore, P: ReseedPolicy> MixEntropy for ReseedingRng<R, Rsdr, P>
+where
+    R: BlockRngCore<Item = u32> + SeedableRng,
+{
+    /// Reseed the wrapped PRNG from its own output XORed with `entropy`, as
+    /// [`rand_core::impls::mix_entropy_via_seed`] does, discarding any
+    /// buffered output. This does not delay the next reseed from the
+    /// reseeder.
+    fn mix_entropy(&mut self, entropy: &[u8]) {
+        self.check_fork();
+        let seed_len = R::Seed::default().as_mut().len();
+        for chunk in entropy.chunks(seed_len.max(1)) {
+            let mut seed = R::Seed::default();
+            self.0.fill_bytes(seed.as_mut());
+            for (x, y) in seed.as_mut().iter_mut().zip(chunk.iter()) {
+                *x ^= *y;
+            }
+            self.0.core.inner = R::from_seed(seed);
+            self.0.reset();
+        }
+    }
+}
+
+impl<R, Rsdr, P> Clone for ReseedingRng<R, Rsdr, P>
 where
     R: BlockRngCore + SeedableRng + Clone,
-    Rsdr: RngCore + Clone,
+    Rsdr: TryRngCore + Clone,
+    P: ReseedPolicy + Clone,
 {
-    fn clone(&self) -> ReseedingRng<R, Rsdr> {
+    fn clone(&self) -> ReseedingRng<R, Rsdr, P> {
         // Recreating `BlockRng` seems easier than cloning it and resetting
         // the index.
         ReseedingRng(BlockRng::new(self.0.core.clone()))
     }
 }
 
-impl<R, Rsdr> CryptoRng for ReseedingRng<R, Rsdr>
+impl<R, Rsdr, P> CryptoRng for ReseedingRng<R, Rsdr, P>
 where
     R: BlockRngCore<Item = u32> + SeedableRng + CryptoBlockRng,
-    Rsdr: CryptoRng,
+    Rsdr: TryCryptoRng,
+    P: ReseedPolicy,
 {
 }
 
 #[derive(Debug)]
-struct ReseedingCore<R, Rsdr> {
+struct ReseedingCore<R, Rsdr, P> {
     inner: R,
     reseeder: Rsdr,
     threshold: i64,
     bytes_until_reseed: i64,
     fork_counter: usize,
+    policy: P,
 }
 
-impl<R, Rsdr> BlockRngCore for ReseedingCore<R, Rsdr>
+impl<R, Rsdr, P> BlockRngCore for ReseedingCore<R, Rsdr, P>
 where
     R: BlockRngCore + SeedableRng,
-    Rsdr: RngCore,
+    Rsdr: TryRngCore,
+    P: ReseedPolicy,
 {
     type Item = <R as BlockRngCore>::Item;
     type Results = <R as BlockRngCore>::Results;
 
     fn generate(&mut self, results: &mut Self::Results) {
         let global_fork_counter = fork::get_fork_counter();
-        if self.bytes_until_reseed <= 0 || self.is_forked(global_fork_counter) {
+        let num_bytes = size_of_val(results.as_ref());
+        if self.bytes_until_reseed <= 0
+            || self.is_forked(global_fork_counter)
+            || self.policy.should_reseed(num_bytes)
+        {
             // We get better performance by not calling only `reseed` here
             // and continuing with the rest of the function, but by directly
             // returning from a non-inlined function.
             return self.reseed_and_generate(results, global_fork_counter);
         }
-        let num_bytes = size_of_val(results.as_ref());
         self.bytes_until_reseed -= num_bytes as i64;
         self.inner.generate(results);
     }
 }
 
-impl<R, Rsdr> ReseedingCore<R, Rsdr>
+impl<R, Rsdr, P> ReseedingCore<R, Rsdr, P>
 where
     R: BlockRngCore + SeedableRng,
-    Rsdr: RngCore,
+    Rsdr: TryRngCore,
+    P: ReseedPolicy,
 {
     /// Create a new `ReseedingCore`.
-    fn new(rng: R, threshold: u64, reseeder: Rsdr) -> Self {
-        use ::core::i64::MAX;
+    fn new(rng: R, threshold: u64, reseeder: Rsdr, policy: P) -> Self {
         fork::register_fork_handler();
+        let threshold = Self::clamp_threshold(threshold);
+        ReseedingCore {
+            inner: rng,
+            reseeder,
+            threshold,
+            bytes_until_reseed: threshold,
+            fork_counter: 0,
+            policy,
+        }
+    }
 
+    fn clamp_threshold(threshold: u64) -> i64 {
+        use ::core::i64::MAX;
         // Because generating more values than `i64::MAX` takes centuries on
         // current hardware, we just clamp to that value.
         // Also we set a threshold of 0, which indicates no limit, to that
         // value.
-        let threshold = if threshold == 0 {
+        if threshold == 0 {
             MAX
         } else if threshold <= MAX as u64 {
             threshold as i64
         } else {
             MAX
-        };
-
-        ReseedingCore {
-            inner: rng,
-            reseeder,
-            threshold,
-            bytes_until_reseed: threshold,
-            fork_counter: 0,
         }
     }
 
+    fn set_threshold(&mut self, threshold: u64) {
+        self.threshold = Self::clamp_threshold(threshold);
+        self.bytes_until_reseed = self.bytes_until_reseed.min(self.threshold);
+    }
+
     /// Reseed the internal PRNG.
-    fn reseed(&mut self) -> Result<(), Error> {
-        R::from_rng(&mut self.reseeder).map(|result| {
+    fn reseed(&mut self) -> Result<(), Rsdr::Error> {
+        R::try_from_rng(&mut self.reseeder).map(|result| {
             self.bytes_until_reseed = self.threshold;
+            self.policy.on_reseed();
             self.inner = result
         })
     }
@@ -252,6 +370,7 @@ where
         if let Err(e) = self.reseed() {
             warn!("Reseeding RNG failed: {}", e);
             let _ = e;
+            self.policy.on_reseed_failed();
         }
         self.fork_counter = global_fork_counter;
 
@@ -260,26 +379,29 @@ where
     }
 }
 
-impl<R, Rsdr> Clone for ReseedingCore<R, Rsdr>
+impl<R, Rsdr, P> Clone for ReseedingCore<R, Rsdr, P>
 where
     R: BlockRngCore + SeedableRng + Clone,
-    Rsdr: RngCore + Clone,
+    Rsdr: TryRngCore + Clone,
+    P: ReseedPolicy + Clone,
 {
-    fn clone(&self) -> ReseedingCore<R, Rsdr> {
+    fn clone(&self) -> ReseedingCore<R, Rsdr, P> {
         ReseedingCore {
             inner: self.inner.clone(),
             reseeder: self.reseeder.clone(),
             threshold: self.threshold,
             bytes_until_reseed: 0, // reseed clone on first use
             fork_counter: self.fork_counter,
+            policy: self.policy.clone(),
         }
     }
 }
 
-impl<R, Rsdr> CryptoBlockRng for ReseedingCore<R, Rsdr>
+impl<R, Rsdr, P> CryptoBlockRng for ReseedingCore<R, Rsdr, P>
 where
     R: BlockRngCore<Item = u32> + SeedableRng + CryptoBlockRng,
-    Rsdr: CryptoRng,
+    Rsdr: TryCryptoRng,
+    P: ReseedPolicy,
 {
 }
 
@@ -382,4 +504,133 @@ mod test {
         let mut rng2 = rng1.clone();
         assert_eq!(first, rng2.gen::<u32>());
     }
+
+    #[test]
+    fn test_fork_discards_buffer() {
+        use super::fork;
+        use crate::RngCore;
+
+        let mut reseeding = ReseedingRng::new(Core::seed_from_u64(1), 0, StepRng::new(0, 0));
+        let mut unforked = crate::rngs::StdRng::seed_from_u64(1);
+        assert_eq!(reseeding.next_u32(), unforked.next_u32());
+
+        // Simulate a fork without affecting other RNGs in this process
+        reseeding.0.core.fork_counter = fork::get_fork_counter().wrapping_sub(1);
+        let mut reseeded = crate::rngs::StdRng::from_rng(StepRng::new(0, 0)).unwrap();
+        assert_eq!(reseeding.next_u32(), reseeded.next_u32());
+        assert_eq!(reseeding.next_u64(), reseeded.next_u64());
+    }
+
+    #[test]
+    fn test_set_threshold() {
+        let mut zero = StepRng::new(0, 0);
+        let rng = Core::from_rng(&mut zero).unwrap();
+        let mut reseeding = ReseedingRng::new(rng, 0, zero);
+        assert_eq!(reseeding.threshold(), 0);
+        let mut buf = ([0u32; 32], [0u32; 32]);
+        reseeding.fill(&mut buf.0);
+        reseeding.fill(&mut buf.1);
+        let seq = buf;
+
+        // Without reseeding the output continues
+        reseeding.fill(&mut buf.0);
+        assert_ne!(buf.0, seq.0);
+
+        // Reseed from zero every time the buffer is exhausted, starting
+        // after the next block
+        reseeding.fill(&mut buf.1);
+        reseeding.set_threshold(1);
+        assert_eq!(reseeding.threshold(), 1);
+        reseeding.fill(&mut buf.0);
+        reseeding.fill(&mut buf.1);
+        assert_ne!(buf, seq);
+        for _ in 0..3 {
+            reseeding.fill(&mut buf.0);
+            reseeding.fill(&mut buf.1);
+            assert_eq!(buf, seq);
+        }
+        reseeding.set_threshold(u64::MAX);
+        assert_eq!(reseeding.threshold(), 0);
+    }
+
+    #[test]
+    fn test_mix_entropy() {
+        use crate::rngs::StdRng;
+        use crate::MixEntropy;
+
+        let mut reseeding = ReseedingRng::new(Core::seed_from_u64(3), 0, StepRng::new(0, 0));
+        let mut std = StdRng::seed_from_u64(3);
+        let _ = reseeding.gen::<u32>();
+        let _ = std.gen::<u32>();
+        // Mixing discards the buffered results, as for `StdRng`
+        let entropy = [7u8; 40];
+        reseeding.mix_entropy(&entropy);
+        std.mix_entropy(&entropy);
+        for _ in 0..100 {
+            assert_eq!(reseeding.gen::<u64>(), std.gen::<u64>());
+        }
+    }
+
+    #[test]
+    fn test_reseed_policy() {
+        use super::super::{ReseedFlag, ReseedInterval};
+        use crate::RngCore;
+        use core::time::Duration;
+
+        // A zero interval reseeds every time the buffer is exhausted
+        let mut zero = StepRng::new(0, 0);
+        let rng = Core::from_rng(&mut zero).unwrap();
+        let policy = ReseedInterval::new(Duration::from_secs(0));
+        let mut reseeding = ReseedingRng::with_policy(rng, 0, zero, policy);
+        let mut buf = ([0u32; 32], [0u32; 32]);
+        reseeding.fill(&mut buf.0);
+        reseeding.fill(&mut buf.1);
+        let seq = buf;
+        for _ in 0..3 {
+            reseeding.fill(&mut buf.0);
+            reseeding.fill(&mut buf.1);
+            assert_eq!(buf, seq);
+        }
+
+        // A raised flag discards buffered values; a long interval is unused
+        let flag = ReseedFlag::new();
+        let policy = (ReseedInterval::new(Duration::from_secs(3600)), flag.clone());
+        let rng = Core::seed_from_u64(2);
+        let mut reseeding = ReseedingRng::with_policy(rng, 0, StepRng::new(0, 0), policy);
+        let mut unflagged = crate::rngs::StdRng::seed_from_u64(2);
+        assert_eq!(reseeding.next_u32(), unflagged.next_u32());
+        assert_eq!(reseeding.next_u64(), unflagged.next_u64());
+
+        let handle = std::thread::spawn(move || flag.raise());
+        handle.join().unwrap();
+        assert!(reseeding.policy().1.is_raised());
+        let mut reseeded = crate::rngs::StdRng::from_rng(StepRng::new(0, 0)).unwrap();
+        assert_eq!(reseeding.next_u32(), reseeded.next_u32());
+        assert!(!reseeding.policy().1.is_raised());
+        for _ in 0..100 {
+            assert_eq!(reseeding.next_u64(), reseeded.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_reseed_flag_failed() {
+        use super::super::ReseedFlag;
+        use crate::rngs::mock::SequenceRng;
+        use crate::RngCore;
+
+        // A failed reseed keeps the request, and the PRNG its state
+        let flag = ReseedFlag::new();
+        let failing = SequenceRng::from_bytes(&[][..]);
+        let rng = Core::seed_from_u64(3);
+        let mut reseeding = ReseedingRng::with_policy(rng, 0, failing, flag.clone());
+        let mut unreseeded = crate::rngs::StdRng::seed_from_u64(3);
+        assert_eq!(reseeding.next_u32(), unreseeded.next_u32());
+        flag.raise();
+        // The buffered values are discarded, continuing with the next block
+        let mut skipped = [0u32; 63];
+        unreseeded.fill(&mut skipped[..]);
+        assert_eq!(reseeding.next_u32(), unreseeded.next_u32());
+        assert!(flag.is_raised());
+        assert!(reseeding.policy().is_raised());
+    }
 }
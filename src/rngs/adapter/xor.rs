@@ -0,0 +1,156 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper combining the output of two RNGs.
+
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// An RNG combining the output of two RNGs via exclusive or (XOR).
+///
+/// If the two RNGs are independent, the output is at least as unpredictable
+/// as that of the stronger RNG: e.g. combining a hardware RNG with a CSPRNG
+/// protects against a (possibly undetectable) failure or bias of the
+/// hardware RNG, while the hardware RNG protects against a compromised
+/// CSPRNG state. Note that combining two RNGs with correlated output, e.g.
+/// two copies of the same generator, may yield *weaker* output.
+///
+/// All [`RngCore`] methods call the equivalent method of each RNG, then
+/// combine the results.
+///
+/// `XorRng` implements [`CryptoRng`] only if both RNGs do: the output is only
+/// as unpredictable as the stronger RNG if the two are independent, which
+/// cannot be assumed for a non-cryptographic RNG, whose output may well be
+/// predicted from (or correlated with) that of the other RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand::prelude::*;
+/// use rand::rngs::adapter::XorRng;
+/// use rand::rngs::OsRng;
+/// use rand::TryRngCore;
+///
+/// let csprng = StdRng::from_entropy();
+/// let mut rng = XorRng::new(csprng, OsRng.unwrap_err());
+/// let key: [u8; 32] = rng.gen();
+/// ```
+#[derive(Debug, Clone)]
+pub struct XorRng<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: RngCore, B: RngCore> XorRng<A, B> {
+    /// Create a new `XorRng` combining the output of `a` and `b`.
+    pub fn new(a: A, b: B) -> Self {
+        XorRng { a, b }
+    }
+
+    /// Returns the two wrapped RNGs.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+/// XOR `src` into `dest`
+fn xor_into(dest: &mut [u8], src: &[u8]) {
+    for (x, y) in dest.iter_mut().zip(src.iter()) {
+        *x ^= *y;
+    }
+}
+
+impl<A: RngCore, B: RngCore> RngCore for XorRng<A, B> {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        self.a.next_u32() ^ self.b.next_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        self.a.next_u64() ^ self.b.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.a.fill_bytes(dest);
+        let mut buf = [0u8; 64];
+        for chunk in dest.chunks_mut(buf.len()) {
+            let buf = &mut buf[..chunk.len()];
+            self.b.fill_bytes(buf);
+            xor_into(chunk, buf);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.a.try_fill_bytes(dest)?;
+        let mut buf = [0u8; 64];
+        for chunk in dest.chunks_mut(buf.len()) {
+            let buf = &mut buf[..chunk.len()];
+            self.b.try_fill_bytes(buf)?;
+            xor_into(chunk, buf);
+        }
+        Ok(())
+    }
+}
+
+impl<A: CryptoRng, B: CryptoRng> CryptoRng for XorRng<A, B> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+    use crate::SeedableRng;
+
+    #[test]
+    fn test_xor() {
+        let mut rng = XorRng::new(StepRng::new(0b1010, 1), StepRng::new(0b0110, 0));
+        assert_eq!(rng.next_u32(), 0b1100);
+        assert_eq!(rng.next_u64(), 0b1101);
+        let (a, b) = rng.into_inner();
+        assert_eq!(a, StepRng::new(0b1100, 1));
+        assert_eq!(b, StepRng::new(0b0110, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "std_rng")]
+    fn test_xor_fill() {
+        use crate::rngs::StdRng;
+
+        let mut a = StdRng::seed_from_u64(1);
+        let mut b = StdRng::seed_from_u64(2);
+        let mut expected = [0u8; 100];
+        let mut buf = [0u8; 100];
+        a.fill_bytes(&mut expected);
+        b.fill_bytes(&mut buf);
+        xor_into(&mut expected, &buf);
+
+        let mut rng = XorRng::new(StdRng::seed_from_u64(1), StdRng::seed_from_u64(2));
+        let mut bytes = [0u8; 100];
+        rng.fill_bytes(&mut bytes);
+        assert_eq!(bytes, expected);
+
+        let mut rng = XorRng::new(StdRng::seed_from_u64(1), StdRng::seed_from_u64(2));
+        rng.try_fill_bytes(&mut bytes).unwrap();
+        assert_eq!(bytes, expected);
+
+        // XOR with a copy of the same generator cancels out
+        let mut rng = XorRng::new(StdRng::seed_from_u64(3), StdRng::seed_from_u64(3));
+        rng.fill_bytes(&mut bytes);
+        assert!(bytes.iter().all(|&x| x == 0));
+    }
+
+    #[test]
+    #[cfg(feature = "std_rng")]
+    fn test_xor_crypto_rng() {
+        use crate::rngs::StdRng;
+
+        // Requires both RNGs to be `CryptoRng`
+        fn assert_crypto_rng<R: CryptoRng>(_: &R) {}
+        let rng = XorRng::new(StdRng::seed_from_u64(1), StdRng::seed_from_u64(2));
+        assert_crypto_rng(&rng);
+    }
+}
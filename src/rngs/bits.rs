@@ -0,0 +1,174 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper buffering random bits for small requests.
+
+use core::mem::MaybeUninit;
+
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// A wrapper around an RNG which buffers unused random bits.
+///
+/// [`Rng::gen_bits`] consumes a full `u32` or `u64` per call, regardless of
+/// the number of bits requested. `BitRng` instead keeps the unused bits of
+/// each `u64` in a buffer: [`BitRng::gen_bits`] only draws a new word from
+/// the inner RNG once the buffer is exhausted, thus e.g. 64 calls to
+/// `gen_bits(1)` consume a single `u64`. Since inherent methods take
+/// precedence, calling `gen_bits` on a `BitRng` always uses the buffer.
+///
+/// Bits are taken from the buffer starting with the least significant bit.
+/// All [`RngCore`] methods are forwarded to the inner RNG and neither use
+/// nor affect the buffer.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::{mock::StepRng, BitRng};
+///
+/// let mut rng = BitRng::new(StepRng::new(0b1011_0110, 0));
+/// assert_eq!(rng.gen_bits(4), 0b0110);
+/// assert_eq!(rng.gen_bits(2), 0b11);
+/// assert_eq!(rng.gen_bits(2), 0b10);
+/// ```
+///
+/// [`Rng::gen_bits`]: crate::Rng::gen_bits
+#[derive(Debug, Clone)]
+pub struct BitRng<R> {
+    rng: R,
+    buf: u64,
+    bits: u32,
+}
+
+impl<R: RngCore> BitRng<R> {
+    /// Create a new `BitRng` wrapping `rng`, with an empty buffer.
+    pub fn new(rng: R) -> Self {
+        BitRng { rng, buf: 0, bits: 0 }
+    }
+
+    /// Return `n` random bits as the low bits of a `u64`; the high bits are
+    /// zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > 64`.
+    pub fn gen_bits(&mut self, n: u32) -> u64 {
+        assert!(n <= 64, "cannot generate more than 64 bits");
+        if n <= self.bits {
+            let value = self.buf & mask(n);
+            self.buf = self.buf.checked_shr(n).unwrap_or(0);
+            self.bits -= n;
+            return value;
+        }
+        // Use all buffered bits, then the low bits of a new word
+        let need = n - self.bits;
+        let word = self.rng.next_u64();
+        let value = self.buf | (word & mask(need)) << self.bits;
+        self.buf = word.checked_shr(need).unwrap_or(0);
+        self.bits = 64 - need;
+        value
+    }
+
+    /// Returns the number of buffered bits.
+    #[inline]
+    pub fn buffered_bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Returns a mutable reference to the inner RNG.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.rng
+    }
+
+    /// Returns the inner RNG, discarding any buffered bits.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.rng
+    }
+}
+
+/// A mask of the `n <= 64` low bits
+#[inline(always)]
+fn mask(n: u32) -> u64 {
+    u64::MAX.checked_shr(64 - n).unwrap_or(0)
+}
+
+impl<R: RngCore> RngCore for BitRng<R> {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+
+    fn fill_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        self.rng.fill_bytes_uninit(dest)
+    }
+}
+
+impl<R: CryptoRng> CryptoRng for BitRng<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+
+    #[test]
+    fn test_bits() {
+        let mut rng = BitRng::new(StepRng::new(0x0123_4567_89ab_cdef, 1));
+        assert_eq!(rng.gen_bits(0), 0);
+        assert_eq!(rng.buffered_bits(), 0);
+        assert_eq!(rng.gen_bits(4), 0xf);
+        assert_eq!(rng.buffered_bits(), 60);
+        assert_eq!(rng.gen_bits(56), 0x12_3456_789a_bcde);
+        assert_eq!(rng.gen_bits(0), 0);
+        // Crosses a word boundary: 4 buffered bits, then 4 from the next word
+        assert_eq!(rng.gen_bits(8), 0x00);
+        assert_eq!(rng.buffered_bits(), 60);
+        assert_eq!(rng.gen_bits(60), 0x012_3456_789a_bcdf);
+        assert_eq!(rng.gen_bits(64), 0x0123_4567_89ab_cdf1);
+        assert_eq!(rng.buffered_bits(), 0);
+
+        // RngCore methods bypass the buffer
+        assert_eq!(rng.gen_bits(60), 0x123_4567_89ab_cdf2);
+        assert_eq!(rng.next_u64(), 0x0123_4567_89ab_cdf3);
+        assert_eq!(rng.gen_bits(4), 0);
+        assert_eq!(rng.into_inner().next_u64(), 0x0123_4567_89ab_cdf4);
+    }
+
+    #[test]
+    fn test_bits_count() {
+        let mut rng = BitRng::new(crate::test::rng(153));
+        let mut ones = 0;
+        for i in 0..6400 {
+            let n = i % 65;
+            let x = rng.gen_bits(n);
+            assert!(n == 64 || x >> n == 0);
+            ones += x.count_ones();
+        }
+        // 6400 * 32 = 204800 bits requested
+        assert!((101_500..103_300).contains(&ones));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bits_invalid() {
+        BitRng::new(StepRng::new(0, 1)).gen_bits(65);
+    }
+}
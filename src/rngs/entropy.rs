@@ -0,0 +1,182 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A seeder combining several entropy sources.
+
+use core::fmt;
+
+use rand_chacha::ChaCha20Rng;
+use rand_core::{CryptoRng, Error, MixEntropy, OsRng, RngCore, SeedableRng, TryRngCore};
+
+use super::JitterRng;
+
+/// A seeder combining the OS random number generator, timing jitter and
+/// optional user-provided bytes.
+///
+/// Construction with [`EntropySeeder::new`] collects 32 bytes from [`OsRng`]
+/// and 32 bytes of timing jitter of the CPU via [`JitterRng`], then mixes
+/// both into the key of a ChaCha20 generator, as [`MixEntropy`] does. Further
+/// entropy, e.g. device serial numbers or user input timings, may be mixed in
+/// via [`EntropySeeder::with_bytes`] or [`MixEntropy::mix_entropy`]. Output
+/// is then generated by the ChaCha20 generator.
+///
+/// As each source is mixed into the key, the output is unpredictable as long
+/// as any one source is; this is intended for platforms where no single
+/// source is fully trusted. If a source fails, e.g. [`OsRng`] returns an error
+/// or the timer fails the health tests of [`JitterRng`], the remaining sources
+/// are used alone, and [`EntropySeeder::has_os_entropy`] respectively
+/// [`EntropySeeder::has_jitter_entropy`] returns `false`.
+///
+/// `EntropySeeder` does not implement `Clone`, since a clone would produce
+/// the same output as the original.
+///
+/// This type requires the crate features `jitter` and `std_rng`.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::{EntropySeeder, StdRng};
+/// use rand::SeedableRng;
+///
+/// let seeder = EntropySeeder::new().with_bytes(b"device serial 1234");
+/// let rng = StdRng::from_rng(seeder).unwrap();
+/// ```
+pub struct EntropySeeder {
+    rng: ChaCha20Rng,
+    os_entropy: bool,
+    jitter_entropy: bool,
+}
+
+impl EntropySeeder {
+    /// Create a new seeder from [`OsRng`] and timing jitter.
+    pub fn new() -> Self {
+        let mut seed = <ChaCha20Rng as SeedableRng>::Seed::default();
+        let os_entropy = match OsRng.try_fill(&mut seed) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("EntropySeeder: OsRng failed: {}", e);
+                let _ = e;
+                false
+            }
+        };
+        let mut rng = ChaCha20Rng::from_seed(seed);
+
+        let mut jitter = [0u8; 32];
+        let jitter_entropy = match JitterRng::new().and_then(|mut j| j.try_fill(&mut jitter)) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("EntropySeeder: JitterRng failed: {}", e);
+                let _ = e;
+                false
+            }
+        };
+        if jitter_entropy {
+            rng.mix_entropy(&jitter);
+        }
+        EntropySeeder { rng, os_entropy, jitter_entropy }
+    }
+
+    /// Mix `bytes` into the seeder, as [`MixEntropy::mix_entropy`] does.
+    pub fn with_bytes(mut self, bytes: &[u8]) -> Self {
+        self.rng.mix_entropy(bytes);
+        self
+    }
+
+    /// Returns `true` if [`OsRng`] contributed to the seeder.
+    #[inline]
+    pub fn has_os_entropy(&self) -> bool {
+        self.os_entropy
+    }
+
+    /// Returns `true` if [`JitterRng`] contributed to the seeder.
+    #[inline]
+    pub fn has_jitter_entropy(&self) -> bool {
+        self.jitter_entropy
+    }
+}
+
+impl Default for EntropySeeder {
+    fn default() -> Self {
+        EntropySeeder::new()
+    }
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for EntropySeeder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("EntropySeeder")
+            .field("os_entropy", &self.os_entropy)
+            .field("jitter_entropy", &self.jitter_entropy)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RngCore for EntropySeeder {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+impl MixEntropy for EntropySeeder {
+    #[inline]
+    fn mix_entropy(&mut self, entropy: &[u8]) {
+        self.rng.mix_entropy(entropy)
+    }
+}
+
+impl CryptoRng for EntropySeeder {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::StdRng;
+
+    #[test]
+    fn test_entropy_seeder() {
+        let a = EntropySeeder::new();
+        let b = EntropySeeder::new();
+        assert!(a.has_os_entropy());
+        assert_eq!(a.has_jitter_entropy(), b.has_jitter_entropy());
+        let x = StdRng::from_rng(a).unwrap().next_u64();
+        assert_ne!(StdRng::from_rng(b).unwrap().next_u64(), x);
+    }
+
+    #[test]
+    fn test_entropy_seeder_bytes() {
+        let seeder = || EntropySeeder {
+            rng: ChaCha20Rng::seed_from_u64(1),
+            os_entropy: true,
+            jitter_entropy: true,
+        };
+
+        // User-provided bytes change the output
+        let mut a = seeder().with_bytes(b"1234");
+        let mut b = seeder();
+        b.mix_entropy(b"1235");
+        let mut c = seeder().with_bytes(b"1234");
+        let x = a.next_u64();
+        assert_ne!(b.next_u64(), x);
+        assert_eq!(c.next_u64(), x);
+    }
+}
@@ -0,0 +1,327 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An interface to the random number generator instructions of the CPU.
+
+use rand_core::{Error, TryCryptoRng, TryRngCore};
+
+/// Number of attempts of a random instruction before giving up, as
+/// recommended by Intel's DRNG Software Implementation Guide
+const RETRY_LIMIT: usize = 10;
+/// Number of attempts of a seed instruction, which fail more often as they
+/// wait for fresh entropy
+const SEED_RETRY_LIMIT: usize = 1024;
+
+/// A random number generator using the random number instructions of the CPU.
+///
+/// Supported instructions are RDRAND and RDSEED on x86 and x86-64, and RNDR
+/// and RNDRRS on AArch64. Availability is detected at run-time: on x86 via
+/// CPUID, on AArch64 via the `std` feature detection (without `std`, only if
+/// the `rand` target feature is enabled at compile time). On other targets
+/// the constructors always return `None`.
+///
+/// [`HardwareRng::new`] uses RDRAND (resp. RNDR), which returns the output of
+/// a CSPRNG reseeded by the hardware entropy source at short intervals.
+/// [`HardwareRng::new_seeder`] uses RDSEED (resp. RNDRRS), returning output
+/// of the entropy source itself; this is slower, but fits better as the
+/// reseeder of a [`ReseedingRng`]. Both retry failed instructions a few times
+/// (the seed instructions waiting for the entropy source in between), then
+/// fail. Thus `HardwareRng` only implements [`TryRngCore`]; use
+/// [`TryRngCore::unwrap_err`] to get an [`RngCore`] panicking on error.
+///
+/// Construction also checks that the instruction does not return a constant,
+/// as seen with some AMD CPUs after resuming from suspend.
+///
+/// Note that this trusts the CPU vendor: consider mixing hardware entropy
+/// with other sources, e.g. via [`MixEntropy`](rand_core::MixEntropy).
+///
+/// This type requires the crate feature `hardware_rng`. Using it on AArch64
+/// requires Rust 1.60 or newer (for `is_aarch64_feature_detected!`).
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::adapter::ReseedingRng;
+/// use rand::rngs::HardwareRng;
+/// use rand::{RngCore, SeedableRng};
+/// use rand_chacha::ChaCha20Core;
+///
+/// if let Some(mut seeder) = HardwareRng::new_seeder() {
+///     let core = ChaCha20Core::try_from_rng(&mut seeder).unwrap();
+///     let mut rng = ReseedingRng::new(core, 1 << 16, seeder);
+///     let _ = rng.next_u64();
+/// }
+/// ```
+///
+/// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
+/// [`RngCore`]: rand_core::RngCore
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HardwareRng {
+    seeder: bool,
+}
+
+impl HardwareRng {
+    /// Create a new `HardwareRng` using RDRAND (resp. RNDR), if available.
+    pub fn new() -> Option<HardwareRng> {
+        HardwareRng::with_source(false)
+    }
+
+    /// Create a new `HardwareRng` using RDSEED (resp. RNDRRS), if available.
+    pub fn new_seeder() -> Option<HardwareRng> {
+        HardwareRng::with_source(true)
+    }
+
+    /// Returns `true` if this uses RDSEED (resp. RNDRRS).
+    #[inline]
+    pub fn is_seeder(&self) -> bool {
+        self.seeder
+    }
+
+    fn with_source(seeder: bool) -> Option<HardwareRng> {
+        let rng = HardwareRng { seeder };
+        if !arch::is_available(seeder) {
+            return None;
+        }
+        // Check the instruction does not only return a constant
+        let first = rng.try_next()?;
+        for _ in 0..8 {
+            if rng.try_next()? != first {
+                return Some(rng);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn try_next(&self) -> Option<u64> {
+        // SAFETY: `self` is only constructed if the instruction is available
+        unsafe { arch::next(self.seeder) }
+    }
+}
+
+fn failed() -> Error {
+    #[cfg(feature = "std")]
+    {
+        Error::new("hardware RNG failed")
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        core::num::NonZeroU32::new(Error::CUSTOM_START).unwrap().into()
+    }
+}
+
+impl TryRngCore for HardwareRng {
+    type Error = Error;
+
+    #[inline]
+    fn try_next_u32(&mut self) -> Result<u32, Error> {
+        self.try_next_u64().map(|x| x as u32)
+    }
+
+    #[inline]
+    fn try_next_u64(&mut self) -> Result<u64, Error> {
+        self.try_next().ok_or_else(failed)
+    }
+
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.try_next().ok_or_else(failed)?.to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
+
+impl TryCryptoRng for HardwareRng {}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod arch {
+    use super::{RETRY_LIMIT, SEED_RETRY_LIMIT};
+    #[cfg(target_arch = "x86")] use core::arch::x86 as x86;
+    #[cfg(target_arch = "x86_64")] use core::arch::x86_64 as x86;
+
+    pub(super) fn is_available(seeder: bool) -> bool {
+        if seeder && cfg!(target_feature = "rdseed") || !seeder && cfg!(target_feature = "rdrand") {
+            return true;
+        }
+        // CPUID is not available in SGX enclaves
+        if cfg!(target_env = "sgx") {
+            return false;
+        }
+        #[cfg(target_arch = "x86")]
+        {
+            if !x86::has_cpuid() {
+                return false;
+            }
+        }
+        // SAFETY: CPUID is available (the intrinsics are safe in newer Rust)
+        #[allow(unused_unsafe)]
+        unsafe {
+            if seeder {
+                x86::__get_cpuid_max(0).0 >= 7 && (x86::__cpuid_count(7, 0).ebx >> 18) & 1 == 1
+            } else {
+                (x86::__cpuid(1).ecx >> 30) & 1 == 1
+            }
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The instruction must be available.
+    pub(super) unsafe fn next(seeder: bool) -> Option<u64> {
+        if seeder {
+            rdseed()
+        } else {
+            rdrand()
+        }
+    }
+
+    #[target_feature(enable = "rdrand")]
+    unsafe fn rdrand() -> Option<u64> {
+        for _ in 0..RETRY_LIMIT {
+            #[cfg(target_arch = "x86_64")]
+            {
+                let mut x = 0;
+                if x86::_rdrand64_step(&mut x) == 1 {
+                    return Some(x);
+                }
+            }
+            #[cfg(target_arch = "x86")]
+            {
+                let (mut lo, mut hi) = (0, 0);
+                if x86::_rdrand32_step(&mut lo) == 1 && x86::_rdrand32_step(&mut hi) == 1 {
+                    return Some(u64::from(hi) << 32 | u64::from(lo));
+                }
+            }
+        }
+        None
+    }
+
+    #[target_feature(enable = "rdseed")]
+    unsafe fn rdseed() -> Option<u64> {
+        for _ in 0..SEED_RETRY_LIMIT {
+            #[cfg(target_arch = "x86_64")]
+            {
+                let mut x = 0;
+                if x86::_rdseed64_step(&mut x) == 1 {
+                    return Some(x);
+                }
+            }
+            #[cfg(target_arch = "x86")]
+            {
+                let (mut lo, mut hi) = (0, 0);
+                if x86::_rdseed32_step(&mut lo) == 1 && x86::_rdseed32_step(&mut hi) == 1 {
+                    return Some(u64::from(hi) << 32 | u64::from(lo));
+                }
+            }
+            x86::_mm_pause();
+        }
+        None
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod arch {
+    use super::{RETRY_LIMIT, SEED_RETRY_LIMIT};
+    use core::arch::asm;
+
+    pub(super) fn is_available(_seeder: bool) -> bool {
+        #[cfg(target_feature = "rand")]
+        {
+            true
+        }
+        #[cfg(all(not(target_feature = "rand"), feature = "std"))]
+        {
+            std::arch::is_aarch64_feature_detected!("rand")
+        }
+        #[cfg(all(not(target_feature = "rand"), not(feature = "std")))]
+        {
+            false
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The instruction must be available.
+    pub(super) unsafe fn next(seeder: bool) -> Option<u64> {
+        let limit = if seeder { SEED_RETRY_LIMIT } else { RETRY_LIMIT };
+        for _ in 0..limit {
+            let x: u64;
+            let nzcv: u64;
+            // RNDR and RNDRRS, by encoding since older assemblers lack the
+            // names; on failure they set the Z flag
+            if seeder {
+                asm!(
+                    "mrs {x}, s3_3_c2_c4_1",
+                    "mrs {nzcv}, nzcv",
+                    x = out(reg) x,
+                    nzcv = out(reg) nzcv,
+                    options(nomem, nostack),
+                );
+            } else {
+                asm!(
+                    "mrs {x}, s3_3_c2_c4_0",
+                    "mrs {nzcv}, nzcv",
+                    x = out(reg) x,
+                    nzcv = out(reg) nzcv,
+                    options(nomem, nostack),
+                );
+            }
+            if (nzcv >> 30) & 1 == 0 {
+                return Some(x);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+mod arch {
+    pub(super) fn is_available(_seeder: bool) -> bool {
+        false
+    }
+
+    pub(super) unsafe fn next(_seeder: bool) -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand_core::RngCore;
+
+    #[test]
+    fn test_hardware_rng() {
+        // Not all CPUs running the tests have the instructions
+        for rng in [HardwareRng::new(), HardwareRng::new_seeder()].iter() {
+            if let Some(mut rng) = *rng {
+                let x = rng.try_next_u64().unwrap();
+                assert_ne!(x, rng.try_next_u64().unwrap());
+                let mut buf = [0u8; 19];
+                rng.try_fill(&mut buf).unwrap();
+                assert!(buf.iter().any(|&b| b != 0));
+
+                let mut rng = rng.unwrap_err();
+                assert_ne!(rng.next_u32(), rng.next_u32());
+            }
+        }
+        if let Some(rng) = HardwareRng::new() {
+            assert!(!rng.is_seeder());
+        }
+        if let Some(rng) = HardwareRng::new_seeder() {
+            assert!(rng.is_seeder());
+        }
+    }
+
+    #[cfg(all(target_arch = "x86_64", target_feature = "rdrand"))]
+    #[test]
+    fn test_hardware_rng_available() {
+        assert!(HardwareRng::new().is_some());
+    }
+}
@@ -0,0 +1,402 @@
+// Copyright 2023 Developers of the Rand project.
+// Copyright 2014 Stephan Mueller <smueller@chronox.de>
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An entropy source based on CPU timing jitter, after Stephan Mueller's
+//! [Jitterentropy](http://www.chronox.de/jent.html).
+
+use core::fmt;
+use std::time::Instant;
+
+use rand_core::{Error, TryCryptoRng, TryRngCore};
+
+/// Size of the memory accessed between timer reads
+const MEMORY_SIZE: usize = 2048;
+/// Offset between memory accesses
+const MEMORY_BLOCK_SIZE: usize = 32;
+/// Number of samples taken by the startup test
+const TEST_LOOP_COUNT: usize = 300;
+/// Number of samples discarded before the startup test, to warm up caches
+const CLEAR_CACHE: usize = 100;
+/// Number of consecutive stuck samples after which generation fails
+const MAX_STUCK_RUN: u32 = 1024;
+
+/// A true random number generator based on jitter in the CPU execution time,
+/// and jitter in memory access time.
+///
+/// `JitterRng` measures the duration of a short computation with a
+/// high-resolution timer. Due to caches, pipelines, interrupts and
+/// frequency scaling, this duration varies unpredictably. Each [`try_next_u64`]
+/// folds the timing variations of 64 (see [`JitterRng::set_rounds`]) samples
+/// into the output.
+///
+/// This is much slower than [`OsRng`]: its purpose is to provide entropy
+/// where no OS random number generator is available or trusted, e.g. in
+/// unikernels or some sandboxes. It is best used to seed (or, as the
+/// reseeder of a [`ReseedingRng`], reseed) a fast CSPRNG.
+///
+/// # Health tests
+///
+/// [`JitterRng::new`] runs a startup test of the timer, failing with a
+/// [`TimerError`] if the timer is missing, too coarse, not monotonic, or
+/// if its variations are too small to be a plausible entropy source.
+/// During generation, samples which are *stuck* — with zero first, second or
+/// third derivative of the time — are not counted towards the rounds. If
+/// 1024 consecutive samples are stuck, generation fails with
+/// [`TimerError::TooManyStuck`]. Thus `JitterRng` only implements
+/// [`TryRngCore`]; use [`TryRngCore::unwrap_err`] to get an [`RngCore`]
+/// panicking on error.
+///
+/// This type requires the crate feature `jitter`, which implies `std`.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::{JitterRng, StdRng};
+/// use rand::SeedableRng;
+///
+/// match JitterRng::new() {
+///     Ok(mut jitter) => {
+///         let _rng = StdRng::try_from_rng(&mut jitter).unwrap();
+///     }
+///     Err(err) => eprintln!("no timing jitter available: {}", err),
+/// }
+/// ```
+///
+/// [`try_next_u64`]: TryRngCore::try_next_u64
+/// [`RngCore`]: rand_core::RngCore
+/// [`OsRng`]: crate::rngs::OsRng
+/// [`ReseedingRng`]: crate::rngs::adapter::ReseedingRng
+#[derive(Clone)]
+pub struct JitterRng {
+    data: u64,
+    rounds: u32,
+    timer: fn() -> u64,
+    prev_time: u64,
+    last_delta: i64,
+    last_delta2: i64,
+    mem_prev_index: usize,
+    mem: [u8; MEMORY_SIZE],
+}
+
+/// An error in the timer used by [`JitterRng`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimerError {
+    /// No timer available.
+    NoTimer,
+    /// Timer too coarse to use as an entropy source.
+    CoarseTimer,
+    /// Timer is not monotonically increasing.
+    NotMonotonic,
+    /// Variations of deltas of time too small.
+    TinyVariations,
+    /// Too many stuck results (indicating no added entropy).
+    TooManyStuck,
+}
+
+impl std::error::Error for TimerError {}
+
+impl fmt::Display for TimerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            TimerError::NoTimer => "no timer available",
+            TimerError::CoarseTimer => "coarse timer",
+            TimerError::NotMonotonic => "timer not monotonic",
+            TimerError::TinyVariations => "time delta variations too small",
+            TimerError::TooManyStuck => "too many stuck results",
+        })
+    }
+}
+
+impl From<TimerError> for Error {
+    fn from(err: TimerError) -> Error {
+        Error::new(err)
+    }
+}
+
+/// The time in nanoseconds, as used by [`JitterRng::new`]
+///
+/// This is measured by the monotonic clock [`Instant`] (unlike the system
+/// time, which may jump, e.g. when adjusted by NTP), from the first call on
+/// the current thread. One is added since a zero time means no timer.
+fn get_nstime() -> u64 {
+    std::thread_local!(static START: Instant = Instant::now());
+    let dur = START.with(|start| start.elapsed());
+    (dur.as_secs() << 30 | u64::from(dur.subsec_nanos())) + 1
+}
+
+impl JitterRng {
+    /// Create a new `JitterRng` using the monotonic clock [`Instant`] as
+    /// timer, after testing the timer.
+    pub fn new() -> Result<JitterRng, TimerError> {
+        JitterRng::new_with_timer(get_nstime)
+    }
+
+    /// Create a new `JitterRng` using the given timer, after testing it.
+    ///
+    /// The timer should return monotonically increasing values, ideally with
+    /// nanosecond resolution or better, e.g. a cycle counter.
+    pub fn new_with_timer(timer: fn() -> u64) -> Result<JitterRng, TimerError> {
+        let mut rng = JitterRng {
+            data: 0,
+            rounds: 64,
+            timer,
+            prev_time: 0,
+            last_delta: 0,
+            last_delta2: 0,
+            mem_prev_index: 0,
+            mem: [0; MEMORY_SIZE],
+        };
+        rng.test_timer()?;
+        // Fill `data`, `prev_time` and the last deltas with initial values
+        rng.gen_entropy()?;
+        Ok(rng)
+    }
+
+    /// Set the number of samples folded into each `u64` of output.
+    ///
+    /// The default of 64 assumes at least one bit of entropy per sample.
+    /// Fewer rounds are faster, but are only safe if the timer is known to
+    /// provide more entropy per sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rounds` is zero.
+    pub fn set_rounds(&mut self, rounds: u32) {
+        assert!(rounds > 0, "JitterRng: rounds must be non-zero");
+        self.rounds = rounds;
+    }
+
+    /// Returns the number of samples folded into each `u64` of output.
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    /// Fold the time delta into `data` with a Fibonacci LFSR of polynomial
+    /// x^64 + x^61 + x^56 + x^31 + x^28 + x^23 + 1.
+    fn lfsr_time(&mut self, time: u64) {
+        let mut data = self.data;
+        for i in 1..65 {
+            data ^= (time << (64 - i)) >> 63;
+            data ^= (data >> 63) & 1;
+            data ^= (data >> 60) & 1;
+            data ^= (data >> 55) & 1;
+            data ^= (data >> 30) & 1;
+            data ^= (data >> 27) & 1;
+            data ^= (data >> 22) & 1;
+            data = data.rotate_left(1);
+        }
+        self.data = data;
+    }
+
+    /// Access memory, in a pattern exceeding the L1 cache line size, for a
+    /// number of iterations depending on the current state.
+    fn memaccess(&mut self) {
+        let iterations = 128 + (self.data & 0x7f) as usize;
+        let mut index = self.mem_prev_index;
+        for _ in 0..iterations {
+            index = (index + MEMORY_BLOCK_SIZE - 1) % MEMORY_SIZE;
+            self.mem[index] = self.mem[index].wrapping_add(1);
+        }
+        self.mem_prev_index = index;
+    }
+
+    /// Take one sample, returning `true` unless it is stuck.
+    fn measure_jitter(&mut self) -> bool {
+        self.memaccess();
+        let time = (self.timer)();
+        let delta = time.wrapping_sub(self.prev_time) as i64;
+        self.prev_time = time;
+        self.lfsr_time(delta as u64);
+
+        let delta2 = delta.wrapping_sub(self.last_delta);
+        let delta3 = delta2.wrapping_sub(self.last_delta2);
+        self.last_delta = delta;
+        self.last_delta2 = delta2;
+        delta != 0 && delta2 != 0 && delta3 != 0
+    }
+
+    /// Generate 64 bits of output, folding in `rounds` samples which are not
+    /// stuck.
+    fn gen_entropy(&mut self) -> Result<u64, TimerError> {
+        let mut rounds = 0;
+        let mut stuck_run = 0;
+        while rounds < self.rounds {
+            if self.measure_jitter() {
+                rounds += 1;
+                stuck_run = 0;
+            } else {
+                stuck_run += 1;
+                if stuck_run >= MAX_STUCK_RUN {
+                    return Err(TimerError::TooManyStuck);
+                }
+            }
+        }
+        Ok(self.data)
+    }
+
+    /// Test the timer, as done by [`JitterRng::new`].
+    fn test_timer(&mut self) -> Result<(), TimerError> {
+        let mut delta_sum = 0u64;
+        let mut old_delta = 0i64;
+        let mut time_backwards = 0;
+        let mut count_stuck = 0;
+
+        for i in 0..(CLEAR_CACHE + TEST_LOOP_COUNT) {
+            let time = (self.timer)();
+            self.memaccess();
+            self.lfsr_time(time);
+            let time2 = (self.timer)();
+
+            if time == 0 || time2 == 0 {
+                return Err(TimerError::NoTimer);
+            }
+            let delta = time2.wrapping_sub(time) as i64;
+            if delta == 0 {
+                return Err(TimerError::CoarseTimer);
+            }
+            if i < CLEAR_CACHE {
+                continue;
+            }
+            if delta < 0 {
+                time_backwards += 1;
+            }
+            let delta2 = delta.wrapping_sub(old_delta);
+            if delta2 == 0 {
+                count_stuck += 1;
+            }
+            delta_sum = delta_sum.saturating_add(delta2.unsigned_abs());
+            old_delta = delta;
+        }
+
+        if time_backwards > 3 {
+            return Err(TimerError::NotMonotonic);
+        }
+        if delta_sum < TEST_LOOP_COUNT as u64 {
+            return Err(TimerError::TinyVariations);
+        }
+        if count_stuck > TEST_LOOP_COUNT * 9 / 10 {
+            return Err(TimerError::TooManyStuck);
+        }
+        Ok(())
+    }
+}
+
+// Custom Debug implementation that does not expose the internal state
+impl fmt::Debug for JitterRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("JitterRng")
+            .field("rounds", &self.rounds)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TryRngCore for JitterRng {
+    type Error = TimerError;
+
+    fn try_next_u32(&mut self) -> Result<u32, TimerError> {
+        self.gen_entropy().map(|x| x as u32)
+    }
+
+    fn try_next_u64(&mut self) -> Result<u64, TimerError> {
+        self.gen_entropy()
+    }
+
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), TimerError> {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.gen_entropy()?.to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+        Ok(())
+    }
+}
+
+impl TryCryptoRng for JitterRng {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand_core::RngCore;
+    use core::sync::atomic::{AtomicU64, Ordering};
+
+    #[test]
+    fn test_get_nstime() {
+        let t = get_nstime();
+        assert!(t > 0);
+        assert!(get_nstime() >= t);
+    }
+
+    #[test]
+    fn test_jitter_rng() {
+        // The timer of the machine running the tests may be unsuitable
+        let mut rng = match JitterRng::new() {
+            Ok(rng) => rng,
+            Err(_) => return,
+        };
+        assert_eq!(rng.rounds(), 64);
+        let x = rng.try_next_u64().unwrap();
+        assert_ne!(x, rng.try_next_u64().unwrap());
+        let mut buf = [0u8; 13];
+        rng.try_fill(&mut buf).unwrap();
+        assert!(buf.iter().any(|&b| b != 0));
+
+        rng.set_rounds(8);
+        let mut rng = rng.unwrap_err();
+        assert_ne!(rng.next_u64(), rng.next_u64());
+    }
+
+    #[test]
+    fn test_jitter_rng_reseeding() {
+        use crate::rngs::adapter::ReseedingRng;
+        use rand_chacha::ChaCha20Core;
+        use rand_core::SeedableRng;
+
+        let mut jitter = match JitterRng::new() {
+            Ok(jitter) => jitter,
+            Err(_) => return,
+        };
+        let core = ChaCha20Core::try_from_rng(&mut jitter).unwrap();
+        let mut rng = ReseedingRng::new(core, 64, jitter);
+        let mut buf = [0u8; 256];
+        rng.fill_bytes(&mut buf);
+        assert!(buf.iter().any(|&b| b != 0));
+    }
+
+    #[test]
+    fn test_jitter_timer_errors() {
+        fn zero() -> u64 {
+            0
+        }
+        assert_eq!(JitterRng::new_with_timer(zero).unwrap_err(), TimerError::NoTimer);
+
+        fn constant() -> u64 {
+            1
+        }
+        assert_eq!(JitterRng::new_with_timer(constant).unwrap_err(), TimerError::CoarseTimer);
+
+        // Constant steps have no variations
+        static COUNTER: AtomicU64 = AtomicU64::new(1);
+        fn counter() -> u64 {
+            COUNTER.fetch_add(10, Ordering::Relaxed)
+        }
+        assert_eq!(JitterRng::new_with_timer(counter).unwrap_err(), TimerError::TinyVariations);
+
+        static BACKWARDS: AtomicU64 = AtomicU64::new(1 << 40);
+        fn backwards() -> u64 {
+            let t = BACKWARDS.fetch_sub(3, Ordering::Relaxed);
+            t + t % 2
+        }
+        assert_eq!(JitterRng::new_with_timer(backwards).unwrap_err(), TimerError::NotMonotonic);
+    }
+
+    #[test]
+    fn test_timer_error_display() {
+        let err = Error::from(TimerError::CoarseTimer);
+        assert_eq!(std::format!("{}", err), "coarse timer");
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper deferring construction of an RNG until first use.
+
+use core::fmt;
+use core::mem::MaybeUninit;
+
+use rand_core::{CryptoRng, Error, RngCore};
+#[cfg(feature = "getrandom")]
+use rand_core::SeedableRng;
+
+/// A wrapper around an RNG which is constructed, e.g. seeded, on first use.
+///
+/// Seeding an RNG from [`OsRng`] usually requires a system call. Structs
+/// which embed an RNG but rarely (or never) use it can instead embed a
+/// `LazyRng`, which only seeds the RNG when a value is first requested.
+/// Afterwards, all calls are forwarded to the inner RNG.
+///
+/// [`LazyRng::new`] seeds the RNG via [`SeedableRng::from_entropy`], while
+/// [`LazyRng::from_fn`] uses any function constructing the RNG.
+///
+/// Cloning a `LazyRng` clones the inner RNG, if already constructed;
+/// otherwise, the clone constructs its own RNG on first use.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::{LazyRng, StdRng};
+/// use rand::Rng;
+///
+/// struct Cache {
+///     // Used for random eviction only once the cache is full
+///     rng: LazyRng<StdRng>,
+/// }
+///
+/// let mut cache = Cache { rng: LazyRng::new() };
+/// assert!(!cache.rng.is_initialized());
+/// let slot = cache.rng.gen_range(0..16);
+/// assert!(cache.rng.is_initialized());
+/// ```
+///
+/// [`OsRng`]: rand_core::OsRng
+/// [`SeedableRng::from_entropy`]: rand_core::SeedableRng::from_entropy
+#[derive(Clone)]
+pub struct LazyRng<R> {
+    rng: Option<R>,
+    init: fn() -> R,
+}
+
+impl<R> LazyRng<R> {
+    /// Create a `LazyRng` constructing its RNG via `init` on first use.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::{mock::StepRng, LazyRng};
+    /// use rand::RngCore;
+    ///
+    /// let mut rng = LazyRng::from_fn(|| StepRng::new(7, 1));
+    /// assert_eq!(rng.next_u64(), 7);
+    /// ```
+    pub fn from_fn(init: fn() -> R) -> Self {
+        LazyRng { rng: None, init }
+    }
+
+    /// Returns `true` if the inner RNG has been constructed.
+    #[inline]
+    pub fn is_initialized(&self) -> bool {
+        self.rng.is_some()
+    }
+
+    /// Returns a mutable reference to the inner RNG, constructing it if
+    /// necessary.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut R {
+        let init = self.init;
+        self.rng.get_or_insert_with(init)
+    }
+
+    /// Returns the inner RNG, if already constructed.
+    #[inline]
+    pub fn into_inner(self) -> Option<R> {
+        self.rng
+    }
+}
+
+#[cfg(feature = "getrandom")]
+impl<R: SeedableRng> LazyRng<R> {
+    /// Create a `LazyRng` seeding its RNG via
+    /// [`SeedableRng::from_entropy`] on first use.
+    ///
+    /// # Panics
+    ///
+    /// Use of the RNG panics if seeding fails, as for `from_entropy`.
+    ///
+    /// [`SeedableRng::from_entropy`]: rand_core::SeedableRng::from_entropy
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "getrandom")))]
+    pub fn new() -> Self {
+        LazyRng::from_fn(R::from_entropy)
+    }
+}
+
+#[cfg(feature = "getrandom")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "getrandom")))]
+impl<R: SeedableRng> Default for LazyRng<R> {
+    fn default() -> Self {
+        LazyRng::new()
+    }
+}
+
+impl<R: RngCore> RngCore for LazyRng<R> {
+    #[inline(always)]
+    fn next_u32(&mut self) -> u32 {
+        self.get_mut().next_u32()
+    }
+
+    #[inline(always)]
+    fn next_u64(&mut self) -> u64 {
+        self.get_mut().next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.get_mut().fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.get_mut().try_fill_bytes(dest)
+    }
+
+    fn fill_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        self.get_mut().fill_bytes_uninit(dest)
+    }
+}
+
+impl<R: CryptoRng> CryptoRng for LazyRng<R> {}
+
+impl<R: fmt::Debug> fmt::Debug for LazyRng<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LazyRng").field("rng", &self.rng).finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+
+    #[test]
+    fn test_lazy() {
+        let mut rng = LazyRng::from_fn(|| StepRng::new(1, 1));
+        assert!(!rng.is_initialized());
+        let clone = rng.clone();
+        assert_eq!(rng.next_u32(), 1);
+        assert_eq!(rng.next_u64(), 2);
+        assert!(rng.is_initialized());
+
+        // Once constructed, a clone continues from the same state
+        let mut clone2 = rng.clone();
+        assert_eq!(clone2.next_u64(), 3);
+        assert_eq!(rng.next_u64(), 3);
+        assert!(!clone.is_initialized());
+        assert_eq!(clone.into_inner(), None);
+
+        let mut buf = [0u8; 4];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [4, 0, 0, 0]);
+        assert!(rng.try_fill_bytes(&mut buf).is_ok());
+        assert_eq!(rng.get_mut().next_u64(), 6);
+        assert_eq!(rng.into_inner().map(|mut r| r.next_u64()), Some(7));
+    }
+
+    #[test]
+    #[cfg(all(feature = "getrandom", feature = "std_rng"))]
+    fn test_lazy_entropy() {
+        use crate::rngs::StdRng;
+        let mut a: LazyRng<StdRng> = LazyRng::new();
+        let mut b: LazyRng<StdRng> = LazyRng::default();
+        assert!(!a.is_initialized() && !b.is_initialized());
+        assert_ne!(a.next_u64(), b.next_u64());
+    }
+}
@@ -8,7 +8,9 @@
 
 //! Mock random number generator
 
-use rand_core::{impls, Error, RngCore};
+use rand_core::{impls, RngCore};
+#[cfg(feature = "alloc")] use alloc::vec::Vec;
+#[cfg(feature = "alloc")] use rand_core::Error;
 
 #[cfg(feature = "serde1")]
 use serde::{Serialize, Deserialize};
@@ -17,10 +19,23 @@ use serde::{Serialize, Deserialize};
 ///
 /// This generates an arithmetic sequence (i.e. adds a constant each step)
 /// over a `u64` number, using wrapping arithmetic. If the increment is 0
-/// the generator yields a constant.
+/// the generator yields a constant. Alternatively the sequence may saturate
+/// at the maximum value, see [`StepRng::with_overflow`].
 ///
 /// Other integer types (64-bit and smaller) are produced via cast from `u64`.
 ///
+/// # 32-bit steps
+///
+/// A `StepRng` constructed with [`StepRng::new_u32`] steps over a `u32`
+/// number instead: each `next_u32` yields one step, while `next_u64` combines
+/// two steps, the first in the low 32 bits.
+///
+/// # Byte output
+///
+/// `fill_bytes` writes the little-endian bytes of successive steps (of 8
+/// bytes, or 4 bytes with 32-bit steps). A final partial chunk takes the
+/// low-order bytes of one more step. This is independent of the platform.
+///
 /// Other types are produced via their implementation of [`Rng`](crate::Rng) or
 /// [`Distribution`](crate::distributions::Distribution).
 /// Output values may not be intuitive and may change in future releases but
@@ -43,6 +58,26 @@ use serde::{Serialize, Deserialize};
 pub struct StepRng {
     v: u64,
     a: u64,
+    #[cfg_attr(feature = "serde1", serde(default))]
+    word32: bool,
+    #[cfg_attr(feature = "serde1", serde(default))]
+    overflow: Overflow,
+}
+
+/// Behaviour of a [`StepRng`] when its sequence exceeds the maximum value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum Overflow {
+    /// Wrap around to zero (the default)
+    Wrap,
+    /// Stay at the maximum value
+    Saturate,
+}
+
+impl Default for Overflow {
+    fn default() -> Self {
+        Overflow::Wrap
+    }
 }
 
 impl StepRng {
@@ -52,31 +87,229 @@ impl StepRng {
         StepRng {
             v: initial,
             a: increment,
+            word32: false,
+            overflow: Overflow::Wrap,
         }
     }
+
+    /// Create a `StepRng` with 32-bit steps, yielding an arithmetic sequence
+    /// of `u32` starting with `initial` and incremented by `increment` each
+    /// time.
+    ///
+    /// ```
+    /// use rand::RngCore;
+    /// use rand::rngs::mock::StepRng;
+    ///
+    /// let mut rng = StepRng::new_u32(1, 1);
+    /// assert_eq!(rng.next_u32(), 1);
+    /// assert_eq!(rng.next_u64(), (3 << 32) | 2);
+    /// ```
+    pub fn new_u32(initial: u32, increment: u32) -> Self {
+        StepRng {
+            v: initial.into(),
+            a: increment.into(),
+            word32: true,
+            overflow: Overflow::Wrap,
+        }
+    }
+
+    /// Set the behaviour when the sequence exceeds the maximum value.
+    ///
+    /// ```
+    /// use rand::RngCore;
+    /// use rand::rngs::mock::{Overflow, StepRng};
+    ///
+    /// let mut rng = StepRng::new(u64::MAX - 1, 1).with_overflow(Overflow::Saturate);
+    /// assert_eq!(rng.next_u64(), u64::MAX - 1);
+    /// assert_eq!(rng.next_u64(), u64::MAX);
+    /// assert_eq!(rng.next_u64(), u64::MAX);
+    /// ```
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Return the current value and advance the sequence.
+    #[inline]
+    fn step(&mut self) -> u64 {
+        let result = self.v;
+        let max = if self.word32 { u64::from(u32::MAX) } else { u64::MAX };
+        self.v = match self.v.checked_add(self.a) {
+            Some(v) if v <= max => v,
+            _ => match self.overflow {
+                Overflow::Wrap => self.v.wrapping_add(self.a) & max,
+                Overflow::Saturate => max,
+            },
+        };
+        result
+    }
 }
 
 impl RngCore for StepRng {
     #[inline]
     fn next_u32(&mut self) -> u32 {
-        self.next_u64() as u32
+        self.step() as u32
     }
 
     #[inline]
     fn next_u64(&mut self) -> u64 {
-        let result = self.v;
-        self.v = self.v.wrapping_add(self.a);
-        result
+        if self.word32 {
+            impls::next_u64_via_u32(self)
+        } else {
+            self.step()
+        }
     }
 
     #[inline]
     fn fill_bytes(&mut self, dest: &mut [u8]) {
-        impls::fill_bytes_via_next(self, dest);
+        let chunk_len = if self.word32 { 4 } else { 8 };
+        for chunk in dest.chunks_mut(chunk_len) {
+            let bytes = self.step().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
     }
+}
 
+/// A mock generator returning exactly the given values, in order
+///
+/// A `SequenceRng` is constructed either from a list of `u64` words, via
+/// [`SequenceRng::new`], or from a list of bytes, via
+/// [`SequenceRng::from_bytes`]:
+///
+/// - From words, `next_u64` returns the next word and `next_u32` its low 32
+///   bits (as for [`StepRng`]). `fill_bytes` consumes one word per 8 bytes (or
+///   part thereof), writing its little-endian bytes.
+/// - From bytes, `fill_bytes` copies the next bytes, while `next_u32` and
+///   `next_u64` take the next 4 respectively 8 bytes in little-endian order.
+///
+/// Once the values are exhausted, `try_fill_bytes` returns an error, leaving
+/// the remaining values unused, while the other methods panic.
+///
+/// This type requires the crate feature `alloc`.
+///
+/// # Example
+///
+/// ```
+/// use rand::Rng;
+/// use rand::rngs::mock::SequenceRng;
+///
+/// let mut rng = SequenceRng::new([5, 1 << 63, 1 << 31]);
+/// assert_eq!(rng.gen::<u64>(), 5);
+/// assert_eq!(rng.gen::<f64>(), 0.5);
+/// assert_eq!(rng.gen::<bool>(), true);
+/// assert_eq!(rng.remaining(), 0);
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceRng {
+    values: Sequence,
+    pos: usize,
+}
+
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Sequence {
+    Words(Vec<u64>),
+    Bytes(Vec<u8>),
+}
+
+#[cfg(feature = "alloc")]
+impl SequenceRng {
+    /// Create a `SequenceRng` returning the words `values`.
+    pub fn new<V: Into<Vec<u64>>>(values: V) -> Self {
+        SequenceRng {
+            values: Sequence::Words(values.into()),
+            pos: 0,
+        }
+    }
+
+    /// Create a `SequenceRng` returning the bytes `values`.
+    pub fn from_bytes<V: Into<Vec<u8>>>(values: V) -> Self {
+        SequenceRng {
+            values: Sequence::Bytes(values.into()),
+            pos: 0,
+        }
+    }
+
+    /// Returns the number of words (or bytes, if constructed from bytes)
+    /// not yet used.
+    pub fn remaining(&self) -> usize {
+        let len = match &self.values {
+            Sequence::Words(words) => words.len(),
+            Sequence::Bytes(bytes) => bytes.len(),
+        };
+        len - self.pos
+    }
+
+    fn exhausted() -> Error {
+        #[cfg(feature = "std")]
+        {
+            Error::new("SequenceRng exhausted")
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            core::num::NonZeroU32::new(Error::CUSTOM_START).unwrap().into()
+        }
+    }
+
+    /// Take the next `N` bytes, panicking when exhausted.
+    fn next_le_bytes<const N: usize>(&mut self) -> [u8; N] {
+        let mut buf = [0; N];
+        if let Err(e) = self.try_fill_bytes(&mut buf) {
+            panic!("{}", e);
+        }
+        buf
+    }
+
+    fn next_word(&mut self) -> u64 {
+        match &self.values {
+            Sequence::Words(words) => {
+                let word = *words.get(self.pos).expect("SequenceRng exhausted");
+                self.pos += 1;
+                word
+            }
+            Sequence::Bytes(_) => u64::from_le_bytes(self.next_le_bytes()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl RngCore for SequenceRng {
     #[inline]
+    fn next_u32(&mut self) -> u32 {
+        match self.values {
+            Sequence::Words(_) => self.next_word() as u32,
+            Sequence::Bytes(_) => u32::from_le_bytes(self.next_le_bytes()),
+        }
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.next_word()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(e) = self.try_fill_bytes(dest) {
+            panic!("{}", e);
+        }
+    }
+
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.fill_bytes(dest);
+        match &self.values {
+            Sequence::Words(words) => {
+                let words = words.get(self.pos..self.pos + (dest.len() + 7) / 8);
+                let words = words.ok_or_else(Self::exhausted)?;
+                for (chunk, word) in dest.chunks_mut(8).zip(words) {
+                    chunk.copy_from_slice(&word.to_le_bytes()[..chunk.len()]);
+                }
+                self.pos += words.len();
+            }
+            Sequence::Bytes(bytes) => {
+                let bytes = bytes.get(self.pos..self.pos + dest.len());
+                dest.copy_from_slice(bytes.ok_or_else(Self::exhausted)?);
+                self.pos += dest.len();
+            }
+        }
         Ok(())
     }
 }
@@ -94,6 +327,12 @@ mod tests {
             bincode::deserialize(&bincode::serialize(&some_rng).unwrap()).unwrap();
         assert_eq!(some_rng.v, de_some_rng.v);
         assert_eq!(some_rng.a, de_some_rng.a);
+        assert_eq!(some_rng, de_some_rng);
+
+        let some_rng = StepRng::new_u32(1, 2).with_overflow(super::Overflow::Saturate);
+        let de_some_rng: StepRng =
+            bincode::deserialize(&bincode::serialize(&some_rng).unwrap()).unwrap();
+        assert_eq!(some_rng, de_some_rng);
 
     }
 
@@ -108,4 +347,80 @@ mod tests {
             rng.sample_iter(Standard).take(6).collect();
         assert_eq!(&result, &[false, true, false, true, false, true]);
     }
+
+    #[test]
+    fn test_step_rng() {
+        use super::{Overflow, StepRng};
+        use crate::RngCore;
+
+        let mut rng = StepRng::new(u64::MAX - 1, 1);
+        assert_eq!(rng.next_u64(), u64::MAX - 1);
+        assert_eq!(rng.next_u32(), u32::MAX);
+        assert_eq!(rng.next_u64(), 0);
+
+        let mut rng = StepRng::new_u32(u32::MAX - 1, 3);
+        assert_eq!(rng.next_u32(), u32::MAX - 1);
+        assert_eq!(rng.next_u64(), (4 << 32) | 1);
+        let mut rng = rng.with_overflow(Overflow::Saturate);
+        assert_eq!(rng.next_u32(), 7);
+        let mut rng = StepRng::new_u32(u32::MAX - 1, 3).with_overflow(Overflow::Saturate);
+        assert_eq!(rng.next_u64(), (u64::from(u32::MAX) << 32) | u64::from(u32::MAX - 1));
+        assert_eq!(rng.next_u32(), u32::MAX);
+    }
+
+    #[test]
+    fn test_step_rng_fill_bytes() {
+        use super::StepRng;
+        use crate::RngCore;
+
+        let mut rng = StepRng::new(0x0807_0605_0403_0201, 0x1010_1010_1010_1010);
+        let mut buf = [0u8; 11];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [1, 2, 3, 4, 5, 6, 7, 8, 0x11, 0x12, 0x13]);
+        assert_eq!(rng.next_u64(), 0x2827_2625_2423_2221);
+
+        let mut rng = StepRng::new_u32(0x0403_0201, 0x1010_1010);
+        let mut buf = [0u8; 10];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [1, 2, 3, 4, 0x11, 0x12, 0x13, 0x14, 0x21, 0x22]);
+        assert_eq!(rng.next_u32(), 0x3433_3231);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_sequence_rng() {
+        use super::SequenceRng;
+        use crate::RngCore;
+
+        let mut rng = SequenceRng::new(alloc::vec![1, 0x0102_0304_0506_0708, 3, 4, 5]);
+        assert_eq!(rng.next_u64(), 1);
+        let mut buf = [0u8; 12];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [8, 7, 6, 5, 4, 3, 2, 1, 3, 0, 0, 0]);
+        assert_eq!(rng.next_u32(), 4);
+        assert_eq!(rng.remaining(), 1);
+        // An error leaves the remaining values unused
+        assert!(rng.try_fill_bytes(&mut [0u8; 9]).is_err());
+        assert_eq!(rng.next_u64(), 5);
+        assert_eq!(rng.remaining(), 0);
+        assert!(rng.try_fill_bytes(&mut []).is_ok());
+
+        let mut rng = SequenceRng::from_bytes(&[1u8, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 9][..]);
+        assert_eq!(rng.next_u32(), 1);
+        assert_eq!(rng.next_u64(), 2);
+        assert!(rng.try_fill_bytes(&mut [0u8; 2]).is_err());
+        let mut buf = [0u8; 1];
+        rng.fill_bytes(&mut buf);
+        assert_eq!(buf, [9]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic]
+    fn test_sequence_rng_exhausted() {
+        use crate::RngCore;
+
+        let mut rng = super::SequenceRng::from_bytes([1u8, 2, 3]);
+        rng.next_u32();
+    }
 }
@@ -65,17 +65,22 @@
 //! -   [`SmallRng`] is an **insecure** PRNG designed to be fast, simple, require
 //!     little memory, and have good output quality.
 //!
+//! The [`LazyRng`] adapter defers seeding of any of these until first use.
+//!
 //! The algorithms selected for [`StdRng`] and [`SmallRng`] may change in any
 //! release and may be platform-dependent, therefore they should be considered
 //! **not reproducible**.
 //!
 //! ## Additional generators
 //!
-//! **TRNGs**: The [`rdrand`] crate provides an interface to the RDRAND and
-//! RDSEED instructions available in modern Intel and AMD CPUs.
-//! The [`rand_jitter`] crate provides a user-space implementation of
-//! entropy harvesting from CPU timer jitter, but is very slow and has
-//! [security issues](https://github.com/rust-random/rand/issues/699).
+//! **TRNGs**: With the `hardware_rng` feature, `HardwareRng` provides an
+//! interface to the RDRAND and RDSEED instructions available in modern Intel
+//! and AMD CPUs, and to the RNDR and RNDRRS instructions of AArch64.
+//! With the `jitter` feature, `JitterRng` provides a user-space
+//! implementation of entropy harvesting from CPU timer jitter, for use where
+//! no OS RNG is available or trusted. It is very slow, and its entropy
+//! estimate relies on the platform's timer
+//! (see [security issues](https://github.com/rust-random/rand/issues/699)).
 //!
 //! **PRNGs**: Several companion crates are available, providing individual or
 //! families of PRNG algorithms. These provide the implementations behind
@@ -89,8 +94,6 @@
 //! [`CryptoRng`]: crate::CryptoRng
 //! [`SeedableRng`]: crate::SeedableRng
 //! [`thread_rng`]: crate::thread_rng
-//! [`rdrand`]: https://crates.io/crates/rdrand
-//! [`rand_jitter`]: https://crates.io/crates/rand_jitter
 //! [`rand_chacha`]: https://crates.io/crates/rand_chacha
 //! [`rand_pcg`]: https://crates.io/crates/rand_pcg
 //! [`rand_xoshiro`]: https://crates.io/crates/rand_xoshiro
@@ -99,21 +102,75 @@
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
 #[cfg(feature = "std")] pub mod adapter;
 
+mod bits;
+mod lazy;
+mod philox;
 pub mod mock; // Public so we don't export `StepRng` directly, making it a bit
               // more clear it is intended for testing.
 
-#[cfg(all(feature = "small_rng", target_pointer_width = "64"))]
+#[cfg(any(feature = "xoshiro", all(feature = "small_rng", any(
+    feature = "small_rng_xoshiro256",
+    all(target_pointer_width = "64", not(feature = "small_rng_xoshiro128"))
+))))]
 mod xoshiro256plusplus;
-#[cfg(all(feature = "small_rng", not(target_pointer_width = "64")))]
+#[cfg(any(feature = "xoshiro", all(
+    feature = "small_rng",
+    not(feature = "small_rng_xoshiro256"),
+    any(feature = "small_rng_xoshiro128", not(target_pointer_width = "64"))
+)))]
 mod xoshiro128plusplus;
 #[cfg(feature = "small_rng")] mod small;
 
 #[cfg(feature = "std_rng")] mod std;
 #[cfg(all(feature = "std", feature = "std_rng"))] pub(crate) mod thread;
+#[cfg(feature = "std")] mod nonblocking;
+#[cfg(feature = "std")] mod sync;
+#[cfg(all(feature = "jitter", feature = "std_rng"))] mod entropy;
+#[cfg(feature = "jitter")] mod jitter;
+#[cfg(feature = "hardware_rng")] mod hardware;
 
+pub use self::bits::BitRng;
+pub use self::lazy::LazyRng;
+pub use self::philox::Philox4x32;
 #[cfg(feature = "small_rng")] pub use self::small::SmallRng;
 #[cfg(feature = "std_rng")] pub use self::std::StdRng;
+#[cfg_attr(doc_cfg, doc(cfg(feature = "xoshiro")))]
+#[cfg(feature = "xoshiro")]
+pub use self::{xoshiro128plusplus::Xoshiro128PlusPlus, xoshiro256plusplus::Xoshiro256PlusPlus};
+/// The PCG64-DXSM generator, as used by default in NumPy, from the
+/// [rand_pcg] crate.
+///
+/// Its output for a given seed is portable and will not change in future
+/// releases. This type requires the crate feature `pcg`.
+///
+/// ```
+/// use rand::rngs::Pcg64Dxsm;
+/// use rand::{Rng, SeedableRng};
+///
+/// let mut rng = Pcg64Dxsm::seed_from_u64(42);
+/// let x: f64 = rng.gen();
+/// ```
+///
+/// [rand_pcg]: https://crates.io/crates/rand_pcg
+#[cfg_attr(doc_cfg, doc(cfg(feature = "pcg")))]
+#[cfg(feature = "pcg")]
+pub use rand_pcg::Pcg64Dxsm;
 #[cfg(all(feature = "std", feature = "std_rng"))] pub use self::thread::ThreadRng;
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "jitter", feature = "std_rng"))))]
+#[cfg(all(feature = "jitter", feature = "std_rng"))]
+pub use self::entropy::EntropySeeder;
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use self::nonblocking::{FallbackOsRng, NonBlockingError, NonBlockingOsRng};
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+#[cfg(feature = "std")]
+pub use self::sync::SyncRng;
+#[cfg_attr(doc_cfg, doc(cfg(feature = "jitter")))]
+#[cfg(feature = "jitter")]
+pub use self::jitter::{JitterRng, TimerError};
+#[cfg_attr(doc_cfg, doc(cfg(feature = "hardware_rng")))]
+#[cfg(feature = "hardware_rng")]
+pub use self::hardware::HardwareRng;
 
 #[cfg_attr(doc_cfg, doc(cfg(feature = "getrandom")))]
 #[cfg(feature = "getrandom")] pub use rand_core::OsRng;
@@ -0,0 +1,255 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An interface to the OS random number generator which does not block.
+
+use core::fmt;
+
+use rand_core::{CryptoRng, Error, OsRng, RngCore, TryCryptoRng, TryRngCore};
+
+/// Error type of [`NonBlockingOsRng`].
+#[derive(Debug)]
+pub enum NonBlockingError {
+    /// The system's entropy pool is not yet initialised. Retrying later
+    /// (typically a few seconds after boot) should succeed.
+    NotReady,
+
+    /// The OS random number generator failed for another reason.
+    Os(Error),
+}
+
+impl std::error::Error for NonBlockingError {}
+
+impl fmt::Display for NonBlockingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NonBlockingError::NotReady => f.write_str("OS entropy pool is not yet initialised"),
+            NonBlockingError::Os(e) => write!(f, "OS random number generator failed: {}", e),
+        }
+    }
+}
+
+/// A random number generator retrieving randomness from the operating system
+/// without blocking.
+///
+/// [`OsRng`] may block when used during early boot, until the system's RNG is
+/// initialised; under Linux this is also seen in freshly started containers
+/// and virtual machines. `NonBlockingOsRng` instead fails with
+/// [`NonBlockingError::NotReady`] in this case. It implements [`TryRngCore`]
+/// (not [`RngCore`]), thus errors have to be handled by the caller, e.g. via
+/// [`SeedableRng::try_from_rng`]. See [`FallbackOsRng`] to use a seeded
+/// CSPRNG while the system's RNG is not ready.
+///
+/// This struct requires the crate feature `std`.
+///
+/// # Platform support
+///
+/// - On Linux and Android, the `getrandom` system call is used with the
+///   `GRND_NONBLOCK` flag. On kernels older than 3.17, which lack this
+///   system call, [`OsRng`] is used instead and may block.
+/// - On other platforms [`OsRng`] is used. This does not block on Windows,
+///   macOS, iOS, OpenBSD or WASI; on other Unix platforms it may block
+///   during early boot.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::{NonBlockingOsRng, StdRng};
+/// use rand::SeedableRng;
+///
+/// match StdRng::try_from_rng(&mut NonBlockingOsRng) {
+///     Ok(rng) => { /* use rng */ }
+///     Err(err) => eprintln!("cannot seed: {}", err),
+/// }
+/// ```
+///
+/// [`SeedableRng::try_from_rng`]: rand_core::SeedableRng::try_from_rng
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NonBlockingOsRng;
+
+impl TryRngCore for NonBlockingOsRng {
+    type Error = NonBlockingError;
+
+    fn try_next_u32(&mut self) -> Result<u32, NonBlockingError> {
+        let mut buf = [0; 4];
+        fill_nonblocking(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn try_next_u64(&mut self) -> Result<u64, NonBlockingError> {
+        let mut buf = [0; 8];
+        fill_nonblocking(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), NonBlockingError> {
+        fill_nonblocking(dest)
+    }
+}
+
+impl TryCryptoRng for NonBlockingOsRng {}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn fill_nonblocking(dest: &mut [u8]) -> Result<(), NonBlockingError> {
+    let mut filled = 0;
+    while filled < dest.len() {
+        let rest = &mut dest[filled..];
+        let ret = unsafe {
+            libc::syscall(libc::SYS_getrandom, rest.as_mut_ptr(), rest.len(), libc::GRND_NONBLOCK)
+        };
+        if ret >= 0 {
+            filled += ret as usize;
+            continue;
+        }
+        let err = std::io::Error::last_os_error();
+        match err.raw_os_error() {
+            Some(libc::EINTR) => {}
+            Some(libc::EAGAIN) => return Err(NonBlockingError::NotReady),
+            Some(libc::ENOSYS) => return OsRng.try_fill(rest).map_err(NonBlockingError::Os),
+            _ => return Err(NonBlockingError::Os(Error::new(err))),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn fill_nonblocking(dest: &mut [u8]) -> Result<(), NonBlockingError> {
+    OsRng.try_fill(dest).map_err(NonBlockingError::Os)
+}
+
+/// A random number generator retrieving randomness from the operating system
+/// without blocking, using a fallback CSPRNG while the system's RNG is not
+/// ready.
+///
+/// Each request is first tried with [`NonBlockingOsRng`]. If that reports
+/// [`NonBlockingError::NotReady`], the output of the fallback generator is
+/// used instead; any other error is returned. Like [`OsRng`], this implements
+/// [`TryRngCore`]; use [`TryRngCore::unwrap_err`] to get an [`RngCore`]
+/// panicking on error. Once the system's RNG is initialised the fallback is
+/// no longer used.
+///
+/// The fallback must be a [`CryptoRng`] seeded independently of the system's
+/// RNG, for example by a seed stored at the previous shutdown. Its output is
+/// only as unpredictable as this seed! [`FallbackOsRng::fallback_used`]
+/// reports whether it has been needed so far.
+///
+/// This struct requires the crate feature `std`.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::{FallbackOsRng, StdRng};
+/// use rand::{SeedableRng, TryRngCore};
+///
+/// # let stored_seed = [0u8; 32];
+/// let mut rng = FallbackOsRng::new(StdRng::from_seed(stored_seed));
+/// let mut key = [0u8; 16];
+/// rng.try_fill(&mut key).expect("OS random number generator failed");
+/// if rng.fallback_used() {
+///     // e.g. re-key once the system's RNG is available
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct FallbackOsRng<R> {
+    fallback: R,
+    fallback_used: bool,
+}
+
+impl<R: RngCore + CryptoRng> FallbackOsRng<R> {
+    /// Create a new `FallbackOsRng` using `fallback` while the system's RNG
+    /// is not ready.
+    pub fn new(fallback: R) -> Self {
+        FallbackOsRng { fallback, fallback_used: false }
+    }
+
+    /// Returns `true` if the output of the fallback generator has been used.
+    #[inline]
+    pub fn fallback_used(&self) -> bool {
+        self.fallback_used
+    }
+
+    /// Returns the fallback generator.
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.fallback
+    }
+}
+
+impl<R: RngCore + CryptoRng> TryRngCore for FallbackOsRng<R> {
+    type Error = Error;
+
+    fn try_next_u32(&mut self) -> Result<u32, Error> {
+        let mut buf = [0; 4];
+        self.try_fill(&mut buf)?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn try_next_u64(&mut self) -> Result<u64, Error> {
+        let mut buf = [0; 8];
+        self.try_fill(&mut buf)?;
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    fn try_fill(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        match fill_nonblocking(dest) {
+            Ok(()) => Ok(()),
+            Err(NonBlockingError::NotReady) => {
+                self.fallback_used = true;
+                self.fallback.try_fill_bytes(dest)
+            }
+            Err(NonBlockingError::Os(e)) => Err(e),
+        }
+    }
+}
+
+impl<R: RngCore + CryptoRng> TryCryptoRng for FallbackOsRng<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn test_nonblocking_os_rng() {
+        // The entropy pool is initialised long before tests run
+        let mut rng = NonBlockingOsRng;
+        let x = rng.try_next_u64().unwrap();
+        let y = rng.try_next_u64().unwrap();
+        assert!(x != 0);
+        assert!(x != y);
+        assert!(rng.try_next_u32().is_ok());
+
+        let mut buf = [0u8; 300];
+        rng.try_fill(&mut buf).unwrap();
+        assert!(buf.iter().any(|&b| b != 0));
+
+        let mut rng = rng.unwrap_err();
+        assert!(rng.next_u64() != 0);
+    }
+
+    #[test]
+    fn test_fallback_os_rng() {
+        // With the pool initialised the fallback is never used
+        let mut rng = FallbackOsRng::new(OsRng.unwrap_err());
+        let x = rng.try_next_u64().unwrap();
+        assert!(x != 0);
+        assert!(x != rng.try_next_u64().unwrap());
+        assert!(!rng.fallback_used());
+
+        let mut rng = rng.unwrap_err();
+        assert!(rng.next_u32() != rng.next_u32());
+    }
+
+    #[test]
+    fn test_nonblocking_error() {
+        let err = NonBlockingError::NotReady;
+        assert_eq!(err.to_string(), "OS entropy pool is not yet initialised");
+        let err = NonBlockingError::Os(Error::from(core::num::NonZeroU32::new(1).unwrap()));
+        assert!(err.to_string().starts_with("OS random number generator failed: "));
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Philox4x32-10 counter-based random number generator.
+
+#[cfg(feature="serde1")] use serde::{Serialize, Deserialize};
+use rand_core::impls::{fill_bytes_via_next, next_u64_via_u32};
+use rand_core::{JumpableRng, RngCore, SeedableRng, StreamRng};
+
+const BLOCK_WORDS: usize = 4;
+
+/// A Philox4x32-10 random number generator.
+///
+/// Philox is a *counter-based* generator: each block of output (4 words) is a
+/// pure function of a 64-bit key and a 128-bit counter, computed by
+/// [`Philox4x32::block`]. Any position of any stream can thus be computed
+/// directly, without generating the preceding output, which makes Philox
+/// ideal for massively parallel computations: each work item may derive its
+/// own stream from its index, without coordination. Philox is not suitable
+/// for cryptographic purposes, but passes the BigCrush test suite.
+///
+/// As for [ChaCha](https://docs.rs/rand_chacha), the counter is split into a
+/// 64-bit block counter and a 64-bit stream: 2<sup>64</sup> independent streams
+/// per key are available via [`StreamRng`], each of 2<sup>66</sup> words.
+/// The position within a stream may be set via
+/// [`Philox4x32::set_word_pos`].
+///
+/// The algorithm is that of [Salmon et al. (2011)], with ten rounds; the output
+/// matches their reference implementation, Random123. One `u64` is made of
+/// two `u32` words, the first in the low half, and bytes are filled in
+/// little-endian order.
+///
+/// # Example
+///
+/// ```
+/// use rand::rngs::Philox4x32;
+/// use rand::{Rng, SeedableRng, StreamRng};
+///
+/// // Work item 42 of a parallel simulation
+/// let mut rng = Philox4x32::seed_from_u64(1);
+/// rng.set_stream(42);
+/// let x: f64 = rng.gen();
+///
+/// // Output is a function of key, stream and position only
+/// let mut rng2 = Philox4x32::seed_from_u64(1);
+/// rng2.set_stream(42);
+/// assert_eq!(rng2.gen::<f64>(), x);
+/// ```
+///
+/// [Salmon et al. (2011)]: https://doi.org/10.1145/2063384.2063405
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature="serde1", derive(Serialize, Deserialize))]
+pub struct Philox4x32 {
+    key: u64,
+    counter: u64,
+    stream: u64,
+    // Always holds the block at `counter`; words from `index` are unused
+    buf: [u32; BLOCK_WORDS],
+    index: usize,
+}
+
+impl Philox4x32 {
+    /// Create a new `Philox4x32` with the given key, at the start of stream
+    /// zero.
+    pub fn new(key: u64) -> Self {
+        Philox4x32 {
+            key,
+            counter: 0,
+            stream: 0,
+            buf: Self::block(key, 0),
+            index: 0,
+        }
+    }
+
+    /// Compute the block of output for `key` and `counter`.
+    ///
+    /// The low 64 bits of `counter` are the block counter and the high 64
+    /// bits are the stream. The words of the key and the counter are taken
+    /// in little-endian order, as in Random123.
+    pub fn block(key: u64, counter: u128) -> [u32; BLOCK_WORDS] {
+        const M0: u64 = 0xD251_1F53;
+        const M1: u64 = 0xCD9E_8D57;
+        const W0: u32 = 0x9E37_79B9;
+        const W1: u32 = 0xBB67_AE85;
+
+        let mut k = [key as u32, (key >> 32) as u32];
+        let mut c = [
+            counter as u32,
+            (counter >> 32) as u32,
+            (counter >> 64) as u32,
+            (counter >> 96) as u32,
+        ];
+        for _ in 0..10 {
+            let p0 = M0 * u64::from(c[0]);
+            let p1 = M1 * u64::from(c[2]);
+            c = [
+                (p1 >> 32) as u32 ^ c[1] ^ k[0],
+                p1 as u32,
+                (p0 >> 32) as u32 ^ c[3] ^ k[1],
+                p0 as u32,
+            ];
+            k = [k[0].wrapping_add(W0), k[1].wrapping_add(W1)];
+        }
+        c
+    }
+
+    /// Get the key.
+    #[inline]
+    pub fn key(&self) -> u64 {
+        self.key
+    }
+
+    /// Get the offset from the start of the stream, in 32-bit words.
+    ///
+    /// Since blocks are 4 words long and the block counter is 64 bits, this
+    /// is a 66-bit number.
+    #[inline]
+    pub fn get_word_pos(&self) -> u128 {
+        let pos = u128::from(self.counter) * BLOCK_WORDS as u128 + self.index as u128;
+        pos % (1 << 66)
+    }
+
+    /// Set the offset from the start of the stream, in 32-bit words.
+    ///
+    /// As with `get_word_pos`, this is a 66-bit number; the upper 62 bits are
+    /// ignored.
+    #[inline]
+    pub fn set_word_pos(&mut self, word_offset: u128) {
+        self.counter = (word_offset / BLOCK_WORDS as u128) as u64;
+        self.index = (word_offset % BLOCK_WORDS as u128) as usize;
+        self.generate();
+    }
+
+    /// Advance the word position by `words`, wrapping after 2<sup>66</sup>
+    /// words.
+    #[inline]
+    pub fn advance(&mut self, words: u128) {
+        let pos = self.get_word_pos().wrapping_add(words % (1 << 66));
+        self.set_word_pos(pos);
+    }
+
+    #[inline]
+    fn generate(&mut self) {
+        let counter = u128::from(self.stream) << 64 | u128::from(self.counter);
+        self.buf = Self::block(self.key, counter);
+    }
+}
+
+impl RngCore for Philox4x32 {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        if self.index >= BLOCK_WORDS {
+            self.counter = self.counter.wrapping_add(1);
+            self.index = 0;
+            self.generate();
+        }
+        let value = self.buf[self.index];
+        self.index += 1;
+        value
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        next_u64_via_u32(self)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        fill_bytes_via_next(self, dest);
+    }
+}
+
+impl SeedableRng for Philox4x32 {
+    type Seed = [u8; 8];
+
+    /// Create a new `Philox4x32`, using the seed as little-endian key.
+    #[inline]
+    fn from_seed(seed: [u8; 8]) -> Philox4x32 {
+        Philox4x32::new(u64::from_le_bytes(seed))
+    }
+}
+
+impl JumpableRng for Philox4x32 {
+    /// Advance the word position by 2<sup>50</sup> (i.e. 2<sup>48</sup> blocks).
+    ///
+    /// Each stream thus holds 2<sup>16</sup> non-overlapping sub-sequences.
+    /// Use `set_stream` for fully independent sequences.
+    #[inline]
+    fn jump(&mut self) {
+        self.advance(1 << 50);
+    }
+
+    /// Advance the word position by 2<sup>62</sup> (i.e. 2<sup>60</sup> blocks).
+    ///
+    /// Each stream thus holds 16 such starting points.
+    #[inline]
+    fn long_jump(&mut self) {
+        self.advance(1 << 62);
+    }
+}
+
+impl StreamRng for Philox4x32 {
+    /// Set the stream, the high 64 bits of the counter, keeping the word
+    /// position. All 2<sup>64</sup> streams are independent.
+    #[inline]
+    fn set_stream(&mut self, stream: u64) {
+        self.stream = stream;
+        self.generate();
+    }
+
+    #[inline]
+    fn stream(&self) -> u64 {
+        self.stream
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_philox_known_answer() {
+        // Test vectors from Random123, kat_vectors
+        assert_eq!(Philox4x32::block(0, 0), [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8]);
+        assert_eq!(
+            Philox4x32::block(u64::MAX, u128::MAX),
+            [0x408f276d, 0x41c83b0e, 0xa20bc7c6, 0x6d5451fd]
+        );
+        assert_eq!(
+            Philox4x32::block(
+                0x299f31d0_a4093822,
+                0x03707344_13198a2e_85a308d3_243f6a88,
+            ),
+            [0xd16cfe09, 0x94fdcceb, 0x5001e420, 0x24126ea1]
+        );
+    }
+
+    #[test]
+    fn test_philox_true_values() {
+        let mut rng = Philox4x32::new(0);
+        let mut results = [0u32; 6];
+        for x in results.iter_mut() {
+            *x = rng.next_u32();
+        }
+        let block1 = Philox4x32::block(0, 1);
+        let expected = [0x6627e8d5, 0xe169c58d, 0xbc57ac4c, 0x9b00dbd8, block1[0], block1[1]];
+        assert_eq!(results, expected);
+
+        // Value-stability of seeding and u64 output
+        let mut rng = Philox4x32::seed_from_u64(0);
+        assert_eq!(rng.next_u64(), 7049136282425558845);
+    }
+
+    #[test]
+    fn test_philox_word_pos() {
+        let mut rng = Philox4x32::seed_from_u64(11);
+        let mut skipped = rng.clone();
+        for _ in 0..7 {
+            rng.next_u32();
+        }
+        assert_eq!(rng.get_word_pos(), 7);
+        skipped.set_word_pos(7);
+        assert_eq!(skipped.next_u64(), rng.next_u64());
+
+        // Output at any position is computed directly
+        let key = rng.key();
+        rng.set_word_pos((5 << 2) + 2);
+        assert_eq!(rng.next_u32(), Philox4x32::block(key, 5)[2]);
+        rng.advance(5);
+        assert_eq!(rng.get_word_pos(), 7 << 2);
+        assert_eq!(rng.next_u32(), Philox4x32::block(key, 7)[0]);
+
+        // The position wraps at the end of the stream
+        rng.set_word_pos((1 << 66) - 1);
+        assert_eq!(rng.next_u32(), Philox4x32::block(key, u64::MAX.into())[3]);
+        assert_eq!(rng.get_word_pos(), 0);
+        assert_eq!(rng.next_u32(), Philox4x32::block(key, 0)[0]);
+    }
+
+    #[test]
+    fn test_philox_stream_and_jump() {
+        let mut rng = Philox4x32::new(3);
+        rng.next_u32();
+        rng.set_stream(9);
+        assert_eq!(rng.stream(), 9);
+        assert_eq!(rng.get_word_pos(), 1);
+        assert_eq!(rng.next_u32(), Philox4x32::block(3, 9 << 64)[1]);
+
+        rng.jump();
+        assert_eq!(rng.get_word_pos(), (1 << 50) + 2);
+        rng.long_jump();
+        assert_eq!(rng.get_word_pos(), (1 << 62) + (1 << 50) + 2);
+        assert_eq!(rng.stream(), 9);
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_philox_serde() {
+        let mut rng = Philox4x32::seed_from_u64(12);
+        rng.next_u32();
+        let encoded = bincode::serialize(&rng).unwrap();
+        let mut decoded: Philox4x32 = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(rng, decoded);
+        assert_eq!(rng.next_u64(), decoded.next_u64());
+    }
+}
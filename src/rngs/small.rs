@@ -8,11 +8,17 @@
 
 //! A small fast RNG
 
-use rand_core::{Error, RngCore, SeedableRng};
+use rand_core::{impls, Error, JumpableRng, MixEntropy, RngCore, SeedableRng, SplitRng};
 
-#[cfg(target_pointer_width = "64")]
+#[cfg(any(
+    feature = "small_rng_xoshiro256",
+    all(target_pointer_width = "64", not(feature = "small_rng_xoshiro128"))
+))]
 type Rng = super::xoshiro256plusplus::Xoshiro256PlusPlus;
-#[cfg(not(target_pointer_width = "64"))]
+#[cfg(all(
+    not(feature = "small_rng_xoshiro256"),
+    any(feature = "small_rng_xoshiro128", not(target_pointer_width = "64"))
+))]
 type Rng = super::xoshiro128plusplus::Xoshiro128PlusPlus;
 
 /// A small-state, fast non-crypto PRNG
@@ -41,6 +47,11 @@ type Rng = super::xoshiro128plusplus::Xoshiro128PlusPlus;
 /// `Xoshiro256PlusPlus` on 64-bit platforms and `Xoshiro128PlusPlus` on 32-bit
 /// platforms. Both are also implemented by the [rand_xoshiro] crate.
 ///
+/// For output independent of the platform, the `small_rng_xoshiro256` or
+/// `small_rng_xoshiro128` feature selects the respective algorithm on all
+/// targets; if both are enabled, `small_rng_xoshiro256` takes precedence. The
+/// algorithm in use is given by [`SmallRng::ALGORITHM`].
+///
 /// # Examples
 ///
 /// Initializing `SmallRng` with a random seed can be done using [`SeedableRng::from_entropy`]:
@@ -80,6 +91,23 @@ type Rng = super::xoshiro128plusplus::Xoshiro128PlusPlus;
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SmallRng(Rng);
 
+impl SmallRng {
+    /// The name of the algorithm in use, `"Xoshiro256PlusPlus"` or
+    /// `"Xoshiro128PlusPlus"`.
+    #[cfg(any(
+        feature = "small_rng_xoshiro256",
+        all(target_pointer_width = "64", not(feature = "small_rng_xoshiro128"))
+    ))]
+    pub const ALGORITHM: &str = "Xoshiro256PlusPlus";
+    /// The name of the algorithm in use, `"Xoshiro256PlusPlus"` or
+    /// `"Xoshiro128PlusPlus"`.
+    #[cfg(all(
+        not(feature = "small_rng_xoshiro256"),
+        any(feature = "small_rng_xoshiro128", not(target_pointer_width = "64"))
+    ))]
+    pub const ALGORITHM: &str = "Xoshiro128PlusPlus";
+}
+
 impl RngCore for SmallRng {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
@@ -102,6 +130,51 @@ impl RngCore for SmallRng {
     }
 }
 
+impl JumpableRng for SmallRng {
+    /// Advance the generator by at least 2<sup>64</sup> steps.
+    ///
+    /// As for the generator itself, the exact distance is platform-dependent.
+    #[inline(always)]
+    fn jump(&mut self) {
+        self.0.jump()
+    }
+
+    /// Advance the generator by at least 2<sup>96</sup> steps.
+    ///
+    /// As for the generator itself, the exact distance is platform-dependent.
+    #[inline(always)]
+    fn long_jump(&mut self) {
+        self.0.long_jump()
+    }
+}
+
+impl MixEntropy for SmallRng {
+    /// Reseed from this generator's output XORed with `entropy`, via
+    /// [`impls::mix_entropy_via_seed`].
+    fn mix_entropy(&mut self, entropy: &[u8]) {
+        impls::mix_entropy_via_seed(self, entropy)
+    }
+}
+
+impl SplitRng for SmallRng {
+    /// Derive a new `SmallRng`, seeded from this generator's output.
+    ///
+    /// Since seeding xoshiro generators directly from the output of the same
+    /// generator may yield correlated sequences, each word of the seed is
+    /// first scrambled via the SplitMix64 output function.
+    fn split(&mut self) -> Self {
+        let mut seed = <Rng as SeedableRng>::Seed::default();
+        for chunk in seed.as_mut().chunks_mut(8) {
+            let mut z = self.0.next_u64();
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z = z ^ (z >> 31);
+            chunk.copy_from_slice(&z.to_le_bytes());
+        }
+        SmallRng(Rng::from_seed(seed))
+    }
+}
+
 impl SeedableRng for SmallRng {
     type Seed = <Rng as SeedableRng>::Seed;
 
@@ -120,3 +193,37 @@ impl SeedableRng for SmallRng {
         SmallRng(Rng::seed_from_u64(state))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_smallrng_split() {
+        let mut parent = SmallRng::seed_from_u64(7);
+        let mut child = parent.split();
+        let mut parent2 = SmallRng::seed_from_u64(7);
+        let child2 = parent2.split();
+        assert_eq!(child, child2);
+        assert_eq!(parent, parent2);
+        assert_ne!(child, parent);
+        assert_ne!(child.split(), parent.split());
+        assert_ne!(child.next_u64(), parent.next_u64());
+    }
+
+    #[test]
+    fn test_smallrng_algorithm() {
+        // Value-stability for each algorithm
+        let mut rng = SmallRng::seed_from_u64(9);
+        let x = rng.next_u64();
+        match SmallRng::ALGORITHM {
+            "Xoshiro256PlusPlus" => assert_eq!(x, 11050184076939766995),
+            "Xoshiro128PlusPlus" => assert_eq!(x, 12452687402104111523),
+            _ => unreachable!(),
+        }
+        #[cfg(feature = "small_rng_xoshiro256")]
+        assert_eq!(SmallRng::ALGORITHM, "Xoshiro256PlusPlus");
+        #[cfg(all(feature = "small_rng_xoshiro128", not(feature = "small_rng_xoshiro256")))]
+        assert_eq!(SmallRng::ALGORITHM, "Xoshiro128PlusPlus");
+    }
+}
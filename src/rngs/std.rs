@@ -8,11 +8,18 @@
 
 //! The standard RNG
 
-use crate::{CryptoRng, Error, RngCore, SeedableRng};
+use core::mem::MaybeUninit;
 
-pub(crate) use rand_chacha::ChaCha12Core as Core;
+use crate::{
+    CryptoRng, Error, JumpableRng, MixEntropy, RngCore, SeedableRng, SplitRng, StreamRng,
+};
 
-use rand_chacha::ChaCha12Rng as Rng;
+#[cfg(all(feature = "std_rng_chacha8", not(feature = "std_rng_chacha20")))]
+pub(crate) use rand_chacha::{ChaCha8Core as Core, ChaCha8Rng as Rng};
+#[cfg(feature = "std_rng_chacha20")]
+pub(crate) use rand_chacha::{ChaCha20Core as Core, ChaCha20Rng as Rng};
+#[cfg(not(any(feature = "std_rng_chacha8", feature = "std_rng_chacha20")))]
+pub(crate) use rand_chacha::{ChaCha12Core as Core, ChaCha12Rng as Rng};
 
 /// The standard RNG. The PRNG algorithm in `StdRng` is chosen to be efficient
 /// on the current platform, to be statistically strong and unpredictable
@@ -27,12 +34,33 @@ use rand_chacha::ChaCha12Rng as Rng;
 /// library versions. For a secure reproducible generator, we recommend use of
 /// the [rand_chacha] crate directly.
 ///
+/// The `std_rng_chacha8` or `std_rng_chacha20` feature selects ChaCha with 8
+/// or 20 rounds instead, trading security margin for throughput or vice versa.
+/// Seeding and serialization are unaffected, but output differs between the
+/// variants. If both features are enabled, `std_rng_chacha20` takes
+/// precedence. The algorithm in use is given by [`StdRng::ALGORITHM`].
+///
 /// [rand_chacha]: https://crates.io/crates/rand_chacha
 /// [rand issue]: https://github.com/rust-random/rand/issues/932
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std_rng")))]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StdRng(Rng);
 
+impl StdRng {
+    /// The name of the algorithm in use, `"ChaCha8"`, `"ChaCha12"` or
+    /// `"ChaCha20"`.
+    #[cfg(all(feature = "std_rng_chacha8", not(feature = "std_rng_chacha20")))]
+    pub const ALGORITHM: &str = "ChaCha8";
+    /// The name of the algorithm in use, `"ChaCha8"`, `"ChaCha12"` or
+    /// `"ChaCha20"`.
+    #[cfg(feature = "std_rng_chacha20")]
+    pub const ALGORITHM: &str = "ChaCha20";
+    /// The name of the algorithm in use, `"ChaCha8"`, `"ChaCha12"` or
+    /// `"ChaCha20"`.
+    #[cfg(not(any(feature = "std_rng_chacha8", feature = "std_rng_chacha20")))]
+    pub const ALGORITHM: &str = "ChaCha12";
+}
+
 impl RngCore for StdRng {
     #[inline(always)]
     fn next_u32(&mut self) -> u32 {
@@ -53,6 +81,57 @@ impl RngCore for StdRng {
     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
         self.0.try_fill_bytes(dest)
     }
+
+    fn fill_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        self.0.fill_bytes_uninit(dest)
+    }
+}
+
+impl JumpableRng for StdRng {
+    /// Advance the generator by at least 2<sup>48</sup> steps.
+    ///
+    /// As for the generator itself, the exact distance may change in future.
+    #[inline(always)]
+    fn jump(&mut self) {
+        self.0.jump()
+    }
+
+    /// Advance the generator by at least 2<sup>64</sup> steps.
+    ///
+    /// As for the generator itself, the exact distance may change in future.
+    #[inline(always)]
+    fn long_jump(&mut self) {
+        self.0.long_jump()
+    }
+}
+
+impl StreamRng for StdRng {
+    /// Select one of 2<sup>64</sup> independent streams.
+    #[inline(always)]
+    fn set_stream(&mut self, stream: u64) {
+        StreamRng::set_stream(&mut self.0, stream)
+    }
+
+    #[inline(always)]
+    fn stream(&self) -> u64 {
+        self.0.stream()
+    }
+}
+
+impl SplitRng for StdRng {
+    /// Derive a new `StdRng`, seeded from this generator's output.
+    fn split(&mut self) -> Self {
+        let mut seed = <Rng as SeedableRng>::Seed::default();
+        self.0.fill_bytes(&mut seed);
+        StdRng(Rng::from_seed(seed))
+    }
+}
+
+impl MixEntropy for StdRng {
+    #[inline]
+    fn mix_entropy(&mut self, entropy: &[u8]) {
+        self.0.mix_entropy(entropy)
+    }
 }
 
 impl SeedableRng for StdRng {
@@ -75,9 +154,10 @@ impl CryptoRng for StdRng {}
 #[cfg(test)]
 mod test {
     use crate::rngs::StdRng;
-    use crate::{RngCore, SeedableRng};
+    use crate::{MixEntropy, RngCore, SeedableRng, SplitRng};
 
     #[test]
+    #[cfg(not(any(feature = "std_rng_chacha8", feature = "std_rng_chacha20")))]
     fn test_stdrng_construction() {
         // Test value-stability of StdRng. This is expected to break any time
         // the algorithm is changed.
@@ -95,4 +175,49 @@ mod test {
 
         assert_eq!([x0, x1], target);
     }
+
+    #[test]
+    #[cfg(not(any(feature = "std_rng_chacha8", feature = "std_rng_chacha20")))]
+    fn test_stdrng_split() {
+        let mut parent = StdRng::seed_from_u64(7);
+        let mut child = parent.split();
+        let mut parent2 = StdRng::seed_from_u64(7);
+        let mut child2 = parent2.split();
+        assert_eq!(child.next_u64(), child2.next_u64());
+        assert_eq!(parent.next_u64(), parent2.next_u64());
+        // Value-stability, expected to break with any change of algorithm
+        let target = [4010214969195185097, 17545275723285031904];
+        assert_eq!([child.next_u64(), parent.next_u64()], target);
+    }
+
+    #[test]
+    #[cfg(not(any(feature = "std_rng_chacha8", feature = "std_rng_chacha20")))]
+    fn test_stdrng_mix_entropy() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let mut unmixed = rng.clone();
+        rng.mix_entropy(&[]);
+        assert_eq!(rng.next_u64(), unmixed.next_u64());
+
+        let mut other = rng.clone();
+        rng.mix_entropy(b"event timing");
+        other.mix_entropy(b"event timinh");
+        let x = rng.next_u64();
+        assert_ne!(x, unmixed.next_u64());
+        assert_ne!(x, other.next_u64());
+        // Value-stability, expected to break with any change of algorithm
+        assert_eq!(x, 15022497381621500877);
+    }
+
+    #[test]
+    fn test_stdrng_algorithm() {
+        // Value-stability for each algorithm
+        let mut rng = StdRng::seed_from_u64(10);
+        let x = rng.next_u64();
+        match StdRng::ALGORITHM {
+            "ChaCha8" => assert_eq!(x, 10327788437131784695),
+            "ChaCha12" => assert_eq!(x, 2414284862012299230),
+            "ChaCha20" => assert_eq!(x, 5530155355974493708),
+            _ => unreachable!(),
+        }
+    }
 }
@@ -0,0 +1,205 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A wrapper sharing an RNG between threads.
+
+use std::sync::{Mutex, MutexGuard};
+
+use rand_core::{CryptoRng, Error, RngCore};
+
+/// A wrapper around an RNG making it usable via a shared reference, and
+/// thus from several threads.
+///
+/// `SyncRng` guards the RNG with a [`Mutex`]. Besides `SyncRng<R>` itself,
+/// `&SyncRng<R>` implements [`RngCore`] (like `&File` implements
+/// `Read`), locking the mutex for each call. Thus an RNG can be stored in
+/// shared state, e.g. a struct behind an [`Arc`], without requiring `&mut`
+/// access. To generate many values at once, [`SyncRng::lock`] returns a
+/// guard holding the lock.
+///
+/// Since each call locks the mutex, this is slower than using an RNG
+/// directly, and threads contend for the lock. Where reproducibility across
+/// threads is not required, [`ThreadRng`] is usually a better choice.
+///
+/// A panic while the lock is held does not poison a `SyncRng`: RNGs keep a
+/// valid state across panics, and further use continues the sequence.
+///
+/// This type requires the crate feature `std`.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use rand::rngs::{StdRng, SyncRng};
+/// use rand::{Rng, SeedableRng};
+///
+/// struct Service {
+///     rng: SyncRng<StdRng>,
+/// }
+///
+/// impl Service {
+///     fn request_id(&self) -> u64 {
+///         let mut rng = &self.rng;
+///         rng.gen()
+///     }
+/// }
+///
+/// let service = Arc::new(Service { rng: SyncRng::new(StdRng::seed_from_u64(1)) });
+/// let service2 = Arc::clone(&service);
+/// let handle = std::thread::spawn(move || service2.request_id());
+/// let id = service.request_id();
+/// assert_ne!(id, handle.join().unwrap());
+///
+/// // Lock once to generate several values
+/// let pair: (u8, u8) = service.rng.lock().gen();
+/// ```
+///
+/// [`Arc`]: std::sync::Arc
+/// [`ThreadRng`]: crate::rngs::ThreadRng
+#[derive(Debug, Default)]
+pub struct SyncRng<R> {
+    rng: Mutex<R>,
+}
+
+impl<R> SyncRng<R> {
+    /// Create a new `SyncRng` wrapping `rng`.
+    pub fn new(rng: R) -> Self {
+        SyncRng { rng: Mutex::new(rng) }
+    }
+
+    /// Lock the RNG, blocking the current thread until it is available.
+    ///
+    /// Other threads using the `SyncRng` block until the returned guard is
+    /// dropped.
+    pub fn lock(&self) -> MutexGuard<'_, R> {
+        self.rng.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Get a mutable reference to the RNG, which does not need locking.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.rng.get_mut().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Returns the wrapped RNG.
+    pub fn into_inner(self) -> R {
+        self.rng.into_inner().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<R> From<R> for SyncRng<R> {
+    fn from(rng: R) -> Self {
+        SyncRng::new(rng)
+    }
+}
+
+impl<R: Clone> Clone for SyncRng<R> {
+    /// Clone the RNG, locking it while doing so.
+    fn clone(&self) -> Self {
+        SyncRng::new(self.lock().clone())
+    }
+}
+
+impl<R: RngCore> RngCore for &SyncRng<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.lock().next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.lock().next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.lock().fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.lock().try_fill_bytes(dest)
+    }
+}
+
+impl<R: RngCore> RngCore for SyncRng<R> {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        self.get_mut().next_u32()
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        self.get_mut().next_u64()
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.get_mut().fill_bytes(dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.get_mut().try_fill_bytes(dest)
+    }
+}
+
+impl<R: RngCore + CryptoRng> CryptoRng for &SyncRng<R> {}
+
+impl<R: RngCore + CryptoRng> CryptoRng for SyncRng<R> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rngs::mock::StepRng;
+    use std::sync::Arc;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_sync_rng() {
+        let rng = SyncRng::new(StepRng::new(0, 1));
+        let mut shared = &rng;
+        assert_eq!(shared.next_u64(), 0);
+        assert_eq!((&rng).next_u64(), 1);
+        assert_eq!(rng.lock().next_u64(), 2);
+
+        let mut cloned = rng.clone();
+        assert_eq!(cloned.next_u64(), 3);
+        assert_eq!(rng.into_inner().next_u64(), 3);
+    }
+
+    #[test]
+    fn test_sync_rng_threads() {
+        let rng = Arc::new(SyncRng::new(StepRng::new(0, 1)));
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let rng = Arc::clone(&rng);
+                std::thread::spawn(move || {
+                    let mut rng = &*rng;
+                    (0..100).map(|_| rng.next_u64()).collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let mut values: Vec<u64> = handles.into_iter().flat_map(|h| h.join().unwrap()).collect();
+        values.sort_unstable();
+        // Each value is drawn exactly once
+        assert_eq!(values, (0..400).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_sync_rng_poison() {
+        let rng = Arc::new(SyncRng::new(StepRng::new(0, 1)));
+        let rng2 = Arc::clone(&rng);
+        let result = std::thread::spawn(move || {
+            let _guard = rng2.lock();
+            panic!("poison");
+        })
+        .join();
+        assert!(result.is_err());
+        assert_eq!((&*rng).next_u64(), 0);
+    }
+}
@@ -9,6 +9,7 @@
 //! Thread-local random number generator
 
 use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
 use std::rc::Rc;
 use std::thread_local;
 use std::fmt;
@@ -16,7 +17,7 @@ use std::fmt;
 use super::std::Core;
 use crate::rngs::adapter::ReseedingRng;
 use crate::rngs::OsRng;
-use crate::{CryptoRng, Error, RngCore, SeedableRng};
+use crate::{CryptoRng, Error, MixEntropy, RngCore, SeedableRng};
 
 // Rationale for using `UnsafeCell` in `ThreadRng`:
 //
@@ -46,12 +47,14 @@ const THREAD_RNG_RESEED_THRESHOLD: u64 = 1024 * 64;
 /// though it is recommended not to use inside a fork handler.
 /// The handle cannot be passed between threads (is not `Send` or `Sync`).
 ///
-/// `ThreadRng` uses the same CSPRNG as [`StdRng`], ChaCha12. As with
+/// `ThreadRng` uses the same CSPRNG as [`StdRng`], ChaCha12 unless selected
+/// otherwise by the `std_rng_chacha8` or `std_rng_chacha20` feature. As with
 /// [`StdRng`], the algorithm may be changed, subject to reasonable expectations
 /// of security and performance.
 ///
 /// `ThreadRng` is automatically seeded from [`OsRng`] with periodic reseeding
-/// (every 64 kiB, as well as "soon" after a fork on Unix — see [`ReseedingRng`]
+/// (every 64 kiB, as well as on first use after a fork on Unix, so that parent
+/// and child processes never share output — see [`ReseedingRng`]
 /// documentation for details).
 ///
 /// Security must be considered relative to a threat model and validation
@@ -81,7 +84,7 @@ thread_local!(
     // We require Rc<..> to avoid premature freeing when thread_rng is used
     // within thread-local destructors. See #968.
     static THREAD_RNG_KEY: Rc<UnsafeCell<ReseedingRng<Core, OsRng>>> = {
-        let r = Core::from_rng(OsRng).unwrap_or_else(|err|
+        let r = Core::try_from_rng(&mut OsRng).unwrap_or_else(|err|
                 panic!("could not initialize thread_rng: {}", err));
         let rng = ReseedingRng::new(r,
                                     THREAD_RNG_RESEED_THRESHOLD,
@@ -113,6 +116,40 @@ pub fn thread_rng() -> ThreadRng {
     ThreadRng { rng }
 }
 
+impl ThreadRng {
+    /// Set the number of generated bytes after which this thread's generator
+    /// is reseeded from [`OsRng`], affecting all handles on this thread.
+    ///
+    /// The default is 64 KiB. Smaller values reduce the amount of output
+    /// depending on a single seed at some cost in throughput; a `threshold` of
+    /// zero disables periodic reseeding, though the generator is still
+    /// reseeded after a fork. See [`ReseedingRng::set_threshold`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// let mut rng = rand::thread_rng();
+    /// rng.set_reseed_threshold(16 * 1024);
+    /// assert_eq!(rand::thread_rng().reseed_threshold(), 16 * 1024);
+    /// ```
+    ///
+    /// [`ReseedingRng::set_threshold`]: crate::rngs::adapter::ReseedingRng::set_threshold
+    pub fn set_reseed_threshold(&mut self, threshold: u64) {
+        // SAFETY: We must make sure to stop using `rng` before anyone else
+        // creates another mutable reference
+        let rng = unsafe { &mut *self.rng.get() };
+        rng.set_threshold(threshold)
+    }
+
+    /// Returns the number of generated bytes after which this thread's
+    /// generator is reseeded, or zero if periodic reseeding is disabled.
+    pub fn reseed_threshold(&self) -> u64 {
+        // SAFETY: No mutable reference exists while this method is active
+        let rng = unsafe { &*self.rng.get() };
+        rng.threshold()
+    }
+}
+
 impl Default for ThreadRng {
     fn default() -> ThreadRng {
         thread_rng()
@@ -149,6 +186,24 @@ impl RngCore for ThreadRng {
         let rng = unsafe { &mut *self.rng.get() };
         rng.try_fill_bytes(dest)
     }
+
+    fn fill_bytes_uninit<'a>(&mut self, dest: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8] {
+        // SAFETY: We must make sure to stop using `rng` before anyone else
+        // creates another mutable reference
+        let rng = unsafe { &mut *self.rng.get() };
+        rng.fill_bytes_uninit(dest)
+    }
+}
+
+impl MixEntropy for ThreadRng {
+    /// Mix `entropy` into the state of this thread's generator, affecting
+    /// all handles on this thread.
+    fn mix_entropy(&mut self, entropy: &[u8]) {
+        // SAFETY: We must make sure to stop using `rng` before anyone else
+        // creates another mutable reference
+        let rng = unsafe { &mut *self.rng.get() };
+        rng.mix_entropy(entropy)
+    }
 }
 
 impl CryptoRng for ThreadRng {}
@@ -164,6 +219,27 @@ mod test {
         assert_eq!(r.gen_range(0..1), 0);
     }
 
+    #[test]
+    fn test_thread_rng_reseed_threshold() {
+        use crate::Rng;
+        let mut r = crate::thread_rng();
+        assert_eq!(r.reseed_threshold(), super::THREAD_RNG_RESEED_THRESHOLD);
+        r.set_reseed_threshold(64);
+        assert_eq!(crate::thread_rng().reseed_threshold(), 64);
+        r.gen::<[u64; 32]>();
+        r.set_reseed_threshold(0);
+        assert_eq!(r.reseed_threshold(), 0);
+        r.gen::<u64>();
+    }
+
+    #[test]
+    fn test_thread_rng_mix_entropy() {
+        use crate::{MixEntropy, Rng};
+        let mut r = crate::thread_rng();
+        r.mix_entropy(&[1, 2, 3]);
+        r.gen::<u64>();
+    }
+
     #[test]
     fn test_debug_output() {
         // We don't care about the exact output here, but it must not include
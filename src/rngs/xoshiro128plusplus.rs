@@ -9,7 +9,7 @@
 #[cfg(feature="serde1")] use serde::{Serialize, Deserialize};
 use rand_core::impls::{next_u64_via_u32, fill_bytes_via_next};
 use rand_core::le::read_u32_into;
-use rand_core::{SeedableRng, RngCore, Error};
+use rand_core::{SeedableRng, RngCore, JumpableRng};
 
 /// A xoshiro128++ random number generator.
 ///
@@ -19,6 +19,17 @@ use rand_core::{SeedableRng, RngCore, Error};
 /// The algorithm used here is translated from [the `xoshiro128plusplus.c`
 /// reference source code](http://xoshiro.di.unimi.it/xoshiro128plusplus.c) by
 /// David Blackman and Sebastiano Vigna.
+///
+/// Unlike [`SmallRng`], whose algorithm is unspecified, this type always
+/// implements xoshiro128++: its output for a given seed is
+/// [portable](https://rust-random.github.io/book/portability.html) and will
+/// not change in future releases. [`SeedableRng::seed_from_u64`] uses the
+/// SplitMix64 generator to expand the seed.
+///
+/// This type requires the crate feature `xoshiro`, and is available as
+/// `rand::rngs::Xoshiro128PlusPlus`.
+///
+/// [`SmallRng`]: crate::rngs::SmallRng
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature="serde1", derive(Serialize, Deserialize))]
 pub struct Xoshiro128PlusPlus {
@@ -89,11 +100,42 @@ impl RngCore for Xoshiro128PlusPlus {
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         fill_bytes_via_next(self, dest);
     }
+}
 
-    #[inline]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.fill_bytes(dest);
-        Ok(())
+impl Xoshiro128PlusPlus {
+    /// Advance the state by the jump polynomial `poly`.
+    fn jump_by(&mut self, poly: [u32; 4]) {
+        let mut s = [0; 4];
+        for &word in poly.iter() {
+            for b in 0..32 {
+                if word & (1 << b) != 0 {
+                    for (x, y) in s.iter_mut().zip(self.s.iter()) {
+                        *x ^= *y;
+                    }
+                }
+                self.next_u32();
+            }
+        }
+        self.s = s;
+    }
+}
+
+impl JumpableRng for Xoshiro128PlusPlus {
+    /// Advance the generator by 2<sup>64</sup> steps.
+    ///
+    /// This can be used to generate 2<sup>64</sup> non-overlapping
+    /// sub-sequences for parallel computations.
+    fn jump(&mut self) {
+        self.jump_by([0x8764000b, 0xf542d2d3, 0x6fa035c3, 0x77f2db5b]);
+    }
+
+    /// Advance the generator by 2<sup>96</sup> steps.
+    ///
+    /// This can be used to generate 2<sup>32</sup> starting points,
+    /// from each of which [`jump`](JumpableRng::jump) generates
+    /// 2<sup>32</sup> non-overlapping sub-sequences.
+    fn long_jump(&mut self) {
+        self.jump_by([0xb523952e, 0x0b6f099f, 0xccf5a0ef, 0x1c580662]);
     }
 }
 
@@ -115,4 +157,30 @@ mod tests {
             assert_eq!(rng.next_u32(), e);
         }
     }
+
+    #[test]
+    fn jump() {
+        let seed = [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0];
+        // These values were computed with a port of the reference `jump`
+        // and `long_jump` functions
+        let mut rng = Xoshiro128PlusPlus::from_seed(seed);
+        rng.jump();
+        for &e in &[3129740764, 111290574, 1158071106] {
+            assert_eq!(rng.next_u32(), e);
+        }
+        let mut rng = Xoshiro128PlusPlus::from_seed(seed);
+        rng.long_jump();
+        for &e in &[2580293941, 2135890358, 163124449] {
+            assert_eq!(rng.next_u32(), e);
+        }
+
+        // Jumping commutes with stepping the generator
+        let mut a = Xoshiro128PlusPlus::seed_from_u64(0);
+        let mut b = a.clone();
+        a.next_u32();
+        a.jump();
+        b.jump();
+        b.next_u32();
+        assert_eq!(a, b);
+    }
 }
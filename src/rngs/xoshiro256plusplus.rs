@@ -9,7 +9,7 @@
 #[cfg(feature="serde1")] use serde::{Serialize, Deserialize};
 use rand_core::impls::fill_bytes_via_next;
 use rand_core::le::read_u64_into;
-use rand_core::{SeedableRng, RngCore, Error};
+use rand_core::{SeedableRng, RngCore, JumpableRng};
 
 /// A xoshiro256++ random number generator.
 ///
@@ -19,6 +19,17 @@ use rand_core::{SeedableRng, RngCore, Error};
 /// The algorithm used here is translated from [the `xoshiro256plusplus.c`
 /// reference source code](http://xoshiro.di.unimi.it/xoshiro256plusplus.c) by
 /// David Blackman and Sebastiano Vigna.
+///
+/// Unlike [`SmallRng`], whose algorithm is unspecified, this type always
+/// implements xoshiro256++: its output for a given seed is
+/// [portable](https://rust-random.github.io/book/portability.html) and will
+/// not change in future releases. [`SeedableRng::seed_from_u64`] uses the
+/// SplitMix64 generator to expand the seed.
+///
+/// This type requires the crate feature `xoshiro`, and is available as
+/// `rand::rngs::Xoshiro256PlusPlus`.
+///
+/// [`SmallRng`]: crate::rngs::SmallRng
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature="serde1", derive(Serialize, Deserialize))]
 pub struct Xoshiro256PlusPlus {
@@ -91,11 +102,42 @@ impl RngCore for Xoshiro256PlusPlus {
     fn fill_bytes(&mut self, dest: &mut [u8]) {
         fill_bytes_via_next(self, dest);
     }
+}
 
-    #[inline]
-    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
-        self.fill_bytes(dest);
-        Ok(())
+impl Xoshiro256PlusPlus {
+    /// Advance the state by the jump polynomial `poly`.
+    fn jump_by(&mut self, poly: [u64; 4]) {
+        let mut s = [0; 4];
+        for &word in poly.iter() {
+            for b in 0..64 {
+                if word & (1 << b) != 0 {
+                    for (x, y) in s.iter_mut().zip(self.s.iter()) {
+                        *x ^= *y;
+                    }
+                }
+                self.next_u64();
+            }
+        }
+        self.s = s;
+    }
+}
+
+impl JumpableRng for Xoshiro256PlusPlus {
+    /// Advance the generator by 2<sup>128</sup> steps.
+    ///
+    /// This can be used to generate 2<sup>128</sup> non-overlapping
+    /// sub-sequences for parallel computations.
+    fn jump(&mut self) {
+        self.jump_by([0x180ec6d33cfd0aba, 0xd5a61266f0c9392c, 0xa9582618e03fc9aa, 0x39abdc4529b1661c]);
+    }
+
+    /// Advance the generator by 2<sup>192</sup> steps.
+    ///
+    /// This can be used to generate 2<sup>64</sup> starting points,
+    /// from each of which [`jump`](JumpableRng::jump) generates
+    /// 2<sup>64</sup> non-overlapping sub-sequences.
+    fn long_jump(&mut self) {
+        self.jump_by([0x76e15d3efefdcbbf, 0xc5004e441c522fb3, 0x77710069854ee241, 0x39109bb02acbe635]);
     }
 }
 
@@ -119,4 +161,30 @@ mod tests {
             assert_eq!(rng.next_u64(), e);
         }
     }
+
+    #[test]
+    fn jump() {
+        let seed = [1, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0];
+        // These values were computed with a port of the reference `jump`
+        // and `long_jump` functions
+        let mut rng = Xoshiro256PlusPlus::from_seed(seed);
+        rng.jump();
+        for &e in &[17043750140134683703, 2364973248208838314, 13951431646535487319] {
+            assert_eq!(rng.next_u64(), e);
+        }
+        let mut rng = Xoshiro256PlusPlus::from_seed(seed);
+        rng.long_jump();
+        for &e in &[13097851138432240629, 5869259491745178931, 2145365994275058833] {
+            assert_eq!(rng.next_u64(), e);
+        }
+
+        // Jumping commutes with stepping the generator
+        let mut a = Xoshiro256PlusPlus::seed_from_u64(0);
+        let mut b = a.clone();
+        a.next_u64();
+        a.jump();
+        b.jump();
+        b.next_u64();
+        assert_eq!(a, b);
+    }
 }
@@ -0,0 +1,211 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Index selection biased toward the front or back of a sequence.
+
+use super::gen_index;
+use crate::Rng;
+
+/// Choose an index of `0..len` with geometric bias toward the front, or
+/// `None` if `len == 0`.
+///
+/// Index `i` is chosen with probability proportional to
+/// `(1 - strength)^i`: each index is `1 - strength` times as likely as the
+/// previous one. With `strength == 0` all indices are equally likely, while
+/// with `strength == 1` the result is always `0`. Sampling takes one random
+/// number and constant time, by inversion of the truncated geometric
+/// distribution.
+///
+/// See [`choose_biased_back`] for bias toward the back and
+/// [`choose_linear_front`] for a weaker, linear bias.
+///
+/// # Panics
+///
+/// Panics if `strength` is not in `[0, 1]`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::choose_biased_front;
+///
+/// // Pick from a history list, most recent entry first
+/// let history = ["ls", "cd src", "cargo test", "git status"];
+/// let i = choose_biased_front(&mut rand::thread_rng(), history.len(), 0.5).unwrap();
+/// println!("repeat: {}", history[i]);
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn choose_biased_front<R>(rng: &mut R, len: usize, strength: f64) -> Option<usize>
+where R: Rng + ?Sized {
+    assert!((0.0..=1.0).contains(&strength), "`strength` must be in [0, 1]");
+    if len == 0 {
+        return None;
+    }
+    if strength == 0.0 {
+        return Some(gen_index(rng, len));
+    }
+    if strength == 1.0 {
+        return Some(0);
+    }
+    // ln(1 - strength) and the total probability 1 - (1 - strength)^len
+    let ln_ratio = (-strength).ln_1p();
+    let total = -(len as f64 * ln_ratio).exp_m1();
+    let u: f64 = rng.gen();
+    let i = ((-u * total).ln_1p() / ln_ratio).floor();
+    // Guard against rounding at the end of the range
+    Some(if i < len as f64 { i as usize } else { len - 1 })
+}
+
+/// Choose an index of `0..len` with geometric bias toward the back, or
+/// `None` if `len == 0`.
+///
+/// This mirrors [`choose_biased_front`]: index `len - 1 - i` is chosen with
+/// probability proportional to `(1 - strength)^i`.
+///
+/// # Panics
+///
+/// Panics if `strength` is not in `[0, 1]`.
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn choose_biased_back<R>(rng: &mut R, len: usize, strength: f64) -> Option<usize>
+where R: Rng + ?Sized {
+    choose_biased_front(rng, len, strength).map(|i| len - 1 - i)
+}
+
+/// Choose an index of `0..len` with linear bias toward the front, or `None`
+/// if `len == 0`.
+///
+/// Index `i` is chosen with probability proportional to `len - i`, thus the
+/// first index is `len` times as likely as the last. This is exact and uses
+/// integer arithmetic only: it is the smaller of two distinct indices chosen
+/// uniformly from `0..=len`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::choose_linear_front;
+///
+/// let mut rng = rand::thread_rng();
+/// let recent = [5, 4, 3, 2, 1];
+/// let i = choose_linear_front(&mut rng, recent.len()).unwrap();
+/// assert!(i < 5);
+/// ```
+pub fn choose_linear_front<R>(rng: &mut R, len: usize) -> Option<usize>
+where R: Rng + ?Sized {
+    if len == 0 {
+        return None;
+    }
+    // There are `len - i` pairs {i, j} with i < j <= len
+    let a = gen_index(rng, len + 1);
+    let mut b = gen_index(rng, len);
+    if b >= a {
+        b += 1;
+    }
+    Some(a.min(b))
+}
+
+/// Choose an index of `0..len` with linear bias toward the back, or `None`
+/// if `len == 0`.
+///
+/// This mirrors [`choose_linear_front`]: index `i` is chosen with
+/// probability proportional to `i + 1`.
+pub fn choose_linear_back<R>(rng: &mut R, len: usize) -> Option<usize>
+where R: Rng + ?Sized {
+    choose_linear_front(rng, len).map(|i| len - 1 - i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_linear() {
+        let mut r = crate::test::rng(631);
+        assert_eq!(choose_linear_front(&mut r, 0), None);
+        assert_eq!(choose_linear_back(&mut r, 0), None);
+        assert_eq!(choose_linear_front(&mut r, 1), Some(0));
+        assert_eq!(choose_linear_back(&mut r, 1), Some(0));
+
+        // Probabilities are 4/10, 3/10, 2/10 and 1/10
+        let mut front = [0; 4];
+        let mut back = [0; 4];
+        for _ in 0..10_000 {
+            front[choose_linear_front(&mut r, 4).unwrap()] += 1;
+            back[choose_linear_back(&mut r, 4).unwrap()] += 1;
+        }
+        for (i, (&f, &b)) in front.iter().zip(back.iter()).enumerate() {
+            let expected = (4 - i) * 1000;
+            assert!(f > expected - 150 && f < expected + 150);
+            let expected = (i + 1) * 1000;
+            assert!(b > expected - 150 && b < expected + 150);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_geometric() {
+        let mut r = crate::test::rng(632);
+        assert_eq!(choose_biased_front(&mut r, 0, 0.5), None);
+        assert_eq!(choose_biased_back(&mut r, 0, 0.5), None);
+        assert_eq!(choose_biased_front(&mut r, 10, 1.0), Some(0));
+        assert_eq!(choose_biased_back(&mut r, 10, 1.0), Some(9));
+        for _ in 0..100 {
+            assert!(choose_biased_front(&mut r, 3, 1e-300).unwrap() < 3);
+            assert!(choose_biased_back(&mut r, 1 << 40, 0.75).unwrap() < 1 << 40);
+        }
+
+        // Probabilities are 8/15, 4/15, 2/15 and 1/15
+        let mut front = [0; 4];
+        let mut back = [0; 4];
+        for _ in 0..15_000 {
+            front[choose_biased_front(&mut r, 4, 0.5).unwrap()] += 1;
+            back[choose_biased_back(&mut r, 4, 0.5).unwrap()] += 1;
+        }
+        for (i, (&f, &b)) in front.iter().zip(back.iter()).enumerate() {
+            let expected = 8000 >> i;
+            assert!(f > expected - 200 && f < expected + 200);
+            let expected = 1000 << i;
+            assert!(b > expected - 200 && b < expected + 200);
+        }
+
+        // Without bias, all indices are equally likely
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            counts[choose_biased_front(&mut r, 4, 0.0).unwrap()] += 1;
+        }
+        crate::test::assert_uniform(&counts);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    #[should_panic]
+    fn test_geometric_invalid() {
+        choose_biased_front(&mut crate::test::rng(633), 4, 1.5);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut r = crate::test::rng(634);
+        let mut v = [0; 8];
+        for x in v.iter_mut() {
+            *x = choose_linear_front(&mut r, 10).unwrap();
+        }
+        assert_eq!(v, [4, 2, 4, 3, 7, 5, 2, 3]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn value_stability_geometric() {
+        let mut r = crate::test::rng(635);
+        let mut v = [0; 8];
+        for x in v.iter_mut() {
+            *x = choose_biased_front(&mut r, 10, 0.3).unwrap();
+        }
+        assert_eq!(v, [0, 3, 2, 0, 3, 1, 1, 2]);
+    }
+}
@@ -0,0 +1,214 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random selection from maps and sets.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::hash::BuildHasher;
+
+use super::IteratorRandom;
+use crate::Rng;
+
+/// Extension trait on maps, providing methods to choose a random entry.
+///
+/// This is implemented for [`BTreeMap`] and (with the `std` feature)
+/// `HashMap`. No keys are collected: an entry is chosen via
+/// [`IteratorRandom::choose`] over the map's iterator, taking one random
+/// number and `O(n)` time, as maps do not support efficient indexing.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashMap;
+/// use rand::seq::MapRandom;
+///
+/// let mut cache = HashMap::new();
+/// cache.insert("a", 1);
+/// cache.insert("b", 2);
+/// // Evict a random entry
+/// let key = *cache.choose_key(&mut rand::thread_rng()).unwrap();
+/// cache.remove(key);
+/// assert_eq!(cache.len(), 1);
+/// ```
+pub trait MapRandom {
+    /// The key type.
+    type Key;
+    /// The value type.
+    type Value;
+
+    /// Returns a random entry, or `None` if the map is empty.
+    fn choose_entry<R>(&self, rng: &mut R) -> Option<(&Self::Key, &Self::Value)>
+    where R: Rng + ?Sized;
+
+    /// Returns a random entry with a mutable value, or `None` if the map is
+    /// empty.
+    fn choose_entry_mut<R>(&mut self, rng: &mut R) -> Option<(&Self::Key, &mut Self::Value)>
+    where R: Rng + ?Sized;
+
+    /// Returns the key of a random entry, or `None` if the map is empty.
+    #[inline]
+    fn choose_key<R>(&self, rng: &mut R) -> Option<&Self::Key>
+    where R: Rng + ?Sized {
+        self.choose_entry(rng).map(|(k, _)| k)
+    }
+
+    /// Returns the value of a random entry, or `None` if the map is empty.
+    #[inline]
+    fn choose_value<R>(&self, rng: &mut R) -> Option<&Self::Value>
+    where R: Rng + ?Sized {
+        self.choose_entry(rng).map(|(_, v)| v)
+    }
+}
+
+/// Extension trait on sets, providing a method to choose a random element.
+///
+/// This is implemented for [`BTreeSet`] and (with the `std` feature)
+/// `HashSet`. As for [`MapRandom`], an element is chosen via
+/// [`IteratorRandom::choose`] over the set's iterator, taking one random
+/// number and `O(n)` time.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::HashSet;
+/// use rand::seq::SetRandom;
+///
+/// let players: HashSet<&str> = ["ann", "bob", "cy"].iter().cloned().collect();
+/// println!("{} starts", players.choose(&mut rand::thread_rng()).unwrap());
+/// ```
+pub trait SetRandom {
+    /// The element type.
+    type Item;
+
+    /// Returns a random element, or `None` if the set is empty.
+    fn choose<R>(&self, rng: &mut R) -> Option<&Self::Item>
+    where R: Rng + ?Sized;
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl<K, V, S: BuildHasher> MapRandom for HashMap<K, V, S> {
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn choose_entry<R>(&self, rng: &mut R) -> Option<(&K, &V)>
+    where R: Rng + ?Sized {
+        self.iter().choose(rng)
+    }
+
+    #[inline]
+    fn choose_entry_mut<R>(&mut self, rng: &mut R) -> Option<(&K, &mut V)>
+    where R: Rng + ?Sized {
+        self.iter_mut().choose(rng)
+    }
+}
+
+impl<K, V> MapRandom for BTreeMap<K, V> {
+    type Key = K;
+    type Value = V;
+
+    #[inline]
+    fn choose_entry<R>(&self, rng: &mut R) -> Option<(&K, &V)>
+    where R: Rng + ?Sized {
+        self.iter().choose(rng)
+    }
+
+    #[inline]
+    fn choose_entry_mut<R>(&mut self, rng: &mut R) -> Option<(&K, &mut V)>
+    where R: Rng + ?Sized {
+        self.iter_mut().choose(rng)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+impl<T, S: BuildHasher> SetRandom for HashSet<T, S> {
+    type Item = T;
+
+    #[inline]
+    fn choose<R>(&self, rng: &mut R) -> Option<&T>
+    where R: Rng + ?Sized {
+        self.iter().choose(rng)
+    }
+}
+
+impl<T> SetRandom for BTreeSet<T> {
+    type Item = T;
+
+    #[inline]
+    fn choose<R>(&self, rng: &mut R) -> Option<&T>
+    where R: Rng + ?Sized {
+        self.iter().choose(rng)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_btree() {
+        let mut r = crate::test::rng(611);
+        let mut map: BTreeMap<u32, u32> = (0..4).map(|i| (i, i * 10)).collect();
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let (&k, &v) = map.choose_entry(&mut r).unwrap();
+            assert_eq!(v, k * 10);
+            counts[k as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| (900..1100).contains(&c)));
+        assert!(map.choose_key(&mut r).unwrap() < &4);
+        assert_eq!(map.choose_value(&mut r).unwrap() % 10, 0);
+        *map.choose_entry_mut(&mut r).unwrap().1 = 1;
+        assert_eq!(map.values().filter(|&&v| v == 1).count(), 1);
+
+        let set: BTreeSet<u32> = (0..4).collect();
+        assert!(set.choose(&mut r).unwrap() < &4);
+
+        assert_eq!(BTreeMap::<u8, u8>::new().choose_entry(&mut r), None);
+        assert_eq!(BTreeSet::<u8>::new().choose(&mut r), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_hash() {
+        let mut r = crate::test::rng(612);
+        let mut map: HashMap<u32, u32> = (0..4).map(|i| (i, i * 10)).collect();
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            counts[*map.choose_key(&mut r).unwrap() as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| (900..1100).contains(&c)));
+        let (&k, v) = map.choose_entry_mut(&mut r).unwrap();
+        *v += 1;
+        assert_eq!(map[&k], k * 10 + 1);
+
+        let set: HashSet<u32> = (0..4).collect();
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            counts[*set.choose(&mut r).unwrap() as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| (900..1100).contains(&c)));
+
+        assert_eq!(HashMap::<u8, u8>::new().choose_value(&mut r), None);
+        assert_eq!(HashSet::<u8>::new().choose(&mut r), None);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut r = crate::test::rng(613);
+        let map: BTreeMap<u32, char> = (0..10).zip("abcdefghij".chars()).collect();
+        let set: BTreeSet<u32> = (0..10).collect();
+        assert_eq!(map.choose_entry(&mut r), Some((&2, &'c')));
+        assert_eq!(set.choose(&mut r), Some(&1));
+    }
+}
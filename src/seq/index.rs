@@ -13,10 +13,9 @@
 #[cfg(feature = "alloc")] use alloc::vec::{self, Vec};
 // BTreeMap is not as fast in tests, but better than nothing.
 #[cfg(all(feature = "alloc", not(feature = "std")))]
-use alloc::collections::BTreeSet;
-#[cfg(feature = "std")] use std::collections::HashSet;
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "std")] use std::collections::{HashMap, HashSet};
 
-#[cfg(feature = "std")]
 use crate::distributions::WeightedError;
 
 #[cfg(feature = "alloc")]
@@ -27,7 +26,10 @@ use serde::{Serialize, Deserialize};
 
 /// A vector of indices.
 ///
-/// Multiple internal representations are possible.
+/// Multiple internal representations are possible. Indices in increasing
+/// order, as returned by [`sample_combination`] and [`sample_split`], are
+/// stored as an [`IndexBitSet`] where this is more compact, i.e. where there
+/// are more than `length / 32` of them.
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub enum IndexVec {
@@ -35,6 +37,8 @@ pub enum IndexVec {
     U32(Vec<u32>),
     #[doc(hidden)]
     USize(Vec<usize>),
+    #[doc(hidden)]
+    BitSet(IndexBitSet),
 }
 
 impl IndexVec {
@@ -44,6 +48,7 @@ impl IndexVec {
         match *self {
             IndexVec::U32(ref v) => v.len(),
             IndexVec::USize(ref v) => v.len(),
+            IndexVec::BitSet(ref set) => set.len(),
         }
     }
 
@@ -53,11 +58,15 @@ impl IndexVec {
         match *self {
             IndexVec::U32(ref v) => v.is_empty(),
             IndexVec::USize(ref v) => v.is_empty(),
+            IndexVec::BitSet(ref set) => set.is_empty(),
         }
     }
 
     /// Return the value at the given `index`.
     ///
+    /// This takes `O(1)` time, except if the indices are stored as a bitset,
+    /// where it takes `O(length / 64)` time.
+    ///
     /// (Note: we cannot implement [`std::ops::Index`] because of lifetime
     /// restrictions.)
     #[inline]
@@ -65,6 +74,19 @@ impl IndexVec {
         match *self {
             IndexVec::U32(ref v) => v[index] as usize,
             IndexVec::USize(ref v) => v[index],
+            IndexVec::BitSet(ref set) => set.select(index),
+        }
+    }
+
+    /// Returns `true` if `index` is one of the indices.
+    ///
+    /// This takes `O(1)` time if the indices are stored as a bitset, and
+    /// `O(len)` time otherwise.
+    pub fn contains(&self, index: usize) -> bool {
+        match *self {
+            IndexVec::U32(ref v) => index <= (u32::MAX as usize) && v.contains(&(index as u32)),
+            IndexVec::USize(ref v) => v.contains(&index),
+            IndexVec::BitSet(ref set) => set.contains(index),
         }
     }
 
@@ -74,6 +96,7 @@ impl IndexVec {
         match self {
             IndexVec::U32(v) => v.into_iter().map(|i| i as usize).collect(),
             IndexVec::USize(v) => v,
+            IndexVec::BitSet(set) => set.into_vec(),
         }
     }
 
@@ -83,6 +106,7 @@ impl IndexVec {
         match *self {
             IndexVec::U32(ref v) => IndexVecIter::U32(v.iter()),
             IndexVec::USize(ref v) => IndexVecIter::USize(v.iter()),
+            IndexVec::BitSet(ref set) => IndexVecIter::BitSet(set.iter()),
         }
     }
 }
@@ -97,6 +121,7 @@ impl IntoIterator for IndexVec {
         match self {
             IndexVec::U32(v) => IndexVecIntoIter::U32(v.into_iter()),
             IndexVec::USize(v) => IndexVecIntoIter::USize(v.into_iter()),
+            IndexVec::BitSet(set) => IndexVecIntoIter::BitSet(set.into_iter()),
         }
     }
 }
@@ -113,6 +138,8 @@ impl PartialEq for IndexVec {
             (&USize(ref v1), &U32(ref v2)) => {
                 (v1.len() == v2.len()) && (v1.iter().zip(v2.iter()).all(|(x, y)| *x == *y as usize))
             }
+            (BitSet(s1), BitSet(s2)) => s1 == s2,
+            _ => self.len() == other.len() && self.iter().eq(other.iter()),
         }
     }
 }
@@ -138,6 +165,8 @@ pub enum IndexVecIter<'a> {
     U32(slice::Iter<'a, u32>),
     #[doc(hidden)]
     USize(slice::Iter<'a, usize>),
+    #[doc(hidden)]
+    BitSet(IndexBitSetIter<'a>),
 }
 
 impl<'a> Iterator for IndexVecIter<'a> {
@@ -149,6 +178,7 @@ impl<'a> Iterator for IndexVecIter<'a> {
         match *self {
             U32(ref mut iter) => iter.next().map(|i| *i as usize),
             USize(ref mut iter) => iter.next().cloned(),
+            BitSet(ref mut iter) => iter.next(),
         }
     }
 
@@ -157,6 +187,7 @@ impl<'a> Iterator for IndexVecIter<'a> {
         match *self {
             IndexVecIter::U32(ref v) => v.size_hint(),
             IndexVecIter::USize(ref v) => v.size_hint(),
+            IndexVecIter::BitSet(ref v) => v.size_hint(),
         }
     }
 }
@@ -170,6 +201,8 @@ pub enum IndexVecIntoIter {
     U32(vec::IntoIter<u32>),
     #[doc(hidden)]
     USize(vec::IntoIter<usize>),
+    #[doc(hidden)]
+    BitSet(IndexBitSetIntoIter),
 }
 
 impl Iterator for IndexVecIntoIter {
@@ -181,6 +214,7 @@ impl Iterator for IndexVecIntoIter {
         match *self {
             U32(ref mut v) => v.next().map(|i| i as usize),
             USize(ref mut v) => v.next(),
+            BitSet(ref mut v) => v.next(),
         }
     }
 
@@ -190,6 +224,7 @@ impl Iterator for IndexVecIntoIter {
         match *self {
             U32(ref v) => v.size_hint(),
             USize(ref v) => v.size_hint(),
+            BitSet(ref v) => v.size_hint(),
         }
     }
 }
@@ -197,6 +232,484 @@ impl Iterator for IndexVecIntoIter {
 impl ExactSizeIterator for IndexVecIntoIter {}
 
 
+/// A vector of `u64` indices, returned by [`sample_u64`].
+///
+/// Multiple internal representations are possible.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum IndexVec64 {
+    #[doc(hidden)]
+    U32(Vec<u32>),
+    #[doc(hidden)]
+    U64(Vec<u64>),
+}
+
+impl IndexVec64 {
+    /// Returns the number of indices
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            IndexVec64::U32(v) => v.len(),
+            IndexVec64::U64(v) => v.len(),
+        }
+    }
+
+    /// Returns `true` if the length is 0.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        match self {
+            IndexVec64::U32(v) => v.is_empty(),
+            IndexVec64::U64(v) => v.is_empty(),
+        }
+    }
+
+    /// Return the value at the given `index`.
+    #[inline]
+    pub fn index(&self, index: usize) -> u64 {
+        match self {
+            IndexVec64::U32(v) => u64::from(v[index]),
+            IndexVec64::U64(v) => v[index],
+        }
+    }
+
+    /// Return result as a `Vec<u64>`. Conversion may or may not be trivial.
+    #[inline]
+    pub fn into_vec(self) -> Vec<u64> {
+        match self {
+            IndexVec64::U32(v) => v.into_iter().map(u64::from).collect(),
+            IndexVec64::U64(v) => v,
+        }
+    }
+
+    /// Iterate over the indices as a sequence of `u64` values
+    #[inline]
+    pub fn iter(&self) -> IndexVec64Iter<'_> {
+        match self {
+            IndexVec64::U32(v) => IndexVec64Iter::U32(v.iter()),
+            IndexVec64::U64(v) => IndexVec64Iter::U64(v.iter()),
+        }
+    }
+}
+
+impl IntoIterator for IndexVec64 {
+    type Item = u64;
+    type IntoIter = IndexVec64IntoIter;
+
+    /// Convert into an iterator over the indices as a sequence of `u64` values
+    #[inline]
+    fn into_iter(self) -> IndexVec64IntoIter {
+        match self {
+            IndexVec64::U32(v) => IndexVec64IntoIter::U32(v.into_iter()),
+            IndexVec64::U64(v) => IndexVec64IntoIter::U64(v.into_iter()),
+        }
+    }
+}
+
+impl PartialEq for IndexVec64 {
+    fn eq(&self, other: &IndexVec64) -> bool {
+        use self::IndexVec64::*;
+        match (self, other) {
+            (U32(v1), U32(v2)) => v1 == v2,
+            (U64(v1), U64(v2)) => v1 == v2,
+            (U32(v1), U64(v2)) | (U64(v2), U32(v1)) => {
+                (v1.len() == v2.len()) && (v1.iter().zip(v2.iter()).all(|(x, y)| u64::from(*x) == *y))
+            }
+        }
+    }
+}
+
+impl From<Vec<u32>> for IndexVec64 {
+    #[inline]
+    fn from(v: Vec<u32>) -> Self {
+        IndexVec64::U32(v)
+    }
+}
+
+impl From<Vec<u64>> for IndexVec64 {
+    #[inline]
+    fn from(v: Vec<u64>) -> Self {
+        IndexVec64::U64(v)
+    }
+}
+
+/// Return type of `IndexVec64::iter`.
+#[derive(Debug)]
+pub enum IndexVec64Iter<'a> {
+    #[doc(hidden)]
+    U32(slice::Iter<'a, u32>),
+    #[doc(hidden)]
+    U64(slice::Iter<'a, u64>),
+}
+
+impl<'a> Iterator for IndexVec64Iter<'a> {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        use self::IndexVec64Iter::*;
+        match self {
+            U32(iter) => iter.next().map(|i| u64::from(*i)),
+            U64(iter) => iter.next().cloned(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self {
+            IndexVec64Iter::U32(v) => v.size_hint(),
+            IndexVec64Iter::U64(v) => v.size_hint(),
+        }
+    }
+}
+
+impl<'a> ExactSizeIterator for IndexVec64Iter<'a> {}
+
+/// Return type of `IndexVec64::into_iter`.
+#[derive(Clone, Debug)]
+pub enum IndexVec64IntoIter {
+    #[doc(hidden)]
+    U32(vec::IntoIter<u32>),
+    #[doc(hidden)]
+    U64(vec::IntoIter<u64>),
+}
+
+impl Iterator for IndexVec64IntoIter {
+    type Item = u64;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        use self::IndexVec64IntoIter::*;
+        match self {
+            U32(v) => v.next().map(u64::from),
+            U64(v) => v.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        use self::IndexVec64IntoIter::*;
+        match self {
+            U32(v) => v.size_hint(),
+            U64(v) => v.size_hint(),
+        }
+    }
+}
+
+impl ExactSizeIterator for IndexVec64IntoIter {}
+
+
+/// A set of indices in `0..length`, stored as a bitset.
+///
+/// This uses `length / 8` bytes regardless of the number of indices, thus is
+/// much more compact than a vector of indices for dense samples, i.e. where
+/// the number of indices is more than a small fraction of `length`. Indices
+/// are iterated in increasing order.
+///
+/// See [`sample_bitset`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", serde(try_from = "IndexBitSetData"))]
+pub struct IndexBitSet {
+    words: Vec<u64>,
+    length: usize,
+    count: usize,
+}
+
+/// The serialized fields of an [`IndexBitSet`], validated on deserialization.
+#[cfg(feature = "serde1")]
+#[derive(Deserialize)]
+struct IndexBitSetData {
+    words: Vec<u64>,
+    length: usize,
+    count: usize,
+}
+
+/// Error type indicating that a deserialized [`IndexBitSet`] was invalid.
+#[cfg(feature = "serde1")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct InvalidIndexBitSet;
+
+#[cfg(feature = "serde1")]
+impl core::fmt::Display for InvalidIndexBitSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("invalid index bitset")
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl TryFrom<IndexBitSetData> for IndexBitSet {
+    type Error = InvalidIndexBitSet;
+
+    /// Check that there is one word per 64 indices, no index is out of
+    /// bounds and `count` is the number of indices.
+    fn try_from(data: IndexBitSetData) -> Result<Self, Self::Error> {
+        let IndexBitSetData { words, length, count } = data;
+        if words.len() != length / 64 + (length % 64 != 0) as usize {
+            return Err(InvalidIndexBitSet);
+        }
+        if length % 64 != 0 && words.last().map_or(false, |&w| w >> (length % 64) != 0) {
+            return Err(InvalidIndexBitSet);
+        }
+        if words.iter().map(|w| w.count_ones() as usize).sum::<usize>() != count {
+            return Err(InvalidIndexBitSet);
+        }
+        Ok(IndexBitSet { words, length, count })
+    }
+}
+
+impl IndexBitSet {
+    /// Construct an empty set of indices in `0..length`.
+    pub fn new(length: usize) -> Self {
+        IndexBitSet {
+            words: alloc::vec![0; length / 64 + (length % 64 != 0) as usize],
+            length,
+            count: 0,
+        }
+    }
+
+    /// Construct the set of all indices in `0..length`.
+    fn full(length: usize) -> Self {
+        let mut words = alloc::vec![u64::MAX; length / 64 + (length % 64 != 0) as usize];
+        if length % 64 != 0 {
+            *words.last_mut().unwrap() = (1 << (length % 64)) - 1;
+        }
+        IndexBitSet {
+            words,
+            length,
+            count: length,
+        }
+    }
+
+    /// Returns the number of indices in the set
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if the set is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Returns the exclusive upper bound `length` of indices in the set.
+    #[inline]
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Returns `true` if `index` is in the set.
+    #[inline]
+    pub fn contains(&self, index: usize) -> bool {
+        index < self.length && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Insert `index` into the set, returning `true` if it was not present.
+    ///
+    /// Panics if `index >= length`.
+    #[inline]
+    pub fn insert(&mut self, index: usize) -> bool {
+        assert!(index < self.length, "index out of bounds");
+        let word = &mut self.words[index / 64];
+        let bit = 1 << (index % 64);
+        let absent = *word & bit == 0;
+        *word |= bit;
+        self.count += absent as usize;
+        absent
+    }
+
+    /// Remove `index` from the set, returning `true` if it was present.
+    #[inline]
+    pub fn remove(&mut self, index: usize) -> bool {
+        if index >= self.length {
+            return false;
+        }
+        let word = &mut self.words[index / 64];
+        let bit = 1 << (index % 64);
+        let present = *word & bit != 0;
+        *word &= !bit;
+        self.count -= present as usize;
+        present
+    }
+
+    /// Iterate over the indices in increasing order
+    #[inline]
+    pub fn iter(&self) -> IndexBitSetIter<'_> {
+        IndexBitSetIter(Bits::new(self.words.iter().copied(), self.count))
+    }
+
+    /// Return the indices in increasing order as a `Vec<usize>`.
+    #[inline]
+    pub fn into_vec(self) -> Vec<usize> {
+        self.iter().collect()
+    }
+
+    /// Returns the `n`-th smallest index, in `O(length / 64)` time.
+    ///
+    /// Panics if `n >= len`.
+    fn select(&self, mut n: usize) -> usize {
+        assert!(n < self.count, "index out of bounds");
+        for (i, &word) in self.words.iter().enumerate() {
+            let ones = word.count_ones() as usize;
+            if n < ones {
+                let mut word = word;
+                for _ in 0..n {
+                    word &= word - 1;
+                }
+                return i * 64 + word.trailing_zeros() as usize;
+            }
+            n -= ones;
+        }
+        unreachable!()
+    }
+}
+
+impl IntoIterator for IndexBitSet {
+    type Item = usize;
+    type IntoIter = IndexBitSetIntoIter;
+
+    /// Convert into an iterator over the indices in increasing order
+    #[inline]
+    fn into_iter(self) -> IndexBitSetIntoIter {
+        IndexBitSetIntoIter(Bits::new(self.words.into_iter(), self.count))
+    }
+}
+
+impl<'a> IntoIterator for &'a IndexBitSet {
+    type Item = usize;
+    type IntoIter = IndexBitSetIter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> IndexBitSetIter<'a> {
+        self.iter()
+    }
+}
+
+impl From<IndexBitSet> for IndexVec {
+    /// Convert into an `IndexVec` of the indices in increasing order. The
+    /// conversion is trivial.
+    #[inline]
+    fn from(set: IndexBitSet) -> Self {
+        IndexVec::BitSet(set)
+    }
+}
+
+/// The iteration over the set bits of a sequence of words.
+#[derive(Clone, Debug)]
+struct Bits<I> {
+    words: I,
+    // The remaining bits of the current word, at index `base - 64`
+    word: u64,
+    base: usize,
+    remaining: usize,
+}
+
+impl<I: Iterator<Item = u64>> Bits<I> {
+    #[inline]
+    fn new(words: I, count: usize) -> Self {
+        Bits { words, word: 0, base: 0, remaining: count }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        while self.word == 0 {
+            self.word = self.words.next()?;
+            self.base += 64;
+        }
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        self.remaining -= 1;
+        Some(self.base - 64 + bit)
+    }
+}
+
+/// Return type of `IndexBitSet::iter`.
+#[derive(Clone, Debug)]
+pub struct IndexBitSetIter<'a>(Bits<core::iter::Copied<slice::Iter<'a, u64>>>);
+
+impl<'a> Iterator for IndexBitSetIter<'a> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.remaining, Some(self.0.remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for IndexBitSetIter<'a> {}
+
+impl<'a> core::iter::FusedIterator for IndexBitSetIter<'a> {}
+
+/// Return type of `IndexBitSet::into_iter`.
+#[derive(Clone, Debug)]
+pub struct IndexBitSetIntoIter(Bits<vec::IntoIter<u64>>);
+
+impl Iterator for IndexBitSetIntoIter {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.0.remaining, Some(self.0.remaining))
+    }
+}
+
+impl ExactSizeIterator for IndexBitSetIntoIter {}
+
+impl core::iter::FusedIterator for IndexBitSetIntoIter {}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length`, and
+/// return them as an [`IndexBitSet`].
+///
+/// Unlike [`sample`], the result is not in random order, and always uses
+/// `length / 8` bytes of memory, which is more compact than [`IndexVec`] if
+/// `amount > length / 32`. This is thus suited to dense samples of large
+/// ranges. Indices are sampled by rejection, using the bitset to detect
+/// duplicates; if `amount > length / 2`, the indices *not* in the sample are
+/// sampled instead, such that the expected number of random values is at most
+/// `2 * min(amount, length - amount)`.
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index::sample_bitset;
+///
+/// // Select 90% of 10 million rows, using 1.25 MB
+/// let rows = sample_bitset(&mut rand::thread_rng(), 10_000_000, 9_000_000);
+/// assert_eq!(rows.len(), 9_000_000);
+/// assert_eq!(rows.iter().count(), 9_000_000);
+/// ```
+pub fn sample_bitset<R>(rng: &mut R, length: usize, amount: usize) -> IndexBitSet
+where R: Rng + ?Sized {
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    if amount > length / 2 {
+        let mut set = IndexBitSet::full(length);
+        while set.len() > amount {
+            set.remove(super::gen_index(rng, length));
+        }
+        set
+    } else {
+        let mut set = IndexBitSet::new(length);
+        while set.len() < amount {
+            set.insert(super::gen_index(rng, length));
+        }
+        set
+    }
+}
+
 /// Randomly sample exactly `amount` distinct indices from `0..length`, and
 /// return them in random order (fully shuffled).
 ///
@@ -218,6 +731,9 @@ impl ExactSizeIterator for IndexVecIntoIter {}
 /// If an allocation-free `no_std` function is required, it is suggested
 /// to adapt the internal `sample_floyd` implementation.
 ///
+/// For indices in increasing order, see [`sample_combination`] and
+/// [`sample_sorted_iter`].
+///
 /// Panics if `amount > length`.
 pub fn sample<R>(rng: &mut R, length: usize, amount: usize) -> IndexVec
 where R: Rng + ?Sized {
@@ -258,8 +774,413 @@ where R: Rng + ?Sized {
     }
 }
 
-/// Randomly sample exactly `amount` distinct indices from `0..length`, and
-/// return them in an arbitrary order (there is no guarantee of shuffling or
+/// Randomly sample exactly `amount` distinct indices from `0..length`,
+/// lazily yielding them in random order.
+///
+/// Unlike [`sample`], indices are only generated as the iterator is
+/// consumed, so a caller may stop early without paying for the full sample.
+/// This performs a partial Fisher-Yates shuffle of `0..length` on demand,
+/// storing only the swapped positions: each index takes `O(1)` expected time
+/// and memory. The output is not identical to that of [`sample`].
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index::sample_iter;
+///
+/// // Find a random free slot among many, trying slots in random order
+/// let is_free = |slot: usize| slot % 7 == 3;
+/// let slot = sample_iter(rand::thread_rng(), 1_000_000, 1_000_000).find(|&s| is_free(s));
+/// assert!(slot.is_some());
+/// ```
+pub fn sample_iter<R: Rng>(rng: R, length: usize, amount: usize) -> SampleIter<R> {
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    SampleIter {
+        rng,
+        length,
+        end: amount,
+        position: 0,
+        swapped: Default::default(),
+    }
+}
+
+/// An iterator yielding distinct random indices, created by [`sample_iter`].
+#[derive(Clone, Debug)]
+pub struct SampleIter<R> {
+    rng: R,
+    length: usize,
+    end: usize,
+    position: usize,
+    // The values at positions of the virtual array `0..length` which differ
+    // from their index
+    #[cfg(feature = "std")]
+    swapped: HashMap<usize, usize>,
+    #[cfg(not(feature = "std"))]
+    swapped: BTreeMap<usize, usize>,
+}
+
+impl<R: Rng> Iterator for SampleIter<R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.position == self.end {
+            return None;
+        }
+        let i = self.position;
+        // Use 32-bit sampling where possible for portable results
+        let j = if self.length <= (u32::MAX as usize) {
+            self.rng.gen_range(i as u32..self.length as u32) as usize
+        } else {
+            self.rng.gen_range(i..self.length)
+        };
+        let value_i = self.swapped.remove(&i).unwrap_or(i);
+        let value_j = if j == i {
+            value_i
+        } else {
+            self.swapped.insert(j, value_i).unwrap_or(j)
+        };
+        self.position += 1;
+        Some(value_j)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.position;
+        (len, Some(len))
+    }
+}
+
+impl<R: Rng> ExactSizeIterator for SampleIter<R> {}
+
+impl<R: Rng> core::iter::FusedIterator for SampleIter<R> {}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length`,
+/// excluding the indices in `excluded`, and return them in random order
+/// (fully shuffled).
+///
+/// Indices are sampled uniformly among the `length - e` indices which are not
+/// excluded, where `e` is the number of distinct excluded indices below
+/// `length`; `excluded` may be unsorted and contain duplicates or indices
+/// outside `0..length`. Unlike resampling until a non-excluded index is found,
+/// performance does not degrade if most indices are excluded: `excluded` is
+/// sorted once, and each sampled index is mapped past the excluded indices by
+/// binary search. Complexity is that of [`sample`] plus
+/// `O(e log e + amount log e)` time and `O(e)` memory.
+///
+/// Panics if `amount` exceeds the number of non-excluded indices.
+pub fn sample_excluding<R>(rng: &mut R, length: usize, amount: usize, excluded: &[usize]) -> IndexVec
+where R: Rng + ?Sized {
+    let mut excluded: Vec<usize> = excluded.iter().cloned().filter(|&i| i < length).collect();
+    excluded.sort_unstable();
+    excluded.dedup();
+    let available = length - excluded.len();
+    if amount > available {
+        panic!("`amount` of samples must be less than or equal to the number of non-excluded indices");
+    }
+
+    // The `rank`-th non-excluded index is `rank + k`, where `k` is the
+    // number of excluded indices below it, i.e. the least `k` such that
+    // `excluded[k] > rank + k`. Since `excluded[k] - k` is non-decreasing,
+    // `k` can be found by binary search.
+    let map = |rank: usize| {
+        let (mut low, mut high) = (0, excluded.len());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if excluded[mid] - mid <= rank {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        rank + low
+    };
+
+    let ranks = sample(rng, available, amount);
+    if excluded.is_empty() {
+        return ranks;
+    }
+    match ranks {
+        IndexVec::U32(mut v) if length <= (u32::MAX as usize) => {
+            for i in v.iter_mut() {
+                *i = map(*i as usize) as u32;
+            }
+            IndexVec::U32(v)
+        }
+        ranks => IndexVec::USize(ranks.into_iter().map(map).collect()),
+    }
+}
+
+/// Randomly sample a uniformly distributed `amount`-combination of
+/// `0..length`, i.e. exactly `amount` distinct indices in increasing order.
+///
+/// Each of the `length choose amount` combinations is equally likely. Unlike
+/// sorting the output of a shuffle, this never materializes `0..length`, so
+/// `length` may be very large: indices are sampled via [`sample`] (or, if
+/// `amount > length / 2`, the indices *not* in the combination are), then
+/// sorted. Complexity is `O(amount log amount)` time and `O(amount)` memory.
+///
+/// If `amount > length / 32`, the result is stored as an [`IndexBitSet`]
+/// instead of being sorted, which is then more compact: complexity is
+/// `O(amount + length / 64)` time and `O(length / 64)` memory.
+///
+/// See [`combinations`] for an iterator over independent combinations.
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index::sample_combination;
+///
+/// let lottery = sample_combination(&mut rand::thread_rng(), 49, 6).into_vec();
+/// assert!(lottery.windows(2).all(|w| w[0] < w[1]));
+/// ```
+pub fn sample_combination<R>(rng: &mut R, length: usize, amount: usize) -> IndexVec
+where R: Rng + ?Sized {
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    if amount > length / 2 {
+        // Sample the complement, then remove it from all indices
+        let mut set = IndexBitSet::full(length);
+        for i in sample(rng, length, length - amount).iter() {
+            set.remove(i);
+        }
+        return IndexVec::BitSet(set);
+    }
+    let indices = sample(rng, length, amount);
+    if is_dense(length, amount) {
+        let mut set = IndexBitSet::new(length);
+        for i in indices.iter() {
+            set.insert(i);
+        }
+        return IndexVec::BitSet(set);
+    }
+    match indices {
+        IndexVec::U32(mut v) => {
+            v.sort_unstable();
+            IndexVec::U32(v)
+        }
+        IndexVec::USize(mut v) => {
+            v.sort_unstable();
+            IndexVec::USize(v)
+        }
+        IndexVec::BitSet(set) => IndexVec::BitSet(set),
+    }
+}
+
+/// Returns `true` if an [`IndexBitSet`] of `amount` indices in `0..length` is
+/// more compact than a vector of them.
+fn is_dense(length: usize, amount: usize) -> bool {
+    let bits = if length <= (u32::MAX as usize) { 32 } else { 64 };
+    amount > length / bits
+}
+
+/// Randomly split `0..length` into a uniformly distributed group of
+/// `amount` indices and its complement, both in increasing order.
+///
+/// The first group is sampled as by [`sample_combination`]; the second holds
+/// the remaining `length - amount` indices. Either group is stored as an
+/// [`IndexBitSet`] if this is more compact, see [`IndexVec`]. Complexity is
+/// `O(amount + length / 64)` time and memory.
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index::sample_split;
+///
+/// let (test, train) = sample_split(&mut rand::thread_rng(), 10, 2);
+/// assert_eq!(test.len(), 2);
+/// assert_eq!(train.len(), 8);
+/// ```
+pub fn sample_split<R>(rng: &mut R, length: usize, amount: usize) -> (IndexVec, IndexVec)
+where R: Rng + ?Sized {
+    let selected = sample_combination(rng, length, amount);
+    let mut rest = IndexBitSet::full(length);
+    for i in selected.iter() {
+        rest.remove(i);
+    }
+    let rest = if is_dense(length, rest.len()) {
+        IndexVec::BitSet(rest)
+    } else if length <= (u32::MAX as usize) {
+        IndexVec::U32(rest.iter().map(|i| i as u32).collect())
+    } else {
+        IndexVec::USize(rest.iter().collect())
+    };
+    (selected, rest)
+}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length`,
+/// lazily yielding them in increasing order.
+///
+/// This uses sequential selection (Vitter's Method A): for each index, the
+/// number of indices to skip before it is sampled directly. Exactly one
+/// random value is used per index, memory use is `O(1)` and total time is
+/// `O(length)` in the worst case. This suits sequential access to files or
+/// arrays; if the sorted indices are needed in memory and `amount` is much
+/// smaller than `length`, [`sample_combination`] is faster. Each of the
+/// `length choose amount` combinations is equally likely, but the output is
+/// not identical to that of [`sample_combination`].
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index::sample_sorted_iter;
+///
+/// let lines = ["a", "b", "c", "d", "e", "f"];
+/// // Read 3 lines at random, in file order
+/// let mut previous = None;
+/// for i in sample_sorted_iter(rand::thread_rng(), lines.len(), 3) {
+///     assert!(previous < Some(i));
+///     println!("{}", lines[i]);
+///     previous = Some(i);
+/// }
+/// ```
+pub fn sample_sorted_iter<R: Rng>(rng: R, length: usize, amount: usize) -> SampleSortedIter<R> {
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    SampleSortedIter {
+        rng,
+        next: 0,
+        remaining: length,
+        amount,
+    }
+}
+
+/// An iterator yielding distinct random indices in increasing order, created
+/// by [`sample_sorted_iter`].
+#[derive(Clone, Debug)]
+pub struct SampleSortedIter<R> {
+    rng: R,
+    // The least index which may be yielded next
+    next: usize,
+    // The number of indices in `next..length`
+    remaining: usize,
+    // The number of indices still to be yielded
+    amount: usize,
+}
+
+impl<R: Rng> Iterator for SampleSortedIter<R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.amount == 0 {
+            return None;
+        }
+        // Sample the number of indices to skip: the probability of skipping
+        // at least `s + 1` indices is the product of `(n - k - i) / (n - i)`
+        // for `i` in `0..=s`, given `n` remaining indices and `k` to choose.
+        let u: f64 = self.rng.gen();
+        let mut top = (self.remaining - self.amount) as f64;
+        let mut n = self.remaining as f64;
+        let mut quot = top / n;
+        let mut skip = 0;
+        while quot > u {
+            skip += 1;
+            top -= 1.0;
+            n -= 1.0;
+            quot *= top / n;
+        }
+        let index = self.next + skip;
+        self.next = index + 1;
+        self.remaining -= skip + 1;
+        self.amount -= 1;
+        Some(index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.amount, Some(self.amount))
+    }
+}
+
+impl<R: Rng> ExactSizeIterator for SampleSortedIter<R> {}
+
+impl<R: Rng> core::iter::FusedIterator for SampleSortedIter<R> {}
+
+/// Create an infinite iterator over independent, uniformly distributed
+/// `amount`-combinations of `0..length`, each in increasing order.
+///
+/// Each item is sampled as by [`sample_combination`].
+///
+/// Panics if `amount > length`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index::combinations;
+///
+/// // Five random test matrices, each selecting 3 of 1000 configurations
+/// for combination in combinations(rand::thread_rng(), 1000, 3).take(5) {
+///     println!("{:?}", combination.into_vec());
+/// }
+/// ```
+pub fn combinations<R: Rng>(rng: R, length: usize, amount: usize) -> Combinations<R> {
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    Combinations { rng, length, amount }
+}
+
+/// An infinite iterator over random combinations, created by
+/// [`combinations`].
+#[derive(Clone, Debug)]
+pub struct Combinations<R> {
+    rng: R,
+    length: usize,
+    amount: usize,
+}
+
+impl<R: Rng> Iterator for Combinations<R> {
+    type Item = IndexVec;
+
+    #[inline]
+    fn next(&mut self) -> Option<IndexVec> {
+        Some(sample_combination(&mut self.rng, self.length, self.amount))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length` over
+/// `u64`, and return them in random order (fully shuffled).
+///
+/// This is a variant of [`sample`] for index spaces which may exceed
+/// `usize::MAX` on 32-bit targets, such as offsets into a large file or a
+/// virtual collection. Results are identical on all platforms. For `length`
+/// up to `u32::MAX`, the same algorithms and thus the same values as
+/// [`sample`] are used.
+///
+/// Since the result is stored in memory, `amount` must still be small
+/// enough to allocate.
+///
+/// Panics if `amount > length`.
+pub fn sample_u64<R>(rng: &mut R, length: u64, amount: u64) -> IndexVec64
+where R: Rng + ?Sized {
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    if length > u64::from(u32::MAX) {
+        return IndexVec64::U64(sample_rejection_vec(rng, length, amount));
+    }
+    match sample(rng, length as usize, amount as usize) {
+        IndexVec::U32(v) => IndexVec64::U32(v),
+        v => IndexVec64::U64(v.into_iter().map(|i| i as u64).collect()),
+    }
+}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length`, and
+/// return them in an arbitrary order (there is no guarantee of shuffling or
 /// ordering). The weights are to be provided by the input function `weights`,
 /// which will be called once for each index.
 ///
@@ -267,73 +1188,293 @@ where R: Rng + ?Sized {
 /// sometimes be useful to have the indices themselves so this is provided as
 /// an alternative.
 ///
-/// This implementation uses `O(length + amount)` space and `O(length)` time.
+/// This implementation uses `O(length + amount)` space and `O(length)` time.
+///
+/// Panics if `amount > length`.
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub fn sample_weighted<R, F, X>(
+    rng: &mut R, length: usize, weight: F, amount: usize,
+) -> Result<IndexVec, WeightedError>
+where
+    R: Rng + ?Sized,
+    F: Fn(usize) -> X,
+    X: Into<f64>,
+{
+    if length > (core::u32::MAX as usize) {
+        sample_efraimidis_spirakis(rng, length, weight, amount)
+    } else {
+        assert!(amount <= core::u32::MAX as usize);
+        let amount = amount as u32;
+        let length = length as u32;
+        sample_efraimidis_spirakis(rng, length, weight, amount)
+    }
+}
+
+
+/// Randomly sample exactly `amount` distinct indices from `0..length`, and
+/// return them in an arbitrary order (there is no guarantee of shuffling or
+/// ordering). The weights are to be provided by the input function `weights`,
+/// which will be called once for each index.
+///
+/// This implementation uses the algorithm described by Efraimidis and Spirakis
+/// in this paper: https://doi.org/10.1016/j.ipl.2005.11.003
+/// It uses `O(length + amount)` space and `O(length)` time.
+///
+/// Panics if `amount > length`.
+#[cfg(feature = "std")]
+fn sample_efraimidis_spirakis<R, F, X, N>(
+    rng: &mut R, length: N, weight: F, amount: N,
+) -> Result<IndexVec, WeightedError>
+where
+    R: Rng + ?Sized,
+    F: Fn(usize) -> X,
+    X: Into<f64>,
+    N: UInt,
+    IndexVec: From<Vec<N>>,
+{
+    if amount == N::zero() {
+        return Ok(IndexVec::U32(Vec::new()));
+    }
+
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+
+    struct Element<N> {
+        index: N,
+        key: f64,
+    }
+    impl<N> PartialOrd for Element<N> {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            self.key.partial_cmp(&other.key)
+        }
+    }
+    impl<N> Ord for Element<N> {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+             // partial_cmp will always produce a value,
+             // because we check that the weights are not nan
+            self.partial_cmp(other).unwrap()
+        }
+    }
+    impl<N> PartialEq for Element<N> {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+    impl<N> Eq for Element<N> {}
+
+    let mut candidates = Vec::with_capacity(length.as_usize());
+    let mut index = N::zero();
+    while index < length {
+        let weight = weight(index.as_usize()).into();
+        if !(weight >= 0.) {
+            return Err(WeightedError::InvalidWeight);
+        }
+
+        let key = rng.gen::<f64>().powf(1.0 / weight);
+        candidates.push(Element { index, key });
+
+        index += N::one();
+    }
+
+    // Partially sort the array to find the `amount` elements with the greatest
+    // keys. Do this by using `select_nth_unstable` to put the elements with
+    // the *smallest* keys at the beginning of the list in `O(n)` time, which
+    // provides equivalent information about the elements with the *greatest* keys.
+    let (_, mid, greater)
+        = candidates.select_nth_unstable(length.as_usize() - amount.as_usize());
+
+    let mut result: Vec<N> = Vec::with_capacity(amount.as_usize());
+    result.push(mid.index);
+    for element in greater {
+        result.push(element.index);
+    }
+    Ok(IndexVec::from(result))
+}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length`, such
+/// that the probability of each index being included is proportional to its
+/// weight (where possible), and return them in an arbitrary order.
+///
+/// The weights are to be provided by the input function `weight`, which will
+/// be called once for each index. Index `i` is included with probability
+/// exactly `min(1, c * weight(i))`, where `c` is chosen such that these
+/// probabilities sum to `amount`: indices whose weight is too large to be
+/// proportional are included with certainty (this includes infinite
+/// weights). If fewer than `amount` indices have a positive weight, the
+/// remaining indices are sampled uniformly from those of weight zero.
+///
+/// This differs from [`sample_weighted`], which chooses indices one at a time,
+/// each with probability proportional to its weight among the remaining
+/// indices; there, inclusion probabilities are *not* proportional to weight
+/// (heavy indices are included less often than proportionally). Here,
+/// randomized systematic sampling is used: after removing indices included
+/// with certainty, the remaining indices are shuffled and `amount` of them are
+/// selected by a single uniform offset along their cumulative inclusion
+/// probabilities. As a trade-off, this uses `O(length)` space and
+/// `O(length log length)` time (for sorting by weight), and does not require
+/// `std`.
+///
+/// Returns [`WeightedError::InvalidWeight`] if a weight is negative or NaN.
+///
+/// Panics if `amount > length`.
+pub fn sample_weighted_exact<R, F, X>(
+    rng: &mut R, length: usize, weight: F, amount: usize,
+) -> Result<IndexVec, WeightedError>
+where
+    R: Rng + ?Sized,
+    F: Fn(usize) -> X,
+    X: Into<f64>,
+{
+    use super::SliceRandom;
+
+    if amount > length {
+        panic!("`amount` of samples must be less than or equal to `length`");
+    }
+    let mut weights = Vec::with_capacity(length);
+    for i in 0..length {
+        let w: f64 = weight(i).into();
+        if !(w >= 0.0) {
+            return Err(WeightedError::InvalidWeight);
+        }
+        weights.push(w);
+    }
+
+    // Sort by decreasing weight, such that indices included with certainty
+    // form a prefix, and compute the sums of finite weights of all suffixes.
+    let mut order: Vec<usize> = (0..length).collect();
+    order.sort_unstable_by(|&a, &b| weights[b].partial_cmp(&weights[a]).unwrap());
+    let mut suffix_sums = alloc::vec![0.0; length + 1];
+    for k in (0..length).rev() {
+        let w = weights[order[k]];
+        suffix_sums[k] = suffix_sums[k + 1] + if w.is_finite() { w } else { 0.0 };
+    }
+    let infinite = weights.iter().filter(|w| w.is_infinite()).count();
+    let mut certain = 0;
+    while certain < amount && infinite < amount {
+        let w = weights[order[certain]];
+        let total = suffix_sums[certain];
+        if w == 0.0 || (w.is_finite() && ((amount - certain) as f64) * w < total) {
+            break;
+        }
+        certain += 1;
+    }
+
+    let mut result: Vec<usize> = order[..certain].to_vec();
+    let m = amount - certain;
+    let rest = &mut order[certain..];
+    let total = suffix_sums[certain];
+    if infinite >= amount {
+        // Choose uniformly among infinite weights, which are sorted first
+        result.extend(sample(rng, infinite, amount).into_iter().map(|k| rest[k]));
+    } else if m > 0 && total == 0.0 {
+        // All remaining weights are zero
+        result.extend(sample(rng, rest.len(), m).into_iter().map(|k| rest[k]));
+    } else if m > 0 {
+        rest.shuffle(rng);
+        // Sum in the same order as the partial sums below, such that the
+        // last partial sum is exactly `total`
+        let total: f64 = rest.iter().map(|&i| weights[i]).sum();
+        let offset: f64 = rng.gen();
+        let (mut j, mut partial_sum) = (0, 0.0);
+        for (k, &i) in rest.iter().enumerate() {
+            partial_sum += weights[i];
+            // Cumulative inclusion probability; `m` at the end, but rounding
+            // of `offset + j` may still leave the last index out, thus the
+            // remaining indices are taken once exactly `m - j` remain.
+            if offset + (j as f64) < partial_sum / total * (m as f64) || rest.len() - k <= m - j {
+                result.push(i);
+                j += 1;
+                if j == m {
+                    break;
+                }
+            }
+        }
+    }
+
+    if length <= (u32::MAX as usize) {
+        Ok(IndexVec::U32(result.into_iter().map(|i| i as u32).collect()))
+    } else {
+        Ok(IndexVec::USize(result))
+    }
+}
+
+/// Randomly sample exactly `amount` distinct indices from `0..length` over
+/// `u64`, and return them in an arbitrary order (there is no guarantee of
+/// shuffling or ordering). The weights are to be provided by the input
+/// function `weight`, which will be called once for each index, in order.
+///
+/// This is a variant of [`sample_weighted`] for index spaces which may exceed
+/// `usize::MAX` on 32-bit targets, such as records of a large file. Only the
+/// `amount` indices with the greatest keys are kept in a binary heap, so this
+/// uses `O(amount)` space and `O(length log amount)` time. Results are
+/// identical on all platforms, and the same indices as by [`sample_weighted`]
+/// are chosen (though possibly in a different order).
 ///
-/// Panics if `amount > length`.
+/// Panics if `amount > length`, or if `amount` exceeds `usize::MAX`.
 #[cfg(feature = "std")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
-pub fn sample_weighted<R, F, X>(
-    rng: &mut R, length: usize, weight: F, amount: usize,
-) -> Result<IndexVec, WeightedError>
+pub fn sample_weighted_u64<R, F, X>(
+    rng: &mut R, length: u64, weight: F, amount: u64,
+) -> Result<IndexVec64, WeightedError>
 where
     R: Rng + ?Sized,
-    F: Fn(usize) -> X,
+    F: Fn(u64) -> X,
     X: Into<f64>,
 {
-    if length > (core::u32::MAX as usize) {
-        sample_efraimidis_spirakis(rng, length, weight, amount)
+    assert!(amount <= usize::MAX as u64);
+    if length > u64::from(u32::MAX) {
+        let v = sample_efraimidis_spirakis_heap(rng, length, weight, amount)?;
+        Ok(IndexVec64::U64(v))
     } else {
-        assert!(amount <= core::u32::MAX as usize);
-        let amount = amount as u32;
-        let length = length as u32;
-        sample_efraimidis_spirakis(rng, length, weight, amount)
+        let weight = |i: u32| weight(u64::from(i));
+        let v = sample_efraimidis_spirakis_heap(rng, length as u32, weight, amount as u32)?;
+        Ok(IndexVec64::U32(v))
     }
 }
 
-
-/// Randomly sample exactly `amount` distinct indices from `0..length`, and
-/// return them in an arbitrary order (there is no guarantee of shuffling or
-/// ordering). The weights are to be provided by the input function `weights`,
-/// which will be called once for each index.
-///
-/// This implementation uses the algorithm described by Efraimidis and Spirakis
-/// in this paper: https://doi.org/10.1016/j.ipl.2005.11.003
-/// It uses `O(length + amount)` space and `O(length)` time.
+/// Like [`sample_efraimidis_spirakis`], but only keeping the `amount`
+/// elements of greatest key in a binary heap, using `O(amount)` space and
+/// `O(length log amount)` time.
 ///
 /// Panics if `amount > length`.
 #[cfg(feature = "std")]
-fn sample_efraimidis_spirakis<R, F, X, N>(
+fn sample_efraimidis_spirakis_heap<R, F, X, N>(
     rng: &mut R, length: N, weight: F, amount: N,
-) -> Result<IndexVec, WeightedError>
+) -> Result<Vec<N>, WeightedError>
 where
     R: Rng + ?Sized,
-    F: Fn(usize) -> X,
+    F: Fn(N) -> X,
     X: Into<f64>,
     N: UInt,
-    IndexVec: From<Vec<N>>,
 {
-    if amount == N::zero() {
-        return Ok(IndexVec::U32(Vec::new()));
-    }
+    use std::collections::BinaryHeap;
 
     if amount > length {
         panic!("`amount` of samples must be less than or equal to `length`");
     }
+    let capacity = amount.as_usize();
+    if capacity == 0 {
+        return Ok(Vec::new());
+    }
 
+    // Ordered by decreasing key, such that the top of the heap is the element
+    // with the least key
     struct Element<N> {
         index: N,
         key: f64,
     }
     impl<N> PartialOrd for Element<N> {
         fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-            self.key.partial_cmp(&other.key)
+            Some(self.cmp(other))
         }
     }
     impl<N> Ord for Element<N> {
         fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-             // partial_cmp will always produce a value,
-             // because we check that the weights are not nan
-            self.partial_cmp(other).unwrap()
+            // partial_cmp will always produce a value,
+            // because we check that the weights are not nan
+            other.key.partial_cmp(&self.key).unwrap()
         }
     }
     impl<N> PartialEq for Element<N> {
@@ -343,33 +1484,179 @@ where
     }
     impl<N> Eq for Element<N> {}
 
-    let mut candidates = Vec::with_capacity(length.as_usize());
+    let mut heap = BinaryHeap::with_capacity(capacity);
     let mut index = N::zero();
     while index < length {
-        let weight = weight(index.as_usize()).into();
+        let weight = weight(index).into();
         if !(weight >= 0.) {
             return Err(WeightedError::InvalidWeight);
         }
 
         let key = rng.gen::<f64>().powf(1.0 / weight);
-        candidates.push(Element { index, key });
+        if heap.len() < capacity {
+            heap.push(Element { index, key });
+        } else if let Some(mut least) = heap.peek_mut() {
+            if key > least.key {
+                *least = Element { index, key };
+            }
+        }
 
         index += N::one();
     }
 
-    // Partially sort the array to find the `amount` elements with the greatest
-    // keys. Do this by using `select_nth_unstable` to put the elements with
-    // the *smallest* keys at the beginning of the list in `O(n)` time, which
-    // provides equivalent information about the elements with the *greatest* keys.
-    let (_, mid, greater)
-        = candidates.select_nth_unstable(length.as_usize() - amount.as_usize());
+    Ok(heap.into_iter().map(|element| element.index).collect())
+}
 
-    let mut result: Vec<N> = Vec::with_capacity(amount.as_usize());
-    result.push(mid.index);
-    for element in greater {
-        result.push(element.index);
+/// Randomly split a sample of `amount` elements over a partitioned population,
+/// returning the number of elements to sample from each partition.
+///
+/// The population consists of `partition_sizes.len()` partitions (e.g.
+/// shards or chunks of a dataset), where partition `i` contains
+/// `partition_sizes[i]` elements. The result has the same distribution as the
+/// number of elements from each partition in a uniform sample of `amount`
+/// distinct elements from the whole population, i.e. it follows the
+/// multivariate hypergeometric distribution. Sampling the returned number of
+/// elements uniformly from within each partition (e.g. with [`sample`]) then
+/// yields an unbiased sample of the whole population.
+///
+/// The population is split in halves recursively, sampling the number of
+/// elements from each half from the hypergeometric distribution by
+/// inversion. Complexity is `O(sqrt(min(amount, total - amount)) *
+/// log(partitions))` time, where `total` is the size of the population, and
+/// `O(partitions)` memory.
+///
+/// Panics if `amount` is greater than the sum of `partition_sizes` or this sum
+/// overflows a `u64`.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::index;
+///
+/// let mut rng = rand::thread_rng();
+/// let counts = index::partition_counts(&mut rng, &[100, 0, 400, 500], 50);
+/// assert_eq!(counts.iter().sum::<usize>(), 50);
+/// assert_eq!(counts[1], 0);
+/// ```
+pub fn partition_counts<R>(rng: &mut R, partition_sizes: &[usize], amount: usize) -> Vec<usize>
+where R: Rng + ?Sized {
+    let total = partition_sizes.iter().fold(0u64, |sum, &size| {
+        sum.checked_add(size as u64)
+            .expect("sum of `partition_sizes` overflows a `u64`")
+    });
+    if amount as u64 > total {
+        panic!("`amount` of samples must be less than or equal to the total size of all partitions");
     }
-    Ok(IndexVec::from(result))
+    let mut counts = alloc::vec![0; partition_sizes.len()];
+    split_partitions(rng, partition_sizes, total, amount as u64, &mut counts);
+    counts
+}
+
+/// Recursively split `amount` over the two halves of `sizes`, whose sum is
+/// `total`.
+fn split_partitions<R>(rng: &mut R, sizes: &[usize], total: u64, amount: u64, counts: &mut [usize])
+where R: Rng + ?Sized {
+    debug_assert_eq!(sizes.len(), counts.len());
+    if sizes.len() <= 1 || amount == 0 {
+        if let Some(count) = counts.first_mut() {
+            *count = amount as usize;
+        }
+        return;
+    }
+    let mid = sizes.len() / 2;
+    let left_total: u64 = sizes[..mid].iter().map(|&size| size as u64).sum();
+    let left_amount = sample_hypergeometric(rng, total, left_total, amount);
+    let (left_counts, right_counts) = counts.split_at_mut(mid);
+    split_partitions(rng, &sizes[..mid], left_total, left_amount, left_counts);
+    split_partitions(rng, &sizes[mid..], total - left_total, amount - left_amount, right_counts);
+}
+
+/// Sample the number of marked elements among `amount` elements drawn without
+/// replacement from a population of `total` elements, `marked` of which are
+/// marked.
+///
+/// This samples the hypergeometric distribution by inversion, searching
+/// outwards from the mode, thus in time proportional to its standard
+/// deviation. Only basic arithmetic is needed (no `ln` or `exp`, which are
+/// not available without `std`): probabilities relative to the mode follow
+/// from the ratio of successive probabilities. Terms below `TAIL_CUTOFF`
+/// times the probability of the mode are ignored. Values are sampled as `u64`
+/// for value stability across platforms.
+fn sample_hypergeometric<R>(rng: &mut R, total: u64, marked: u64, amount: u64) -> u64
+where R: Rng + ?Sized {
+    const TAIL_CUTOFF: f64 = 1e-20;
+
+    debug_assert!(marked <= total && amount <= total);
+    if amount > total / 2 {
+        // Count the marked elements which are *not* drawn
+        return marked - sample_hypergeometric(rng, total, marked, total - amount);
+    }
+    if marked > total / 2 {
+        // Count the unmarked elements which are drawn
+        return amount - sample_hypergeometric(rng, total, total - marked, amount);
+    }
+    if amount == 0 || marked == 0 {
+        return 0;
+    }
+    // Now `amount + marked <= total`, thus the support is `0..=max`
+    let max = amount.min(marked);
+    let mode = ((u128::from(amount) + 1) * (u128::from(marked) + 1) / (u128::from(total) + 2)) as u64;
+    let mode = mode.min(max);
+    let unmarked_left = total - marked - amount;
+    // Weight of `x + 1` (resp. `x - 1`), given the weight `w` of `x`
+    let up = |x: u64, w: f64| {
+        w * ((marked - x) as f64 * (amount - x) as f64)
+            / ((x + 1) as f64 * (unmarked_left + x + 1) as f64)
+    };
+    let down = |x: u64, w: f64| {
+        w * (x as f64 * (unmarked_left + x) as f64)
+            / ((marked - x + 1) as f64 * (amount - x + 1) as f64)
+    };
+
+    // Sum of the weights, with the mode having weight 1
+    let mut sum = 1.0;
+    let (mut x, mut w) = (mode, 1.0);
+    while x < max && w > TAIL_CUTOFF {
+        w = up(x, w);
+        x += 1;
+        sum += w;
+    }
+    let (mut x, mut w) = (mode, 1.0);
+    while x > 0 && w > TAIL_CUTOFF {
+        w = down(x, w);
+        x -= 1;
+        sum += w;
+    }
+
+    // Invert, visiting the values in order of decreasing weight (roughly)
+    let mut u = rng.gen::<f64>() * sum - 1.0;
+    let (mut high, mut w_high) = (mode, 1.0);
+    let (mut low, mut w_low) = (mode, 1.0);
+    while u >= 0.0 {
+        let can_up = high < max && w_high > TAIL_CUTOFF;
+        let can_down = low > 0 && w_low > TAIL_CUTOFF;
+        if !can_up && !can_down {
+            // Only reachable through rounding errors
+            break;
+        }
+        if can_up {
+            w_high = up(high, w_high);
+            high += 1;
+            u -= w_high;
+            if u < 0.0 {
+                return high;
+            }
+        }
+        if can_down {
+            w_low = down(low, w_low);
+            low -= 1;
+            u -= w_low;
+            if u < 0.0 {
+                return low;
+            }
+        }
+    }
+    mode
 }
 
 /// Randomly sample exactly `amount` indices from `0..length`, using Floyd's
@@ -443,6 +1730,22 @@ impl UInt for u32 {
         self as usize
     }
 }
+impl UInt for u64 {
+    #[inline]
+    fn zero() -> Self {
+        0
+    }
+
+    #[inline]
+    fn one() -> Self {
+        1
+    }
+
+    #[inline]
+    fn as_usize(self) -> usize {
+        self as usize
+    }
+}
 impl UInt for usize {
     #[inline]
     fn zero() -> Self {
@@ -474,6 +1777,12 @@ where
     R: Rng + ?Sized,
     IndexVec: From<Vec<X>>,
 {
+    IndexVec::from(sample_rejection_vec(rng, length, amount))
+}
+
+/// As [`sample_rejection`], but returning a `Vec` of any `UInt` type.
+fn sample_rejection_vec<X: UInt, R>(rng: &mut R, length: X, amount: X) -> Vec<X>
+where R: Rng + ?Sized {
     debug_assert!(amount < length);
     #[cfg(feature = "std")]
     let mut cache = HashSet::with_capacity(amount.as_usize());
@@ -490,7 +1799,7 @@ where
     }
 
     debug_assert_eq!(indices.len(), amount.as_usize());
-    IndexVec::from(indices)
+    indices
 }
 
 #[cfg(test)]
@@ -513,6 +1822,32 @@ mod test {
         }
     }
 
+    #[test]
+    #[cfg(feature = "serde1")]
+    fn test_serialization_index_bitset() {
+        let mut set = IndexBitSet::new(70);
+        set.insert(3);
+        set.insert(69);
+        let de_set: IndexBitSet = bincode::deserialize(&bincode::serialize(&set).unwrap()).unwrap();
+        assert_eq!(de_set, set);
+        let index_vec = IndexVec::from(set);
+        let de_index_vec: IndexVec = bincode::deserialize(&bincode::serialize(&index_vec).unwrap()).unwrap();
+        assert_eq!(de_index_vec, index_vec);
+
+        // Only consistent words, length and count are accepted
+        let valid = (vec![1u64 << 3, 1 << 5], 70usize, 2usize);
+        assert!(bincode::deserialize::<IndexBitSet>(&bincode::serialize(&valid).unwrap()).is_ok());
+        for invalid in &[
+            (vec![1u64 << 3], 70usize, 1usize),
+            (vec![1u64 << 3, 1 << 6], 70, 2),
+            (vec![1u64 << 3, 1 << 5], 70, 3),
+            (vec![], usize::MAX, 0),
+        ] {
+            let encoded = bincode::serialize(invalid).unwrap();
+            assert!(bincode::deserialize::<IndexBitSet>(&encoded).is_err());
+        }
+    }
+
     #[cfg(feature = "alloc")] use alloc::vec;
 
     #[test]
@@ -588,9 +1923,534 @@ mod test {
                         assert!((i as usize) < len);
                     }
                 },
-                IndexVec::USize(_) => panic!("expected `IndexVec::U32`"),
+                _ => panic!("expected `IndexVec::U32`"),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sample_weighted_u64() {
+        let seed_rng = crate::test::rng;
+        for &(amount, len) in &[(0, 10), (5, 10), (10, 10), (3, 1000)] {
+            let v = sample_weighted_u64(&mut seed_rng(440), len, |i| i as f64, amount).unwrap();
+            let mut v64 = v.clone().into_vec();
+            v64.sort_unstable();
+            v64.dedup();
+            assert_eq!(v64.len() as u64, amount);
+            assert!(v64.iter().all(|&i| 0 < i && i < len) || amount == len);
+
+            // The same indices as those of `sample_weighted` are chosen
+            let w = sample_weighted(&mut seed_rng(440), len as usize, |i| i as f64, amount as usize)
+                .unwrap();
+            let mut w: Vec<u64> = w.into_iter().map(|i| i as u64).collect();
+            w.sort_unstable();
+            assert_eq!(v64, w);
+            assert!(matches!(v, IndexVec64::U32(_)));
+        }
+
+        let v = sample_weighted_u64(&mut seed_rng(441), 1 << 33, |_| 1.0, 0);
+        assert!(v.unwrap().is_empty());
+        let v = sample_weighted_u64(&mut seed_rng(441), 100, |i| 50.0 - i as f64, 2);
+        assert_eq!(v.unwrap_err(), WeightedError::InvalidWeight);
+    }
+
+    #[test]
+    fn test_sample_weighted_exact() {
+        let mut r = crate::test::rng(442);
+        let mut sample_counts = |weights: &[f64], amount: usize| {
+            let mut counts = [0; 5];
+            for _ in 0..10_000 {
+                let v = sample_weighted_exact(&mut r, weights.len(), |i| weights[i], amount)
+                    .unwrap()
+                    .into_vec();
+                assert_eq!(v.len(), amount);
+                for &i in &v {
+                    counts[i] += 1;
+                }
+                let mut v = v;
+                v.sort_unstable();
+                v.dedup();
+                assert_eq!(v.len(), amount);
+            }
+            counts
+        };
+
+        // Inclusion probabilities 0.2, 0.4, 0.6 and 0.8
+        let counts = sample_counts(&[1.0, 2.0, 3.0, 4.0], 2);
+        for (i, &c) in counts[..4].iter().enumerate() {
+            let expected = 2000 * (i as i32 + 1);
+            assert!((c - expected).abs() < 200, "{:?}", counts);
+        }
+
+        // The first weight is too large to be proportional
+        let counts = sample_counts(&[10.0, 1.0, 1.0, 1.0, 1.0], 2);
+        assert_eq!(counts[0], 10_000);
+        assert!(counts[1..].iter().all(|&c| (2300..2700).contains(&c)));
+
+        // Infinite weights are included with certainty, if possible
+        let inf = f64::INFINITY;
+        let counts = sample_counts(&[inf, inf, 1.0, inf], 2);
+        assert_eq!(counts[2], 0);
+        assert!([counts[0], counts[1], counts[3]].iter().all(|&c| (6300..7000).contains(&c)));
+        let counts = sample_counts(&[0.0, inf, 0.0, 0.0], 3);
+        assert_eq!(counts[1], 10_000);
+        assert!([counts[0], counts[2], counts[3]].iter().all(|&c| (6300..7000).contains(&c)));
+
+        assert_eq!(sample_counts(&[0.0, 1.0], 0), [0; 5]);
+        assert_eq!(sample_counts(&[0.0, 1.0], 2), [10_000, 10_000, 0, 0, 0]);
+
+        for &w in &[-1.0, f64::NAN] {
+            assert_eq!(
+                sample_weighted_exact(&mut r, 3, |i| if i == 1 { w } else { 1.0 }, 1).unwrap_err(),
+                WeightedError::InvalidWeight
+            );
+        }
+    }
+
+    #[test]
+    fn test_sample_weighted_exact_rounding() {
+        use crate::rngs::mock::SequenceRng;
+
+        // The shuffle draws a zero, then `offset + 1` rounds up to 2 with the
+        // greatest offset below 1; the last index must still be included
+        let mut r = SequenceRng::new([0, u64::MAX]);
+        let v = sample_weighted_exact(&mut r, 3, |_| 1.0, 2).unwrap();
+        assert_eq!(v.len(), 2);
+        assert_eq!(r.remaining(), 0);
+    }
+
+    #[test]
+    fn test_index_bitset() {
+        let mut set = IndexBitSet::new(130);
+        assert!(set.is_empty());
+        assert_eq!(set.length(), 130);
+        for &i in &[129, 0, 64, 63, 5] {
+            assert!(set.insert(i));
+        }
+        assert!(!set.insert(64));
+        assert_eq!(set.len(), 5);
+        assert!(set.contains(63) && !set.contains(62) && !set.contains(1000));
+        assert_eq!(set.iter().len(), 5);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0, 5, 63, 64, 129]);
+        assert!(set.remove(5));
+        assert!(!set.remove(5) && !set.remove(1000));
+        assert_eq!(IndexVec::from(set.clone()), IndexVec::U32(vec![0, 63, 64, 129]));
+        assert_eq!(set.into_vec(), vec![0, 63, 64, 129]);
+
+        let full = IndexBitSet::full(70);
+        assert_eq!((&full).into_iter().collect::<Vec<_>>(), (0..70).collect::<Vec<_>>());
+        assert_eq!(full.clone().into_iter().len(), 70);
+        assert_eq!(full.clone().into_iter().collect::<Vec<_>>(), (0..70).collect::<Vec<_>>());
+        assert_eq!(IndexBitSet::full(128).iter().count(), 128);
+        assert!(IndexBitSet::full(0).is_empty());
+    }
+
+    #[test]
+    fn test_index_vec_bitset() {
+        let mut set = IndexBitSet::new(200);
+        for &i in &[7, 64, 130, 199] {
+            set.insert(i);
+        }
+        let v = IndexVec::from(set);
+        assert_eq!(v.len(), 4);
+        assert!(!v.is_empty());
+        assert_eq!((0..4).map(|i| v.index(i)).collect::<Vec<_>>(), vec![7, 64, 130, 199]);
+        assert!(v.contains(130) && !v.contains(131) && !v.contains(1000));
+        assert_eq!(v.iter().len(), 4);
+        assert_eq!(v, IndexVec::USize(vec![7, 64, 130, 199]));
+        assert_ne!(v, IndexVec::U32(vec![7, 64, 130]));
+        assert_eq!(v.clone().into_iter().collect::<Vec<_>>(), vec![7, 64, 130, 199]);
+        assert_eq!(v.into_vec(), vec![7, 64, 130, 199]);
+
+        assert!(IndexVec::U32(vec![3, 1]).contains(1));
+        assert!(!IndexVec::U32(vec![3, 1]).contains(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds")]
+    fn test_index_vec_bitset_index_out_of_bounds() {
+        IndexVec::from(IndexBitSet::full(10)).index(10);
+    }
+
+    #[test]
+    fn test_sample_combination_representation() {
+        let mut r = crate::test::rng(451);
+        // Dense combinations are stored as a bitset, sparse ones as a vector
+        match sample_combination(&mut r, 1000, 40) {
+            IndexVec::BitSet(set) => assert_eq!(set.len(), 40),
+            _ => panic!("expected `IndexVec::BitSet`"),
+        }
+        match sample_combination(&mut r, 1000, 990) {
+            IndexVec::BitSet(set) => assert_eq!(set.len(), 990),
+            _ => panic!("expected `IndexVec::BitSet`"),
+        }
+        match sample_combination(&mut r, 1000, 20) {
+            IndexVec::U32(v) => assert_eq!(v.len(), 20),
+            _ => panic!("expected `IndexVec::U32`"),
+        }
+        let (a, b) = sample_split(&mut r, 1000, 990);
+        assert!(matches!(a, IndexVec::BitSet(_)));
+        assert!(matches!(b, IndexVec::U32(_)));
+    }
+
+    #[test]
+    fn test_sample_bitset() {
+        let mut r = crate::test::rng(444);
+        for &(length, amount) in &[(0, 0), (1, 1), (100, 0), (100, 30), (100, 70), (100, 100)] {
+            let set = sample_bitset(&mut r, length, amount);
+            assert_eq!(set.len(), amount);
+            assert_eq!(set.iter().count(), amount);
+            assert!(set.iter().all(|i| i < length));
+        }
+
+        // Each index is equally likely to be included
+        let mut counts = [0; 5];
+        for &amount in &[1, 4] {
+            for _ in 0..5000 {
+                for i in sample_bitset(&mut r, 5, amount).iter() {
+                    counts[i] += 1;
+                }
+            }
+        }
+        assert!(counts.iter().all(|&c| (4800..5200).contains(&c)));
+    }
+
+    #[test]
+    fn test_sample_iter() {
+        let mut r = crate::test::rng(434);
+        assert_eq!(sample_iter(&mut r, 0, 0).next(), None);
+        assert_eq!(sample_iter(&mut r, 1, 1).collect::<Vec<_>>(), vec![0]);
+
+        // A full sample is a permutation
+        let mut v: Vec<usize> = sample_iter(&mut r, 100, 100).collect();
+        v.sort_unstable();
+        assert_eq!(v, (0..100).collect::<Vec<_>>());
+
+        let mut iter = sample_iter(&mut r, 1 << 40, 5);
+        assert_eq!(iter.len(), 5);
+        let first = iter.next().unwrap();
+        assert!(first < 1 << 40);
+        assert_eq!(iter.len(), 4);
+        assert_eq!(iter.by_ref().count(), 4);
+        assert_eq!(iter.next(), None);
+
+        // Each index is equally likely at each position
+        let mut counts = [[0; 5]; 2];
+        for _ in 0..5000 {
+            let mut iter = sample_iter(&mut r, 5, 2);
+            counts[0][iter.next().unwrap()] += 1;
+            counts[1][iter.next().unwrap()] += 1;
+        }
+        crate::test::assert_uniform(counts.iter().flatten());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_iter_too_many() {
+        sample_iter(crate::test::rng(435), 5, 6);
+    }
+
+    #[test]
+    fn test_sample_combination() {
+        let mut r = crate::test::rng(437);
+        assert_eq!(sample_combination(&mut r, 0, 0).len(), 0);
+        assert_eq!(sample_combination(&mut r, 5, 5).into_vec(), vec![0, 1, 2, 3, 4]);
+
+        for &(length, amount) in &[(10, 3), (10, 7), (1000, 20), (1000, 990), (1 << 40, 10)] {
+            let v = sample_combination(&mut r, length, amount).into_vec();
+            assert_eq!(v.len(), amount);
+            assert!(v.windows(2).all(|w| w[0] < w[1]));
+            assert!(v[amount - 1] < length);
+        }
+
+        // Each of the 10 combinations of 2 (or 3) of 5 is equally likely, whether
+        // sampled directly or via the complement
+        for &amount in &[2, 3] {
+            let mut counts = [0; 32];
+            for c in combinations(&mut r, 5, amount).take(10_000) {
+                counts[c.iter().map(|i| 1 << i).sum::<usize>()] += 1;
+            }
+            let counts: Vec<_> = counts.iter().cloned().filter(|&c| c > 0).collect();
+            assert_eq!(counts.len(), 10);
+            crate::test::assert_uniform(&counts);
+        }
+    }
+
+    #[test]
+    fn test_sample_sorted_iter() {
+        let mut r = crate::test::rng(446);
+        assert_eq!(sample_sorted_iter(&mut r, 0, 0).next(), None);
+        assert_eq!(sample_sorted_iter(&mut r, 5, 5).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+        for &(length, amount) in &[(10, 3), (10, 9), (1000, 20), (1 << 20, 5)] {
+            let mut iter = sample_sorted_iter(&mut r, length, amount);
+            assert_eq!(iter.len(), amount);
+            let v: Vec<usize> = iter.by_ref().collect();
+            assert_eq!(v.len(), amount);
+            assert!(v.windows(2).all(|w| w[0] < w[1]));
+            assert!(v[amount - 1] < length);
+            assert_eq!(iter.next(), None);
+        }
+
+        // Each of the 10 combinations of 2 of 5 is equally likely
+        let mut counts = [0; 32];
+        for _ in 0..10_000 {
+            let c: usize = sample_sorted_iter(&mut r, 5, 2).map(|i| 1 << i).sum();
+            counts[c] += 1;
+        }
+        let counts: Vec<_> = counts.iter().cloned().filter(|&c| c > 0).collect();
+        assert_eq!(counts.len(), 10);
+        crate::test::assert_uniform(&counts);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_combinations_too_many() {
+        combinations(crate::test::rng(438), 5, 6);
+    }
+
+    #[test]
+    fn test_sample_excluding() {
+        let seed_rng = crate::test::rng;
+
+        // Without exclusions, results equal `sample`
+        let v1 = sample(&mut seed_rng(428), 100, 10);
+        let v2 = sample_excluding(&mut seed_rng(428), 100, 10, &[100, 200]);
+        assert_eq!(v1, v2);
+
+        let mut r = seed_rng(429);
+        let excluded = [7, 3, 3, 0, 9, 5, 1000];
+        let mut counts = [0; 10];
+        for _ in 0..1000 {
+            let v = sample_excluding(&mut r, 10, 2, &excluded);
+            assert!(matches!(v, IndexVec::U32(_)));
+            assert_eq!(v.len(), 2);
+            assert_ne!(v.index(0), v.index(1));
+            for i in v.iter() {
+                counts[i] += 1;
+            }
+        }
+        // 2000 indices over the 5 non-excluded indices 1, 2, 4, 6 and 8
+        for (i, &count) in counts.iter().enumerate() {
+            if excluded.contains(&i) {
+                assert_eq!(count, 0);
+            } else {
+                assert!((320..480).contains(&count));
+            }
+        }
+
+        // Almost all indices excluded
+        let excluded: Vec<usize> = (0..10_000).filter(|&i| i != 1234 && i != 9999).collect();
+        let mut v = sample_excluding(&mut r, 10_000, 2, &excluded).into_vec();
+        v.sort_unstable();
+        assert_eq!(v, vec![1234, 9999]);
+        assert_eq!(sample_excluding(&mut r, 3, 0, &[0, 1, 2]).len(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_excluding_too_many() {
+        sample_excluding(&mut crate::test::rng(429), 10, 8, &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_sample_u64() {
+        let seed_rng = crate::test::rng;
+
+        // Small lengths give the same results as `sample`
+        for &(length, amount) in &[(0, 0), (10, 6), (100, 50), (1 << 20, 600)] {
+            let v1 = sample(&mut seed_rng(424), length, amount);
+            let v2 = sample_u64(&mut seed_rng(424), length as u64, amount as u64);
+            assert_eq!(v2.len(), amount);
+            assert_eq!(v1.into_vec().into_iter().map(|i| i as u64).collect::<Vec<_>>(), v2.into_vec());
+        }
+
+        let length = 1 << 40;
+        let v = sample_u64(&mut seed_rng(425), length, 100);
+        assert_eq!(v.len(), 100);
+        assert!(!v.is_empty());
+        let mut indices: Vec<u64> = v.iter().collect();
+        assert_eq!(indices, v.clone().into_iter().collect::<Vec<_>>());
+        assert_eq!(indices[3], v.index(3));
+        indices.sort_unstable();
+        indices.dedup();
+        assert_eq!(indices.len(), 100);
+        assert!(indices.iter().all(|&i| i < length));
+        // Not all indices fit in 32 bits
+        assert!(indices.iter().any(|&i| i > u64::from(u32::MAX)));
+
+        assert_eq!(IndexVec64::from(vec![1u32, 2]), IndexVec64::from(vec![1u64, 2]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_u64_too_many() {
+        sample_u64(&mut crate::test::rng(426), 1 << 40, (1 << 40) + 1);
+    }
+
+    #[test]
+    fn test_partition_counts() {
+        let mut r = crate::test::rng(430);
+        assert_eq!(partition_counts(&mut r, &[], 0), Vec::<usize>::new());
+        assert_eq!(partition_counts(&mut r, &[0, 0], 0), vec![0, 0]);
+        assert_eq!(partition_counts(&mut r, &[5], 3), vec![3]);
+        assert_eq!(partition_counts(&mut r, &[3, 0, 4, 5], 12), vec![3, 0, 4, 5]);
+
+        let sizes = [10, 0, 200, 1, 50, 739];
+        let mut totals = [0; 6];
+        for _ in 0..1000 {
+            let counts = partition_counts(&mut r, &sizes, 100);
+            assert_eq!(counts.iter().sum::<usize>(), 100);
+            for ((total, &count), &size) in totals.iter_mut().zip(&counts).zip(&sizes) {
+                assert!(count <= size);
+                *total += count;
+            }
+        }
+        // Expected: 1000, 0, 20_000, 100, 5000, 73_900
+        assert!((800..1200).contains(&totals[0]));
+        assert_eq!(totals[1], 0);
+        assert!((19_500..20_500).contains(&totals[2]));
+        assert!((50..150).contains(&totals[3]));
+        assert!((4700..5300).contains(&totals[4]));
+        assert!((73_000..74_800).contains(&totals[5]));
+
+        // Large population, sampling most elements
+        let counts = partition_counts(&mut r, &[1 << 40, 1 << 20, 1], (1 << 40) + (1 << 20));
+        assert_eq!(counts.iter().sum::<usize>(), (1 << 40) + (1 << 20));
+
+        // Large population, sampling half the elements
+        let counts = partition_counts(&mut r, &[1 << 40, 1 << 40], 1 << 40);
+        assert_eq!(counts[0] + counts[1], 1 << 40);
+        // The standard deviation is below 2^19
+        assert!((counts[0] as i64 - (1 << 39)).abs() < 1 << 22);
+    }
+
+    #[test]
+    fn test_sample_hypergeometric() {
+        // Compare with the exact distribution for total = 50, marked = 20,
+        // amount = 10 (and the symmetric cases)
+        let mut pmf = [0.0; 11];
+        let choose = |n: u64, k: u64| (0..k).fold(1.0, |c, i| c * (n - i) as f64 / (i + 1) as f64);
+        for (x, p) in pmf.iter_mut().enumerate() {
+            let x = x as u64;
+            *p = choose(20, x) * choose(30, 10 - x) / choose(50, 10);
+        }
+
+        let mut r = crate::test::rng(433);
+        let n = 20_000;
+        let mut counts = [[0u32; 11]; 3];
+        for _ in 0..n {
+            counts[0][sample_hypergeometric(&mut r, 50, 20, 10) as usize] += 1;
+            counts[1][sample_hypergeometric(&mut r, 50, 10, 20) as usize] += 1;
+            counts[2][20 - sample_hypergeometric(&mut r, 50, 20, 40) as usize] += 1;
+        }
+        for counts in counts.iter() {
+            for (&count, &p) in counts.iter().zip(pmf.iter()) {
+                let expected = p * n as f64;
+                let sd = (expected * (1.0 - p)).sqrt();
+                assert!((count as f64 - expected).abs() <= 5.0 * sd + 1.0, "{:?}", counts);
             }
         }
+
+        assert_eq!(sample_hypergeometric(&mut r, 10, 0, 5), 0);
+        assert_eq!(sample_hypergeometric(&mut r, 10, 10, 5), 5);
+        assert_eq!(sample_hypergeometric(&mut r, 10, 3, 10), 3);
+        for _ in 0..100 {
+            assert!(sample_hypergeometric(&mut r, 5, 2, 2) <= 2);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_partition_counts_too_many() {
+        partition_counts(&mut crate::test::rng(431), &[3, 4], 8);
+    }
+
+    #[test]
+    fn value_stability_partition_counts() {
+        let mut r = crate::test::rng(432);
+        assert_eq!(partition_counts(&mut r, &[10, 20, 30, 40], 25), vec![2, 9, 4, 10]);
+        assert_eq!(partition_counts(&mut r, &[1000, 5, 10, 2000, 1], 2900), vec![963, 5, 10, 1921, 1]);
+    }
+
+    #[test]
+    fn value_stability_sample_weighted_exact() {
+        let mut r = crate::test::rng(443);
+        let v = sample_weighted_exact(&mut r, 10, |i| i as f64, 4).unwrap();
+        assert_eq!(v.into_vec(), vec![5, 8, 6, 7]);
+    }
+
+    #[test]
+    fn value_stability_sample_bitset() {
+        let mut r = crate::test::rng(445);
+        assert_eq!(sample_bitset(&mut r, 20, 5).into_vec(), vec![2, 3, 10, 15, 17]);
+        assert_eq!(sample_bitset(&mut r, 10, 8).into_vec(), vec![1, 2, 3, 4, 5, 6, 8, 9]);
+    }
+
+    #[test]
+    fn test_sample_split() {
+        let mut r = crate::test::rng(448);
+        let (a, b) = sample_split(&mut r, 0, 0);
+        assert!(a.is_empty() && b.is_empty());
+
+        for &(length, amount) in &[(10, 0), (10, 3), (10, 7), (10, 10), (1000, 20)] {
+            let (a, b) = sample_split(&mut r, length, amount);
+            assert_eq!(a.len(), amount);
+            assert_eq!(b.len(), length - amount);
+            let (a, b) = (a.into_vec(), b.into_vec());
+            assert!(a.windows(2).all(|w| w[0] < w[1]));
+            assert!(b.windows(2).all(|w| w[0] < w[1]));
+            let mut all: Vec<usize> = a.iter().chain(b.iter()).cloned().collect();
+            all.sort_unstable();
+            assert_eq!(all, (0..length).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sample_split_too_many() {
+        sample_split(&mut crate::test::rng(449), 5, 6);
+    }
+
+    #[test]
+    fn value_stability_sample_split() {
+        let (a, b) = sample_split(&mut crate::test::rng(450), 8, 3);
+        assert_eq!(a.into_vec(), vec![1, 2, 5]);
+        assert_eq!(b.into_vec(), vec![0, 3, 4, 6, 7]);
+    }
+
+    #[test]
+    fn value_stability_sample_sorted_iter() {
+        let v: Vec<usize> = sample_sorted_iter(crate::test::rng(447), 100, 8).collect();
+        assert_eq!(v, vec![7, 13, 14, 33, 34, 52, 70, 78]);
+    }
+
+    #[test]
+    fn value_stability_sample_iter() {
+        let v: Vec<usize> = sample_iter(crate::test::rng(436), 100, 8).collect();
+        assert_eq!(v, vec![80, 82, 51, 99, 41, 4, 91, 77]);
+    }
+
+    #[test]
+    fn value_stability_sample_combination() {
+        let mut r = crate::test::rng(439);
+        let v = sample_combination(&mut r, 100, 8).into_vec();
+        assert_eq!(v, vec![1, 3, 16, 18, 22, 48, 71, 87]);
+        let v = sample_combination(&mut r, 10, 8).into_vec();
+        assert_eq!(v, vec![0, 1, 2, 3, 4, 5, 6, 9]);
+    }
+
+    #[test]
+    fn value_stability_sample_excluding() {
+        let mut r = crate::test::rng(433);
+        assert_eq!(sample_excluding(&mut r, 20, 5, &[0, 2, 4, 6, 8, 10]).into_vec(), vec![9, 11, 17, 13, 15]);
+    }
+
+    #[test]
+    fn value_stability_sample_u64() {
+        let mut r = crate::test::rng(427);
+        assert_eq!(sample_u64(&mut r, 10, 6).into_vec(), vec![8, 4, 5, 6, 9, 0]);
+        assert_eq!(sample_u64(&mut r, 1 << 40, 4).into_vec(), vec![394693691381, 393113995045, 226710637352, 14907079563]);
     }
 
     #[test]
@@ -0,0 +1,202 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random sampling and shuffling of non-contiguous indexed collections.
+
+use core::ops::IndexMut;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+
+use super::{gen_index, partial_shuffle_by};
+use crate::Rng;
+
+/// Extension trait on index-addressable collections, providing random
+/// sampling and shuffling methods like those of [`SliceRandom`].
+///
+/// This allows choosing and shuffling elements of collections which are not
+/// stored as one contiguous slice, such as [`VecDeque`], without first
+/// moving their elements (e.g. via `VecDeque::make_contiguous`). Collections
+/// which dereference to a slice, such as `Vec` or `SmallVec`, should use
+/// [`SliceRandom`] instead.
+///
+/// Implementors only need to provide [`len`](IndexedRandom::len) and
+/// [`swap`](IndexedRandom::swap) in addition to [`IndexMut<usize>`]. All
+/// methods use the same algorithms as [`SliceRandom`], thus yield the same
+/// results as the equivalent methods on a slice of the same elements.
+///
+/// # Example
+///
+/// ```
+/// use std::collections::VecDeque;
+/// use rand::seq::IndexedRandom;
+///
+/// let mut rng = rand::thread_rng();
+/// let mut queue: VecDeque<u32> = (1..=10).collect();
+/// queue.rotate_left(3);
+/// queue.shuffle(&mut rng);
+/// println!("{:?}, next: {:?}", queue, queue.choose(&mut rng));
+/// ```
+///
+/// [`SliceRandom`]: super::SliceRandom
+/// [`VecDeque`]: alloc::collections::VecDeque
+pub trait IndexedRandom: IndexMut<usize> {
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Swap the elements at indices `i` and `j`.
+    ///
+    /// # Panics
+    ///
+    /// May panic if either index is out of bounds.
+    fn swap(&mut self, i: usize, j: usize);
+
+    /// Returns a reference to one random element, or `None` if the
+    /// collection is empty.
+    ///
+    /// This is equivalent to [`SliceRandom::choose`].
+    ///
+    /// [`SliceRandom::choose`]: super::SliceRandom::choose
+    fn choose<R>(&self, rng: &mut R) -> Option<&Self::Output>
+    where R: Rng + ?Sized {
+        match self.len() {
+            0 => None,
+            len => Some(&self[gen_index(rng, len)]),
+        }
+    }
+
+    /// Returns a mutable reference to one random element, or `None` if the
+    /// collection is empty.
+    ///
+    /// This is equivalent to [`SliceRandom::choose_mut`].
+    ///
+    /// [`SliceRandom::choose_mut`]: super::SliceRandom::choose_mut
+    fn choose_mut<R>(&mut self, rng: &mut R) -> Option<&mut Self::Output>
+    where R: Rng + ?Sized {
+        match self.len() {
+            0 => None,
+            len => Some(&mut self[gen_index(rng, len)]),
+        }
+    }
+
+    /// Shuffle the collection in place.
+    ///
+    /// This is equivalent to [`SliceRandom::shuffle`].
+    ///
+    /// [`SliceRandom::shuffle`]: super::SliceRandom::shuffle
+    fn shuffle<R>(&mut self, rng: &mut R)
+    where R: Rng + ?Sized {
+        let len = self.len();
+        if len <= 1 {
+            // There is no need to shuffle an empty or single element collection
+            return;
+        }
+        self.partial_shuffle(rng, len);
+    }
+
+    /// Shuffle a random subset of the collection in place.
+    ///
+    /// Afterwards, the last `amount` elements (or all elements, if
+    /// `amount` exceeds the length) are a uniformly random sample of all
+    /// elements, in random order. Unlike [`SliceRandom::partial_shuffle`],
+    /// which returns the sample as a slice, this returns the index of the
+    /// first sampled element; the sample is `start..self.len()`.
+    ///
+    /// [`SliceRandom::partial_shuffle`]: super::SliceRandom::partial_shuffle
+    fn partial_shuffle<R>(&mut self, rng: &mut R, amount: usize) -> usize
+    where R: Rng + ?Sized {
+        let len = self.len();
+        partial_shuffle_by(rng, len, amount, |i, j| self.swap(i, j));
+        len.saturating_sub(amount)
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+impl<T> IndexedRandom for VecDeque<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        VecDeque::is_empty(self)
+    }
+
+    #[inline]
+    fn swap(&mut self, i: usize, j: usize) {
+        VecDeque::swap(self, i, j)
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use super::*;
+    use crate::seq::SliceRandom;
+    use alloc::vec::Vec;
+
+    /// A deque whose elements wrap around the end of its buffer
+    fn wrapped_deque(len: u32) -> VecDeque<u32> {
+        let mut deque = VecDeque::with_capacity(len as usize);
+        deque.extend(len / 2..len);
+        for x in (0..len / 2).rev() {
+            deque.push_front(x);
+        }
+        deque
+    }
+
+    #[test]
+    fn test_matches_slice() {
+        let mut deque = wrapped_deque(20);
+        let mut slice: Vec<u32> = (0..20).collect();
+
+        let mut r1 = crate::test::rng(601);
+        let mut r2 = crate::test::rng(601);
+        assert_eq!(
+            IndexedRandom::choose(&deque, &mut r1),
+            SliceRandom::choose(&slice[..], &mut r2)
+        );
+        *IndexedRandom::choose_mut(&mut deque, &mut r1).unwrap() += 100;
+        *SliceRandom::choose_mut(&mut slice[..], &mut r2).unwrap() += 100;
+        IndexedRandom::shuffle(&mut deque, &mut r1);
+        SliceRandom::shuffle(&mut slice[..], &mut r2);
+        assert!(deque.iter().eq(slice.iter()));
+
+        let start = IndexedRandom::partial_shuffle(&mut deque, &mut r1, 5);
+        let (sample, _) = SliceRandom::partial_shuffle(&mut slice[..], &mut r2, 5);
+        assert_eq!(start, 15);
+        assert!(deque.range(start..).eq(sample.iter()));
+        assert!(deque.iter().eq(slice.iter()));
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut r = crate::test::rng(602);
+        let mut deque = VecDeque::<u8>::new();
+        assert_eq!(IndexedRandom::choose(&deque, &mut r), None);
+        assert_eq!(IndexedRandom::choose_mut(&mut deque, &mut r), None);
+        IndexedRandom::shuffle(&mut deque, &mut r);
+        assert!(IndexedRandom::is_empty(&deque));
+        assert_eq!(IndexedRandom::partial_shuffle(&mut deque, &mut r, 3), 0);
+    }
+
+    #[test]
+    fn value_stability() {
+        let mut r = crate::test::rng(603);
+        let mut deque = wrapped_deque(10);
+        IndexedRandom::shuffle(&mut deque, &mut r);
+        assert_eq!(deque, [3, 5, 6, 4, 1, 8, 7, 0, 2, 9]);
+    }
+}
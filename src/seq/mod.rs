@@ -12,8 +12,21 @@
 //!
 //! *   [`SliceRandom`] slice sampling and mutation
 //! *   [`IteratorRandom`] iterator sampling
+//! *   [`IndexedRandom`] sampling and mutation of indexed collections such as
+//!     `VecDeque`
+//! *   [`MapRandom`] and [`SetRandom`] to choose random entries of maps and
+//!     sets
+//! *   [`shuffle_by`] to apply one random order to several slices
+//! *   [`riffle`] to randomly interleave two sequences
+//! *   [`choose_linear_front`] and [`choose_biased_front`] (with `std`) to
+//!     choose indices biased toward the front (or back) of a sequence
+//! *   [`Permutation`] reusable random permutations, which may be inverted
+//!     and composed
 //! *   [`index::sample`] low-level API to choose multiple indices from
-//!     `0..length`
+//!     `0..length`, [`index::sample_u64`] for `u64` lengths and
+//!     [`index::sample_bitset`] for compact dense samples
+//! *   [`index::partition_counts`] to split a sample over a partitioned
+//!     population
 //!
 //! Also see:
 //!
@@ -24,12 +37,19 @@
 //! `usize` indices are sampled as a `u32` where possible (also providing a
 //! small performance boost in some cases).
 
+mod biased;
 mod coin_flipper;
 #[cfg(feature = "alloc")]
+mod collections;
+#[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub mod index;
 
 mod increasing_uniform;
+mod indexed;
+#[cfg(feature = "alloc")]
+mod permutation;
+mod riffle;
 
 #[cfg(feature = "alloc")]
 use core::ops::Index;
@@ -39,12 +59,22 @@ use alloc::vec::Vec;
 
 #[cfg(feature = "alloc")]
 use crate::distributions::uniform::{SampleBorrow, SampleUniform};
-#[cfg(feature = "alloc")]
 use crate::distributions::WeightedError;
 use crate::Rng;
 
-use self::coin_flipper::CoinFlipper;
 use self::increasing_uniform::IncreasingUniform;
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub use self::biased::{choose_biased_back, choose_biased_front};
+pub use self::biased::{choose_linear_back, choose_linear_front};
+pub use self::indexed::IndexedRandom;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::collections::{MapRandom, SetRandom};
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::permutation::{InvalidPermutation, Permutation};
+pub use self::riffle::{riffle, Riffle};
 
 /// Extension trait on slices, providing random mutation and sampling methods.
 ///
@@ -126,6 +156,80 @@ pub trait SliceRandom {
     where
         R: Rng + ?Sized;
 
+    /// Chooses `amount` distinct elements from the slice at random, in random
+    /// order, returning mutable references to them.
+    ///
+    /// If `amount` exceeds the length of the slice, all elements are
+    /// returned. Indices are chosen as by [`choose_multiple`] (using
+    /// [`index::sample`]); complexity is that of [`index::sample`] plus
+    /// `O(amount log amount)`.
+    ///
+    /// # Example
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// let mut scores = [10, 20, 30, 40, 50];
+    /// // Give a bonus to three random players
+    /// for score in scores.choose_multiple_mut(&mut rng, 3) {
+    ///     *score += 5;
+    /// }
+    /// assert_eq!(scores.iter().filter(|&&s| s % 10 == 5).count(), 3);
+    /// ```
+    /// [`choose_multiple`]: SliceRandom::choose_multiple
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn choose_multiple_mut<R>(&mut self, rng: &mut R, amount: usize) -> Vec<&mut Self::Item>
+    where
+        R: Rng + ?Sized;
+
+    /// Randomly splits the slice into a group of `amount` distinct elements
+    /// and its complement, returning references to both groups.
+    ///
+    /// If `amount` exceeds the length of the slice, all elements are
+    /// selected. Each group is a uniformly random combination; both
+    /// preserve the order of the elements in the slice, which itself is not
+    /// modified (unlike when using [`shuffle`] and `split_at`). Indices are
+    /// chosen via [`index::sample_split`].
+    ///
+    /// # Example
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let samples: Vec<u32> = (0..100).collect();
+    /// let (test, train) = samples.split_random(&mut rand::thread_rng(), 20);
+    /// assert_eq!(test.len(), 20);
+    /// assert_eq!(train.len(), 80);
+    /// ```
+    /// [`shuffle`]: SliceRandom::shuffle
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn split_random<R>(&self, rng: &mut R, amount: usize) -> (Vec<&Self::Item>, Vec<&Self::Item>)
+    where
+        R: Rng + ?Sized;
+
+    /// Chooses `N` distinct elements from the slice at random, in random
+    /// order, returning an array of references.
+    ///
+    /// Returns `None` if the slice has fewer than `N` elements. Unlike
+    /// [`choose_multiple`], this does not allocate and is available without
+    /// the `alloc` feature. Indices are sampled with Floyd's combination
+    /// algorithm, so complexity is `O(N^2)`; this is intended for small `N`.
+    ///
+    /// # Example
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// let players = ["Alice", "Bob", "Carol", "Dave"];
+    /// let [a, b] = players.choose_multiple_array(&mut rng).unwrap();
+    /// println!("{} vs {}", a, b);
+    /// ```
+    /// [`choose_multiple`]: SliceRandom::choose_multiple
+    fn choose_multiple_array<R, const N: usize>(&self, rng: &mut R) -> Option<[&Self::Item; N]>
+    where
+        R: Rng + ?Sized;
+
     /// Similar to [`choose`], but where the likelihood of each outcome may be
     /// specified.
     ///
@@ -242,6 +346,90 @@ pub trait SliceRandom {
         F: Fn(&Self::Item) -> X,
         X: Into<f64>;
 
+    /// Similar to [`choose_multiple_weighted`], but where the probability of
+    /// each element's inclusion in the output is exactly proportional to its
+    /// weight, where possible. The elements are returned in an arbitrary,
+    /// unspecified order.
+    ///
+    /// With [`choose_multiple_weighted`], elements are chosen one at a time,
+    /// thus heavy elements are included less often than proportionally to
+    /// their weight: in its example, `'a'` has half of the total weight, but
+    /// is included with probability 83%, not `2 * 50%`. Here, each element
+    /// `x` is included with probability `min(1, c * weight(x))`, where `c` is
+    /// chosen such that these probabilities sum to `amount`; elements of too
+    /// large (or infinite) weight are included with certainty.
+    ///
+    /// See [`index::sample_weighted_exact`] for details. As a trade-off, this
+    /// uses `O(length)` space and `O(length log length)` time, compared to
+    /// `O(length)` time with the "nightly" feature for
+    /// [`choose_multiple_weighted`], but does not require `std`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::prelude::*;
+    ///
+    /// // Audit 2 of 4 accounts, with probability proportional to their value
+    /// let accounts = [("a", 500), ("b", 300), ("c", 100), ("d", 100)];
+    /// let mut rng = thread_rng();
+    /// // Inclusion probabilities are 100%, 60%, 20% and 20%
+    /// let audit = accounts.choose_multiple_weighted_exact(&mut rng, 2, |a| a.1).unwrap();
+    /// println!("{:?}", audit.collect::<Vec<_>>());
+    /// ```
+    /// [`choose_multiple_weighted`]: SliceRandom::choose_multiple_weighted
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn choose_multiple_weighted_exact<R, F, X>(
+        &self, rng: &mut R, amount: usize, weight: F,
+    ) -> Result<SliceChooseIter<'_, Self, Self::Item>, WeightedError>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Self::Item) -> X,
+        X: Into<f64>;
+
+    /// Similar to [`choose_multiple_weighted`], but writes the indices of the
+    /// chosen elements into `buf` instead of allocating.
+    ///
+    /// Chooses `min(buf.len(), self.len())` distinct elements, writes their
+    /// indices in increasing order to the start of `buf` and returns their
+    /// number. Like [`choose_multiple_weighted`], elements are chosen one at a
+    /// time, each with probability proportional to its weight among the
+    /// elements not yet chosen. Elements of infinite weight are chosen first,
+    /// and once all remaining weights are zero, the remaining elements are
+    /// chosen uniformly.
+    ///
+    /// Returns [`WeightedError::InvalidWeight`] if a weight is negative or
+    /// NaN, or if the sum of finite weights overflows; `buf` may be partially
+    /// written in this case.
+    ///
+    /// This method neither allocates nor requires `std`, but calls `weight`
+    /// for each element once per chosen element, so complexity is
+    /// `O(length * amount)`. For large `amount`, prefer
+    /// [`choose_multiple_weighted`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::prelude::*;
+    ///
+    /// let choices = [('a', 2), ('b', 1), ('c', 1)];
+    /// let mut rng = thread_rng();
+    /// let mut buf = [0; 2];
+    /// let n = choices.choose_multiple_weighted_fill(&mut rng, &mut buf, |item| item.1).unwrap();
+    /// for &i in &buf[..n] {
+    ///     println!("{}", choices[i].0);
+    /// }
+    /// ```
+    /// [`choose_multiple_weighted`]: SliceRandom::choose_multiple_weighted
+    /// [`WeightedError::InvalidWeight`]: crate::distributions::WeightedError::InvalidWeight
+    fn choose_multiple_weighted_fill<R, F, X>(
+        &self, rng: &mut R, buf: &mut [usize], weight: F,
+    ) -> Result<usize, WeightedError>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Self::Item) -> X,
+        X: Into<f64>;
+
     /// Shuffle a mutable slice in place.
     ///
     /// For slices of length `n`, complexity is `O(n)`.
@@ -285,6 +473,254 @@ pub trait SliceRandom {
     ) -> (&mut [Self::Item], &mut [Self::Item])
     where
         R: Rng + ?Sized;
+
+    /// Shuffle a slice in place lazily, yielding elements in random order.
+    ///
+    /// Each call to `next()` performs one step of the Fisher–Yates shuffle,
+    /// choosing a random element among those not yet yielded. If only a
+    /// prefix of the permutation is consumed, this avoids the cost of
+    /// shuffling the whole slice.
+    ///
+    /// Yielded elements end up at the start of the slice in the order they
+    /// were yielded; the remaining elements are in unspecified order.
+    ///
+    /// For slices, complexity is `O(1)` per element yielded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// let mut players = ["Alice", "Bob", "Carol", "Dave", "Eve"];
+    /// // Deal to players in random order until someone accepts
+    /// for player in players.partial_shuffle_iter(&mut rng) {
+    ///     if player.len() == 3 {
+    ///         println!("{} accepted", player);
+    ///         break;
+    ///     }
+    /// }
+    /// ```
+    fn partial_shuffle_iter<R>(&mut self, rng: R) -> PartialShuffleIter<'_, Self::Item, R>
+    where
+        R: Rng;
+
+    /// Shuffle a mutable slice in place, such that no element ends up within
+    /// `distance` positions of its original index.
+    ///
+    /// Every element is moved by more than `distance` positions; with
+    /// `distance == 0` the result is a derangement, i.e. no element stays in
+    /// place. This is useful to shuffle e.g. a playlist or a schedule, while
+    /// keeping items away from their original slots.
+    ///
+    /// The algorithm starts from a random cyclic shift of the slice by more
+    /// than `distance` (and less than `len - distance`) positions, which is
+    /// valid, then applies `O(len log len)` random swaps, skipping those which
+    /// would move an element too close to its original index. This takes
+    /// `O(len log len)` time regardless of `distance`. The result is
+    /// approximately, but not exactly, uniformly distributed over all valid
+    /// permutations; close to the limit of `2 * distance + 2` elements, few
+    /// swaps are valid and the result is less well mixed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice is not empty and has fewer than
+    /// `2 * distance + 2` elements, since no valid permutation exists.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// let mut playlist: Vec<u32> = (0..10).collect();
+    /// playlist.shuffle_displaced(&mut rng, 2);
+    /// for (i, &track) in playlist.iter().enumerate() {
+    ///     assert!((i as i64 - track as i64).abs() > 2);
+    /// }
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn shuffle_displaced<R>(&mut self, rng: &mut R, distance: usize)
+    where
+        R: Rng + ?Sized;
+
+    /// Shuffle a slice in place, such that elements of greater weight
+    /// tend to appear earlier.
+    ///
+    /// The resulting order is that of repeated weighted sampling without
+    /// replacement: the first element is chosen with probability proportional
+    /// to its weight, the second is chosen likewise among the remaining
+    /// elements, and so on. This uses the keys `u^(1/w)` of Efraimidis and
+    /// Spirakis (computed as `ln(u) / w`) and sorts by them, taking
+    /// `O(n log n)` time and `O(n)` memory.
+    ///
+    /// Elements of infinite weight come first, then those of positive finite
+    /// weight, then those of weight zero; elements of infinite or zero weight
+    /// are uniformly shuffled among themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WeightedError::InvalidWeight`] if a weight is negative or
+    /// NaN, in which case the slice is not modified.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// // Order backends for a request, preferring those of greater capacity
+    /// let mut backends = [("a", 5), ("b", 1), ("c", 3), ("d", 0)];
+    /// backends.shuffle_weighted(&mut rand::thread_rng(), |b| b.1).unwrap();
+    /// assert_eq!(backends[3].0, "d");
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn shuffle_weighted<R, F, X>(&mut self, rng: &mut R, weight: F) -> Result<(), WeightedError>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Self::Item) -> X,
+        X: Into<f64>;
+
+    /// Shuffle the order of consecutive chunks of `chunk_size` elements in
+    /// place, keeping the order of elements within each chunk.
+    ///
+    /// If the length is not a multiple of `chunk_size`, the last
+    /// `len % chunk_size` elements (as by [`slice::chunks_exact`]) are not
+    /// moved. Chunks are ordered as if by [`SliceRandom::shuffle`] on a slice
+    /// of one element per chunk, and swapped in place without allocating.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// // Interleaved stereo samples: shuffle frames, keeping channels paired
+    /// let mut samples = [0.1, -0.1, 0.2, -0.2, 0.3, -0.3];
+    /// samples.shuffle_chunks(&mut rand::thread_rng(), 2);
+    /// for frame in samples.chunks(2) {
+    ///     assert_eq!(frame[0], -frame[1]);
+    /// }
+    /// ```
+    fn shuffle_chunks<R>(&mut self, rng: &mut R, chunk_size: usize)
+    where
+        R: Rng + ?Sized;
+
+    /// Shuffle the order of consecutive groups of elements in place, keeping
+    /// the order of elements within each group.
+    ///
+    /// The slice is split into groups of the lengths given by `group_lens`, in
+    /// order; groups may have different lengths, including zero. Groups are
+    /// ordered as if by [`SliceRandom::shuffle`] on a slice of one element
+    /// per group. This allocates `O(len + group_lens.len())` memory.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum of `group_lens` differs from the slice length.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// // Shuffle record batches of varying sizes
+    /// let mut records = ["a1", "a2", "b1", "c1", "c2", "c3"];
+    /// records.shuffle_groups(&mut rand::thread_rng(), &[2, 1, 3]);
+    /// let a = records.iter().position(|&r| r == "a1").unwrap();
+    /// assert_eq!(records[a + 1], "a2");
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    fn shuffle_groups<R>(&mut self, rng: &mut R, group_lens: &[usize])
+    where
+        R: Rng + ?Sized;
+
+    /// Rotate the slice in place by a uniformly random offset, returning the
+    /// offset.
+    ///
+    /// The offset `k` is uniformly distributed in `0..len` (0 for an empty
+    /// slice), and the slice is rotated as by `self.rotate_left(k)`, such that
+    /// the element previously at index `k` is moved to the front. Each of the
+    /// `len` rotations is thus equally likely.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut genome = [1, 2, 3, 4, 5];
+    /// let k = genome.rotate_random(&mut rand::thread_rng());
+    /// assert_eq!(genome[(5 - k) % 5], 1);
+    /// ```
+    fn rotate_random<R>(&mut self, rng: &mut R) -> usize
+    where
+        R: Rng + ?Sized;
+
+    /// Reverse a uniformly random non-empty sub-range of the slice in place,
+    /// returning the range.
+    ///
+    /// Each of the `len * (len + 1) / 2` non-empty ranges `start..end` with
+    /// `end <= len` is equally likely; these include the `len` ranges of a
+    /// single element, reversal of which has no effect. For an empty slice,
+    /// `0..0` is returned. This is the inversion (or 2-opt) mutation operator
+    /// of genetic algorithms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::SliceRandom;
+    ///
+    /// let mut tour = [0, 1, 2, 3, 4, 5];
+    /// let range = tour.reverse_random_range(&mut rand::thread_rng());
+    /// assert_eq!(tour[range.start], range.end - 1);
+    /// ```
+    fn reverse_random_range<R>(&mut self, rng: &mut R) -> core::ops::Range<usize>
+    where
+        R: Rng + ?Sized;
+
+    /// Shuffle a slice in place, in parallel using [`rayon`].
+    ///
+    /// The slice is recursively partitioned into two parts by a random bit per
+    /// element; both parts are then shuffled in parallel, each with a new RNG
+    /// seeded from the RNG of its parent via [`SeedableRng::from_rng`]. Small
+    /// parts are shuffled sequentially as by [`SliceRandom::shuffle`]. Each
+    /// permutation is equally likely.
+    ///
+    /// # Reproducibility
+    ///
+    /// The result depends only on the slice length and the state of `rng`,
+    /// not on the number of threads or their scheduling, hence is
+    /// reproducible with a seeded `rng`. It differs from the result of
+    /// [`SliceRandom::shuffle`] with the same RNG. `rng` is advanced by a
+    /// number of values depending on the slice length only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`SeedableRng::from_rng`] fails.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::rngs::StdRng;
+    /// use rand::seq::SliceRandom;
+    /// use rand::SeedableRng;
+    ///
+    /// let mut data: Vec<u32> = (0..1_000_000).collect();
+    /// data.par_shuffle(&mut StdRng::seed_from_u64(42));
+    /// ```
+    ///
+    /// [`rayon`]: https://docs.rs/rayon
+    /// [`SeedableRng::from_rng`]: crate::SeedableRng::from_rng
+    #[cfg(feature = "rayon")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rayon")))]
+    fn par_shuffle<R>(&mut self, rng: &mut R)
+    where
+        Self::Item: Send,
+        R: Rng + crate::SeedableRng + Send;
 }
 
 /// Extension trait on iterators, providing random sampling methods.
@@ -316,6 +752,16 @@ pub trait IteratorRandom: Iterator + Sized {
     /// available, complexity is `O(n)` where `n` is the iterator length.
     /// Partial hints (where `lower > 0`) also improve performance.
     ///
+    /// In particular, if the hint is exact (`lower == upper`), as for
+    /// ranges, slice iterators and other [`ExactSizeIterator`]s and adapters
+    /// thereof, an index is chosen up-front using a single random number and
+    /// the element is fetched with [`Iterator::nth`]. An exact hint of zero
+    /// is not trusted: the iterator is checked with [`Iterator::next`], and if
+    /// it nevertheless yields elements, the general algorithm is used. Other
+    /// incorrect exact hints do not cause a panic, but the result is then
+    /// unspecified: elements beyond the hinted length are never chosen, and
+    /// `None` may be returned if the iterator is shorter than hinted.
+    ///
     /// Note that the output values and the number of RNG samples used
     /// depends on size hints. In particular, `Iterator` combinators that don't
     /// change the values yielded but change the size hints may result in
@@ -331,9 +777,10 @@ pub trait IteratorRandom: Iterator + Sized {
         // Handling for this condition outside the loop allows the optimizer to eliminate the loop
         // when the Iterator is an ExactSizeIterator. This has a large performance impact on e.g.
         // seq_iter_choose_from_1000.
-        if upper == Some(lower) {
+        // An exact hint of zero falls through, such that `next` is called by
+        // the loop below.
+        if upper == Some(lower) && lower > 0 {
             return match lower {
-                0 => None,
                 1 => self.next(),
                 _ => self.nth(gen_index(rng, lower)),
             };
@@ -388,52 +835,36 @@ pub trait IteratorRandom: Iterator + Sized {
     /// need consistent results no matter what type of iterator you are working
     /// with. If you do not need this stability prefer [`choose`].
     ///
-    /// Note that this method still uses [`Iterator::size_hint`] to skip
-    /// constructing elements where possible, however the selection and `rng`
-    /// calls are the same in the face of this optimization. If you want to
-    /// force every element to be created regardless call `.inspect(|e| ())`.
+    /// Rather than making a random decision per element, this samples how
+    /// many elements to skip before the next element which replaces the
+    /// current choice, and skips these using [`Iterator::nth`]. Elements are
+    /// replaced `O(log n)` times for an iterator of length `n`, thus only
+    /// `O(log n)` random numbers are used. Skip lengths are computed in `f64`
+    /// arithmetic, which is exact enough for any practical length.
     ///
     /// [`choose`]: IteratorRandom::choose
     fn choose_stable<R>(mut self, rng: &mut R) -> Option<Self::Item>
     where
         R: Rng + ?Sized,
     {
-        let mut consumed = 0;
-        let mut result = None;
-        let mut coin_flipper = CoinFlipper::new(rng);
+        use crate::distributions::Open01;
 
+        let mut result = self.next()?;
+        let mut consumed: usize = 1;
         loop {
-            // Currently the only way to skip elements is `nth()`. So we need to
-            // store what index to access next here.
-            // This should be replaced by `advance_by()` once it is stable:
-            // https://github.com/rust-lang/rust/issues/77404
-            let mut next = 0;
-
-            let (lower, _) = self.size_hint();
-            if lower >= 2 {
-                let highest_selected = (0..lower)
-                    .filter(|ix| coin_flipper.gen_ratio_one_over(consumed + ix + 1))
-                    .last();
-
-                consumed += lower;
-                next = lower;
-
-                if let Some(ix) = highest_selected {
-                    result = self.nth(ix);
-                    next -= ix + 1;
-                    debug_assert!(result.is_some(), "iterator shorter than size_hint().0");
+            // Having consumed `n` elements, none of the next `s` replaces the
+            // current choice with probability `n / (n + s)`. Thus the number
+            // of elements up to the next replacement is `floor(n / u)` for
+            // `u` uniform in (0, 1).
+            let u: f64 = rng.sample(Open01);
+            let next = (consumed as f64 / u) as usize;
+            match self.nth(next.saturating_sub(consumed)) {
+                Some(elem) => {
+                    result = elem;
+                    consumed = next.saturating_add(1);
                 }
+                None => return Some(result),
             }
-
-            let elem = self.nth(next);
-            if elem.is_none() {
-                return result;
-            }
-
-            if coin_flipper.gen_ratio_one_over(consumed + 1) {
-                result = elem;
-            }
-            consumed += 1;
         }
     }
 
@@ -517,6 +948,253 @@ pub trait IteratorRandom: Iterator + Sized {
         }
         reservoir
     }
+
+    /// Collects up to `amount` values at random per key from an iterator of
+    /// `(key, value)` pairs in a single pass, i.e. a stratified sample.
+    ///
+    /// The result maps each key occurring in the iterator to a uniformly
+    /// random sample of `amount` of its values (or all of them, if there are
+    /// fewer), as if [`choose_multiple`] was used on the values of each key.
+    /// As for [`choose_multiple`], the order of each sample is neither stable
+    /// nor fully random.
+    ///
+    /// Complexity is `O(n)` time where `n` is the length of the iterator, and
+    /// `O(k * amount)` memory for `k` distinct keys.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::seq::IteratorRandom;
+    ///
+    /// let log = [("error", 1), ("info", 2), ("info", 3), ("info", 4), ("error", 5)];
+    /// let samples = log.iter().cloned().choose_multiple_stratified(&mut rand::thread_rng(), 2);
+    /// assert_eq!(samples["error"].len(), 2);
+    /// assert_eq!(samples["info"].len(), 2);
+    /// ```
+    ///
+    /// [`choose_multiple`]: IteratorRandom::choose_multiple
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn choose_multiple_stratified<R, K, V>(
+        self, rng: &mut R, amount: usize,
+    ) -> std::collections::HashMap<K, Vec<V>>
+    where
+        Self: Iterator<Item = (K, V)>,
+        R: Rng + ?Sized,
+        K: Eq + core::hash::Hash,
+    {
+        use std::collections::HashMap;
+
+        // For each key, the number of values seen and the reservoir
+        let mut strata: HashMap<K, (usize, Vec<V>)> = HashMap::new();
+        for (key, value) in self {
+            let (seen, reservoir) = strata.entry(key).or_insert_with(|| (0, Vec::new()));
+            if *seen < amount {
+                reservoir.push(value);
+            } else {
+                let k = gen_index(rng, *seen + 1);
+                if let Some(slot) = reservoir.get_mut(k) {
+                    *slot = value;
+                }
+            }
+            *seen += 1;
+        }
+        strata.into_iter().map(|(key, (_, reservoir))| (key, reservoir)).collect()
+    }
+
+    /// Choose one element at random from the iterator, where the likelihood
+    /// of each element may be specified.
+    ///
+    /// The specified function `weight` maps each item `x` to a relative
+    /// likelihood `weight(x)`. The probability of each item being selected is
+    /// therefore `weight(x) / s`, where `s` is the sum of all `weight(x)`.
+    ///
+    /// This consumes the iterator in a single pass, without collecting it, by
+    /// weighted reservoir sampling: a running total of weights is kept, and
+    /// each item replaces the current choice with probability
+    /// `weight(x) / total`. For a reservoir of one item, this yields the same
+    /// distribution as the A-Res algorithm by Efraimidis and Spirakis, but
+    /// avoids computing powers. `weight` is called once per item.
+    ///
+    /// Returns an error if the iterator is empty, if a weight is negative, NaN
+    /// or infinite, if the sum of weights overflows, or if all weights are
+    /// zero. Complexity is `O(n)` where `n` is the length of the iterator.
+    /// For slices, prefer [`SliceRandom::choose_weighted`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::prelude::*;
+    ///
+    /// let log = "GET / 200\nGET /a 404\nPOST /b 500";
+    /// let mut rng = thread_rng();
+    /// // Prefer lines reporting errors
+    /// let line = log
+    ///     .lines()
+    ///     .choose_weighted(&mut rng, |line| if line.ends_with("200") { 1 } else { 10 })
+    ///     .unwrap();
+    /// println!("{}", line);
+    /// ```
+    fn choose_weighted<R, F, X>(self, rng: &mut R, mut weight: F) -> Result<Self::Item, WeightedError>
+    where
+        R: Rng + ?Sized,
+        F: FnMut(&Self::Item) -> X,
+        X: Into<f64>,
+    {
+        let mut result = None;
+        let mut total = 0.0;
+        let mut is_empty = true;
+        for elem in self {
+            is_empty = false;
+            let w = weight(&elem).into();
+            if !(w >= 0.0) || w == f64::INFINITY {
+                return Err(WeightedError::InvalidWeight);
+            }
+            if w == 0.0 {
+                continue;
+            }
+            total += w;
+            if total == f64::INFINITY {
+                return Err(WeightedError::InvalidWeight);
+            }
+            // The first element of positive weight is always chosen
+            if rng.gen::<f64>() * total < w {
+                result = Some(elem);
+            }
+        }
+        match result {
+            Some(elem) => Ok(elem),
+            None if is_empty => Err(WeightedError::NoItem),
+            None => Err(WeightedError::AllWeightsZero),
+        }
+    }
+
+    /// Collects `amount` values at random from the iterator into a vector,
+    /// where the likelihood of each element's inclusion may be specified.
+    ///
+    /// The specified function `weight` maps each item `x` to a relative
+    /// likelihood `weight(x)`; elements are chosen with the same distribution
+    /// as [`SliceRandom::choose_multiple_weighted`]. Elements of weight zero
+    /// are only included if fewer than `amount` elements have positive
+    /// weight. The elements are returned in an arbitrary, unspecified order.
+    ///
+    /// This consumes the iterator in a single pass, using the A-ExpJ algorithm
+    /// by Efraimidis and Spirakis: only a reservoir of `amount` elements is
+    /// kept, and random numbers are only generated to decide how much weight
+    /// to skip before the next replacement, not for every element. It is
+    /// suited to sampling streams of unknown length, such as log lines.
+    ///
+    /// If the iterator has fewer than `amount` elements, all are returned.
+    /// Returns an error if a weight is negative or NaN.
+    ///
+    /// Complexity is `O(n + amount * log(n / amount) * log(amount))` for an
+    /// iterator of length `n`, using `O(amount)` space; `weight` is called
+    /// once per element.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rand::prelude::*;
+    ///
+    /// let mut rng = thread_rng();
+    /// // Sample 3 requests, favouring slow ones
+    /// let durations_ms = [12, 250, 8, 1300, 45, 9];
+    /// let sample = durations_ms
+    ///     .iter()
+    ///     .choose_multiple_weighted(&mut rng, 3, |&&ms| ms)
+    ///     .unwrap();
+    /// assert_eq!(sample.len(), 3);
+    /// ```
+    //
+    // Note: this is feature-gated on std due to usage of f64::ln.
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    fn choose_multiple_weighted<R, F, X>(
+        self, rng: &mut R, amount: usize, mut weight: F,
+    ) -> Result<Vec<Self::Item>, WeightedError>
+    where
+        R: Rng + ?Sized,
+        F: FnMut(&Self::Item) -> X,
+        X: Into<f64>,
+    {
+        use crate::distributions::Open01;
+        use std::collections::BinaryHeap;
+
+        // An element with key `ln(u) / weight` for `u` uniform in (0, 1).
+        // `Ord` is reversed to make `BinaryHeap` a min-heap of keys.
+        struct Element<T> {
+            key: f64,
+            item: T,
+        }
+        impl<T> PartialEq for Element<T> {
+            fn eq(&self, other: &Self) -> bool {
+                self.key == other.key
+            }
+        }
+        impl<T> Eq for Element<T> {}
+        impl<T> PartialOrd for Element<T> {
+            fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl<T> Ord for Element<T> {
+            fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+                // Keys are never NaN, since u > 0 and weights are not NaN
+                other.key.partial_cmp(&self.key).unwrap()
+            }
+        }
+
+        if amount == 0 {
+            return Ok(Vec::new());
+        }
+
+        let mut reservoir = BinaryHeap::with_capacity(amount);
+        let mut iter = self;
+        for item in iter.by_ref() {
+            let w = weight(&item).into();
+            if !(w >= 0.0) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            let u: f64 = rng.sample(Open01);
+            reservoir.push(Element { key: u.ln() / w, item });
+            if reservoir.len() == amount {
+                break;
+            }
+        }
+
+        // `threshold` is the smallest (log) key in the reservoir, and
+        // `skip` the weight to skip until the next replacement.
+        let mut threshold = reservoir.peek().map_or(0.0, |e| e.key);
+        let sample_skip = |rng: &mut R, threshold: f64| {
+            let r: f64 = rng.sample(Open01);
+            r.ln() / threshold
+        };
+        let mut skip = sample_skip(rng, threshold);
+        for item in iter {
+            let w = weight(&item).into();
+            if !(w >= 0.0) {
+                return Err(WeightedError::InvalidWeight);
+            }
+            // Elements of weight zero are never chosen, and once the
+            // threshold is 0 only ties with infinite weights remain.
+            if w == 0.0 || threshold == 0.0 {
+                continue;
+            }
+            if skip > w {
+                skip -= w;
+                continue;
+            }
+            // Sample the key of `item`, conditional on exceeding the threshold
+            let t = (threshold * w).exp();
+            let u: f64 = rng.sample(Open01);
+            let key = (t + u * (1.0 - t)).ln() / w;
+            *reservoir.peek_mut().unwrap() = Element { key, item };
+            threshold = reservoir.peek().unwrap().key;
+            skip = sample_skip(rng, threshold);
+        }
+
+        Ok(reservoir.into_iter().map(|e| e.item).collect())
+    }
 }
 
 impl<T> SliceRandom for [T] {
@@ -558,6 +1236,64 @@ impl<T> SliceRandom for [T] {
         }
     }
 
+    #[cfg(feature = "alloc")]
+    fn choose_multiple_mut<R>(&mut self, rng: &mut R, amount: usize) -> Vec<&mut Self::Item>
+    where
+        R: Rng + ?Sized,
+    {
+        let amount = ::core::cmp::min(amount, self.len());
+        let indices = index::sample(rng, self.len(), amount).into_vec();
+
+        // Split off the chosen elements in increasing order of index, which
+        // proves they are disjoint, then restore the random order.
+        let mut order: Vec<usize> = (0..amount).collect();
+        order.sort_unstable_by_key(|&k| indices[k]);
+        let mut chosen: Vec<Option<&mut T>> = (0..amount).map(|_| None).collect();
+        let mut rest = self;
+        let mut offset = 0;
+        for k in order {
+            let (_, tail) = core::mem::take(&mut rest).split_at_mut(indices[k] - offset);
+            let (elem, tail) = tail.split_first_mut().unwrap();
+            chosen[k] = Some(elem);
+            rest = tail;
+            offset = indices[k] + 1;
+        }
+        chosen.into_iter().map(Option::unwrap).collect()
+    }
+
+    #[cfg(feature = "alloc")]
+    fn split_random<R>(&self, rng: &mut R, amount: usize) -> (Vec<&T>, Vec<&T>)
+    where
+        R: Rng + ?Sized,
+    {
+        let amount = ::core::cmp::min(amount, self.len());
+        let (selected, rest) = index::sample_split(rng, self.len(), amount);
+        (
+            selected.iter().map(|i| &self[i]).collect(),
+            rest.iter().map(|i| &self[i]).collect(),
+        )
+    }
+
+    fn choose_multiple_array<R, const N: usize>(&self, rng: &mut R) -> Option<[&Self::Item; N]>
+    where
+        R: Rng + ?Sized,
+    {
+        let len = self.len();
+        if N > len {
+            return None;
+        }
+        // Floyd's combination algorithm, as in `index::sample_floyd`
+        let mut indices = [0; N];
+        for (n, j) in (len - N..len).enumerate() {
+            let t = gen_index(rng, j + 1);
+            if let Some(pos) = indices[..n].iter().position(|&x| x == t) {
+                indices[pos] = j;
+            }
+            indices[n] = t;
+        }
+        Some(indices.map(|i| &self[i]))
+    }
+
     #[cfg(feature = "alloc")]
     fn choose_weighted<R, F, B, X>(
         &self, rng: &mut R, weight: F,
@@ -619,6 +1355,94 @@ impl<T> SliceRandom for [T] {
         })
     }
 
+    #[cfg(feature = "alloc")]
+    fn choose_multiple_weighted_exact<R, F, X>(
+        &self, rng: &mut R, amount: usize, weight: F,
+    ) -> Result<SliceChooseIter<'_, Self, Self::Item>, WeightedError>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Self::Item) -> X,
+        X: Into<f64>,
+    {
+        let amount = ::core::cmp::min(amount, self.len());
+        Ok(SliceChooseIter {
+            slice: self,
+            _phantom: Default::default(),
+            indices: index::sample_weighted_exact(
+                rng,
+                self.len(),
+                |idx| weight(&self[idx]).into(),
+                amount,
+            )?
+            .into_iter(),
+        })
+    }
+
+    fn choose_multiple_weighted_fill<R, F, X>(
+        &self, rng: &mut R, buf: &mut [usize], weight: F,
+    ) -> Result<usize, WeightedError>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Self::Item) -> X,
+        X: Into<f64>,
+    {
+        let amount = ::core::cmp::min(buf.len(), self.len());
+        for n in 0..amount {
+            let chosen = &buf[..n];
+
+            let mut total = 0.0;
+            let mut num_infinite = 0;
+            for i in skip_sorted(self.len(), chosen) {
+                let w = weight(&self[i]).into();
+                if !(w >= 0.) {
+                    return Err(WeightedError::InvalidWeight);
+                }
+                if w == f64::INFINITY {
+                    num_infinite += 1;
+                } else {
+                    total += w;
+                }
+            }
+            if num_infinite == 0 && total == f64::INFINITY {
+                return Err(WeightedError::InvalidWeight);
+            }
+
+            let index = if num_infinite > 0 {
+                let nth = gen_index(rng, num_infinite);
+                skip_sorted(self.len(), chosen)
+                    .filter(|&i| weight(&self[i]).into() == f64::INFINITY)
+                    .nth(nth)
+                    .unwrap()
+            } else if total > 0.0 {
+                // Find the element at a uniform position within the total
+                // weight; fall back to the last element of positive weight in
+                // case of rounding errors.
+                let mut target = rng.gen::<f64>() * total;
+                let mut last = 0;
+                for i in skip_sorted(self.len(), chosen) {
+                    let w = weight(&self[i]).into();
+                    if w > 0.0 {
+                        last = i;
+                        if target < w {
+                            break;
+                        }
+                        target -= w;
+                    }
+                }
+                last
+            } else {
+                let nth = gen_index(rng, self.len() - n);
+                skip_sorted(self.len(), chosen).nth(nth).unwrap()
+            };
+
+            // Insert `index`, keeping the chosen indices sorted
+            let pos = chosen.partition_point(|&i| i < index);
+            buf[n] = index;
+            buf[pos..=n].rotate_right(1);
+        }
+        Ok(amount)
+    }
+
     fn shuffle<R>(&mut self, rng: &mut R)
     where
         R: Rng + ?Sized,
@@ -627,81 +1451,371 @@ impl<T> SliceRandom for [T] {
             // There is no need to shuffle an empty or single element slice
             return;
         }
-        self.partial_shuffle(rng, self.len());
+        self.partial_shuffle(rng, self.len());
+    }
+
+    fn partial_shuffle<R>(
+        &mut self, rng: &mut R, amount: usize,
+    ) -> (&mut [Self::Item], &mut [Self::Item])
+    where
+        R: Rng + ?Sized,
+    {
+        let m = self.len().saturating_sub(amount);
+        partial_shuffle_by(rng, self.len(), amount, |i, j| self.swap(i, j));
+        let r = self.split_at_mut(m);
+        (r.1, r.0)
+    }
+
+    fn partial_shuffle_iter<R>(&mut self, rng: R) -> PartialShuffleIter<'_, Self::Item, R>
+    where
+        R: Rng,
+    {
+        PartialShuffleIter { rest: self, rng }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn shuffle_displaced<R>(&mut self, rng: &mut R, distance: usize)
+    where
+        R: Rng + ?Sized,
+    {
+        let len = self.len();
+        if len == 0 {
+            return;
+        }
+        assert!(
+            len / 2 > distance,
+            "slice too short to move all elements by more than `distance`"
+        );
+
+        // Whether the element from index `j` may be moved to index `i`
+        let valid = |i: usize, j: usize| (if i > j { i - j } else { j - i }) > distance;
+
+        // Start from a random cyclic shift by `s`, which moves every element
+        // by either `s` or `len - s` positions; both exceed `distance`.
+        let s = distance + 1 + gen_index(rng, len - 2 * distance - 1);
+        let mut indices: Vec<usize> = (0..len).map(|i| (i + len - s) % len).collect();
+        // Then apply random swaps, keeping only those after which both
+        // elements are still valid.
+        let steps = 2 * len * (usize::BITS - len.leading_zeros() + 1) as usize;
+        for _ in 0..steps {
+            let i = gen_index(rng, len);
+            let j = gen_index(rng, len);
+            if valid(i, indices[j]) && valid(j, indices[i]) {
+                indices.swap(i, j);
+            }
+        }
+        Permutation::from_indices(indices).unwrap().apply(self);
+    }
+
+    #[cfg(feature = "std")]
+    fn shuffle_weighted<R, F, X>(&mut self, rng: &mut R, weight: F) -> Result<(), WeightedError>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&Self::Item) -> X,
+        X: Into<f64>,
+    {
+        use crate::distributions::Open01;
+
+        // Sort key: (class, key, index), where the class orders infinite,
+        // positive and zero weights, and all keys are finite.
+        let mut keys = Vec::with_capacity(self.len());
+        for (i, item) in self.iter().enumerate() {
+            let w: f64 = weight(item).into();
+            let u: f64 = rng.sample(Open01);
+            let key = if w == f64::INFINITY {
+                (2u8, u)
+            } else if w > 0.0 {
+                (1, u.ln() / w)
+            } else if w == 0.0 {
+                (0, u)
+            } else {
+                return Err(WeightedError::InvalidWeight);
+            };
+            keys.push((key, i));
+        }
+        keys.sort_unstable_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+        let indices = keys.into_iter().map(|(_, i)| i).collect();
+        Permutation::from_indices(indices).unwrap().apply(self);
+        Ok(())
+    }
+
+    fn shuffle_chunks<R>(&mut self, rng: &mut R, chunk_size: usize)
+    where
+        R: Rng + ?Sized,
+    {
+        assert!(chunk_size > 0, "`chunk_size` must be non-zero");
+        let chunks = self.len() / chunk_size;
+        if chunks <= 1 {
+            return;
+        }
+        partial_shuffle_by(rng, chunks, chunks, |i, j| {
+            if i != j {
+                let (low, high) = if i < j { (i, j) } else { (j, i) };
+                let (head, tail) = self.split_at_mut(high * chunk_size);
+                head[low * chunk_size..][..chunk_size].swap_with_slice(&mut tail[..chunk_size]);
+            }
+        });
+    }
+
+    #[cfg(feature = "alloc")]
+    fn shuffle_groups<R>(&mut self, rng: &mut R, group_lens: &[usize])
+    where
+        R: Rng + ?Sized,
+    {
+        let mut starts = Vec::with_capacity(group_lens.len());
+        let mut len = 0usize;
+        for &group_len in group_lens {
+            starts.push(len);
+            len = len.checked_add(group_len).expect("sum of `group_lens` overflows");
+        }
+        assert_eq!(len, self.len(), "sum of `group_lens` differs from slice length");
+
+        let mut order: Vec<usize> = (0..group_lens.len()).collect();
+        order.shuffle(rng);
+        let mut indices = Vec::with_capacity(len);
+        for g in order {
+            indices.extend(starts[g]..starts[g] + group_lens[g]);
+        }
+        Permutation::from_indices(indices).unwrap().apply(self);
+    }
+
+    fn rotate_random<R>(&mut self, rng: &mut R) -> usize
+    where
+        R: Rng + ?Sized,
+    {
+        if self.is_empty() {
+            return 0;
+        }
+        let k = gen_index(rng, self.len());
+        self.rotate_left(k);
+        k
+    }
+
+    fn reverse_random_range<R>(&mut self, rng: &mut R) -> core::ops::Range<usize>
+    where
+        R: Rng + ?Sized,
+    {
+        let len = self.len();
+        if len == 0 {
+            return 0..0;
+        }
+        // Choose two distinct bounds in `0..=len`, uniformly
+        let a = gen_index(rng, len + 1);
+        let mut b = gen_index(rng, len);
+        if b >= a {
+            b += 1;
+        }
+        let range = a.min(b)..a.max(b);
+        self[range.clone()].reverse();
+        range
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_shuffle<R>(&mut self, rng: &mut R)
+    where
+        Self::Item: Send,
+        R: Rng + crate::SeedableRng + Send,
+    {
+        par_shuffle_rec(self, rng);
+    }
+}
+
+impl<I> IteratorRandom for I where I: Iterator + Sized {}
+
+/// An iterator over multiple slice elements.
+///
+/// This struct is created by
+/// [`SliceRandom::choose_multiple`](trait.SliceRandom.html#tymethod.choose_multiple).
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Debug)]
+pub struct SliceChooseIter<'a, S: ?Sized + 'a, T: 'a> {
+    slice: &'a S,
+    _phantom: ::core::marker::PhantomData<T>,
+    indices: index::IndexVecIntoIter,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, S: Index<usize, Output = T> + ?Sized + 'a, T: 'a> Iterator for SliceChooseIter<'a, S, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // TODO: investigate using SliceIndex::get_unchecked when stable
+        self.indices.next().map(|i| &self.slice[i])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.indices.len(), Some(self.indices.len()))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, S: Index<usize, Output = T> + ?Sized + 'a, T: 'a> ExactSizeIterator
+    for SliceChooseIter<'a, S, T>
+{
+    fn len(&self) -> usize {
+        self.indices.len()
+    }
+}
+
+/// Shuffle a sequence of length `len` via the function `swap`, e.g. to apply
+/// the same random permutation to several slices.
+///
+/// This calls `swap(i, j)` to swap the elements at indices `i` and `j` in
+/// exactly the same order as [`SliceRandom::shuffle`] would on a slice of
+/// length `len` with the same RNG state, thus the result of shuffling
+/// parallel slices is identical to shuffling each with a copy of the RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::shuffle_by;
+///
+/// let mut rng = rand::thread_rng();
+/// let mut features = [[0.1, 0.2], [0.3, 0.4], [0.5, 0.6], [0.7, 0.8]];
+/// let mut labels = ["a", "b", "c", "d"];
+/// let mut weights = vec![1.0, 2.0, 3.0, 4.0];
+/// shuffle_by(&mut rng, labels.len(), |i, j| {
+///     features.swap(i, j);
+///     labels.swap(i, j);
+///     weights.swap(i, j);
+/// });
+/// ```
+pub fn shuffle_by<R, F>(rng: &mut R, len: usize, swap: F)
+where
+    R: Rng + ?Sized,
+    F: FnMut(usize, usize),
+{
+    if len <= 1 {
+        // There is no need to shuffle an empty or single element sequence
+        return;
+    }
+    partial_shuffle_by(rng, len, len, swap);
+}
+
+/// Shuffle `slice` by recursive random partitioning, see
+/// [`SliceRandom::par_shuffle`].
+#[cfg(feature = "rayon")]
+fn par_shuffle_rec<T, R>(slice: &mut [T], rng: &mut R)
+where
+    T: Send,
+    R: Rng + crate::SeedableRng + Send,
+{
+    // Below this length, the overhead of parallelism is not worthwhile
+    const SEQUENTIAL_LEN: usize = 1 << 14;
+
+    if slice.len() <= SEQUENTIAL_LEN {
+        slice.shuffle(rng);
+        return;
+    }
+
+    // Move the elements with a set bit to the front
+    let mut front = 0;
+    let mut bits = 0;
+    for i in 0..slice.len() {
+        if i % 64 == 0 {
+            bits = rng.next_u64();
+        }
+        if bits & 1 == 1 {
+            slice.swap(front, i);
+            front += 1;
+        }
+        bits >>= 1;
     }
 
-    fn partial_shuffle<R>(
-        &mut self, rng: &mut R, amount: usize,
-    ) -> (&mut [Self::Item], &mut [Self::Item])
-    where
-        R: Rng + ?Sized,
-    {
-        let m = self.len().saturating_sub(amount);
+    let mut rng_front = R::from_rng(&mut *rng).unwrap();
+    let mut rng_back = R::from_rng(&mut *rng).unwrap();
+    let (slice_front, slice_back) = slice.split_at_mut(front);
+    rayon::join(
+        || par_shuffle_rec(slice_front, &mut rng_front),
+        || par_shuffle_rec(slice_back, &mut rng_back),
+    );
+}
 
-        // The algorithm below is based on Durstenfeld's algorithm for the
-        // [Fisher–Yates shuffle](https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle#The_modern_algorithm)
-        // for an unbiased permutation.
-        // It ensures that the last `amount` elements of the slice
-        // are randomly selected from the whole slice.
-        
-        //`IncreasingUniform::next_index()` is faster than `gen_index`
-        //but only works for 32 bit integers
-        //So we must use the slow method if the slice is longer than that.
-        if self.len() < (u32::MAX as usize) {
-            let mut chooser = IncreasingUniform::new(rng, m as u32);
-            for i in m..self.len() {
-                let index = chooser.next_index();
-                self.swap(i, index);
-            }
-        } else {            
-            for i in m..self.len() {
-                let index = gen_index(rng, i + 1);
-                self.swap(i, index);
-            }
+// Partially shuffle a sequence of length `len` via `swap`, randomly selecting
+// the last `amount` elements from the whole sequence.
+fn partial_shuffle_by<R, F>(rng: &mut R, len: usize, amount: usize, mut swap: F)
+where
+    R: Rng + ?Sized,
+    F: FnMut(usize, usize),
+{
+    let m = len.saturating_sub(amount);
+
+    // The algorithm below is based on Durstenfeld's algorithm for the
+    // [Fisher–Yates shuffle](https://en.wikipedia.org/wiki/Fisher%E2%80%93Yates_shuffle#The_modern_algorithm)
+    // for an unbiased permutation.
+    // It ensures that the last `amount` elements of the slice
+    // are randomly selected from the whole slice.
+
+    //`IncreasingUniform::next_index()` is faster than `gen_index`
+    //but only works for 32 bit integers
+    //So we must use the slow method if the slice is longer than that.
+    if len < (u32::MAX as usize) {
+        let mut chooser = IncreasingUniform::new(rng, m as u32);
+        for i in m..len {
+            let index = chooser.next_index();
+            swap(i, index);
+        }
+    } else {
+        for i in m..len {
+            let index = gen_index(rng, i + 1);
+            swap(i, index);
         }
-        let r = self.split_at_mut(m);
-        (r.1, r.0)
     }
 }
 
-impl<I> IteratorRandom for I where I: Iterator + Sized {}
-
-/// An iterator over multiple slice elements.
+/// An iterator over the elements of a slice in random order, shuffling the
+/// slice in place lazily.
 ///
-/// This struct is created by
-/// [`SliceRandom::choose_multiple`](trait.SliceRandom.html#tymethod.choose_multiple).
-#[cfg(feature = "alloc")]
-#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+/// This struct is created by [`SliceRandom::partial_shuffle_iter`].
 #[derive(Debug)]
-pub struct SliceChooseIter<'a, S: ?Sized + 'a, T: 'a> {
-    slice: &'a S,
-    _phantom: ::core::marker::PhantomData<T>,
-    indices: index::IndexVecIntoIter,
+pub struct PartialShuffleIter<'a, T: 'a, R> {
+    rest: &'a mut [T],
+    rng: R,
 }
 
-#[cfg(feature = "alloc")]
-impl<'a, S: Index<usize, Output = T> + ?Sized + 'a, T: 'a> Iterator for SliceChooseIter<'a, S, T> {
-    type Item = &'a T;
+impl<'a, T: 'a, R: Rng> Iterator for PartialShuffleIter<'a, T, R> {
+    type Item = &'a mut T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // TODO: investigate using SliceIndex::get_unchecked when stable
-        self.indices.next().map(|i| &self.slice[i])
+        let rest = core::mem::take(&mut self.rest);
+        if rest.is_empty() {
+            return None;
+        }
+        let index = gen_index(&mut self.rng, rest.len());
+        rest.swap(0, index);
+        let (first, rest) = rest.split_first_mut().unwrap();
+        self.rest = rest;
+        Some(first)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        (self.indices.len(), Some(self.indices.len()))
+        (self.rest.len(), Some(self.rest.len()))
     }
 }
 
-#[cfg(feature = "alloc")]
-impl<'a, S: Index<usize, Output = T> + ?Sized + 'a, T: 'a> ExactSizeIterator
-    for SliceChooseIter<'a, S, T>
-{
+impl<'a, T: 'a, R: Rng> ExactSizeIterator for PartialShuffleIter<'a, T, R> {
     fn len(&self) -> usize {
-        self.indices.len()
+        self.rest.len()
     }
 }
 
+impl<'a, T: 'a, R: Rng> core::iter::FusedIterator for PartialShuffleIter<'a, T, R> {}
+
+// Iterate over `0..len`, skipping the elements of the increasing sequence
+// `sorted`.
+fn skip_sorted(len: usize, sorted: &[usize]) -> impl Iterator<Item = usize> + '_ {
+    let mut sorted = sorted.iter().peekable();
+    (0..len).filter(move |&i| {
+        if sorted.peek() == Some(&&i) {
+            sorted.next();
+            false
+        } else {
+            true
+        }
+    })
+}
+
 // Sample a number uniformly between 0 and `ubound`. Uses 32-bit sampling where
 // possible, primarily in order to produce the same output on 32-bit and 64-bit
 // platforms.
@@ -722,6 +1836,8 @@ mod test {
     use crate::Rng;
     #[cfg(all(feature = "alloc", not(feature = "std")))]
     use alloc::vec::Vec;
+    #[cfg(feature = "std")]
+    use crate::rngs::adapter::CountingRng;
 
     #[test]
     fn test_slice_choose() {
@@ -785,6 +1901,52 @@ mod test {
         let res = nums.partial_shuffle(&mut r, 6);
         assert_eq!(res.0, &mut [7, 12, 6, 8, 1, 9]);
         assert_eq!(res.1, &mut [0, 11, 2, 3, 4, 5, 10]);
+        nums = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        let mut buf = [0; 4];
+        for (x, y) in buf.iter_mut().zip(nums.partial_shuffle_iter(&mut r)) {
+            *x = *y;
+        }
+        assert_eq!(buf, [5, 9, 4, 0]);
+        let mut buf = [0; 4];
+        let weights = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13];
+        assert_eq!(weights.choose_multiple_weighted_fill(&mut r, &mut buf, |&w| w), Ok(4));
+        assert_eq!(buf, [1, 6, 10, 12]);
+        assert_eq!(nums.choose_multiple_array(&mut r), Some([&0, &1, &7]));
+        #[cfg(feature = "alloc")]
+        {
+            nums = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+            nums.shuffle_displaced(&mut r, 3);
+            assert_eq!(nums, [7, 5, 6, 9, 0, 11, 10, 2, 12, 1, 4, 3, 8]);
+        }
+        #[cfg(feature = "std")]
+        {
+            nums = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+            nums.shuffle_weighted(&mut r, |&x| x as f64).unwrap();
+            assert_eq!(nums, [11, 9, 8, 10, 12, 7, 5, 2, 6, 3, 4, 1, 0]);
+        }
+    }
+
+    #[test]
+    fn value_stability_rotate_reverse() {
+        let mut r = crate::test::rng(134);
+        let mut nums = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        assert_eq!(nums.rotate_random(&mut r), 7);
+        assert_eq!(nums.reverse_random_range(&mut r), 3..9);
+        assert_eq!(nums, [7, 8, 9, 5, 4, 3, 2, 1, 0, 6]);
+    }
+
+    #[test]
+    fn value_stability_shuffle_chunks() {
+        let mut r = crate::test::rng(130);
+        let mut nums = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+        nums.shuffle_chunks(&mut r, 3);
+        assert_eq!(nums, [3, 4, 5, 0, 1, 2, 9, 10, 11, 6, 7, 8, 12]);
+        #[cfg(feature = "alloc")]
+        {
+            nums = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12];
+            nums.shuffle_groups(&mut r, &[1, 4, 0, 2, 6]);
+            assert_eq!(nums, [0, 5, 6, 1, 2, 3, 4, 7, 8, 9, 10, 11, 12]);
+        }
     }
 
     #[derive(Clone)]
@@ -987,6 +2149,104 @@ mod test {
         assert_eq!(UnhintedIterator { iter: 0..0 }.choose(r), None);
     }
 
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_iterator_choose_exact_size() {
+        let mut r = CountingRng::new(crate::test::rng(111));
+        let v: Vec<u32> = (0..1000).collect();
+
+        assert!((0..1000).choose(&mut r).is_some());
+        assert!(v.iter().choose(&mut r).is_some());
+        assert!(v.iter().rev().map(|x| x + 1).choose(&mut r).is_some());
+        assert!(v.iter().enumerate().skip(10).choose(&mut r).is_some());
+        assert!(v.iter().chain(v.iter()).choose(&mut r).is_some());
+        assert_eq!(r.counts().calls(), 5);
+
+        // Hints which are exact but incorrect
+        struct LyingIterator<I> {
+            iter: I,
+            hint: usize,
+        }
+        impl<I: Iterator> Iterator for LyingIterator<I> {
+            type Item = I::Item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                self.iter.next()
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.hint, Some(self.hint))
+            }
+        }
+
+        let mut counts = [0; 3];
+        for _ in 0..3000 {
+            let iter = LyingIterator { iter: 0..3, hint: 0 };
+            counts[iter.choose(&mut r).unwrap()] += 1;
+        }
+        crate::test::assert_uniform(&counts);
+        assert_eq!(LyingIterator { iter: 0..0, hint: 0 }.choose(&mut r), None);
+
+        for _ in 0..10 {
+            let iter = LyingIterator { iter: 0..2, hint: 5 };
+            assert!(iter.choose(&mut r).map_or(true, |x| x < 2));
+            let iter = LyingIterator { iter: 0..5, hint: 2 };
+            assert!(iter.choose(&mut r).unwrap() < 2);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_iterator_choose_multiple_stratified() {
+        let mut r1 = crate::test::rng(135);
+        let items = (0..1000u32).map(|i| (i % 3, i));
+        let samples = items.clone().choose_multiple_stratified(&mut r1, 10);
+        assert_eq!(samples.len(), 3);
+        for (&key, sample) in &samples {
+            assert_eq!(sample.len(), 10);
+            assert!(sample.iter().all(|&v| v % 3 == key));
+        }
+
+        // Strata with fewer values are returned entirely
+        let items = [(1, 'a'), (2, 'b'), (1, 'c'), (1, 'd'), (3, 'e')];
+        let samples = items.iter().cloned().choose_multiple_stratified(&mut r1, 2);
+        assert_eq!(samples[&2], ['b']);
+        assert_eq!(samples[&3], ['e']);
+        assert_eq!(samples[&1].len(), 2);
+        let samples = items.iter().cloned().choose_multiple_stratified(&mut r1, 0);
+        assert!(samples.values().all(|s| s.is_empty()) && samples.len() == 3);
+
+        // Each stratum is sampled like `choose_multiple` on its values
+        let mut r1 = crate::test::rng(136);
+        let mut r2 = crate::test::rng(136);
+        let samples = (0..100).map(|i| ((), i)).choose_multiple_stratified(&mut r1, 5);
+        assert_eq!(samples[&()], (0..100).choose_multiple(&mut r2, 5));
+
+        // Inclusion is uniform
+        let mut counts = [0; 6];
+        for _ in 0..3000 {
+            let items = (0..6).map(|i| (i % 2, i));
+            for sample in items.choose_multiple_stratified(&mut r1, 1).values() {
+                counts[sample[0]] += 1;
+            }
+        }
+        crate::test::assert_uniform(&counts);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_iterator_choose_stable_draws() {
+        // About ln(1e6) = 14 expected draws
+        let mut r = CountingRng::new(crate::test::rng(110));
+        let mut total = 0;
+        for _ in 0..100 {
+            assert!((0..1_000_000).choose_stable(&mut r).is_some());
+            total += r.reset().calls();
+        }
+        assert!(total < 100 * 30);
+        assert_eq!((0..1).choose_stable(&mut r), Some(0));
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Miri is too slow
     fn test_iterator_choose_stable_stability() {
@@ -1111,6 +2371,366 @@ mod test {
         assert!(res.1[0] == 1 || res.1[1] == 2 || res.1[2] == 3);
     }
 
+    #[test]
+    fn test_shuffle_by() {
+        let mut r1 = crate::test::rng(120);
+        let mut r2 = crate::test::rng(120);
+
+        let mut a = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let mut b = ['a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j'];
+        let mut c = a;
+        shuffle_by(&mut r1, a.len(), |i, j| {
+            a.swap(i, j);
+            b.swap(i, j);
+        });
+        c.shuffle(&mut r2);
+        assert_eq!(a, c);
+        for (&x, &y) in a.iter().zip(b.iter()) {
+            assert_eq!(y, (b'a' + x as u8) as char);
+        }
+
+        // No swaps or random numbers for short sequences
+        shuffle_by(&mut r1, 1, |_, _| panic!());
+        assert_eq!(r1.gen::<u64>(), r2.gen::<u64>());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_choose_multiple_mut() {
+        let mut r1 = crate::test::rng(122);
+        let mut r2 = crate::test::rng(122);
+        let mut v: Vec<u32> = (0..20).collect();
+        let expected: Vec<u32> = v.choose_multiple(&mut r2, 8).cloned().collect();
+        let mut chosen = v.choose_multiple_mut(&mut r1, 8);
+        // Same elements and order as `choose_multiple`
+        assert_eq!(chosen.iter().map(|x| **x).collect::<Vec<_>>(), expected);
+        for x in chosen.iter_mut() {
+            **x += 100;
+        }
+        assert_eq!(v.iter().filter(|&&x| x >= 100).count(), 8);
+        for x in &expected {
+            assert_eq!(v[*x as usize], x + 100);
+        }
+
+        assert_eq!(v.choose_multiple_mut(&mut r1, 30).len(), 20);
+        assert!([0u8; 0].choose_multiple_mut(&mut r1, 2).is_empty());
+    }
+
+    #[test]
+    fn test_choose_multiple_array() {
+        let mut r = crate::test::rng(121);
+        let v = [0, 1, 2, 3, 4];
+        assert_eq!(v.choose_multiple_array::<_, 6>(&mut r), None);
+        assert_eq!(v.choose_multiple_array::<_, 0>(&mut r), Some([]));
+        assert_eq!([0u8; 0].choose_multiple_array::<_, 0>(&mut r), Some([]));
+
+        let mut counts = [[0; 5]; 3];
+        for _ in 0..5000 {
+            let [a, b, c] = v.choose_multiple_array(&mut r).unwrap();
+            assert!(a != b && b != c && a != c);
+            counts[0][*a] += 1;
+            counts[1][*b] += 1;
+            counts[2][*c] += 1;
+        }
+        // Each element is equally likely at each position
+        crate::test::assert_uniform(counts.iter().flatten());
+
+        let [a, b, c, d, e] = v.choose_multiple_array(&mut r).unwrap();
+        let mut all = [*a, *b, *c, *d, *e];
+        all.sort_unstable();
+        assert_eq!(all, v);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_shuffle_displaced() {
+        let mut r = crate::test::rng(123);
+        let cases = [(0, 5), (2, 0), (3, 0), (4, 1), (10, 0), (10, 4), (100, 10), (22, 10), (42, 20)];
+        for &(len, distance) in &cases {
+            for _ in 0..20 {
+                let mut v: Vec<usize> = (0..len).collect();
+                v.shuffle_displaced(&mut r, distance);
+                let mut seen = alloc::vec![false; len];
+                for (i, &x) in v.iter().enumerate() {
+                    assert!((i as isize - x as isize).unsigned_abs() > distance);
+                    assert!(!seen[x]);
+                    seen[x] = true;
+                }
+            }
+        }
+
+        // All 9 derangements of 4 elements occur
+        let mut seen = Vec::new();
+        for _ in 0..1000 {
+            let mut v = [0, 1, 2, 3];
+            v.shuffle_displaced(&mut r, 0);
+            if !seen.contains(&v) {
+                seen.push(v);
+            }
+        }
+        assert_eq!(seen.len(), 9);
+
+        // Running time does not depend on the distance, even at the limit
+        let mut v: Vec<usize> = (0..1000).collect();
+        v.shuffle_displaced(&mut r, 450);
+        assert!(v.iter().enumerate().all(|(i, &x)| (i as isize - x as isize).unsigned_abs() > 450));
+        let mut v: Vec<usize> = (0..1000).collect();
+        v.shuffle_displaced(&mut r, 499);
+        assert!(v.iter().enumerate().all(|(i, &x)| (i as isize - x as isize).unsigned_abs() > 499));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn test_shuffle_displaced_too_short() {
+        [0, 1, 2, 3, 4].shuffle_displaced(&mut crate::test::rng(124), 2);
+    }
+
+    #[test]
+    fn test_shuffle_chunks() {
+        let mut r1 = crate::test::rng(126);
+        let mut r2 = crate::test::rng(126);
+        let mut v: [u32; 11] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let mut chunks = [0u32, 1, 2];
+        v.shuffle_chunks(&mut r1, 3);
+        chunks.shuffle(&mut r2);
+        for (chunk, &c) in v.chunks_exact(3).zip(chunks.iter()) {
+            assert_eq!(chunk, [3 * c, 3 * c + 1, 3 * c + 2]);
+        }
+        assert_eq!(v[9..], [9, 10]);
+
+        let mut empty: [u8; 0] = [];
+        empty.shuffle_chunks(&mut r1, 4);
+        let mut short = [1, 2, 3];
+        short.shuffle_chunks(&mut r1, 4);
+        assert_eq!(short, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rotate_random() {
+        let mut r = crate::test::rng(131);
+        let mut empty: [u8; 0] = [];
+        assert_eq!(empty.rotate_random(&mut r), 0);
+
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let mut v = [0, 1, 2, 3];
+            let k = v.rotate_random(&mut r);
+            assert_eq!(v, [k, (k + 1) % 4, (k + 2) % 4, (k + 3) % 4]);
+            counts[k] += 1;
+        }
+        crate::test::assert_uniform(&counts);
+    }
+
+    #[test]
+    fn test_reverse_random_range() {
+        let mut r = crate::test::rng(132);
+        let mut empty: [u8; 0] = [];
+        assert_eq!(empty.reverse_random_range(&mut r), 0..0);
+        assert_eq!([1].reverse_random_range(&mut r), 0..1);
+
+        // Each of the 6 non-empty ranges of 0..3 is equally likely
+        let mut counts = [[0; 4]; 4];
+        for _ in 0..6000 {
+            let mut v = [0, 1, 2];
+            let range = v.reverse_random_range(&mut r);
+            assert!(range.start < range.end && range.end <= 3);
+            for (i, &x) in v.iter().enumerate() {
+                if range.contains(&i) {
+                    assert_eq!(x, range.start + range.end - 1 - i);
+                } else {
+                    assert_eq!(x, i);
+                }
+            }
+            counts[range.start][range.end] += 1;
+        }
+        crate::test::assert_uniform(counts.iter().enumerate().flat_map(|(start, c)| &c[start + 1..]));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_par_shuffle() {
+        use rand_pcg::Pcg32;
+        use crate::SeedableRng;
+
+        let mut v: Vec<u32> = (0..100_000).collect();
+        let mut w = v.clone();
+        v.par_shuffle(&mut Pcg32::seed_from_u64(1));
+        w.par_shuffle(&mut Pcg32::seed_from_u64(1));
+        assert_eq!(v, w);
+        assert_ne!(v[..100], (0..100).collect::<Vec<_>>()[..]);
+        w.sort_unstable();
+        assert_eq!(w, (0..100_000).collect::<Vec<_>>());
+
+        // Each element is equally likely to end up in the first half
+        let mut first_half = [0; 4];
+        let mut rng = Pcg32::seed_from_u64(2);
+        for _ in 0..100 {
+            let mut v: Vec<usize> = (0..40_000).collect();
+            v.par_shuffle(&mut rng);
+            for &x in &v[..20_000] {
+                first_half[x / 10_000] += 1;
+            }
+        }
+        assert!(first_half.iter().all(|&c| (495_000..505_000).contains(&c)));
+
+        let mut short = [1, 2, 3];
+        short.par_shuffle(&mut Pcg32::seed_from_u64(3));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_split_random() {
+        let mut r = crate::test::rng(138);
+        let data: Vec<u32> = (0..10).collect();
+        let (a, b) = data.split_random(&mut r, 4);
+        assert_eq!((a.len(), b.len()), (4, 6));
+        assert!(a.windows(2).all(|w| w[0] < w[1]));
+        assert!(b.windows(2).all(|w| w[0] < w[1]));
+        assert!(a.iter().all(|x| !b.contains(x)));
+
+        let (a, b) = data.split_random(&mut r, 20);
+        assert_eq!((a.len(), b.len()), (10, 0));
+        let (a, b) = data[..0].split_random(&mut r, 1);
+        assert!(a.is_empty() && b.is_empty());
+
+        // Each element is equally likely to be selected
+        let mut counts = [0; 10];
+        for _ in 0..1000 {
+            for &x in data.split_random(&mut r, 3).0 {
+                counts[x as usize] += 1;
+            }
+        }
+        assert!(counts.iter().all(|&c| (250..350).contains(&c)));
+
+        let (a, b) = data.split_random(&mut crate::test::rng(139), 3);
+        assert_eq!(a, [&1, &6, &7]);
+        assert_eq!(b, [&0, &2, &3, &4, &5, &8, &9]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_shuffle_chunks_zero() {
+        [1, 2, 3].shuffle_chunks(&mut crate::test::rng(127), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_shuffle_groups() {
+        let mut r1 = crate::test::rng(128);
+        let mut r2 = crate::test::rng(128);
+        let lens = [2, 0, 3, 1, 4];
+        let mut v: Vec<usize> = (0..10).collect();
+        let mut groups: [usize; 5] = [0, 1, 2, 3, 4];
+        v.shuffle_groups(&mut r1, &lens);
+        groups.shuffle(&mut r2);
+        let starts = [0, 2, 2, 5, 6];
+        let expected: Vec<usize> = groups
+            .iter()
+            .flat_map(|&g| starts[g]..starts[g] + lens[g])
+            .collect();
+        assert_eq!(v, expected);
+
+        let mut empty: [u8; 0] = [];
+        empty.shuffle_groups(&mut r1, &[]);
+        empty.shuffle_groups(&mut r1, &[0, 0]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    #[should_panic]
+    fn test_shuffle_groups_wrong_length() {
+        [1, 2, 3].shuffle_groups(&mut crate::test::rng(129), &[1, 1]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_shuffle_weighted() {
+        let mut r = crate::test::rng(125);
+        let mut empty: [u32; 0] = [];
+        empty.shuffle_weighted(&mut r, |_| 1.0).unwrap();
+
+        let mut v = [0, 1, 2, 3];
+        assert_eq!(
+            v.shuffle_weighted(&mut r, |&x| if x == 2 { -1.0 } else { 1.0 }),
+            Err(WeightedError::InvalidWeight)
+        );
+        assert_eq!(
+            v.shuffle_weighted(&mut r, |&x| if x == 2 { f64::NAN } else { 1.0 }),
+            Err(WeightedError::InvalidWeight)
+        );
+        assert_eq!(v, [0, 1, 2, 3]);
+
+        // Infinite weights come first and zero weights last, each shuffled
+        let weights = [0.0, f64::INFINITY, 1.0, 0.0, f64::INFINITY];
+        let mut first = [0; 5];
+        for _ in 0..1000 {
+            let mut v = [0, 1, 2, 3, 4];
+            v.shuffle_weighted(&mut r, |&i| weights[i]).unwrap();
+            assert!(v[..2].contains(&1) && v[..2].contains(&4));
+            assert_eq!(v[2], 2);
+            assert!(v[3..].contains(&0) && v[3..].contains(&3));
+            first[v[0]] += 1;
+            first[v[3]] += 1;
+        }
+        assert!((400..600).contains(&first[1]));
+        assert!((400..600).contains(&first[0]));
+
+        // Weights 1, 2, 3: the probability of each order is the product of
+        // successive choice probabilities, e.g. P(2, 1, 0) = 3/6 * 2/3 = 1/3
+        let mut counts = [[0; 3]; 3];
+        for _ in 0..6000 {
+            let mut v = [0, 1, 2];
+            v.shuffle_weighted(&mut r, |&i| (i + 1) as f64).unwrap();
+            for (pos, &i) in v.iter().enumerate() {
+                counts[pos][i] += 1;
+            }
+        }
+        // P(first = i) = (i + 1) / 6
+        assert!((900..1100).contains(&counts[0][0]));
+        assert!((1850..2150).contains(&counts[0][1]));
+        assert!((2850..3150).contains(&counts[0][2]));
+        // P(last = 0) = 2/6 * 3/4 + 3/6 * 2/3 = 0.583...
+        assert!((3350..3650).contains(&counts[2][0]));
+    }
+
+    #[test]
+    fn test_partial_shuffle_iter() {
+        let mut r = crate::test::rng(119);
+
+        let mut empty: [u32; 0] = [];
+        assert_eq!(empty.partial_shuffle_iter(&mut r).next(), None);
+
+        let mut v = [0, 1, 2, 3, 4, 5, 6, 7];
+        let (a, b) = {
+            let mut iter = v.partial_shuffle_iter(&mut r);
+            assert_eq!(iter.len(), 8);
+            let a = *iter.next().unwrap();
+            let b = *iter.next().unwrap();
+            assert_eq!(iter.len(), 6);
+            (a, b)
+        };
+        // Yielded elements are moved to the front
+        assert_eq!(&v[..2], &[a, b]);
+        assert_ne!(a, b);
+
+        let mut seen = [false; 8];
+        for x in v.partial_shuffle_iter(&mut r) {
+            assert!(!seen[*x]);
+            seen[*x] = true;
+            *x += 10;
+        }
+        assert!(v.iter().all(|&x| x >= 10));
+
+        // Each element is equally likely to be yielded first
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let mut v = [0, 1, 2, 3];
+            counts[*v.partial_shuffle_iter(&mut r).next().unwrap()] += 1;
+        }
+        crate::test::assert_uniform(&counts);
+    }
+
     #[test]
     #[cfg(feature = "alloc")]
     fn test_sample_iter() {
@@ -1258,8 +2878,8 @@ mod test {
         }
 
         assert_eq!(choose([].iter().cloned()), None);
-        assert_eq!(choose(0..100), Some(27));
-        assert_eq!(choose(UnhintedIterator { iter: 0..100 }), Some(27));
+        assert_eq!(choose(0..100), Some(94));
+        assert_eq!(choose(UnhintedIterator { iter: 0..100 }), Some(94));
         assert_eq!(
             choose(ChunkHintedIterator {
                 iter: 0..100,
@@ -1267,7 +2887,7 @@ mod test {
                 chunk_remaining: 32,
                 hint_total_size: false,
             }),
-            Some(27)
+            Some(94)
         );
         assert_eq!(
             choose(ChunkHintedIterator {
@@ -1276,7 +2896,7 @@ mod test {
                 chunk_remaining: 32,
                 hint_total_size: true,
             }),
-            Some(27)
+            Some(94)
         );
         assert_eq!(
             choose(WindowHintedIterator {
@@ -1284,7 +2904,7 @@ mod test {
                 window_size: 32,
                 hint_total_size: false,
             }),
-            Some(27)
+            Some(94)
         );
         assert_eq!(
             choose(WindowHintedIterator {
@@ -1292,7 +2912,7 @@ mod test {
                 window_size: 32,
                 hint_total_size: true,
             }),
-            Some(27)
+            Some(94)
         );
     }
 
@@ -1316,6 +2936,23 @@ mod test {
         do_test(0..100, &[77, 95, 38, 23, 25, 8, 58, 40]);
     }
 
+    #[test]
+    fn value_stability_choose_weighted() {
+        let mut rng = crate::test::rng(417);
+        let mut buf = [0; 8];
+        for x in buf.iter_mut() {
+            *x = (0..100u32).choose_weighted(&mut rng, |&i| i).unwrap();
+        }
+        assert_eq!(buf, [36, 67, 19, 80, 95, 1, 46, 73]);
+
+        #[cfg(feature = "std")]
+        {
+            let mut result = (0..100u32).choose_multiple_weighted(&mut rng, 8, |&i| i).unwrap();
+            result.sort_unstable();
+            assert_eq!(result, [9, 31, 38, 62, 76, 77, 88, 98]);
+        }
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn test_multiple_weighted_edge_cases() {
@@ -1401,6 +3038,201 @@ mod test {
             .is_ok());
     }
 
+    #[test]
+    fn test_iterator_choose_weighted() {
+        let mut rng = crate::test::rng(416);
+        let choices = [('a', 2), ('b', 1), ('c', 0), ('d', 1)];
+        let mut counts = [0; 4];
+        for _ in 0..4000 {
+            let (c, _) = choices.iter().choose_weighted(&mut rng, |item| item.1).unwrap();
+            counts[(*c as u8 - b'a') as usize] += 1;
+        }
+        assert!((1900..2100).contains(&counts[0]));
+        assert!((900..1100).contains(&counts[1]));
+        assert_eq!(counts[2], 0);
+        assert!((900..1100).contains(&counts[3]));
+
+        // The weight function may be stateful and is called once per item
+        let mut calls = 0;
+        let result = (0..10).choose_weighted(&mut rng, |&i| {
+            calls += 1;
+            if i == 7 { 1.0 } else { 0.0 }
+        });
+        assert_eq!(result, Ok(7));
+        assert_eq!(calls, 10);
+
+        assert_eq!(
+            core::iter::empty::<u32>().choose_weighted(&mut rng, |_| 1),
+            Err(WeightedError::NoItem)
+        );
+        assert_eq!((0..3).choose_weighted(&mut rng, |_| 0), Err(WeightedError::AllWeightsZero));
+        for &invalid in &[-1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                (0..3).choose_weighted(&mut rng, |&i| if i == 1 { invalid } else { 1.0 }),
+                Err(WeightedError::InvalidWeight)
+            );
+        }
+        assert_eq!(
+            (0..3).choose_weighted(&mut rng, |_| f64::MAX),
+            Err(WeightedError::InvalidWeight)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_iterator_choose_multiple_weighted() {
+        let mut rng = crate::test::rng(418);
+
+        // Probabilities of the pairs ab, ac and bc are 1/6, 5/12 and 5/12
+        let mut counts = [0; 3];
+        for _ in 0..6000 {
+            let mut result = (0..3)
+                .choose_multiple_weighted(&mut rng, 2, |&i| if i == 2 { 2 } else { 1 })
+                .unwrap();
+            result.sort_unstable();
+            match result[..] {
+                [0, 1] => counts[0] += 1,
+                [0, 2] => counts[1] += 1,
+                [1, 2] => counts[2] += 1,
+                _ => panic!("unexpected result"),
+            }
+        }
+        assert!((900..1100).contains(&counts[0]));
+        assert!((2350..2650).contains(&counts[1]));
+        assert!((2350..2650).contains(&counts[2]));
+
+        // In a long stream, the element of weight 1000 is almost always chosen
+        let mut hits = 0;
+        for _ in 0..100 {
+            let result = (0..10_000u32)
+                .choose_multiple_weighted(&mut rng, 10, |&i| if i == 5000 { 1000.0 } else { 1.0 })
+                .unwrap();
+            assert_eq!(result.len(), 10);
+            let mut sorted = result.clone();
+            sorted.sort_unstable();
+            sorted.dedup();
+            assert_eq!(sorted.len(), 10);
+            hits += result.contains(&5000) as u32;
+        }
+        assert!(hits >= 55);
+
+        // Zero weights are only chosen when needed
+        for _ in 0..100 {
+            let result = (0..10)
+                .choose_multiple_weighted(&mut rng, 3, |&i| if i % 5 == 0 { 1 } else { 0 })
+                .unwrap();
+            assert!(result.contains(&0) && result.contains(&5));
+        }
+        let mut result = (0..5).choose_multiple_weighted(&mut rng, 5, |_| 0).unwrap();
+        result.sort_unstable();
+        assert_eq!(result, [0, 1, 2, 3, 4]);
+
+        // Infinite weights are always chosen
+        for _ in 0..100 {
+            let result = (0..10)
+                .choose_multiple_weighted(&mut rng, 2, |&i| if i == 8 { f64::INFINITY } else { 1.0 })
+                .unwrap();
+            assert!(result.contains(&8));
+        }
+
+        assert_eq!((0..3).choose_multiple_weighted(&mut rng, 0, |_| 1).unwrap(), Vec::<i32>::new());
+        assert_eq!((0..2).choose_multiple_weighted(&mut rng, 3, |_| 1).unwrap().len(), 2);
+        for &invalid in &[-1.0, f64::NAN] {
+            for &i in &[0, 9] {
+                assert_eq!(
+                    (0..10).choose_multiple_weighted(&mut rng, 3, |&j| if j == i { invalid } else { 1.0 }),
+                    Err(WeightedError::InvalidWeight)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiple_weighted_fill() {
+        let mut rng = crate::test::rng(415);
+        let mut buf = [usize::MAX; 4];
+
+        let choices = [('a', 2), ('b', 1), ('c', 0)];
+        for _ in 0..100 {
+            let n = choices.choose_multiple_weighted_fill(&mut rng, &mut buf[..2], |item| item.1);
+            assert_eq!(n, Ok(2));
+            assert_eq!(&buf[..2], &[0, 1]);
+        }
+        // All elements are chosen; the zero weight last
+        assert_eq!(choices.choose_multiple_weighted_fill(&mut rng, &mut buf, |item| item.1), Ok(3));
+        assert_eq!(&buf[..3], &[0, 1, 2]);
+
+        let choices = [0.0, 0.0, 0.0, 0.0];
+        assert_eq!(choices.choose_multiple_weighted_fill(&mut rng, &mut buf[..3], |&w| w), Ok(3));
+        assert!(buf[0] < buf[1] && buf[1] < buf[2] && buf[2] < 4);
+
+        let choices = [1.0, f64::INFINITY, 1.0, f64::INFINITY];
+        assert_eq!(choices.choose_multiple_weighted_fill(&mut rng, &mut buf[..2], |&w| w), Ok(2));
+        assert_eq!(&buf[..2], &[1, 3]);
+
+        for &invalid in &[-1.0, f64::NAN, f64::NEG_INFINITY] {
+            let choices = [1.0, invalid, 1.0];
+            assert_eq!(
+                choices.choose_multiple_weighted_fill(&mut rng, &mut buf[..2], |&w| w),
+                Err(WeightedError::InvalidWeight)
+            );
+        }
+        let choices = [f64::MAX, f64::MAX];
+        assert_eq!(
+            choices.choose_multiple_weighted_fill(&mut rng, &mut buf[..1], |&w| w),
+            Err(WeightedError::InvalidWeight)
+        );
+        assert_eq!([(); 0].choose_multiple_weighted_fill(&mut rng, &mut buf, |_| 1), Ok(0));
+
+        // Probabilities of the pairs ab, ac and bc are 1/6, 5/12 and 5/12
+        let choices = [1, 1, 2];
+        let mut counts = [0; 3];
+        for _ in 0..6000 {
+            choices.choose_multiple_weighted_fill(&mut rng, &mut buf[..2], |&w| w).unwrap();
+            match (buf[0], buf[1]) {
+                (0, 1) => counts[0] += 1,
+                (0, 2) => counts[1] += 1,
+                (1, 2) => counts[2] += 1,
+                _ => panic!("unexpected result"),
+            }
+        }
+        assert!((900..1100).contains(&counts[0]));
+        assert!((2350..2650).contains(&counts[1]));
+        assert!((2350..2650).contains(&counts[2]));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_multiple_weighted_exact() {
+        let mut r = crate::test::rng(137);
+        let choices = [('a', 2), ('b', 1), ('c', 1)];
+        let mut counts = [0; 3];
+        for _ in 0..6000 {
+            let chosen: Vec<_> = choices
+                .choose_multiple_weighted_exact(&mut r, 2, |item| item.1)
+                .unwrap()
+                .collect();
+            assert_eq!(chosen.len(), 2);
+            for &&(c, _) in &chosen {
+                counts[(c as u8 - b'a') as usize] += 1;
+            }
+        }
+        // Inclusion probabilities 1, 1/2 and 1/2
+        assert_eq!(counts[0], 6000);
+        assert!((2850..3150).contains(&counts[1]));
+
+        let empty: [u8; 0] = [];
+        assert_eq!(empty.choose_multiple_weighted_exact(&mut r, 2, |_| 1).unwrap().count(), 0);
+        assert_eq!(
+            choices.choose_multiple_weighted_exact(&mut r, 5, |item| item.1).unwrap().count(),
+            3
+        );
+        assert_eq!(
+            choices.choose_multiple_weighted_exact(&mut r, 1, |_| -1).unwrap_err(),
+            WeightedError::InvalidWeight
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn test_multiple_weighted_distributions() {
@@ -0,0 +1,357 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A reusable permutation of indices.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::iter::Copied;
+use core::slice;
+
+use super::SliceRandom;
+use crate::Rng;
+
+#[cfg(feature = "serde1")]
+use serde::{Serialize, Deserialize};
+
+/// A permutation of the indices `0..len`.
+///
+/// A `Permutation` maps each position `i` to the index `self[i]` of the
+/// element moved there: applying it to a slice `x` yields `y` with
+/// `y[i] == x[perm[i]]`. Unlike [`SliceRandom::shuffle`], a permutation can be
+/// stored, applied to several slices, inverted or composed.
+///
+/// [`Permutation::random`] generates a uniformly random permutation using the
+/// same Fisher–Yates shuffle as [`SliceRandom::shuffle`]: applying it to a
+/// slice has the same result as shuffling that slice with the same RNG.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::Permutation;
+///
+/// let mut rng = rand::thread_rng();
+/// let perm = Permutation::random(&mut rng, 4);
+///
+/// let mut names = ["a", "b", "c", "d"];
+/// let mut ages = [31, 27, 45, 19];
+/// perm.apply(&mut names);
+/// perm.apply(&mut ages);
+///
+/// // Restore the original order
+/// perm.apply_inverse(&mut names);
+/// assert_eq!(names, ["a", "b", "c", "d"]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde1", serde(try_from = "Vec<usize>", into = "Vec<usize>"))]
+pub struct Permutation {
+    indices: Vec<usize>,
+}
+
+impl Permutation {
+    /// Construct the identity permutation of `0..len`, which does not move
+    /// any element.
+    pub fn identity(len: usize) -> Self {
+        Permutation {
+            indices: (0..len).collect(),
+        }
+    }
+
+    /// Generate a uniformly random permutation of `0..len`.
+    ///
+    /// For slices of length `len`, [`apply`](Permutation::apply) then
+    /// produces the same order as [`SliceRandom::shuffle`] with the same RNG.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R, len: usize) -> Self {
+        let mut perm = Permutation::identity(len);
+        perm.indices.shuffle(rng);
+        perm
+    }
+
+    /// Construct a permutation from its index mapping, where position `i` is
+    /// mapped to `indices[i]`.
+    ///
+    /// Returns `None` unless `indices` contains each of `0..indices.len()`
+    /// exactly once.
+    pub fn from_indices(indices: Vec<usize>) -> Option<Self> {
+        let mut seen = vec![false; indices.len()];
+        for &i in &indices {
+            match seen.get_mut(i) {
+                Some(seen) if !*seen => *seen = true,
+                _ => return None,
+            }
+        }
+        Some(Permutation { indices })
+    }
+
+    /// Returns the number of indices permuted.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if the permutation is of zero indices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the index mapping, where position `i` is mapped to `self[i]`.
+    #[inline]
+    pub fn as_slice(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns the index mapping as a `Vec`.
+    #[inline]
+    pub fn into_vec(self) -> Vec<usize> {
+        self.indices
+    }
+
+    /// Iterate over the index mapping, i.e. the index of the element moved
+    /// to each position.
+    #[inline]
+    pub fn iter(&self) -> Copied<slice::Iter<'_, usize>> {
+        self.indices.iter().copied()
+    }
+
+    /// Permute `slice` in place, such that afterwards `slice[i]` holds the
+    /// element previously at `slice[self[i]]`.
+    ///
+    /// This allocates a `Vec<bool>` of length `len` to track visited cycles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice.len() != self.len()`.
+    pub fn apply<T>(&self, slice: &mut [T]) {
+        assert_eq!(slice.len(), self.len(), "slice length differs from permutation");
+        let mut visited = vec![false; self.len()];
+        for start in 0..self.len() {
+            if visited[start] {
+                continue;
+            }
+            let mut j = start;
+            loop {
+                visited[j] = true;
+                let k = self.indices[j];
+                if k == start {
+                    break;
+                }
+                slice.swap(j, k);
+                j = k;
+            }
+        }
+    }
+
+    /// Undo [`apply`](Permutation::apply): permute `slice` in place, such that
+    /// afterwards `slice[self[i]]` holds the element previously at `slice[i]`.
+    ///
+    /// This allocates a `Vec<bool>` of length `len` to track visited cycles.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `slice.len() != self.len()`.
+    pub fn apply_inverse<T>(&self, slice: &mut [T]) {
+        assert_eq!(slice.len(), self.len(), "slice length differs from permutation");
+        let mut visited = vec![false; self.len()];
+        for start in 0..self.len() {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut j = self.indices[start];
+            while j != start {
+                visited[j] = true;
+                slice.swap(start, j);
+                j = self.indices[j];
+            }
+        }
+    }
+
+    /// Returns the inverse permutation, whose [`apply`](Permutation::apply)
+    /// is equivalent to [`apply_inverse`](Permutation::apply_inverse) of
+    /// `self`.
+    pub fn inverse(&self) -> Permutation {
+        let mut indices = vec![0; self.len()];
+        for (i, &j) in self.indices.iter().enumerate() {
+            indices[j] = i;
+        }
+        Permutation { indices }
+    }
+
+    /// Returns the composition of `self` followed by `other`: applying the
+    /// result is equivalent to applying `self`, then `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other.len() != self.len()`.
+    pub fn compose(&self, other: &Permutation) -> Permutation {
+        assert_eq!(other.len(), self.len(), "permutation lengths differ");
+        Permutation {
+            indices: other.indices.iter().map(|&i| self.indices[i]).collect(),
+        }
+    }
+}
+
+/// Error type indicating that a [`Permutation`] was constructed from indices
+/// which are not a permutation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidPermutation;
+
+impl fmt::Display for InvalidPermutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("indices are not a permutation")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidPermutation {}
+
+impl TryFrom<Vec<usize>> for Permutation {
+    type Error = InvalidPermutation;
+
+    /// Construct a permutation as [`Permutation::from_indices`] does.
+    fn try_from(indices: Vec<usize>) -> Result<Self, Self::Error> {
+        Permutation::from_indices(indices).ok_or(InvalidPermutation)
+    }
+}
+
+impl From<Permutation> for Vec<usize> {
+    #[inline]
+    fn from(perm: Permutation) -> Vec<usize> {
+        perm.indices
+    }
+}
+
+impl core::ops::Index<usize> for Permutation {
+    type Output = usize;
+
+    #[inline]
+    fn index(&self, index: usize) -> &usize {
+        &self.indices[index]
+    }
+}
+
+impl<'a> IntoIterator for &'a Permutation {
+    type Item = usize;
+    type IntoIter = Copied<slice::Iter<'a, usize>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_indices() {
+        assert!(Permutation::from_indices(vec![]).unwrap().is_empty());
+        let perm = Permutation::from_indices(vec![2, 0, 1]).unwrap();
+        assert_eq!(perm.len(), 3);
+        assert_eq!(perm[0], 2);
+        assert_eq!(perm.iter().collect::<Vec<_>>(), [2, 0, 1]);
+        assert_eq!((&perm).into_iter().collect::<Vec<_>>(), perm.as_slice());
+        assert_eq!(perm.into_vec(), [2, 0, 1]);
+
+        assert_eq!(Permutation::from_indices(vec![0, 0]), None);
+        assert_eq!(Permutation::from_indices(vec![0, 2]), None);
+        assert_eq!(Permutation::from_indices(vec![1]), None);
+        assert_eq!(Permutation::try_from(vec![1, 0]).unwrap().as_slice(), [1, 0]);
+        assert_eq!(Permutation::try_from(vec![1, 1]), Err(InvalidPermutation));
+    }
+
+    #[cfg(feature = "serde1")]
+    #[test]
+    fn test_serde() {
+        let perm = Permutation::from_indices(vec![2, 0, 1]).unwrap();
+        let encoded = bincode::serialize(&perm).unwrap();
+        assert_eq!(bincode::deserialize::<Permutation>(&encoded).unwrap(), perm);
+
+        // Only permutations are accepted
+        let encoded = bincode::serialize(&vec![1usize, 1]).unwrap();
+        assert!(bincode::deserialize::<Permutation>(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_apply() {
+        let perm = Permutation::from_indices(vec![2, 0, 1, 4, 3, 5]).unwrap();
+        let mut x = ['a', 'b', 'c', 'd', 'e', 'f'];
+        perm.apply(&mut x);
+        assert_eq!(x, ['c', 'a', 'b', 'e', 'd', 'f']);
+        perm.apply_inverse(&mut x);
+        assert_eq!(x, ['a', 'b', 'c', 'd', 'e', 'f']);
+        perm.inverse().apply(&mut x);
+        perm.apply(&mut x);
+        assert_eq!(x, ['a', 'b', 'c', 'd', 'e', 'f']);
+
+        let mut empty: [u8; 0] = [];
+        Permutation::identity(0).apply(&mut empty);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_apply_wrong_length() {
+        Permutation::identity(3).apply(&mut [0; 4]);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut r1 = crate::test::rng(501);
+        let mut r2 = crate::test::rng(501);
+        for len in 0..20 {
+            let perm = Permutation::random(&mut r1, len);
+            assert!(Permutation::from_indices(perm.clone().into_vec()).is_some());
+
+            let mut x: Vec<usize> = (100..100 + len).collect();
+            let mut y = x.clone();
+            perm.apply(&mut x);
+            y.shuffle(&mut r2);
+            assert_eq!(x, y);
+            for (i, &v) in x.iter().enumerate() {
+                assert_eq!(v, 100 + perm[i]);
+            }
+        }
+
+        // Each of the 6 permutations of 3 elements is equally likely
+        let mut counts = [0; 6];
+        for _ in 0..6000 {
+            let perm = Permutation::random(&mut r1, 3);
+            counts[perm[0] * 2 + (perm[1] > perm[2]) as usize] += 1;
+        }
+        assert!(counts.iter().all(|&c| (900..1100).contains(&c)));
+    }
+
+    #[test]
+    fn test_compose() {
+        let mut r = crate::test::rng(502);
+        let p = Permutation::random(&mut r, 10);
+        let q = Permutation::random(&mut r, 10);
+
+        let mut x: Vec<u32> = (0..10).collect();
+        let mut y = x.clone();
+        p.apply(&mut x);
+        q.apply(&mut x);
+        p.compose(&q).apply(&mut y);
+        assert_eq!(x, y);
+
+        assert_eq!(p.compose(&p.inverse()), Permutation::identity(10));
+        assert_eq!(p.inverse().compose(&p), Permutation::identity(10));
+        assert_eq!(p.compose(&Permutation::identity(10)), p);
+    }
+
+    #[test]
+    fn value_stability() {
+        let perm = Permutation::random(&mut crate::test::rng(503), 8);
+        assert_eq!(perm.as_slice(), [6, 4, 5, 0, 7, 1, 3, 2]);
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2023 Developers of the Rand project.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or https://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Random interleaving of two sequences.
+
+use core::iter::FusedIterator;
+
+use super::gen_index;
+use crate::Rng;
+
+/// Randomly interleave two sequences, preserving the relative order of the
+/// elements of each.
+///
+/// Each of the `(n + m) choose n` interleavings of sequences of lengths `n`
+/// and `m` is equally likely: while `a` has `i` and `b` has `j` elements
+/// remaining, the next element is taken from `a` with probability
+/// `i / (i + j)`. This is the interleaving step of the Gilbert–Shannon–Reeds
+/// model of a riffle shuffle. The result is yielded lazily, using one random
+/// number per element taken while both sequences are non-empty and no
+/// allocation.
+///
+/// Both iterators must report their exact length. If either ends early, the
+/// returned iterator ends too.
+///
+/// # Example
+///
+/// ```
+/// use rand::seq::riffle;
+///
+/// let left = ["a", "b", "c"];
+/// let right = ["x", "y"];
+/// let merged: Vec<_> = riffle(rand::thread_rng(), &left, &right).collect();
+/// assert_eq!(merged.len(), 5);
+/// let from_left: Vec<_> = merged.iter().filter(|s| left.contains(s)).collect();
+/// assert_eq!(from_left, [&&"a", &&"b", &&"c"]);
+/// ```
+pub fn riffle<R, A, B>(rng: R, a: A, b: B) -> Riffle<R, A::IntoIter, B::IntoIter>
+where
+    R: Rng,
+    A: IntoIterator,
+    A::IntoIter: ExactSizeIterator,
+    B: IntoIterator<Item = A::Item>,
+    B::IntoIter: ExactSizeIterator,
+{
+    let (a, b) = (a.into_iter(), b.into_iter());
+    Riffle {
+        rng,
+        a_len: a.len(),
+        b_len: b.len(),
+        a,
+        b,
+    }
+}
+
+/// An iterator over the random interleaving of two sequences.
+///
+/// This struct is created by [`riffle`].
+#[derive(Debug, Clone)]
+pub struct Riffle<R, A, B> {
+    rng: R,
+    a: A,
+    b: B,
+    a_len: usize,
+    b_len: usize,
+}
+
+impl<R: Rng, A, B> Iterator for Riffle<R, A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+    type Item = A::Item;
+
+    fn next(&mut self) -> Option<A::Item> {
+        let take_a = match (self.a_len, self.b_len) {
+            (0, 0) => return None,
+            (_, 0) => true,
+            (0, _) => false,
+            (a_len, b_len) => gen_index(&mut self.rng, a_len + b_len) < a_len,
+        };
+        let next = if take_a {
+            self.a_len -= 1;
+            self.a.next()
+        } else {
+            self.b_len -= 1;
+            self.b.next()
+        };
+        if next.is_none() {
+            // An iterator reported the wrong length
+            self.a_len = 0;
+            self.b_len = 0;
+        }
+        next
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.a_len + self.b_len;
+        (len, Some(len))
+    }
+}
+
+impl<R: Rng, A, B> ExactSizeIterator for Riffle<R, A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+}
+
+impl<R: Rng, A, B> FusedIterator for Riffle<R, A, B>
+where
+    A: Iterator,
+    B: Iterator<Item = A::Item>,
+{
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_riffle() {
+        let mut r = crate::test::rng(621);
+        let a: Vec<u32> = (0..10).collect();
+        let b: Vec<u32> = (10..15).collect();
+        let merged: Vec<u32> = riffle(&mut r, a.iter().cloned(), b.iter().cloned()).collect();
+        assert_eq!(merged.len(), 15);
+        assert_eq!(merged.iter().filter(|&&x| x < 10).cloned().collect::<Vec<_>>(), a);
+        assert_eq!(merged.iter().filter(|&&x| x >= 10).cloned().collect::<Vec<_>>(), b);
+
+        let mut iter = riffle(&mut r, &a[..3], &b[..0]);
+        assert_eq!(iter.len(), 3);
+        assert!(iter.by_ref().eq(&a[..3]));
+        assert_eq!(iter.next(), None);
+        assert_eq!(riffle(&mut r, &a[..0], &b[..2]).count(), 2);
+        assert_eq!(riffle(&mut r, &a[..0], &b[..0]).next(), None);
+
+        // Each of the 10 interleavings of 3 and 2 elements is equally likely
+        let mut counts = [0; 32];
+        for _ in 0..10_000 {
+            let key = riffle(&mut r, &[1, 1, 1], &[0, 0])
+                .fold(0, |key, &bit| key * 2 + bit);
+            counts[key] += 1;
+        }
+        let counts: Vec<_> = counts.iter().cloned().filter(|&c| c > 0).collect();
+        assert_eq!(counts.len(), 10);
+        crate::test::assert_uniform(&counts);
+    }
+
+    #[test]
+    fn value_stability() {
+        let merged: Vec<u8> = riffle(crate::test::rng(622), b"abcde", b"XYZ").cloned().collect();
+        assert_eq!(merged, b"aXbcdYZe");
+    }
+}